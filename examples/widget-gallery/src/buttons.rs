@@ -19,6 +19,7 @@ pub fn button_view() -> impl View {
                     .keyboard_navigatable()
                     .focus_visible_style(|s| s.border(2.).border_color(Color::BLUE))
                     .style(|s| s.border(1.0).border_radius(10.0).padding(10.0))
+                    .tooltip(|| label(|| "Prints a message"))
             }),
             form_item("Styled Button:".to_string(), 120.0, || {
                 label(|| "Click me")
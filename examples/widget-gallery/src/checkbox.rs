@@ -1,18 +1,23 @@
 use floem::{
     peniko::Color,
-    reactive::create_signal,
+    reactive::{create_rw_signal, create_signal},
     view::View,
-    views::{checkbox, label, stack, Decorators},
+    views::{
+        checkbox, labeled_checkbox, radio_group, toggle_switch, tristate_checkbox, CheckboxState,
+        Decorators,
+    },
 };
 
 use crate::form::{form, form_item};
 
 pub fn checkbox_view() -> impl View {
     let (is_checked, set_is_checked) = create_signal(true);
+    let (state, set_state) = create_signal(CheckboxState::Indeterminate);
+    let fruit = create_rw_signal("Apple");
     form({
         (
             form_item("Basic Checkbox:".to_string(), 120.0, move || {
-                checkbox(is_checked)
+                checkbox(move || is_checked.get())
                     .focus_visible_style(|s| s.border_color(Color::BLUE).border(2.))
                     .on_click(move |_| {
                         set_is_checked.update(|checked| *checked = !*checked);
@@ -20,32 +25,41 @@ pub fn checkbox_view() -> impl View {
                     })
             }),
             form_item("Labelled Checkbox:".to_string(), 120.0, move || {
-                stack({
-                    (
-                        checkbox(is_checked)
-                            .focus_visible_style(|s| s.border_color(Color::BLUE).border(2.)),
-                        label(|| "Check me!"),
-                    )
-                })
-                .on_click(move |_| {
-                    set_is_checked.update(|checked| *checked = !*checked);
-                    true
-                })
+                labeled_checkbox(move || is_checked.get(), || "Check me!")
+                    .focus_visible_style(|s| s.color(Color::BLUE))
+                    .on_click(move |_| {
+                        set_is_checked.update(|checked| *checked = !*checked);
+                        true
+                    })
+            }),
+            form_item("Tristate Checkbox:".to_string(), 120.0, move || {
+                tristate_checkbox(move || state.get())
+                    .focus_visible_style(|s| s.border_color(Color::BLUE).border(2.))
+                    .on_click(move |_| {
+                        set_state.update(|state| {
+                            *state = match state {
+                                CheckboxState::Checked => CheckboxState::Unchecked,
+                                CheckboxState::Unchecked => CheckboxState::Indeterminate,
+                                CheckboxState::Indeterminate => CheckboxState::Checked,
+                            }
+                        });
+                        true
+                    })
             }),
             form_item("Disabled Checkbox:".to_string(), 120.0, move || {
-                stack({
-                    (
-                        checkbox(is_checked)
-                            .focus_visible_style(|s| s.border_color(Color::BLUE).border(2.)),
-                        label(|| "Check me!"),
-                    )
-                })
-                .style(|s| s.color(Color::GRAY))
-                .disabled(|| true)
-                .on_click(move |_| {
-                    set_is_checked.update(|checked| *checked = !*checked);
-                    true
-                })
+                labeled_checkbox(move || is_checked.get(), || "Check me!")
+                    .style(|s| s.color(Color::GRAY))
+                    .disabled(|| true)
+                    .on_click(move |_| {
+                        set_is_checked.update(|checked| *checked = !*checked);
+                        true
+                    })
+            }),
+            form_item("Toggle Switch:".to_string(), 120.0, move || {
+                toggle_switch(move || is_checked.get()).on_toggle(move |on| set_is_checked.set(on))
+            }),
+            form_item("Radio Group:".to_string(), 120.0, move || {
+                radio_group(vec!["Apple", "Banana", "Cherry"], fruit, |fruit| *fruit)
             }),
         )
     })
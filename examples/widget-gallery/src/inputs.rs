@@ -3,13 +3,17 @@ use floem::{
     reactive::create_rw_signal,
     style::CursorStyle,
     view::View,
-    views::{text_input, Decorators},
+    views::{combobox, dropdown, slider, text_input, Decorators},
 };
 
 use crate::form::{form, form_item};
 
 pub fn text_input_view() -> impl View {
     let text = create_rw_signal("".to_string());
+    let volume = create_rw_signal(50.0);
+    let fruit = create_rw_signal("Apple");
+    let city = create_rw_signal("".to_string());
+    let fruits = || vec!["Apple", "Banana", "Cherry", "Grape", "Orange"];
 
     form({
         (
@@ -47,6 +51,23 @@ pub fn text_input_view() -> impl View {
                     .keyboard_navigatable()
                     .disabled(|| true)
             }),
+            form_item("Slider:".to_string(), 120.0, move || {
+                slider(volume)
+                    .step(10.0)
+                    .tick_marks(true)
+                    .thumb_style(|s| s.border_color(Color::rgb8(66, 133, 244)))
+                    .style(|s| s.width(200.0))
+            }),
+            form_item("Dropdown:".to_string(), 120.0, move || {
+                dropdown(fruits, fruit)
+            }),
+            form_item("Combobox:".to_string(), 120.0, move || {
+                combobox(
+                    || vec!["Amsterdam", "Berlin", "Lisbon", "London", "Paris"],
+                    city,
+                )
+                .style(|s| s.width(200.0))
+            }),
         )
     })
 }
@@ -63,7 +63,7 @@ fn enhanced_list() -> impl View {
                 container({
                     stack({
                         (
-                            checkbox(is_checked).on_click(move |_| {
+                            checkbox(move || is_checked.get()).on_click(move |_| {
                                 set_is_checked.update(|checked: &mut bool| *checked = !*checked);
                                 true
                             }),
@@ -1,9 +1,13 @@
 use std::any::{Any, TypeId};
 
-use crate::runtime::RUNTIME;
+use crate::{id::Id, runtime::RUNTIME};
 
 /// Try to retrieve a stored Context value in the reactive system.
-/// You can store a Context value anywhere, and retrieve it from anywhere afterwards.
+///
+/// The value is the one [provided](provide_context) in the closest Scope to the current one,
+/// going up from the current Scope to its parents, so a view gets the values provided by the
+/// views it's nested in. The values provided before any view was created, in the first
+/// Scope, can be retrieved from anywhere.
 pub fn use_context<T>() -> Option<T>
 where
     T: Clone + 'static,
@@ -11,16 +15,28 @@ where
     let ty = TypeId::of::<T>();
     RUNTIME.with(|runtime| {
         let contexts = runtime.contexts.borrow();
-        let context = contexts
-            .get(&ty)
-            .and_then(|val| val.downcast_ref::<T>())
-            .cloned();
-        context
+        let parents = runtime.parents.borrow();
+        let lookup = |scope: &Id| {
+            contexts
+                .get(scope)
+                .and_then(|contexts| contexts.get(&ty))
+                .and_then(|val| val.downcast_ref::<T>())
+                .cloned()
+        };
+        let mut scope = Some(*runtime.current_scope.borrow());
+        while let Some(current) = scope {
+            if let Some(context) = lookup(&current) {
+                return Some(context);
+            }
+            scope = parents.get(&current).copied();
+        }
+        lookup(&runtime.root_scope)
     })
 }
 
-/// Sets a context value to be stored in the reative system.
-/// The stored context value can be retrieved from anywhere by using [use_context](use_context)
+/// Sets a context value to be stored in the reative system, in the current Scope.
+/// The stored context value can be retrieved by using [use_context](use_context) in this
+/// Scope and the Scopes under it, until the Scope is disposed.
 pub fn provide_context<T>(value: T)
 where
     T: Clone + 'static,
@@ -28,7 +44,11 @@ where
     let id = value.type_id();
 
     RUNTIME.with(|runtime| {
+        let scope = *runtime.current_scope.borrow();
         let mut contexts = runtime.contexts.borrow_mut();
-        contexts.insert(id, Box::new(value) as Box<dyn Any>);
+        contexts
+            .entry(scope)
+            .or_default()
+            .insert(id, Box::new(value) as Box<dyn Any>);
     });
 }
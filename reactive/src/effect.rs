@@ -59,7 +59,27 @@ where
     run_effect(effect);
 }
 
+/// Calls `on_change` with the new and the previous value of `source` each time a signal read
+/// by `source` changes. Unlike an effect, it isn't called for the first value.
+///
+/// `on_change` doesn't subscribe to the signals it reads.
+pub fn create_watch<T>(source: impl Fn() -> T + 'static, on_change: impl Fn(&T, &T) + 'static)
+where
+    T: 'static,
+{
+    create_effect(move |prev: Option<T>| {
+        let value = source();
+        if let Some(prev) = prev {
+            untrack(|| on_change(&value, &prev));
+        }
+        value
+    });
+}
+
 /// Signals that's wrapped this untrack will not subscribe to any effect
+///
+/// The current effect is still the one that's running once `f` returns, so the reads after
+/// it are tracked as usual.
 pub fn untrack<T>(f: impl FnOnce() -> T) -> T {
     let prev_effect = RUNTIME.with(|runtime| runtime.current_effect.borrow_mut().take());
     let result = f();
@@ -69,9 +89,114 @@ pub fn untrack<T>(f: impl FnOnce() -> T) -> T {
     result
 }
 
+/// Runs `f`, holding back the effects of the Signals it updates until it returns, so an
+/// effect that depends on several of them runs once instead of once for each update.
+///
+/// A batch inside another one is part of the outer batch. If `f` panics, the batch still
+/// ends, and the effects held back are dropped instead of being run.
+pub fn batch<T>(f: impl FnOnce() -> T) -> T {
+    let already_batching = RUNTIME.with(|runtime| runtime.batching.replace(true));
+    if already_batching {
+        return f();
+    }
+    let _batch = Batch;
+    f()
+}
+
+/// Ends the outermost [batch] when it's dropped, whether `f` returned or panicked
+struct Batch;
+
+impl Drop for Batch {
+    fn drop(&mut self) {
+        RUNTIME.with(|runtime| runtime.batching.set(false));
+        let pending =
+            RUNTIME.with(|runtime| std::mem::take(&mut *runtime.pending_effects.borrow_mut()));
+        // running them while unwinding could panic again
+        if std::thread::panicking() {
+            return;
+        }
+        for effect in pending {
+            run_effect(effect);
+        }
+    }
+}
+
+/// Tracks the Signals that are read in [track](SignalTracker::track), for reactive work
+/// that has to happen outside of an Effect, like painting a view.
+///
+/// Unlike an Effect, the tracked function isn't run again when one of the Signals updates.
+/// `on_change` is called instead, once, and the Signals are tracked again by the next
+/// call to [track](SignalTracker::track).
+pub struct SignalTracker {
+    tracker: Rc<Tracker>,
+}
+
+struct Tracker {
+    id: Id,
+    on_change: Box<dyn Fn()>,
+    observers: RefCell<Option<HashSet<Id>>>,
+}
+
+impl SignalTracker {
+    pub fn new(on_change: impl Fn() + 'static) -> Self {
+        Self {
+            tracker: Rc::new(Tracker {
+                id: Id::next(),
+                on_change: Box::new(on_change),
+                observers: RefCell::new(None),
+            }),
+        }
+    }
+
+    /// Runs `f`, subscribing to the Signals it reads in place of the ones read last time
+    pub fn track<T>(&self, f: impl FnOnce() -> T) -> T {
+        let tracker: Rc<dyn EffectTrait> = self.tracker.clone();
+        observer_clean_up(&tracker);
+        let prev_effect =
+            RUNTIME.with(|runtime| runtime.current_effect.borrow_mut().replace(tracker));
+        let result = f();
+        RUNTIME.with(|runtime| {
+            *runtime.current_effect.borrow_mut() = prev_effect;
+        });
+        result
+    }
+}
+
+impl Drop for SignalTracker {
+    fn drop(&mut self) {
+        let tracker: Rc<dyn EffectTrait> = self.tracker.clone();
+        observer_clean_up(&tracker);
+    }
+}
+
+impl EffectTrait for Tracker {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn run(&self) -> bool {
+        (self.on_change)();
+        true
+    }
+
+    fn add_observer(&self, id: Id) {
+        let mut observers = self.observers.borrow_mut();
+        if let Some(observers) = observers.as_mut() {
+            observers.insert(id);
+        } else {
+            *observers = Some(HashSet::from_iter([id]));
+        }
+    }
+
+    fn clear_observers(&self) -> Option<HashSet<Id>> {
+        self.observers.borrow_mut().take()
+    }
+}
+
 pub(crate) fn run_effect(effect: Rc<dyn EffectTrait>) {
     let effect_id = effect.id();
-    effect_id.dispose();
+    effect_id.dispose_owned();
+    RUNTIME.with(|runtime| runtime.graph.borrow_mut().effect_ran(effect_id));
 
     observer_clean_up(&effect);
 
@@ -150,3 +275,62 @@ where
         self.observers.borrow_mut().take()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, panic, rc::Rc};
+
+    use super::{batch, create_effect};
+    use crate::{runtime::RUNTIME, signal::create_rw_signal};
+
+    #[test]
+    fn batch_runs_effects_once_at_the_end() {
+        let first = create_rw_signal(0);
+        let second = create_rw_signal(0);
+        let runs = Rc::new(Cell::new(0));
+        {
+            let runs = runs.clone();
+            create_effect(move |_| {
+                first.track();
+                second.track();
+                runs.set(runs.get() + 1);
+            });
+        }
+        assert_eq!(runs.get(), 1);
+
+        batch(|| {
+            first.set(1);
+            batch(|| second.set(1));
+            // the inner batch doesn't end the outer one
+            assert_eq!(runs.get(), 1);
+        });
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn batch_ends_when_it_panics() {
+        let signal = create_rw_signal(0);
+        let runs = Rc::new(Cell::new(0));
+        {
+            let runs = runs.clone();
+            create_effect(move |_| {
+                signal.track();
+                runs.set(runs.get() + 1);
+            });
+        }
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            batch(|| {
+                signal.set(1);
+                panic!("in a batch");
+            })
+        }));
+        assert!(result.is_err());
+        assert!(!RUNTIME.with(|runtime| runtime.batching.get()));
+        // the effect held back by the batch was dropped
+        assert_eq!(runs.get(), 1);
+
+        signal.set(2);
+        assert_eq!(runs.get(), 2);
+    }
+}
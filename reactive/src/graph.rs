@@ -0,0 +1,263 @@
+//! A look at the dependencies between the signals and the effects, for finding out why a
+//! view keeps updating.
+//!
+//! [reactive_graph] takes a snapshot of them, with the update and run counts that are kept
+//! while [recording](record_reactive_graph), and its `Display` is a text dump of it. Floem has
+//! no inspector to show it in, so the snapshot is read or printed from the app's own code,
+//! like from a key handler that's only there in debug builds.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::{id::Id, runtime::RUNTIME};
+
+/// What's recorded about the reactive system while
+/// [recording](record_reactive_graph) is on
+#[derive(Default)]
+pub(crate) struct GraphRecorder {
+    pub(crate) recording: bool,
+    pub(crate) names: HashMap<Id, String>,
+    pub(crate) updates: HashMap<Id, u64>,
+    pub(crate) runs: HashMap<Id, u64>,
+}
+
+impl GraphRecorder {
+    pub(crate) fn signal_updated(&mut self, id: Id) {
+        if self.recording {
+            *self.updates.entry(id).or_default() += 1;
+        }
+    }
+
+    pub(crate) fn effect_ran(&mut self, id: Id) {
+        if self.recording {
+            *self.runs.entry(id).or_default() += 1;
+        }
+    }
+
+    pub(crate) fn signal_disposed(&mut self, id: Id) {
+        self.names.remove(&id);
+        self.updates.remove(&id);
+    }
+}
+
+/// Starts or stops counting the updates of the signals and the runs of the effects for
+/// [reactive_graph], which is off by default as it costs a little on every update.
+/// Stopping it drops the counts.
+pub fn record_reactive_graph(recording: bool) {
+    RUNTIME.with(|runtime| {
+        let mut graph = runtime.graph.borrow_mut();
+        graph.recording = recording;
+        if !recording {
+            graph.updates.clear();
+            graph.runs.clear();
+        }
+    });
+}
+
+/// Names the signal with `id` in the [ReactiveGraph]
+pub(crate) fn set_debug_name(id: Id, name: String) {
+    RUNTIME.with(|runtime| {
+        runtime.graph.borrow_mut().names.insert(id, name);
+    });
+}
+
+/// A signal in a [ReactiveGraph]
+#[derive(Clone, Debug)]
+pub struct SignalNode {
+    pub id: u64,
+    /// The name given with `debug_name`, if there's one
+    pub name: Option<String>,
+    /// The id of the Scope the signal belongs to
+    pub owner: Option<u64>,
+    /// How many times the signal was updated while recording
+    pub updates: u64,
+    /// The ids of the effects that rerun when the signal is updated
+    pub subscribers: Vec<u64>,
+}
+
+/// An effect in a [ReactiveGraph], which can also be a view's closure
+#[derive(Clone, Debug)]
+pub struct EffectNode {
+    pub id: u64,
+    /// The id of the Scope the effect belongs to
+    pub owner: Option<u64>,
+    /// How many times the effect ran while recording
+    pub runs: u64,
+    /// The ids of the signals the effect read the last time it ran
+    pub dependencies: Vec<u64>,
+}
+
+/// A snapshot of the signals of the current thread and of the effects that depend on
+/// them, taken by [reactive_graph]. Its `Display` lists the nodes that changed the most
+/// first, which helps finding out why a view keeps updating.
+#[derive(Clone, Debug, Default)]
+pub struct ReactiveGraph {
+    pub signals: Vec<SignalNode>,
+    pub effects: Vec<EffectNode>,
+}
+
+/// Takes a snapshot of the reactive system. The update and run counts are only there while
+/// [recording](record_reactive_graph).
+pub fn reactive_graph() -> ReactiveGraph {
+    RUNTIME.with(|runtime| {
+        let graph = runtime.graph.borrow();
+        let mut owners = HashMap::new();
+        for (scope, children) in runtime.children.borrow().iter() {
+            for child in children {
+                owners.insert(*child, scope.raw());
+            }
+        }
+
+        let mut dependencies: HashMap<Id, HashSet<u64>> = HashMap::new();
+        let mut signals: Vec<SignalNode> = runtime
+            .signals
+            .borrow()
+            .values()
+            // an effect reads its own Scope to keep itself alive, which isn't worth showing
+            .filter(|signal| !signal.subscribers.borrow().contains_key(&signal.id))
+            .map(|signal| {
+                let mut subscribers: Vec<u64> = signal
+                    .subscribers
+                    .borrow()
+                    .keys()
+                    .map(|effect| {
+                        dependencies
+                            .entry(*effect)
+                            .or_default()
+                            .insert(signal.id.raw());
+                        effect.raw()
+                    })
+                    .collect();
+                subscribers.sort_unstable();
+                SignalNode {
+                    id: signal.id.raw(),
+                    name: graph.names.get(&signal.id).cloned(),
+                    owner: owners.get(&signal.id).copied(),
+                    updates: graph.updates.get(&signal.id).copied().unwrap_or(0),
+                    subscribers,
+                }
+            })
+            .collect();
+        signals.sort_by(|a, b| b.updates.cmp(&a.updates).then(a.id.cmp(&b.id)));
+
+        let mut effects: Vec<EffectNode> = dependencies
+            .into_iter()
+            .map(|(effect, dependencies)| {
+                let mut dependencies: Vec<u64> = dependencies.into_iter().collect();
+                dependencies.sort_unstable();
+                EffectNode {
+                    id: effect.raw(),
+                    owner: owners.get(&effect).copied(),
+                    runs: graph.runs.get(&effect).copied().unwrap_or(0),
+                    dependencies,
+                }
+            })
+            .collect();
+        effects.sort_by(|a, b| b.runs.cmp(&a.runs).then(a.id.cmp(&b.id)));
+
+        ReactiveGraph { signals, effects }
+    })
+}
+
+fn write_ids(f: &mut fmt::Formatter<'_>, ids: &[u64]) -> fmt::Result {
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{id}")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for ReactiveGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for signal in &self.signals {
+            write!(f, "signal {}", signal.id)?;
+            if let Some(name) = &signal.name {
+                write!(f, " \"{name}\"")?;
+            }
+            if let Some(owner) = signal.owner {
+                write!(f, " in scope {owner}")?;
+            }
+            write!(f, ": {} updates, read by [", signal.updates)?;
+            write_ids(f, &signal.subscribers)?;
+            writeln!(f, "]")?;
+        }
+        for effect in &self.effects {
+            write!(f, "effect {}", effect.id)?;
+            if let Some(owner) = effect.owner {
+                write!(f, " in scope {owner}")?;
+            }
+            write!(f, ": {} runs, reads [", effect.runs)?;
+            write_ids(f, &effect.dependencies)?;
+            writeln!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reactive_graph, record_reactive_graph};
+    use crate::scope::Scope;
+
+    #[test]
+    fn snapshot_has_the_signals_and_the_effects_reading_them() {
+        let scope = Scope::new();
+        let count = scope.create_rw_signal(0).debug_name("count");
+        let label = scope.create_rw_signal("count").debug_name("label");
+        scope.create_effect(move |_| {
+            count.track();
+        });
+        scope.create_effect(move |_| {
+            count.track();
+            label.track();
+        });
+
+        record_reactive_graph(true);
+        count.set(1);
+        count.set(2);
+        let graph = reactive_graph();
+        record_reactive_graph(false);
+
+        let signal = |name: &str| {
+            graph
+                .signals
+                .iter()
+                .find(|signal| signal.name.as_deref() == Some(name))
+                .unwrap()
+        };
+        let count = signal("count");
+        let label = signal("label");
+        assert_eq!((count.updates, label.updates), (2, 0));
+        assert_eq!(count.owner, Some(scope.0.raw()));
+        assert_eq!(count.subscribers.len(), 2);
+        assert_eq!(label.subscribers.len(), 1);
+        // the signal updated the most comes first
+        assert_eq!(graph.signals[0].id, count.id);
+
+        assert_eq!(graph.effects.len(), 2);
+        for effect in &graph.effects {
+            assert_eq!(effect.owner, Some(scope.0.raw()));
+            assert_eq!(effect.runs, 2);
+            assert!(count.subscribers.contains(&effect.id));
+        }
+        let both = graph
+            .effects
+            .iter()
+            .find(|effect| effect.dependencies.len() == 2)
+            .unwrap();
+        assert_eq!(both.dependencies, [count.id, label.id]);
+        assert_eq!(label.subscribers, [both.id]);
+
+        let dump = graph.to_string();
+        assert!(dump.starts_with(&format!(
+            "signal {} \"count\" in scope {}: 2 updates, read by [",
+            count.id,
+            scope.0.raw()
+        )));
+        assert!(dump.contains(&format!(": 2 runs, reads [{}]\n", count.id)));
+    }
+}
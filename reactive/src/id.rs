@@ -13,6 +13,11 @@ impl Id {
         Id(COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
     }
 
+    /// The number of the id, which is what the graph snapshot refers to it by
+    pub(crate) fn raw(&self) -> u64 {
+        self.0
+    }
+
     /// Try to get the Signal that links with this Id
     pub(crate) fn signal(&self) -> Option<Signal> {
         RUNTIME.with(|runtime| runtime.signals.borrow().get(self).cloned())
@@ -26,20 +31,33 @@ impl Id {
     /// Make this Id a child of the current Scope
     pub(crate) fn set_scope(&self) {
         RUNTIME.with(|runtime| {
-            let scope = runtime.current_scope.borrow();
-            let mut children = runtime.children.borrow_mut();
-            let children = children.entry(*scope).or_default();
-            children.insert(*self);
+            let scope = *runtime.current_scope.borrow();
+            runtime.add_child(scope, *self);
         });
     }
 
     /// Dispose the relevant resources that's linking to this Id, and the all the children
-    /// and grandchildren.
+    /// and grandchildren, and detach it from its parent.
     pub(crate) fn dispose(&self) {
-        if let Ok((children, signal)) = RUNTIME.try_with(|runtime| {
+        let _ = RUNTIME.try_with(|runtime| {
+            if let Some(parent) = runtime.parents.borrow_mut().remove(self) {
+                if let Some(children) = runtime.children.borrow_mut().get_mut(&parent) {
+                    children.remove(self);
+                }
+            }
+        });
+        self.dispose_owned();
+    }
+
+    /// Dispose what's linking to this Id like [dispose](Id::dispose), but keep it attached
+    /// to its parent, as an effect does each time it runs.
+    pub(crate) fn dispose_owned(&self) {
+        if let Ok((children, signal, cleanups)) = RUNTIME.try_with(|runtime| {
+            runtime.contexts.borrow_mut().remove(self);
             (
                 runtime.children.borrow_mut().remove(self),
                 runtime.signals.borrow_mut().remove(self),
+                runtime.cleanups.borrow_mut().remove(self),
             )
         }) {
             if let Some(children) = children {
@@ -48,7 +66,15 @@ impl Id {
                 }
             }
 
+            if let Some(cleanups) = cleanups {
+                for cleanup in cleanups {
+                    cleanup();
+                }
+            }
+
             if let Some(signal) = signal {
+                let _ =
+                    RUNTIME.try_with(|runtime| runtime.graph.borrow_mut().signal_disposed(*self));
                 for (_, effect) in signal.subscribers() {
                     observer_clean_up(&effect);
                 }
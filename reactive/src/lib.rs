@@ -1,15 +1,21 @@
 mod context;
 mod effect;
+mod graph;
 mod id;
 mod memo;
 mod runtime;
 mod scope;
 mod signal;
+mod signal_vec;
+mod store;
 mod trigger;
 
 pub use context::{provide_context, use_context};
-pub use effect::{create_effect, untrack};
+pub use effect::{batch, create_effect, create_watch, untrack, SignalTracker};
+pub use graph::{reactive_graph, record_reactive_graph, EffectNode, ReactiveGraph, SignalNode};
 pub use memo::{create_memo, Memo};
-pub use scope::{as_child_of_current_scope, with_scope, Scope};
+pub use scope::{as_child_of_current_scope, on_cleanup, with_scope, Scope};
 pub use signal::{create_rw_signal, create_signal, ReadSignal, RwSignal, WriteSignal};
+pub use signal_vec::{create_signal_vec, RwSignalVec, VecChange};
+pub use store::{create_store, Store, StoreField};
 pub use trigger::{create_trigger, Trigger};
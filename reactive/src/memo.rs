@@ -72,6 +72,10 @@ impl<T> Memo<T> {
 
 /// Create a Memo which takes the computed value of the given function, and triggers
 /// the reactive system when the computed value is different with the last computed value.
+///
+/// The closure gets the last computed value, which is `None` on the first run. It reruns
+/// whenever a signal it reads changes, but the effects that read the Memo only rerun when
+/// the new value isn't equal to the last one.
 pub fn create_memo<T>(f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
 where
     T: PartialEq + 'static,
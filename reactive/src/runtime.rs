@@ -1,24 +1,36 @@
 use std::{
     any::{Any, TypeId},
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
     rc::Rc,
 };
 
-use crate::{effect::EffectTrait, id::Id, signal::Signal};
+use crate::{effect::EffectTrait, graph::GraphRecorder, id::Id, signal::Signal};
 
 thread_local! {
     pub(crate) static RUNTIME: Runtime = Runtime::new();
 }
 
+/// The values provided to each Scope, by their type
+type Contexts = HashMap<Id, HashMap<TypeId, Box<dyn Any>>>;
+/// What runs when each Scope is disposed
+type Cleanups = HashMap<Id, Vec<Box<dyn FnOnce()>>>;
+
 /// The internal reactive Runtime which stores all the reactive system states in a
 /// thread local
 pub(crate) struct Runtime {
     pub(crate) current_effect: RefCell<Option<Rc<dyn EffectTrait>>>,
     pub(crate) current_scope: RefCell<Id>,
+    /// The first Scope, whose contexts can be used from any Scope
+    pub(crate) root_scope: Id,
     pub(crate) children: RefCell<HashMap<Id, HashSet<Id>>>,
+    pub(crate) parents: RefCell<HashMap<Id, Id>>,
     pub(crate) signals: RefCell<HashMap<Id, Signal>>,
-    pub(crate) contexts: RefCell<HashMap<TypeId, Box<dyn Any>>>,
+    pub(crate) contexts: RefCell<Contexts>,
+    pub(crate) cleanups: RefCell<Cleanups>,
+    pub(crate) batching: Cell<bool>,
+    pub(crate) pending_effects: RefCell<Vec<Rc<dyn EffectTrait>>>,
+    pub(crate) graph: RefCell<GraphRecorder>,
 }
 
 impl Default for Runtime {
@@ -29,12 +41,37 @@ impl Default for Runtime {
 
 impl Runtime {
     pub(crate) fn new() -> Self {
+        let root_scope = Id::next();
         Self {
             current_effect: RefCell::new(None),
-            current_scope: RefCell::new(Id::next()),
+            current_scope: RefCell::new(root_scope),
+            root_scope,
             children: RefCell::new(HashMap::new()),
+            parents: RefCell::new(HashMap::new()),
             signals: Default::default(),
             contexts: Default::default(),
+            cleanups: Default::default(),
+            batching: Cell::new(false),
+            pending_effects: RefCell::new(Vec::new()),
+            graph: Default::default(),
+        }
+    }
+
+    pub(crate) fn add_child(&self, parent: Id, child: Id) {
+        self.children
+            .borrow_mut()
+            .entry(parent)
+            .or_default()
+            .insert(child);
+        self.parents.borrow_mut().insert(child, parent);
+    }
+
+    /// Queues `effect` to run at the end of the current batch, once however many of its
+    /// signals are updated
+    pub(crate) fn add_pending_effect(&self, effect: Rc<dyn EffectTrait>) {
+        let mut pending = self.pending_effects.borrow_mut();
+        if !pending.iter().any(|pending| pending.id() == effect.id()) {
+            pending.push(effect);
         }
     }
 }
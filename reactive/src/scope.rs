@@ -45,11 +45,7 @@ impl Scope {
     /// Create a child Scope of this Scope
     pub fn create_child(&self) -> Scope {
         let child = Id::next();
-        RUNTIME.with(|runtime| {
-            let mut children = runtime.children.borrow_mut();
-            let children = children.entry(self.0).or_default();
-            children.insert(child);
-        });
+        RUNTIME.with(|runtime| runtime.add_child(self.0, child));
         Scope(child)
     }
 
@@ -135,6 +131,23 @@ where
     result
 }
 
+/// Registers `f` to run when the current Scope is disposed, for dropping what was set up
+/// under it, like a timer or a subscription made by a view that's later removed.
+///
+/// Inside an effect, the Scope is the effect's own, so `f` also runs before each time the
+/// effect runs again.
+pub fn on_cleanup(f: impl FnOnce() + 'static) {
+    RUNTIME.with(|runtime| {
+        let scope = *runtime.current_scope.borrow();
+        runtime
+            .cleanups
+            .borrow_mut()
+            .entry(scope)
+            .or_default()
+            .push(Box::new(f));
+    });
+}
+
 /// Wrap the closure so that whenever the closure runs, it will be under a child Scope
 /// of the current Scope
 pub fn as_child_of_current_scope<T, U>(f: impl Fn(T) -> U + 'static) -> impl Fn(T) -> (U, Scope)
@@ -160,3 +173,68 @@ where
         (result, scope)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use super::{on_cleanup, with_scope, Scope};
+    use crate::{effect::create_effect, runtime::RUNTIME, signal::create_rw_signal};
+
+    #[test]
+    fn cleanups_run_when_the_scope_is_disposed() {
+        let cleaned = Rc::new(Cell::new(0));
+        let scope = Scope::new();
+        {
+            let cleaned = cleaned.clone();
+            with_scope(scope.create_child(), move || {
+                on_cleanup(move || cleaned.set(cleaned.get() + 1));
+            });
+        }
+        assert_eq!(cleaned.get(), 0);
+
+        // disposing a Scope disposes its children too
+        scope.dispose();
+        assert_eq!(cleaned.get(), 1);
+    }
+
+    #[test]
+    fn cleanups_of_an_effect_run_before_it_runs_again() {
+        let signal = create_rw_signal(0);
+        let cleaned = Rc::new(RefCell::new(Vec::new()));
+        {
+            let cleaned = cleaned.clone();
+            create_effect(move |_| {
+                let value = signal.get();
+                let cleaned = cleaned.clone();
+                on_cleanup(move || cleaned.borrow_mut().push(value));
+            });
+        }
+        assert!(cleaned.take().is_empty());
+
+        signal.set(1);
+        assert_eq!(cleaned.take(), [0]);
+        signal.set(2);
+        assert_eq!(cleaned.take(), [1]);
+    }
+
+    #[test]
+    fn disposed_scopes_are_detached_from_their_parent() {
+        let scope = Scope::new();
+        let child = scope.create_child();
+        let grandchild = child.create_child();
+
+        child.dispose();
+        RUNTIME.with(|runtime| {
+            let parents = runtime.parents.borrow();
+            assert!(!parents.contains_key(&child.0));
+            assert!(!parents.contains_key(&grandchild.0));
+            let children = runtime.children.borrow();
+            assert!(!children[&scope.0].contains(&child.0));
+            assert!(!children.contains_key(&child.0));
+        });
+    }
+}
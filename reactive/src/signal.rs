@@ -1,7 +1,15 @@
-use std::{any::Any, cell::RefCell, collections::HashMap, fmt, marker::PhantomData, rc::Rc};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    marker::PhantomData,
+    rc::Rc,
+};
 
 use crate::{
-    effect::{run_effect, EffectTrait},
+    effect::{create_effect, run_effect, EffectTrait},
+    graph::set_debug_name,
     id::Id,
     runtime::RUNTIME,
 };
@@ -59,12 +67,12 @@ impl<T> RwSignal<T> {
     }
 
     /// Update the stored value with the given function, triggers effect run,
-    /// and returns the value returned by the function
+    /// and returns the value returned by the function, or None when the Signal was disposed
     pub fn try_update<O>(&self, f: impl FnOnce(&mut T) -> O) -> Option<O>
     where
         T: 'static,
     {
-        let signal = self.id.signal().unwrap();
+        let signal = self.id.signal()?;
         signal_update_value(&signal, f)
     }
 
@@ -107,6 +115,61 @@ impl<T> RwSignal<T> {
         signal.subscribe();
     }
 
+    /// Names the Signal in the [ReactiveGraph](crate::ReactiveGraph), for telling it apart
+    /// from the others
+    pub fn debug_name(self, name: impl Into<String>) -> Self {
+        set_debug_name(self.id, name.into());
+        self
+    }
+
+    /// Creates a Signal with a view of this one's value, made by `getter`, which writes back
+    /// to this one with `setter` when it's set. A `String` view of an `f64` can be bound to
+    /// a text input this way, which then edits the number.
+    ///
+    /// A change to this Signal updates the derived one, unless the change was the derived
+    /// one being written back, so what's typed isn't replaced by the formatted value of what
+    /// was parsed from it.
+    pub fn derive_rw<U>(
+        &self,
+        getter: impl Fn(&T) -> U + 'static,
+        setter: impl Fn(&mut T, &U) + 'static,
+    ) -> RwSignal<U>
+    where
+        T: 'static,
+        U: PartialEq + Clone + 'static,
+    {
+        let source = *self;
+        let derived = create_rw_signal(source.with_untracked(&getter));
+        let writing_back = Rc::new(Cell::new(false));
+        // so the derived Signal being updated from this one isn't written back to it
+        let syncing = Rc::new(Cell::new(false));
+
+        {
+            let writing_back = writing_back.clone();
+            let syncing = syncing.clone();
+            create_effect(move |_| {
+                let value = source.with(&getter);
+                if writing_back.replace(false) {
+                    return;
+                }
+                if derived.with_untracked(|derived| *derived != value) {
+                    syncing.set(true);
+                    derived.set(value);
+                    syncing.set(false);
+                }
+            });
+        }
+        create_effect(move |first_run: Option<()>| {
+            let value = derived.get();
+            if first_run.is_some() && !syncing.get() {
+                writing_back.set(true);
+                source.update(|source| setter(source, &value));
+            }
+        });
+
+        derived
+    }
+
     /// Create a Getter of this Signal
     pub fn read_only(&self) -> ReadSignal<T> {
         ReadSignal {
@@ -222,6 +285,13 @@ impl<T: Clone> ReadSignal<T> {
 }
 
 impl<T> ReadSignal<T> {
+    /// Names the Signal in the [ReactiveGraph](crate::ReactiveGraph), for telling it apart
+    /// from the others
+    pub fn debug_name(self, name: impl Into<String>) -> Self {
+        set_debug_name(self.id, name.into());
+        self
+    }
+
     /// Applies a clsoure to the current value stored in the Signal, and subcribes
     /// to the current runnig effect to this Memo.
     pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O
@@ -347,8 +417,13 @@ impl Signal {
     }
 
     pub(crate) fn run_effects(&self) {
+        let batching = RUNTIME.with(|runtime| runtime.batching.get());
         for (_, subscriber) in self.subscribers() {
-            run_effect(subscriber);
+            if batching {
+                RUNTIME.with(|runtime| runtime.add_pending_effect(subscriber));
+            } else {
+                run_effect(subscriber);
+            }
         }
     }
 
@@ -387,6 +462,7 @@ fn signal_with_untracked<O, T: 'static>(signal: &Signal, f: impl FnOnce(&T) -> O
 }
 
 fn signal_update_value<U, T: 'static>(signal: &Signal, f: impl FnOnce(&mut T) -> U) -> Option<U> {
+    RUNTIME.with(|runtime| runtime.graph.borrow_mut().signal_updated(signal.id));
     let result = {
         let mut value = signal.value.borrow_mut();
         value.downcast_mut::<T>().map(f)
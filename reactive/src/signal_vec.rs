@@ -0,0 +1,217 @@
+use std::rc::Rc;
+
+use crate::signal::{create_rw_signal, RwSignal};
+
+/// One edit of a [RwSignalVec], as passed to the functions registered with
+/// [on_change](RwSignalVec::on_change)
+#[derive(Clone, Debug, PartialEq)]
+pub enum VecChange<T> {
+    Insert {
+        at: usize,
+        value: T,
+    },
+    Remove {
+        at: usize,
+    },
+    Swap {
+        a: usize,
+        b: usize,
+    },
+    /// All the items were replaced by these ones
+    Reset(Vec<T>),
+}
+
+type Listener<T> = (RwSignal<()>, Rc<dyn Fn(&VecChange<T>)>);
+
+/// A Vec held in the reactive system which tells about each of its edits, so a view
+/// showing its items can add or remove the one view that changed, instead of comparing
+/// the whole Vec with the last one.
+///
+/// Reading it, like with [with](RwSignalVec::with) or [len](RwSignalVec::len), subscribes
+/// to every edit, like a Signal does. Once the Scope it was created in is disposed, edits
+/// are dropped without telling anyone.
+pub struct RwSignalVec<T> {
+    values: RwSignal<Vec<T>>,
+    listeners: RwSignal<Vec<Listener<T>>>,
+}
+
+impl<T> Copy for RwSignalVec<T> {}
+
+impl<T> Clone for RwSignalVec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone + 'static> RwSignalVec<T> {
+    /// Applies a closure to the items, and subscribes the current running effect to every
+    /// edit of them.
+    pub fn with<O>(&self, f: impl FnOnce(&Vec<T>) -> O) -> O {
+        self.values.with(f)
+    }
+
+    pub fn with_untracked<O>(&self, f: impl FnOnce(&Vec<T>) -> O) -> O {
+        self.values.with_untracked(f)
+    }
+
+    /// Clones and returns the items, and subscribes the current running effect to every
+    /// edit of them.
+    pub fn get(&self) -> Vec<T> {
+        self.values.get()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.with(|values| values.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push(&self, value: T) {
+        let at = self.untracked_len();
+        self.insert(at, value);
+    }
+
+    /// Inserts `value` at `at`, moving the items after it
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than the length
+    pub fn insert(&self, at: usize, value: T) {
+        self.values
+            .update(|values| values.insert(at, value.clone()));
+        self.notify(VecChange::Insert { at, value });
+    }
+
+    /// Removes and returns the item at `at`, moving the items after it
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is out of bounds, or the vec was disposed
+    pub fn remove(&self, at: usize) -> T {
+        let value = self
+            .values
+            .try_update(|values| values.remove(at))
+            .expect("to remove from a signal vec");
+        self.notify(VecChange::Remove { at });
+        value
+    }
+
+    /// Removes and returns the last item, if there's one
+    pub fn pop(&self) -> Option<T> {
+        let len = self.untracked_len();
+        (len > 0).then(|| self.remove(len - 1))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds
+    pub fn swap(&self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        self.values.update(|values| values.swap(a, b));
+        self.notify(VecChange::Swap { a, b });
+    }
+
+    /// Replaces all the items
+    pub fn set(&self, values: Vec<T>) {
+        self.values.set(values.clone());
+        self.notify(VecChange::Reset(values));
+    }
+
+    pub fn clear(&self) {
+        self.set(Vec::new());
+    }
+
+    /// Registers `f` to be called with each edit, after it's been made, until the current
+    /// Scope is disposed
+    pub fn on_change(&self, f: impl Fn(&VecChange<T>) + 'static) {
+        let alive = create_rw_signal(());
+        self.listeners
+            .update(|listeners| listeners.push((alive, Rc::new(f))));
+    }
+
+    /// The length, which is 0 once disposed
+    fn untracked_len(&self) -> usize {
+        self.values
+            .try_with_untracked(|values| values.map_or(0, |values| values.len()))
+    }
+
+    fn notify(&self, change: VecChange<T>) {
+        let listeners = self.listeners.try_update(|listeners| {
+            listeners.retain(|(alive, _)| alive.try_get_untracked().is_some());
+            listeners.iter().map(|(_, f)| f.clone()).collect::<Vec<_>>()
+        });
+        for f in listeners.into_iter().flatten() {
+            f(&change);
+        }
+    }
+}
+
+/// Creates a [RwSignalVec] holding `values`
+pub fn create_signal_vec<T: 'static>(values: Vec<T>) -> RwSignalVec<T> {
+    RwSignalVec {
+        values: create_rw_signal(values),
+        listeners: create_rw_signal(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{create_signal_vec, RwSignalVec, VecChange};
+    use crate::scope::{with_scope, Scope};
+
+    /// Collects the edits `vec` tells its listeners about
+    fn changes(vec: RwSignalVec<u32>) -> Rc<RefCell<Vec<VecChange<u32>>>> {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let collected = changes.clone();
+        vec.on_change(move |change| collected.borrow_mut().push(change.clone()));
+        changes
+    }
+
+    #[test]
+    fn edits_tell_only_about_the_index_they_change() {
+        let vec = create_signal_vec(vec![1, 2, 3]);
+        let changes = changes(vec);
+
+        vec.push(4);
+        assert_eq!(changes.take(), [VecChange::Insert { at: 3, value: 4 }]);
+        assert_eq!(vec.remove(1), 2);
+        assert_eq!(changes.take(), [VecChange::Remove { at: 1 }]);
+        vec.swap(0, 0);
+        assert!(changes.take().is_empty());
+        vec.set(vec![5]);
+        assert_eq!(changes.take(), [VecChange::Reset(vec![5])]);
+        assert_eq!(vec.get(), [5]);
+    }
+
+    #[test]
+    fn edits_are_dropped_once_disposed() {
+        let scope = Scope::new();
+        let vec = with_scope(scope, || create_signal_vec(vec![1]));
+        let changes = changes(vec);
+
+        // a listener stops being called once the Scope it was registered in is disposed
+        let listener_scope = Scope::new();
+        let dropped = Rc::new(RefCell::new(Vec::new()));
+        {
+            let dropped = dropped.clone();
+            with_scope(listener_scope, move || {
+                vec.on_change(move |change| dropped.borrow_mut().push(change.clone()))
+            });
+        }
+        listener_scope.dispose();
+        vec.push(2);
+        assert_eq!(changes.take(), [VecChange::Insert { at: 1, value: 2 }]);
+        assert!(dropped.take().is_empty());
+
+        scope.dispose();
+        vec.push(3);
+        vec.set(vec![4]);
+        assert!(changes.take().is_empty());
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use crate::{
+    effect::batch,
+    scope::Scope,
+    signal::{create_rw_signal, RwSignal},
+    trigger::Trigger,
+};
+
+/// A struct kept in the reactive system whose fields can be subscribed to one by one.
+///
+/// Reading the whole Store subscribes to every change of it, like a Signal does, while
+/// reading a [StoreField] only subscribes to the changes of that field, so a view bound to
+/// one field isn't updated when another one is edited.
+pub struct Store<T> {
+    value: RwSignal<T>,
+    /// The trigger of each field, by its key
+    fields: RwSignal<HashMap<&'static str, Trigger>>,
+    scope: Scope,
+}
+
+impl<T> Copy for Store<T> {}
+
+impl<T> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Store<T> {
+    /// A lens to the field that `get` and `get_mut` borrow, which are usually closures like
+    /// `|s| &s.name` and `|s| &mut s.name`, named by `key`.
+    ///
+    /// The fields with the same key share their subscribers, so getting a field again, like
+    /// in each row of a list, notifies the same effects as the first one. A key is meant to
+    /// name one field only.
+    pub fn field<U: 'static>(
+        &self,
+        key: &'static str,
+        get: fn(&T) -> &U,
+        get_mut: fn(&mut T) -> &mut U,
+    ) -> StoreField<T, U> {
+        let trigger = self
+            .fields
+            .with_untracked(|fields| fields.get(key).copied());
+        let trigger = trigger.unwrap_or_else(|| {
+            // made in the Scope of the Store, so it outlives the Scope the field is got in
+            let trigger = self.scope.create_trigger();
+            self.fields.update(|fields| {
+                fields.insert(key, trigger);
+            });
+            trigger
+        });
+        StoreField {
+            store: *self,
+            trigger,
+            get,
+            get_mut,
+        }
+    }
+
+    /// Applies a closure to the whole struct, and subscribes the current running effect to
+    /// every change of it.
+    pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        self.value.with(f)
+    }
+
+    /// Applies a closure to the whole struct, but it doesn't subscribe the current running
+    /// effect.
+    pub fn with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        self.value.with_untracked(f)
+    }
+
+    /// Updates the whole struct, which notifies the subscribers of every field
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        batch(|| {
+            self.value.update(f);
+            self.fields.with_untracked(|fields| {
+                for field in fields.values() {
+                    field.notify();
+                }
+            });
+        });
+    }
+
+    pub fn set(&self, value: T) {
+        self.update(|current| *current = value);
+    }
+}
+
+impl<T: Clone + 'static> Store<T> {
+    /// Clones and returns the whole struct, and subscribes the current running effect to
+    /// every change of it.
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    pub fn get_untracked(&self) -> T {
+        self.value.get_untracked()
+    }
+}
+
+/// Creates a [Store] holding `value`
+pub fn create_store<T: 'static>(value: T) -> Store<T> {
+    Store {
+        value: create_rw_signal(value),
+        fields: create_rw_signal(HashMap::new()),
+        scope: Scope::current(),
+    }
+}
+
+/// A field of a [Store], made by [Store::field], which can be read and written on its own
+pub struct StoreField<T, U> {
+    store: Store<T>,
+    trigger: Trigger,
+    get: fn(&T) -> &U,
+    get_mut: fn(&mut T) -> &mut U,
+}
+
+impl<T, U> Copy for StoreField<T, U> {}
+
+impl<T, U> Clone for StoreField<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, U: 'static> StoreField<T, U> {
+    /// Applies a closure to the field, and subscribes the current running effect to the
+    /// changes of this field only.
+    pub fn with<O>(&self, f: impl FnOnce(&U) -> O) -> O {
+        self.trigger.track();
+        self.with_untracked(f)
+    }
+
+    pub fn with_untracked<O>(&self, f: impl FnOnce(&U) -> O) -> O {
+        self.store
+            .value
+            .with_untracked(|value| f((self.get)(value)))
+    }
+
+    /// Updates the field, which notifies its own subscribers and the ones of the whole
+    /// [Store], but not the ones of the other fields
+    pub fn update(&self, f: impl FnOnce(&mut U)) {
+        batch(|| {
+            self.store.value.update(|value| f((self.get_mut)(value)));
+            self.trigger.notify();
+        });
+    }
+
+    pub fn set(&self, value: U) {
+        self.update(|current| *current = value);
+    }
+}
+
+impl<T: 'static, U: Clone + 'static> StoreField<T, U> {
+    /// Clones and returns the field, and subscribes the current running effect to the
+    /// changes of this field only.
+    pub fn get(&self) -> U {
+        self.with(U::clone)
+    }
+
+    pub fn get_untracked(&self) -> U {
+        self.with_untracked(U::clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::create_store;
+    use crate::{
+        effect::create_effect,
+        scope::{with_scope, Scope},
+    };
+
+    #[derive(Clone)]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    /// Counts the runs of an effect that calls `read`
+    fn runs(read: impl Fn() + 'static) -> Rc<Cell<usize>> {
+        let runs = Rc::new(Cell::new(0));
+        let counted = runs.clone();
+        create_effect(move |_| {
+            read();
+            counted.set(counted.get() + 1);
+        });
+        runs
+    }
+
+    #[test]
+    fn fields_notify_their_own_subscribers() {
+        let store = create_store(Person {
+            name: "Ada".to_string(),
+            age: 36,
+        });
+        let name = store.field("name", |p| &p.name, |p| &mut p.name);
+        let age = store.field("age", |p| &p.age, |p| &mut p.age);
+        let name_runs = runs(move || name.with(|_| ()));
+        let age_runs = runs(move || age.with(|_| ()));
+        let store_runs = runs(move || store.with(|_| ()));
+
+        age.set(37);
+        assert_eq!(
+            (name_runs.get(), age_runs.get(), store_runs.get()),
+            (1, 2, 2)
+        );
+        assert_eq!(store.get_untracked().age, 37);
+
+        // updating the whole store notifies every field
+        store.update(|p| p.name.push('!'));
+        assert_eq!(
+            (name_runs.get(), age_runs.get(), store_runs.get()),
+            (2, 3, 3)
+        );
+        assert_eq!(name.get_untracked(), "Ada!");
+    }
+
+    #[test]
+    fn fields_with_a_key_share_their_subscribers() {
+        let store = create_store(Person {
+            name: "Ada".to_string(),
+            age: 36,
+        });
+        let first = store.field("age", |p| &p.age, |p| &mut p.age);
+        let first_runs = runs(move || first.with(|_| ()));
+
+        // a field got again in a Scope that's gone still notifies the first one's effects
+        let scope = Scope::new();
+        let second = with_scope(scope, move || {
+            store.field("age", |p| &p.age, |p| &mut p.age)
+        });
+        scope.dispose();
+        second.set(37);
+        assert_eq!(first_runs.get(), 2);
+        assert_eq!(store.fields.with_untracked(|fields| fields.len()), 1);
+    }
+}
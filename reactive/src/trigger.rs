@@ -1,5 +1,8 @@
 use crate::signal::{create_rw_signal, RwSignal};
 
+/// A Signal without a value, for making the effects that [track](Trigger::track) it run
+/// again when [notified](Trigger::notify), like when some state outside of the reactive
+/// system, such as a file, has changed and has to be read again.
 #[derive(Debug)]
 pub struct Trigger {
     signal: RwSignal<()>,
@@ -14,15 +17,18 @@ impl Clone for Trigger {
 }
 
 impl Trigger {
+    /// Makes the effects that track the Trigger run again
     pub fn notify(&self) {
         self.signal.set(());
     }
 
+    /// Subscribes the current running effect to the Trigger
     pub fn track(&self) {
         self.signal.with(|_| {});
     }
 }
 
+/// Creates a [Trigger] in the current Scope
 pub fn create_trigger() -> Trigger {
     Trigger {
         signal: create_rw_signal(()),
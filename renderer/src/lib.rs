@@ -29,6 +29,12 @@ pub trait Renderer {
 
     fn clear_clip(&mut self);
 
+    /// Clip to a [`Shape`] inside of the current clip, where [`clip`](Renderer::clip) replaces it.
+    fn intersect_clip(&mut self, shape: &impl Shape);
+
+    /// Clip to the alpha channel of an image stretched over `rect`, inside of the current clip.
+    fn intersect_clip_img(&mut self, img: Img<'_>, rect: Rect);
+
     /// Stroke a [`Shape`].
     fn stroke<'b>(&mut self, shape: &impl Shape, brush: impl Into<BrushRef<'b>>, width: f64);
 
@@ -41,11 +47,35 @@ pub trait Renderer {
     ///
     /// The `pos` parameter specifies the upper-left corner of the layout object
     /// (even for right-to-left text).
-    fn draw_text(&mut self, layout: &TextLayout, pos: impl Into<Point>);
+    fn draw_text(&mut self, layout: &TextLayout, pos: impl Into<Point>) {
+        self.draw_text_spaced(layout, pos, 0.0);
+    }
+
+    /// Draw a [`TextLayout`] like [`draw_text`](Renderer::draw_text), moving each glyph of a
+    /// line `letter_spacing` further than the one before it.
+    fn draw_text_spaced(&mut self, layout: &TextLayout, pos: impl Into<Point>, letter_spacing: f64);
 
     fn draw_svg<'b>(&mut self, svg: Svg<'b>, rect: Rect, brush: Option<impl Into<BrushRef<'b>>>);
 
     fn draw_img(&mut self, img: Img<'_>, width: u32, height: u32, rect: Rect);
 
+    /// Draw an image that's `width` by `height` pixels stretched over `rect`, where the data of
+    /// `img` is the RGBA8 pixels of the image instead of an encoded image.
+    ///
+    /// The pixels are kept under `img.hash` until they're drawn with another `version`, which
+    /// replaces them, so pixels that keep changing, like the frames of a video, reuse the one
+    /// image instead of adding a new one for each change.
+    fn draw_pixels(&mut self, img: Img<'_>, version: u64, width: u32, height: u32, rect: Rect);
+
+    /// Draw everything until the matching [`pop_layer`](Renderer::pop_layer) into a layer of
+    /// its own, which is then blurred by `blur` and composited with `alpha`.
+    fn push_layer(&mut self, alpha: f32, blur: f64);
+
+    /// Composite the layer of the last [`push_layer`](Renderer::push_layer)
+    fn pop_layer(&mut self);
+
+    /// Blur by `radius` what's already drawn inside of `shape`.
+    fn blur_backdrop(&mut self, shape: &impl Shape, radius: f64);
+
     fn finish(&mut self);
 }
@@ -1,17 +1,15 @@
 use std::{
-    path::PathBuf,
     sync::atomic::AtomicU64,
     time::{Duration, Instant},
 };
 
-use floem_reactive::Scope;
 use kurbo::{Point, Size, Vec2};
 use winit::window::ResizeDirection;
 
 use crate::{
     app::{add_app_update_event, AppUpdateEvent},
-    ext_event::create_ext_action,
     file::{FileDialogOptions, FileInfo},
+    keyboard::InputPurpose,
     menu::Menu,
     update::{UpdateMessage, CENTRAL_UPDATE_MESSAGES},
     window_handle::{get_current_view, set_current_view},
@@ -103,39 +101,42 @@ pub fn exec_after(duration: Duration, action: impl FnOnce(TimerToken) + 'static)
     token
 }
 
+/// Shows a dialog for picking a file, see [dialogs::open_file](crate::dialogs::open_file)
+/// for picking several of them
 pub fn open_file(
     options: FileDialogOptions,
     file_info_action: impl Fn(Option<FileInfo>) + 'static,
 ) {
-    let send = create_ext_action(Scope::new(), move |path: Option<PathBuf>| {
-        file_info_action(path.map(|path| FileInfo { path, format: None }))
-    });
-    std::thread::spawn(move || {
-        let mut dialog = rfd::FileDialog::new();
-        if let Some(path) = options.starting_directory.as_ref() {
-            dialog = dialog.set_directory(path);
-        }
-        let path = if options.select_directories {
-            dialog.pick_folder()
-        } else {
-            dialog.pick_file()
-        };
-        send(path);
+    crate::dialogs::open_file(options, move |files| {
+        file_info_action(files.into_iter().next())
     });
 }
 
+/// Shows a dialog for picking where to save a file, like
+/// [dialogs::save_file](crate::dialogs::save_file)
 pub fn save_as(options: FileDialogOptions, file_info_action: impl Fn(Option<FileInfo>) + 'static) {
-    let send = create_ext_action(Scope::new(), move |path: Option<PathBuf>| {
-        file_info_action(path.map(|path| FileInfo { path, format: None }))
-    });
-    std::thread::spawn(move || {
-        let mut dialog = rfd::FileDialog::new();
-        if let Some(path) = options.starting_directory.as_ref() {
-            dialog = dialog.set_directory(path);
-        }
-        let path = dialog.save_file();
-        send(path);
-    });
+    crate::dialogs::save_file(options, file_info_action);
+}
+
+/// Opens `url` in the default browser, or in whatever the system opens that kind of URL
+/// with. The browser is started in the background, so this only fails if it couldn't be
+/// started at all.
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    let mut child = command.arg(url).spawn()?;
+    // reaped once the opener exits, which it does once it's handed the url over
+    std::thread::spawn(move || child.wait());
+    Ok(())
 }
 
 pub fn show_context_menu(menu: Menu, pos: Option<Point>) {
@@ -161,3 +162,28 @@ pub fn set_ime_allowed(allowed: bool) {
 pub fn set_ime_cursor_area(position: Point, size: Size) {
     add_update_message(UpdateMessage::SetImeCursorArea { position, size });
 }
+
+pub fn set_ime_purpose(purpose: InputPurpose) {
+    add_update_message(UpdateMessage::SetImePurpose { purpose });
+}
+
+/// Runs `action` with the winit window of the current view, if it has one
+#[cfg(feature = "webview")]
+pub(crate) fn with_window(action: impl FnOnce(&winit::window::Window) + 'static) {
+    add_update_message(UpdateMessage::WithWindow {
+        action: Box::new(action),
+    });
+}
+
+/// Allows IME input in the window, which is as close as winit gets to showing the
+/// on-screen keyboard. winit has no call for the keyboard itself, so whether one shows up
+/// is left to the platform.
+pub fn show_soft_keyboard() {
+    set_ime_allowed(true);
+}
+
+/// Stops IME input in the window, the counterpart of [show_soft_keyboard]. Like it, this
+/// leaves it to the platform whether an on-screen keyboard goes away.
+pub fn hide_soft_keyboard() {
+    set_ime_allowed(false);
+}
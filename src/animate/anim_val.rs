@@ -3,20 +3,23 @@ use peniko::Color;
 #[derive(Debug, Clone)]
 pub enum AnimValue {
     Float(f64),
+    /// A size as a percentage of the parent's, for widths and heights that should end up
+    /// following the parent instead of staying fixed
+    Pct(f64),
     Color(Color),
 }
 
 impl AnimValue {
     pub fn get_f32(self) -> f32 {
         match self {
-            AnimValue::Float(v) => v as f32,
+            AnimValue::Float(v) | AnimValue::Pct(v) => v as f32,
             AnimValue::Color(_) => panic!(),
         }
     }
 
     pub fn get_f64(self) -> f64 {
         match self {
-            AnimValue::Float(v) => v,
+            AnimValue::Float(v) | AnimValue::Pct(v) => v,
             AnimValue::Color(_) => panic!(),
         }
     }
@@ -24,7 +27,7 @@ impl AnimValue {
     pub fn get_color(self) -> Color {
         match self {
             AnimValue::Color(c) => c,
-            AnimValue::Float(_) => panic!(),
+            AnimValue::Float(_) | AnimValue::Pct(_) => panic!(),
         }
     }
 }
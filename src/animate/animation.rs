@@ -169,6 +169,55 @@ impl Animation {
         self
     }
 
+    /// Animates the width towards a percentage of the parent's width. Once the animation
+    /// finishes the view keeps following the parent's width, like `width_pct` in a style.
+    pub fn width_pct(self, width_fn: impl Fn() -> f64 + 'static) -> Self {
+        create_effect(move |_| {
+            let to_width = width_fn();
+
+            self.id
+                .update_prop(AnimPropKind::Width, AnimValue::Pct(to_width));
+        });
+
+        self
+    }
+
+    /// Animates the height towards a percentage of the parent's height
+    pub fn height_pct(self, height_fn: impl Fn() -> f64 + 'static) -> Self {
+        create_effect(move |_| {
+            let height = height_fn();
+
+            self.id
+                .update_prop(AnimPropKind::Height, AnimValue::Pct(height));
+        });
+
+        self
+    }
+
+    /// Animates the padding on all sides, starting from the current left padding
+    pub fn padding(self, padding_fn: impl Fn() -> f64 + 'static) -> Self {
+        create_effect(move |_| {
+            let padding = padding_fn();
+
+            self.id
+                .update_prop(AnimPropKind::Padding, AnimValue::Float(padding));
+        });
+
+        self
+    }
+
+    /// Animates the gap between both the rows and the columns of the children
+    pub fn gap(self, gap_fn: impl Fn() -> f64 + 'static) -> Self {
+        create_effect(move |_| {
+            let gap = gap_fn();
+
+            self.id
+                .update_prop(AnimPropKind::Gap, AnimValue::Float(gap));
+        });
+
+        self
+    }
+
     pub fn auto_reverse(mut self, auto_rev: bool) -> Self {
         self.auto_reverse = auto_rev;
         self
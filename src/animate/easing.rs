@@ -63,6 +63,10 @@ fn elastic_easing(time: f64) -> f64 {
 }
 
 impl Easing {
+    pub fn new(func: EasingFn, mode: EasingMode) -> Self {
+        Self { mode, func }
+    }
+
     pub(crate) fn apply_easing_fn(&self, time: f64) -> f64 {
         assert_valid_time(time);
         match self.func {
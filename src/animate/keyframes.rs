@@ -0,0 +1,182 @@
+use std::time::{Duration, Instant};
+
+use crate::style::Style;
+
+use super::{interpolate, Easing, PropValue, RepeatMode, TransitionProp};
+
+const PROPS: [TransitionProp; 11] = [
+    TransitionProp::Background,
+    TransitionProp::BorderColor,
+    TransitionProp::Color,
+    TransitionProp::Width,
+    TransitionProp::Height,
+    TransitionProp::Padding,
+    TransitionProp::BorderRadius,
+    TransitionProp::Translate,
+    TransitionProp::Rotate,
+    TransitionProp::Scale,
+    TransitionProp::Opacity,
+];
+
+/// The styles a [KeyframeAnimation] goes through, each at an offset from 0, the start of a
+/// pass, to 1, its end. The properties that can be animated are the ones of
+/// [TransitionProp], and each of them is interpolated between the frames that set it.
+/// ```rust
+/// # use floem::{animate::Keyframes, peniko::Color};
+/// let pulse = Keyframes::new()
+///     .frame(0.0, |s| s.background(Color::rgb8(255, 240, 180)))
+///     .frame(0.5, |s| s.background(Color::rgb8(255, 200, 60)))
+///     .frame(1.0, |s| s.background(Color::rgb8(255, 240, 180)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Keyframes {
+    frames: Vec<(f64, Style)>,
+}
+
+impl Keyframes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the frame at `offset`, which is clamped to 0 to 1
+    pub fn frame(mut self, offset: f64, style: impl FnOnce(Style) -> Style) -> Self {
+        let offset = offset.clamp(0.0, 1.0);
+        let at = self.frames.partition_point(|(o, _)| *o <= offset);
+        self.frames.insert(at, (offset, style(Style::BASE)));
+        self
+    }
+
+    /// The value of `prop` at `time` of a pass, if a frame sets it
+    fn value(&self, prop: TransitionProp, time: f64) -> Option<PropValue> {
+        let mut before: Option<(f64, PropValue)> = None;
+        for (offset, style) in &self.frames {
+            if !prop.is_set(style) {
+                continue;
+            }
+            let Some(value) = prop.value(style) else {
+                continue;
+            };
+            if *offset >= time {
+                return Some(match before {
+                    Some((from_offset, from)) if *offset > from_offset => {
+                        interpolate(from, value, (time - from_offset) / (*offset - from_offset))
+                    }
+                    _ => value,
+                });
+            }
+            before = Some((*offset, value));
+        }
+        before.map(|(_, value)| value)
+    }
+}
+
+/// Which way the passes of a [KeyframeAnimation] go through the [Keyframes]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyframeDirection {
+    /// From the first frame to the last one
+    #[default]
+    Normal,
+    /// From the last frame to the first one
+    Reverse,
+    /// Forward on the first pass, then backward on the second one, and so on
+    Alternate,
+    /// Backward on the first pass, then forward on the second one, and so on
+    AlternateReverse,
+}
+
+/// An animation going through [Keyframes], attached to a view with
+/// [keyframe_animation](crate::views::Decorators::keyframe_animation). It starts when the view
+/// is shown, and once the passes are over the view goes back to its own style.
+/// ```rust
+/// # use std::time::Duration;
+/// # use floem::{animate::{KeyframeAnimation, KeyframeDirection, Keyframes, RepeatMode}, peniko::Color, view::View, views::{Decorators, label}};
+/// fn unsaved_badge() -> impl View {
+///     let blink = Keyframes::new()
+///         .frame(0.0, |s| s.color(Color::BLACK))
+///         .frame(1.0, |s| s.color(Color::rgb8(218, 54, 51)));
+///     label(|| "Unsaved".to_string()).keyframe_animation(KeyframeAnimation::new(
+///         blink,
+///         Duration::from_millis(600),
+///         RepeatMode::LoopForever,
+///         KeyframeDirection::Alternate,
+///     ))
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyframeAnimation {
+    pub(crate) keyframes: Keyframes,
+    pub(crate) duration: Duration,
+    pub(crate) repeat_mode: RepeatMode,
+    pub(crate) direction: KeyframeDirection,
+    pub(crate) easing: Easing,
+    pub(crate) started_on: Option<Instant>,
+}
+
+impl KeyframeAnimation {
+    /// An animation whose passes take `duration` each, and are repeated as `repeat_mode` says
+    pub fn new(
+        keyframes: Keyframes,
+        duration: Duration,
+        repeat_mode: RepeatMode,
+        direction: KeyframeDirection,
+    ) -> Self {
+        Self {
+            keyframes,
+            duration,
+            repeat_mode,
+            direction,
+            easing: Easing::default(),
+            started_on: None,
+        }
+    }
+
+    /// How each pass goes through the frames, which is at a constant pace by default
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// The time of the current pass from 0 to 1, or None once the passes are over
+    fn time(&self, now: Instant) -> Option<f64> {
+        let started_on = self.started_on?;
+        let duration = self.duration.as_secs_f64();
+        if duration == 0.0 {
+            return None;
+        }
+        let elapsed = (now - started_on).as_secs_f64() / duration;
+        let pass = elapsed.floor();
+        if let RepeatMode::Times(times) = self.repeat_mode {
+            if pass >= times as f64 {
+                return None;
+            }
+        }
+        let time = elapsed - pass;
+        let forward = match self.direction {
+            KeyframeDirection::Normal => true,
+            KeyframeDirection::Reverse => false,
+            KeyframeDirection::Alternate => pass as u64 % 2 == 0,
+            KeyframeDirection::AlternateReverse => pass as u64 % 2 == 1,
+        };
+        let time = if forward { time } else { 1.0 - time };
+        Some(self.easing.ease(time))
+    }
+
+    pub(crate) fn is_in_progress(&self, now: Instant) -> bool {
+        self.started_on.is_none() || self.time(now).is_some()
+    }
+
+    /// Applies the frames to `style` for now, starting the animation the first time
+    pub(crate) fn apply(&mut self, mut style: Style) -> Style {
+        let now = Instant::now();
+        self.started_on.get_or_insert(now);
+        let Some(time) = self.time(now) else {
+            return style;
+        };
+        for prop in PROPS {
+            if let Some(value) = self.keyframes.value(prop, time) {
+                style = prop.apply(style, value);
+            }
+        }
+        style
+    }
+}
@@ -15,3 +15,9 @@ pub use easing::*;
 
 mod prop;
 pub use prop::*;
+
+mod transition;
+pub use transition::*;
+
+mod keyframes;
+pub use keyframes::*;
@@ -17,6 +17,8 @@ pub enum AnimatedProp {
     BorderWidth { from: f64, to: f64 },
     BorderColor { from: Color, to: Color },
     Color { from: Color, to: Color },
+    Padding { from: f64, to: f64 },
+    Gap { from: f64, to: f64 },
 }
 
 impl AnimatedProp {
@@ -25,7 +27,9 @@ impl AnimatedProp {
             AnimatedProp::Width { from, .. }
             | AnimatedProp::Height { from, .. }
             | AnimatedProp::BorderWidth { from, .. }
-            | AnimatedProp::BorderRadius { from, .. } => AnimValue::Float(*from),
+            | AnimatedProp::BorderRadius { from, .. }
+            | AnimatedProp::Padding { from, .. }
+            | AnimatedProp::Gap { from, .. } => AnimValue::Float(*from),
             AnimatedProp::Scale { .. } => todo!(),
             AnimatedProp::Background { from, .. }
             | AnimatedProp::BorderColor { from, .. }
@@ -118,7 +122,10 @@ impl AnimatedProp {
                 AnimValue::Color(self.animate_color(*from, *to, time, direction))
             }
             AnimatedProp::Scale { .. } => todo!(),
-            AnimatedProp::BorderRadius { from, to } | AnimatedProp::BorderWidth { from, to } => {
+            AnimatedProp::BorderRadius { from, to }
+            | AnimatedProp::BorderWidth { from, to }
+            | AnimatedProp::Padding { from, to }
+            | AnimatedProp::Gap { from, to } => {
                 AnimValue::Float(self.animate_float(*from, *to, time, direction))
             }
         }
@@ -136,4 +143,6 @@ pub enum AnimPropKind {
     Height,
     BorderRadius,
     BorderColor,
+    Padding,
+    Gap,
 }
@@ -0,0 +1,263 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use peniko::Color;
+
+use crate::{
+    style::{Style, StyleValue},
+    unit::{PxPct, PxPctAuto},
+};
+
+use super::Easing;
+
+/// A style property that can be [transitioned](crate::style::Style::transition)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransitionProp {
+    Background,
+    BorderColor,
+    Color,
+    /// The width, when it's in pixels or a percentage
+    Width,
+    /// The height, when it's in pixels or a percentage
+    Height,
+    /// The padding of all the sides, when it's in pixels
+    Padding,
+    BorderRadius,
+    /// The translation, when it's in pixels
+    Translate,
+    Rotate,
+    Scale,
+    Opacity,
+}
+
+/// How long a [TransitionProp] takes to get to its new value, and how it gets there
+#[derive(Clone, Debug)]
+pub struct Transition {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PropValue {
+    Px(f64),
+    Pct(f64),
+    Color(Color),
+    Sides([f64; 4]),
+    Pair([f64; 2]),
+}
+
+impl TransitionProp {
+    /// The value the property has in `style`, if it's one that can be transitioned
+    pub(crate) fn value(self, style: &Style) -> Option<PropValue> {
+        let size = |value: StyleValue<PxPctAuto>| match value {
+            StyleValue::Val(PxPctAuto::Px(px)) => Some(PropValue::Px(px)),
+            StyleValue::Val(PxPctAuto::Pct(pct)) => Some(PropValue::Pct(pct)),
+            _ => None,
+        };
+        let px = |value: StyleValue<PxPct>| match value {
+            StyleValue::Val(PxPct::Px(px)) => Some(px),
+            StyleValue::Val(PxPct::Pct(_)) => None,
+            _ => Some(0.0),
+        };
+        match self {
+            TransitionProp::Background => match style.background {
+                StyleValue::Val(Some(color)) => Some(PropValue::Color(color)),
+                _ => None,
+            },
+            TransitionProp::BorderColor => match style.border_color {
+                StyleValue::Val(color) => Some(PropValue::Color(color)),
+                _ => Some(PropValue::Color(Color::BLACK)),
+            },
+            TransitionProp::Color => match style.color {
+                StyleValue::Val(Some(color)) => Some(PropValue::Color(color)),
+                _ => None,
+            },
+            TransitionProp::Width => size(style.width),
+            TransitionProp::Height => size(style.height),
+            TransitionProp::Padding => Some(PropValue::Sides([
+                px(style.padding_top)?,
+                px(style.padding_right)?,
+                px(style.padding_bottom)?,
+                px(style.padding_left)?,
+            ])),
+            TransitionProp::BorderRadius => match style.border_radius {
+                StyleValue::Val(radius) => Some(PropValue::Px(radius.0)),
+                _ => Some(PropValue::Px(0.0)),
+            },
+            TransitionProp::Translate => Some(PropValue::Pair([
+                px(style.translate_x)?,
+                px(style.translate_y)?,
+            ])),
+            TransitionProp::Rotate => match style.rotate {
+                StyleValue::Val(degrees) => Some(PropValue::Px(degrees)),
+                _ => Some(PropValue::Px(0.0)),
+            },
+            TransitionProp::Scale => {
+                let scale = |value: StyleValue<f64>| match value {
+                    StyleValue::Val(scale) => scale,
+                    _ => 1.0,
+                };
+                Some(PropValue::Pair([
+                    scale(style.scale_x),
+                    scale(style.scale_y),
+                ]))
+            }
+            TransitionProp::Opacity => match style.opacity {
+                StyleValue::Val(opacity) => Some(PropValue::Px(opacity as f64)),
+                _ => Some(PropValue::Px(1.0)),
+            },
+        }
+    }
+
+    /// Whether `style` sets the property, rather than leaving it to the style under it
+    pub(crate) fn is_set(self, style: &Style) -> bool {
+        fn set<T>(value: &StyleValue<T>) -> bool {
+            matches!(value, StyleValue::Val(_))
+        }
+        match self {
+            TransitionProp::Background => set(&style.background),
+            TransitionProp::BorderColor => set(&style.border_color),
+            TransitionProp::Color => set(&style.color),
+            TransitionProp::Width => set(&style.width),
+            TransitionProp::Height => set(&style.height),
+            TransitionProp::Padding => {
+                set(&style.padding_top)
+                    || set(&style.padding_right)
+                    || set(&style.padding_bottom)
+                    || set(&style.padding_left)
+            }
+            TransitionProp::BorderRadius => set(&style.border_radius),
+            TransitionProp::Translate => set(&style.translate_x) || set(&style.translate_y),
+            TransitionProp::Rotate => set(&style.rotate),
+            TransitionProp::Scale => set(&style.scale_x) || set(&style.scale_y),
+            TransitionProp::Opacity => set(&style.opacity),
+        }
+    }
+
+    pub(crate) fn apply(self, style: Style, value: PropValue) -> Style {
+        match (self, value) {
+            (TransitionProp::Background, PropValue::Color(color)) => style.background(color),
+            (TransitionProp::BorderColor, PropValue::Color(color)) => style.border_color(color),
+            (TransitionProp::Color, PropValue::Color(color)) => style.color(color),
+            (TransitionProp::Width, PropValue::Px(px)) => style.width(px),
+            (TransitionProp::Width, PropValue::Pct(pct)) => style.width_pct(pct),
+            (TransitionProp::Height, PropValue::Px(px)) => style.height(px),
+            (TransitionProp::Height, PropValue::Pct(pct)) => style.height_pct(pct),
+            (TransitionProp::Padding, PropValue::Sides([top, right, bottom, left])) => style
+                .padding_top(top)
+                .padding_right(right)
+                .padding_bottom(bottom)
+                .padding_left(left),
+            (TransitionProp::BorderRadius, PropValue::Px(px)) => style.border_radius(px),
+            (TransitionProp::Translate, PropValue::Pair([x, y])) => style.translate(x, y),
+            (TransitionProp::Rotate, PropValue::Px(degrees)) => style.rotate(degrees),
+            (TransitionProp::Scale, PropValue::Pair([x, y])) => style.scale_x(x).scale_y(y),
+            (TransitionProp::Opacity, PropValue::Px(opacity)) => style.opacity(opacity as f32),
+            _ => style,
+        }
+    }
+}
+
+/// A property of a view on its way from one value to another
+#[derive(Clone, Debug)]
+pub(crate) struct TransitionState {
+    from: PropValue,
+    to: PropValue,
+    started_on: Instant,
+    transition: Transition,
+}
+
+impl TransitionState {
+    fn progress(&self, now: Instant) -> f64 {
+        let duration = self.transition.duration.as_secs_f64();
+        if duration == 0.0 {
+            return 1.0;
+        }
+        let time = ((now - self.started_on).as_secs_f64() / duration).min(1.0);
+        self.transition.easing.ease(time)
+    }
+
+    fn value(&self, now: Instant) -> PropValue {
+        let time = self.progress(now);
+        if time >= 1.0 {
+            return self.to;
+        }
+        interpolate(self.from, self.to, time)
+    }
+
+    fn is_in_progress(&self, now: Instant) -> bool {
+        self.from != self.to && now - self.started_on < self.transition.duration
+    }
+}
+
+/// The value `time` of the way from `from` to `to`, with `time` from 0 to 1
+pub(crate) fn interpolate(from: PropValue, to: PropValue, time: f64) -> PropValue {
+    let float = |from: f64, to: f64| from * (1.0 - time) + to * time;
+    match (from, to) {
+        (PropValue::Px(from), PropValue::Px(to)) => PropValue::Px(float(from, to)),
+        (PropValue::Pct(from), PropValue::Pct(to)) => PropValue::Pct(float(from, to)),
+        (PropValue::Color(from), PropValue::Color(to)) => {
+            let channel = |from: u8, to: u8| float(from as f64, to as f64).round() as u8;
+            PropValue::Color(Color {
+                r: channel(from.r, to.r),
+                g: channel(from.g, to.g),
+                b: channel(from.b, to.b),
+                a: channel(from.a, to.a),
+            })
+        }
+        (PropValue::Sides(from), PropValue::Sides(to)) => {
+            PropValue::Sides([0, 1, 2, 3].map(|i| float(from[i], to[i])))
+        }
+        (PropValue::Pair(from), PropValue::Pair(to)) => {
+            PropValue::Pair([0, 1].map(|i| float(from[i], to[i])))
+        }
+        // a pixel size can't be interpolated with a percentage one
+        _ => to,
+    }
+}
+
+/// Applies the transitions of `style` to it, starting a transition for each property whose
+/// value changed since the last time, and replacing the value of the properties that are
+/// still in transition with their value for now
+pub(crate) fn apply_transitions(
+    states: &mut HashMap<TransitionProp, TransitionState>,
+    mut style: Style,
+) -> Style {
+    let transitions = match &style.transitions {
+        StyleValue::Val(transitions) => transitions.clone(),
+        _ => Vec::new(),
+    };
+    states.retain(|prop, _| transitions.iter().any(|(p, _)| p == prop));
+
+    let now = Instant::now();
+    for (prop, transition) in transitions {
+        let Some(target) = prop.value(&style) else {
+            states.remove(&prop);
+            continue;
+        };
+        let state = states.entry(prop).or_insert_with(|| TransitionState {
+            // the first value is taken as it is
+            from: target,
+            to: target,
+            started_on: now,
+            transition: transition.clone(),
+        });
+        if state.to != target {
+            state.from = state.value(now);
+            state.to = target;
+            state.started_on = now;
+            state.transition = transition;
+        }
+        if state.is_in_progress(now) {
+            style = prop.apply(style, state.value(now));
+        }
+    }
+    style
+}
+
+pub(crate) fn transitions_in_progress(states: &HashMap<TransitionProp, TransitionState>) -> bool {
+    let now = Instant::now();
+    states.values().any(|state| state.is_in_progress(now))
+}
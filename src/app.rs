@@ -31,6 +31,8 @@ pub(crate) enum UserEvent {
     AppUpdate,
     Idle,
     QuitApp,
+    #[cfg(feature = "gamepad")]
+    Gamepad(crate::gamepad::GamepadEvent),
 }
 
 pub(crate) enum AppUpdateEvent {
@@ -81,6 +83,8 @@ impl Application {
             .expect("can't start the event loop");
         let event_loop_proxy = event_loop.create_proxy();
         *EVENT_LOOP_PROXY.lock() = Some(event_loop_proxy.clone());
+        #[cfg(feature = "gamepad")]
+        crate::gamepad::spawn_gamepad_thread();
         let handle = ApplicationHandle::new();
         Self {
             handle: Some(handle),
@@ -132,6 +136,8 @@ impl Application {
                     handle.redraw_requested(window_id);
                 }
                 winit::event::Event::LoopExiting => {
+                    #[cfg(feature = "persist")]
+                    let _ = crate::persist::save_persisted();
                     if let Some(action) = self.event_listener.as_ref() {
                         action(AppEvent::WillTerminate);
                     }
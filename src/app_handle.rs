@@ -1,6 +1,7 @@
 use std::{collections::HashMap, time::Instant};
 
-use kurbo::{Point, Size};
+use floem_reactive::batch;
+use kurbo::Size;
 use winit::{
     dpi::{LogicalPosition, LogicalSize},
     event::WindowEvent,
@@ -12,8 +13,11 @@ use crate::{
     action::{Timer, TimerToken},
     app::{AppUpdateEvent, UserEvent, APP_UPDATE_EVENTS},
     ext_event::EXT_EVENT_HANDLER,
+    modal::remove_modal_layer,
+    overlay::remove_overlay_layer,
+    toast::remove_toast_layer,
     view::View,
-    window::WindowConfig,
+    window::{WindowConfig, WindowSignals},
     window_handle::WindowHandle,
 };
 
@@ -46,6 +50,26 @@ impl ApplicationHandle {
             UserEvent::QuitApp => {
                 control_flow.set_exit();
             }
+            #[cfg(feature = "gamepad")]
+            UserEvent::Gamepad(event) => {
+                self.gamepad_event(event);
+            }
+        }
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn gamepad_event(&mut self, event: crate::gamepad::GamepadEvent) {
+        if let Some(direction) = crate::gamepad::update_gamepad(event) {
+            let window_handle = self.window_handles.values_mut().find(|handle| {
+                handle
+                    .window
+                    .as_ref()
+                    .map(|window| window.has_focus())
+                    .unwrap_or(false)
+            });
+            if let Some(window_handle) = window_handle {
+                window_handle.spatial_navigation(direction);
+            }
         }
     }
 
@@ -96,67 +120,10 @@ impl ApplicationHandle {
         };
 
         match event {
-            WindowEvent::ActivationTokenDone { .. } => {}
-            WindowEvent::Resized(size) => {
-                let size: LogicalSize<f64> = size.to_logical(window_handle.scale);
-                let size = Size::new(size.width, size.height);
-                window_handle.size(size);
-            }
-            WindowEvent::Moved(position) => {
-                let position: LogicalPosition<f64> = position.to_logical(window_handle.scale);
-                let point = Point::new(position.x, position.y);
-                window_handle.position(point);
-            }
-            WindowEvent::CloseRequested => {
-                self.close_window(window_id, control_flow);
-            }
-            WindowEvent::Destroyed => {
+            WindowEvent::CloseRequested | WindowEvent::Destroyed => {
                 self.close_window(window_id, control_flow);
             }
-            WindowEvent::DroppedFile(_) => {}
-            WindowEvent::HoveredFile(_) => {}
-            WindowEvent::HoveredFileCancelled => {}
-            WindowEvent::Focused(focused) => {
-                window_handle.focused(focused);
-            }
-            WindowEvent::KeyboardInput { event, .. } => {
-                window_handle.key_event(event);
-            }
-            WindowEvent::ModifiersChanged(modifiers) => {
-                window_handle.modifiers = modifiers.state();
-            }
-            WindowEvent::Ime(ime) => {
-                window_handle.ime(ime);
-            }
-            WindowEvent::CursorMoved { position, .. } => {
-                let position: LogicalPosition<f64> = position.to_logical(window_handle.scale);
-                let point = Point::new(position.x, position.y);
-                window_handle.pointer_move(point);
-            }
-            WindowEvent::CursorEntered { .. } => {}
-            WindowEvent::CursorLeft { .. } => {}
-            WindowEvent::MouseWheel { delta, .. } => {
-                window_handle.mouse_wheel(delta);
-            }
-            WindowEvent::MouseInput { state, button, .. } => {
-                window_handle.mouse_input(button, state);
-            }
-            WindowEvent::TouchpadMagnify { .. } => {}
-            WindowEvent::SmartMagnify { .. } => {}
-            WindowEvent::TouchpadRotate { .. } => {}
-            WindowEvent::TouchpadPressure { .. } => {}
-            WindowEvent::AxisMotion { .. } => {}
-            WindowEvent::Touch(_) => {}
-            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
-                window_handle.scale(scale_factor);
-            }
-            WindowEvent::ThemeChanged(theme) => {
-                window_handle.theme_changed(theme);
-            }
-            WindowEvent::Occluded(_) => {}
-            WindowEvent::MenuAction(id) => {
-                window_handle.menu_action(id);
-            }
+            event => window_handle.window_event(event),
         }
     }
 
@@ -173,6 +140,14 @@ impl ApplicationHandle {
         config: Option<WindowConfig>,
     ) {
         let mut window_builder = winit::window::WindowBuilder::new();
+        let resize_mode = config
+            .as_ref()
+            .map(|config| config.resize_mode)
+            .unwrap_or_default();
+        let spatial_navigation = config
+            .as_ref()
+            .map(|config| config.spatial_navigation)
+            .unwrap_or_default();
         if let Some(config) = config {
             if let Some(size) = config.size {
                 let size = if size.width == 0.0 || size.height == 0.0 {
@@ -207,7 +182,7 @@ impl ApplicationHandle {
             Err(_) => return,
         };
         let window_id = window.id();
-        let window_handle = WindowHandle::new(window, view_fn);
+        let window_handle = WindowHandle::new(window, view_fn, resize_mode, spatial_navigation);
         self.window_handles.insert(window_id, window_handle);
     }
 
@@ -220,8 +195,12 @@ impl ApplicationHandle {
         if let Some(handle) = self.window_handles.get_mut(&window_id) {
             handle.window = None;
             handle.destroy();
+            remove_overlay_layer(handle.view.id());
+            remove_modal_layer(handle.view.id());
+            remove_toast_layer(handle.view.id());
         }
         self.window_handles.remove(&window_id);
+        WindowSignals::remove(window_id);
         #[cfg(not(target_os = "macos"))]
         if self.window_handles.is_empty() {
             control_flow.set_exit();
@@ -229,9 +208,12 @@ impl ApplicationHandle {
     }
 
     pub(crate) fn idle(&mut self) {
-        while let Some(trigger) = { EXT_EVENT_HANDLER.queue.lock().pop_front() } {
-            trigger.notify();
-        }
+        // what several threads sent since the last time is taken in as one update
+        batch(|| {
+            while let Some(trigger) = { EXT_EVENT_HANDLER.queue.lock().pop_front() } {
+                trigger.notify();
+            }
+        });
         for (_, handle) in self.window_handles.iter_mut() {
             handle.process_update();
         }
@@ -1,29 +1,38 @@
 use std::{
     collections::{HashMap, HashSet},
     ops::{Deref, DerefMut},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use floem_renderer::{
     cosmic_text::{LineHeightValue, Style as FontStyle, Weight},
-    Renderer as FloemRenderer,
+    Img, Renderer as FloemRenderer,
 };
 use kurbo::{Affine, Point, Rect, RoundedRect, Shape, Size, Vec2};
 use peniko::Color;
 use taffy::{
     prelude::{Layout, Node},
-    style::{AvailableSpace, Display},
+    style::{AvailableSpace, Display, LengthPercentage},
 };
 use winit::window::CursorIcon;
 
 use crate::{
-    animate::{AnimId, AnimPropKind, Animation},
+    animate::{
+        apply_transitions, transitions_in_progress, AnimId, AnimPropKind, AnimatedProp, Animation,
+        KeyframeAnimation, SizeUnit, TransitionProp, TransitionState,
+    },
     event::{Event, EventListener},
     id::Id,
     menu::Menu,
+    overlay::Tooltip,
     pointer::PointerInputEvent,
-    responsive::{GridBreakpoints, ScreenSize, ScreenSizeBp},
-    style::{ComputedStyle, CursorStyle, Style, StyleSelector},
+    responsive::{GridBreakpoints, QueryTarget, ScreenSize, ScreenSizeBp, WidthQuery},
+    style::{
+        CaretShape, ComputedStyle, CursorStyle, Style, StyleSelector, TextDecoration, TextTransform,
+    },
+    stylesheet::{stylesheet_styles, view_type_name, SheetStyles},
+    view::View,
+    views::Mask,
 };
 
 pub type EventCallback = dyn Fn(&Event) -> bool;
@@ -50,22 +59,36 @@ pub struct ViewState {
     pub(crate) animation: Option<Animation>,
     pub(crate) base_style: Option<Style>,
     pub(crate) style: Style,
+    pub(crate) dyn_style: Option<Style>,
+    /// The `View::view_style` from the last layout, so the style can be recomputed outside of it
+    pub(crate) view_style: Option<Style>,
     pub(crate) dragging_style: Option<Style>,
     pub(crate) hover_style: Option<Style>,
     pub(crate) disabled_style: Option<Style>,
     pub(crate) focus_style: Option<Style>,
     pub(crate) focus_visible_style: Option<Style>,
     pub(crate) responsive_styles: HashMap<ScreenSizeBp, Vec<Style>>,
+    pub(crate) width_styles: Vec<WidthStyle>,
     pub(crate) active_style: Option<Style>,
     pub(crate) combined_style: Style,
     pub(crate) computed_style: ComputedStyle,
     pub(crate) event_listeners: HashMap<EventListener, Box<EventCallback>>,
     pub(crate) context_menu: Option<Box<MenuCallback>>,
     pub(crate) popout_menu: Option<Box<MenuCallback>>,
+    pub(crate) tooltip: Option<Tooltip>,
+    pub(crate) mask: Option<Mask>,
     pub(crate) resize_listener: Option<ResizeListener>,
     pub(crate) move_listener: Option<MoveListener>,
     pub(crate) cleanup_listener: Option<Box<dyn Fn()>>,
     pub(crate) last_pointer_down: Option<PointerInputEvent>,
+    /// The type name of the view, which stylesheet rules select views by
+    pub(crate) view_name: &'static str,
+    pub(crate) classes: Vec<String>,
+    pub(crate) style_id: Option<String>,
+    /// The stylesheet styles matching the view the last time its style was computed
+    pub(crate) sheet_styles: Option<SheetStyles>,
+    pub(crate) transition_states: HashMap<TransitionProp, TransitionState>,
+    pub(crate) keyframe_animation: Option<KeyframeAnimation>,
 }
 
 impl ViewState {
@@ -78,6 +101,8 @@ impl ViewState {
             animation: None,
             base_style: None,
             style: Style::BASE,
+            dyn_style: None,
+            view_style: None,
             combined_style: Style::BASE,
             computed_style: ComputedStyle::default(),
             hover_style: None,
@@ -87,14 +112,23 @@ impl ViewState {
             focus_visible_style: None,
             active_style: None,
             responsive_styles: HashMap::new(),
+            width_styles: Vec::new(),
             children_nodes: Vec::new(),
             event_listeners: HashMap::new(),
             context_menu: None,
             popout_menu: None,
+            tooltip: None,
+            mask: None,
             resize_listener: None,
             move_listener: None,
             cleanup_listener: None,
             last_pointer_down: None,
+            view_name: "",
+            classes: Vec::new(),
+            style_id: None,
+            sheet_styles: None,
+            transition_states: HashMap::new(),
+            keyframe_animation: None,
         }
     }
 
@@ -104,17 +138,27 @@ impl ViewState {
         interact_state: InteractionState,
         screen_size_bp: ScreenSizeBp,
     ) {
-        let mut computed_style = if let Some(view_style) = view_style {
-            if let Some(base_style) = self.base_style.clone() {
-                view_style.apply(base_style).apply(self.style.clone())
-            } else {
-                view_style.apply(self.style.clone())
-            }
-        } else if let Some(base_style) = self.base_style.clone() {
-            base_style.apply(self.style.clone())
-        } else {
-            self.style.clone()
+        self.view_style = view_style.clone();
+        self.sheet_styles = stylesheet_styles(
+            view_type_name(self.view_name),
+            self.style_id.as_deref(),
+            &self.classes,
+        );
+        let sheet_styles = self.sheet_styles.clone().unwrap_or_default();
+
+        let mut computed_style = match (view_style, self.base_style.clone()) {
+            (Some(view_style), Some(base_style)) => view_style.apply(base_style),
+            (Some(style), None) | (None, Some(style)) => style,
+            (None, None) => Style::BASE,
         };
+        if let Some(sheet_style) = sheet_styles.base {
+            computed_style = computed_style.apply(sheet_style);
+        }
+        computed_style = computed_style.apply(self.style.clone());
+
+        if let Some(dyn_style) = self.dyn_style.clone() {
+            computed_style = computed_style.apply(dyn_style);
+        }
 
         if let Some(resp_styles) = self.responsive_styles.get(&screen_size_bp) {
             for style in resp_styles {
@@ -122,13 +166,25 @@ impl ViewState {
             }
         }
 
+        for width_style in &self.width_styles {
+            if width_style.matches {
+                computed_style = computed_style.apply(width_style.style.clone());
+            }
+        }
+
         if interact_state.is_hovered && !interact_state.is_disabled {
+            if let Some(sheet_style) = sheet_styles.hover.clone() {
+                computed_style = computed_style.apply(sheet_style);
+            }
             if let Some(hover_style) = self.hover_style.clone() {
                 computed_style = computed_style.apply(hover_style);
             }
         }
 
         if interact_state.is_focused {
+            if let Some(sheet_style) = sheet_styles.focus.clone() {
+                computed_style = computed_style.apply(sheet_style);
+            }
             if let Some(focus_style) = self.focus_style.clone() {
                 computed_style = computed_style.apply(focus_style);
             }
@@ -137,6 +193,9 @@ impl ViewState {
         let focused_keyboard =
             interact_state.using_keyboard_navigation && interact_state.is_focused;
         if focused_keyboard {
+            if let Some(sheet_style) = sheet_styles.focus_visible.clone() {
+                computed_style = computed_style.apply(sheet_style);
+            }
             if let Some(focus_visible_style) = self.focus_visible_style.clone() {
                 computed_style = computed_style.apply(focus_visible_style);
             }
@@ -144,12 +203,18 @@ impl ViewState {
 
         let active_mouse = interact_state.is_hovered && !interact_state.using_keyboard_navigation;
         if interact_state.is_active && (active_mouse || focused_keyboard) {
+            if let Some(sheet_style) = sheet_styles.active.clone() {
+                computed_style = computed_style.apply(sheet_style);
+            }
             if let Some(active_style) = self.active_style.clone() {
                 computed_style = computed_style.apply(active_style);
             }
         }
 
         if interact_state.is_disabled {
+            if let Some(sheet_style) = sheet_styles.disabled.clone() {
+                computed_style = computed_style.apply(sheet_style);
+            }
             if let Some(disabled_style) = self.disabled_style.clone() {
                 computed_style = computed_style.apply(disabled_style);
             }
@@ -163,15 +228,37 @@ impl ViewState {
 
                 let props = animation.props();
 
-                for kind in props.keys() {
+                for (kind, prop) in props {
                     let val =
                         animation.animate_prop(animation.elapsed().unwrap_or(Duration::ZERO), kind);
                     match kind {
                         AnimPropKind::Width => {
-                            computed_style = computed_style.width(val.get_f32());
+                            computed_style = match prop {
+                                AnimatedProp::Width {
+                                    unit: SizeUnit::Pct,
+                                    ..
+                                } => computed_style.width_pct(val.get_f64()),
+                                _ => computed_style.width(val.get_f32()),
+                            };
                         }
                         AnimPropKind::Height => {
-                            computed_style = computed_style.height(val.get_f32());
+                            computed_style = match prop {
+                                AnimatedProp::Height {
+                                    unit: SizeUnit::Pct,
+                                    ..
+                                } => computed_style.height_pct(val.get_f64()),
+                                _ => computed_style.height(val.get_f32()),
+                            };
+                        }
+                        AnimPropKind::Padding => {
+                            computed_style = computed_style.padding(val.get_f32());
+                        }
+                        AnimPropKind::Gap => {
+                            let gap = LengthPercentage::Points(val.get_f32());
+                            computed_style = computed_style.gap(taffy::geometry::Size {
+                                width: gap,
+                                height: gap,
+                            });
                         }
                         AnimPropKind::Background => {
                             computed_style = computed_style.background(val.get_color());
@@ -194,6 +281,11 @@ impl ViewState {
             }
         }
 
+        computed_style = apply_transitions(&mut self.transition_states, computed_style);
+        if let Some(animation) = self.keyframe_animation.as_mut() {
+            computed_style = animation.apply(computed_style);
+        }
+
         self.combined_style = computed_style.clone();
         self.computed_style = computed_style.compute(&ComputedStyle::default());
     }
@@ -210,6 +302,17 @@ impl ViewState {
     }
 }
 
+/// A style of [responsive](crate::views::Decorators::responsive), applied while its query
+/// matches
+pub(crate) struct WidthStyle {
+    /// Tells apart the styles of the different `responsive` calls on the view
+    pub(crate) key: Id,
+    pub(crate) query: WidthQuery,
+    pub(crate) style: Style,
+    /// Whether the query matched the last time it was checked
+    pub(crate) matches: bool,
+}
+
 pub struct DragState {
     pub(crate) id: Id,
     pub(crate) offset: Vec2,
@@ -221,6 +324,8 @@ pub struct DragState {
 pub struct AppState {
     /// keyboard focus
     pub(crate) focus: Option<Id>,
+    /// while a modal is open, the view that keyboard focus is kept inside of
+    pub(crate) focus_scope: Option<Id>,
     /// when a view is active, it gets mouse event even when the mouse is
     /// not on it
     pub(crate) active: Option<Id>,
@@ -242,11 +347,19 @@ pub struct AppState {
     /// This keeps track of all views that have an animation,
     /// regardless of the status of the animation
     pub(crate) animated: HashSet<Id>,
+    /// The views with a style transition or a keyframe animation in progress
+    pub(crate) transitioning: HashSet<Id>,
     pub(crate) cursor: Option<CursorStyle>,
     pub(crate) last_cursor: CursorIcon,
     pub(crate) keyboard_navigation: bool,
     pub(crate) window_menu: HashMap<usize, Box<dyn Fn()>>,
     pub(crate) context_menu: HashMap<usize, Box<dyn Fn()>>,
+    /// when the frame being painted started
+    pub(crate) frame_time: Instant,
+    /// the views that asked to be painted again on the next frame
+    pub(crate) frame_requests: HashSet<Id>,
+    /// The focus events waiting to be sent to the views that lost or gained focus
+    pub(crate) focus_events: Vec<(Id, Event)>,
 }
 
 impl Default for AppState {
@@ -262,6 +375,7 @@ impl AppState {
         Self {
             root: None,
             focus: None,
+            focus_scope: None,
             active: None,
             scale: 1.0,
             root_size: Size::ZERO,
@@ -270,6 +384,7 @@ impl AppState {
             taffy,
             view_states: HashMap::new(),
             animated: HashSet::new(),
+            transitioning: HashSet::new(),
             disabled: HashSet::new(),
             keyboard_navigable: HashSet::new(),
             draggable: HashSet::new(),
@@ -283,6 +398,9 @@ impl AppState {
             grid_bps: GridBreakpoints::default(),
             window_menu: HashMap::new(),
             context_menu: HashMap::new(),
+            frame_time: Instant::now(),
+            frame_requests: HashSet::new(),
+            focus_events: Vec::new(),
         }
     }
 
@@ -311,6 +429,14 @@ impl AppState {
             .collect()
     }
 
+    /// The views with a style transition or a keyframe animation in progress, which have to
+    /// be styled again for the next frame
+    pub(crate) fn ids_with_transition_in_progress(&mut self) -> Vec<Id> {
+        self.transitioning
+            .retain(|id| self.view_states.contains_key(id));
+        self.transitioning.iter().copied().collect()
+    }
+
     pub fn is_hidden(&self, id: Id) -> bool {
         self.view_states
             .get(&id)
@@ -335,8 +461,16 @@ impl AppState {
         self.hovered.contains(id)
     }
 
+    /// Whether the view is disabled, or is inside a disabled view
     pub fn is_disabled(&self, id: &Id) -> bool {
+        if self.disabled.is_empty() {
+            return false;
+        }
         self.disabled.contains(id)
+            || id
+                .id_path()
+                .map(|path| path.0.iter().any(|id| self.disabled.contains(id)))
+                .unwrap_or(false)
     }
 
     pub fn is_focused(&self, id: &Id) -> bool {
@@ -374,6 +508,40 @@ impl AppState {
         let screen_size_bp = self.screen_size_bp;
         let view_state = self.view_state(id);
         view_state.compute_style(view_style, interact_state, screen_size_bp);
+        self.update_transitioning(id);
+    }
+
+    fn update_transitioning(&mut self, id: Id) {
+        let view_state = self.view_state(id);
+        let now = Instant::now();
+        let keyframes_in_progress = view_state
+            .keyframe_animation
+            .as_ref()
+            .map(|animation| animation.is_in_progress(now))
+            .unwrap_or(false);
+        if keyframes_in_progress || transitions_in_progress(&view_state.transition_states) {
+            self.transitioning.insert(id);
+        } else {
+            self.transitioning.remove(&id);
+        }
+    }
+
+    /// Recomputes the style of a view outside of layout. Returns false if the new style changes
+    /// the layout or what children inherit, so a layout pass is still needed.
+    pub(crate) fn restyle(&mut self, id: Id) -> bool {
+        let interact_state = self.get_interact_state(&id);
+        let screen_size_bp = self.screen_size_bp;
+        let view_state = self.view_state(id);
+        // Animations advance whenever the style is computed, so leave them to layout
+        if view_state.request_layout || view_state.animation.is_some() {
+            return false;
+        }
+        let old = view_state.computed_style.clone();
+        let view_style = view_state.view_style.clone();
+        view_state.compute_style(view_style, interact_state, screen_size_bp);
+        let new = view_state.computed_style.clone();
+        self.update_transitioning(id);
+        old.to_taffy_style() == new.to_taffy_style() && old.inherited_eq(&new)
     }
 
     pub(crate) fn get_computed_style(&mut self, id: Id) -> &ComputedStyle {
@@ -404,6 +572,29 @@ impl AppState {
         }
     }
 
+    /// Marks the view and the views inside it for layout, so their styles are computed again
+    pub(crate) fn request_layout_subtree(&mut self, id: Id) {
+        for (view_id, view_state) in self.view_states.iter_mut() {
+            if view_id
+                .id_path()
+                .map(|path| path.0.contains(&id))
+                .unwrap_or(false)
+            {
+                view_state.request_layout = true;
+            }
+        }
+        if let Some(parent) = id.parent() {
+            self.request_layout(parent);
+        }
+    }
+
+    /// Marks every view for layout, so all the styles are computed again
+    pub(crate) fn request_layout_all(&mut self) {
+        for view_state in self.view_states.values_mut() {
+            view_state.request_layout = true;
+        }
+    }
+
     pub(crate) fn set_viewport(&mut self, id: Id, viewport: Rect) {
         let view = self.view_state(id);
         view.viewport = Some(viewport);
@@ -417,6 +608,23 @@ impl AppState {
             .copied()
     }
 
+    pub(crate) fn get_z_index(&self, id: Id) -> i32 {
+        self.view_states
+            .get(&id)
+            .and_then(|view| view.computed_style.z_index)
+            .unwrap_or(0)
+    }
+
+    /// The transform the style of the view paints it with, about its transform origin
+    pub(crate) fn get_transform(&self, id: Id) -> Option<Affine> {
+        let view = self.view_states.get(&id)?;
+        let layout = self.taffy.layout(view.node).ok()?;
+        view.computed_style.transform(Size::new(
+            layout.size.width as f64,
+            layout.size.height as f64,
+        ))
+    }
+
     pub(crate) fn get_layout_rect(&mut self, id: Id) -> Rect {
         self.view_state(id).layout_rect
     }
@@ -435,6 +643,56 @@ impl AppState {
         }
     }
 
+    pub(crate) fn set_width_style(&mut self, id: Id, key: Id, query: WidthQuery, style: Style) {
+        let view_state = self.view_state(id);
+        match view_state.width_styles.iter_mut().find(|s| s.key == key) {
+            Some(width_style) => {
+                width_style.query = query;
+                width_style.style = style;
+            }
+            None => view_state.width_styles.push(WidthStyle {
+                key,
+                query,
+                style,
+                matches: false,
+            }),
+        }
+        self.update_width_styles(id);
+        self.request_layout(id);
+    }
+
+    /// Checks the queries of the width styles of the view against the widths from the last
+    /// layout, and requests a layout of the view when one of them changed
+    pub(crate) fn update_width_styles(&mut self, id: Id) {
+        if self
+            .view_states
+            .get(&id)
+            .map(|view| view.width_styles.is_empty())
+            .unwrap_or(true)
+        {
+            return;
+        }
+        let window_width = self.root_size.width / self.scale;
+        let container_width = id
+            .parent()
+            .and_then(|parent| self.get_layout(parent))
+            .map(|layout| layout.size.width as f64);
+        let mut changed = false;
+        for width_style in self.view_state(id).width_styles.iter_mut() {
+            let matches = match width_style.query.target() {
+                QueryTarget::Window => width_style.query.matches(window_width),
+                QueryTarget::Container => container_width
+                    .map(|width| width_style.query.matches(width))
+                    .unwrap_or(false),
+            };
+            changed |= matches != width_style.matches;
+            width_style.matches = matches;
+        }
+        if changed {
+            self.request_layout(id);
+        }
+    }
+
     pub(crate) fn update_screen_size_bp(&mut self, size: Size) {
         let bp = self.grid_bps.get_width_bp(size.width);
         self.screen_size_bp = bp;
@@ -452,7 +710,7 @@ impl AppState {
     }
 
     pub(crate) fn update_focus(&mut self, id: Id, keyboard_navigation: bool) {
-        if self.focus.is_some() {
+        if self.focus.is_some() || !self.in_focus_scope(id) {
             return;
         }
 
@@ -460,8 +718,27 @@ impl AppState {
         self.keyboard_navigation = keyboard_navigation;
     }
 
+    /// Whether `id` can have keyboard focus, which is only inside the topmost modal
+    /// while there is one
+    pub(crate) fn in_focus_scope(&self, id: Id) -> bool {
+        match self.focus_scope {
+            Some(scope) => id
+                .id_path()
+                .map(|path| path.0.contains(&scope))
+                .unwrap_or(false),
+            None => true,
+        }
+    }
+
     pub(crate) fn has_style_for_sel(&mut self, id: Id, selector_kind: StyleSelector) -> bool {
         let view_state = self.view_state(id);
+        if view_state
+            .sheet_styles
+            .as_ref()
+            .map_or(false, |styles| styles.has_style_for_sel(&selector_kind))
+        {
+            return true;
+        }
 
         match selector_kind {
             StyleSelector::Hover => view_state.hover_style.is_some(),
@@ -518,19 +795,9 @@ impl AppState {
             .and_then(|s| s.event_listeners.get(listener))
     }
 
+    /// Updates the focus styles, and queues the focus events, which the window sends to the
+    /// views themselves before their listeners, like the other events
     pub(crate) fn focus_changed(&mut self, old: Option<Id>, new: Option<Id>) {
-        if let Some(id) = new {
-            // To apply the styles of the Focus selector
-            if self.has_style_for_sel(id, StyleSelector::Focus)
-                || self.has_style_for_sel(id, StyleSelector::FocusVisible)
-            {
-                self.request_layout(id);
-            }
-            if let Some(action) = self.get_event_listener(id, &EventListener::FocusGained) {
-                (*action)(&Event::FocusGained);
-            }
-        }
-
         if let Some(old_id) = old {
             // To remove the styles applied by the Focus selector
             if self.has_style_for_sel(old_id, StyleSelector::Focus)
@@ -538,9 +805,17 @@ impl AppState {
             {
                 self.request_layout(old_id);
             }
-            if let Some(action) = self.get_event_listener(old_id, &EventListener::FocusLost) {
-                (*action)(&Event::FocusLost);
+            self.focus_events.push((old_id, Event::FocusLost));
+        }
+
+        if let Some(id) = new {
+            // To apply the styles of the Focus selector
+            if self.has_style_for_sel(id, StyleSelector::Focus)
+                || self.has_style_for_sel(id, StyleSelector::FocusVisible)
+            {
+                self.request_layout(id);
             }
+            self.focus_events.push((id, Event::FocusGained));
         }
     }
 }
@@ -613,16 +888,39 @@ impl<'a> EventCx<'a> {
             .get(&id)
             .and_then(|view| view.viewport);
 
-        if let Some(layout) = self.get_layout(id) {
+        let event = if let Some(layout) = self.get_layout(id) {
             event.offset((
                 layout.location.x as f64 - viewport.map(|rect| rect.x0).unwrap_or(0.0),
                 layout.location.y as f64 - viewport.map(|rect| rect.y0).unwrap_or(0.0),
             ))
         } else {
             event
+        };
+        match self.app_state.get_transform(id) {
+            Some(transform) => event.transform(transform.inverse()),
+            None => event,
         }
     }
 
+    /// Sends the event to the `children` it should go to, starting with the one painted on top,
+    /// until one of them handles it. This is the reverse of the order of
+    /// [PaintCx::paint_children].
+    pub fn event_children(
+        &mut self,
+        mut children: Vec<&mut dyn View>,
+        id_path: Option<&[Id]>,
+        event: &Event,
+    ) -> bool {
+        children.sort_by_key(|child| self.app_state.get_z_index(child.id()));
+        for child in children.into_iter().rev() {
+            if self.should_send(child.id(), event) && child.event_main(self, id_path, event.clone())
+            {
+                return true;
+            }
+        }
+        false
+    }
+
     /// Used to determine if you should send an event to another view. This is basically a check for pointer events to see if the pointer is inside a child view and to make sure the current view isn't hidden or disabled.
     /// Usually this is used if you want to propagate an event to a child view
     pub fn should_send(&mut self, id: Id, event: &Event) -> bool {
@@ -634,13 +932,12 @@ impl<'a> EventCx<'a> {
         if let Some(point) = event.point() {
             let layout_rect = self.app_state.get_layout_rect(id);
             if let Some(layout) = self.get_layout(id) {
-                if layout_rect
-                    .with_origin(Point::new(
-                        layout.location.x as f64,
-                        layout.location.y as f64,
-                    ))
-                    .contains(point)
-                {
+                let mut point =
+                    point - Vec2::new(layout.location.x as f64, layout.location.y as f64);
+                if let Some(transform) = self.app_state.get_transform(id) {
+                    point = transform.inverse() * point;
+                }
+                if layout_rect.with_origin(Point::ZERO).contains(point) {
                     return true;
                 }
             }
@@ -675,6 +972,8 @@ pub struct LayoutCx<'a> {
     pub(crate) font_weight: Option<Weight>,
     pub(crate) font_style: Option<FontStyle>,
     pub(crate) line_height: Option<LineHeightValue>,
+    pub(crate) letter_spacing: Option<f64>,
+    pub(crate) text_transform: Option<TextTransform>,
     pub(crate) window_origin: Point,
     pub(crate) saved_viewports: Vec<Option<Rect>>,
     pub(crate) saved_colors: Vec<Option<Color>>,
@@ -687,6 +986,8 @@ pub struct LayoutCx<'a> {
     pub(crate) saved_font_weights: Vec<Option<Weight>>,
     pub(crate) saved_font_styles: Vec<Option<FontStyle>>,
     pub(crate) saved_line_heights: Vec<Option<LineHeightValue>>,
+    pub(crate) saved_letter_spacings: Vec<Option<f64>>,
+    pub(crate) saved_text_transforms: Vec<Option<TextTransform>>,
     pub(crate) saved_window_origins: Vec<Point>,
 }
 
@@ -701,6 +1002,8 @@ impl<'a> LayoutCx<'a> {
             font_weight: None,
             font_style: None,
             line_height: None,
+            letter_spacing: None,
+            text_transform: None,
             window_origin: Point::ZERO,
             saved_viewports: Vec::new(),
             saved_colors: Vec::new(),
@@ -709,6 +1012,8 @@ impl<'a> LayoutCx<'a> {
             saved_font_weights: Vec::new(),
             saved_font_styles: Vec::new(),
             saved_line_heights: Vec::new(),
+            saved_letter_spacings: Vec::new(),
+            saved_text_transforms: Vec::new(),
             saved_window_origins: Vec::new(),
             scroll_bar_color: None,
             scroll_bar_rounded: None,
@@ -740,6 +1045,8 @@ impl<'a> LayoutCx<'a> {
         self.saved_font_weights.clear();
         self.saved_font_styles.clear();
         self.saved_line_heights.clear();
+        self.saved_letter_spacings.clear();
+        self.saved_text_transforms.clear();
         self.saved_window_origins.clear();
     }
 
@@ -757,6 +1064,8 @@ impl<'a> LayoutCx<'a> {
         self.saved_font_weights.push(self.font_weight);
         self.saved_font_styles.push(self.font_style);
         self.saved_line_heights.push(self.line_height);
+        self.saved_letter_spacings.push(self.letter_spacing);
+        self.saved_text_transforms.push(self.text_transform);
         self.saved_window_origins.push(self.window_origin);
     }
 
@@ -772,6 +1081,8 @@ impl<'a> LayoutCx<'a> {
         self.font_weight = self.saved_font_weights.pop().unwrap_or_default();
         self.font_style = self.saved_font_styles.pop().unwrap_or_default();
         self.line_height = self.saved_line_heights.pop().unwrap_or_default();
+        self.letter_spacing = self.saved_letter_spacings.pop().unwrap_or_default();
+        self.text_transform = self.saved_text_transforms.pop().unwrap_or_default();
         self.window_origin = self.saved_window_origins.pop().unwrap_or_default();
     }
 
@@ -891,11 +1202,20 @@ impl<'a> LayoutCx<'a> {
     }
 }
 
+/// A mask being painted with, and where it was set
+#[derive(Clone)]
+pub(crate) struct ActiveMask {
+    mask: Mask,
+    size: Size,
+    transform: Affine,
+}
+
 pub struct PaintCx<'a> {
     pub(crate) app_state: &'a mut AppState,
     pub(crate) paint_state: &'a mut PaintState,
     pub(crate) transform: Affine,
     pub(crate) clip: Option<RoundedRect>,
+    pub(crate) mask: Option<ActiveMask>,
     pub(crate) color: Option<Color>,
     pub(crate) scroll_bar_color: Option<Color>,
     pub(crate) scroll_bar_rounded: Option<bool>,
@@ -906,9 +1226,17 @@ pub struct PaintCx<'a> {
     pub(crate) font_weight: Option<Weight>,
     pub(crate) font_style: Option<FontStyle>,
     pub(crate) line_height: Option<LineHeightValue>,
+    pub(crate) letter_spacing: Option<f64>,
+    pub(crate) text_decoration: Option<TextDecoration>,
+    pub(crate) cursor_color: Option<Color>,
+    pub(crate) caret_width: Option<f64>,
+    pub(crate) caret_shape: Option<CaretShape>,
+    pub(crate) selection_background: Option<Color>,
+    pub(crate) selection_color: Option<Color>,
     pub(crate) z_index: Option<i32>,
     pub(crate) saved_transforms: Vec<Affine>,
     pub(crate) saved_clips: Vec<Option<RoundedRect>>,
+    pub(crate) saved_masks: Vec<Option<ActiveMask>>,
     pub(crate) saved_colors: Vec<Option<Color>>,
     pub(crate) saved_scroll_bar_colors: Vec<Option<Color>>,
     pub(crate) saved_scroll_bar_roundeds: Vec<Option<bool>>,
@@ -919,6 +1247,13 @@ pub struct PaintCx<'a> {
     pub(crate) saved_font_weights: Vec<Option<Weight>>,
     pub(crate) saved_font_styles: Vec<Option<FontStyle>>,
     pub(crate) saved_line_heights: Vec<Option<LineHeightValue>>,
+    pub(crate) saved_letter_spacings: Vec<Option<f64>>,
+    pub(crate) saved_text_decorations: Vec<Option<TextDecoration>>,
+    pub(crate) saved_cursor_colors: Vec<Option<Color>>,
+    pub(crate) saved_caret_widths: Vec<Option<f64>>,
+    pub(crate) saved_caret_shapes: Vec<Option<CaretShape>>,
+    pub(crate) saved_selection_backgrounds: Vec<Option<Color>>,
+    pub(crate) saved_selection_colors: Vec<Option<Color>>,
     pub(crate) saved_z_indexes: Vec<Option<i32>>,
 }
 
@@ -926,6 +1261,7 @@ impl<'a> PaintCx<'a> {
     pub fn save(&mut self) {
         self.saved_transforms.push(self.transform);
         self.saved_clips.push(self.clip);
+        self.saved_masks.push(self.mask.clone());
         self.saved_colors.push(self.color);
         self.saved_scroll_bar_colors.push(self.scroll_bar_color);
         self.saved_scroll_bar_roundeds.push(self.scroll_bar_rounded);
@@ -938,12 +1274,21 @@ impl<'a> PaintCx<'a> {
         self.saved_font_weights.push(self.font_weight);
         self.saved_font_styles.push(self.font_style);
         self.saved_line_heights.push(self.line_height);
+        self.saved_letter_spacings.push(self.letter_spacing);
+        self.saved_text_decorations.push(self.text_decoration);
+        self.saved_cursor_colors.push(self.cursor_color);
+        self.saved_caret_widths.push(self.caret_width);
+        self.saved_caret_shapes.push(self.caret_shape);
+        self.saved_selection_backgrounds
+            .push(self.selection_background);
+        self.saved_selection_colors.push(self.selection_color);
         self.saved_z_indexes.push(self.z_index);
     }
 
     pub fn restore(&mut self) {
         self.transform = self.saved_transforms.pop().unwrap_or_default();
         self.clip = self.saved_clips.pop().unwrap_or_default();
+        self.mask = self.saved_masks.pop().unwrap_or_default();
         self.color = self.saved_colors.pop().unwrap_or_default();
         self.scroll_bar_color = self.saved_scroll_bar_colors.pop().unwrap_or_default();
         self.scroll_bar_rounded = self.saved_scroll_bar_roundeds.pop().unwrap_or_default();
@@ -954,6 +1299,13 @@ impl<'a> PaintCx<'a> {
         self.font_weight = self.saved_font_weights.pop().unwrap_or_default();
         self.font_style = self.saved_font_styles.pop().unwrap_or_default();
         self.line_height = self.saved_line_heights.pop().unwrap_or_default();
+        self.letter_spacing = self.saved_letter_spacings.pop().unwrap_or_default();
+        self.text_decoration = self.saved_text_decorations.pop().unwrap_or_default();
+        self.cursor_color = self.saved_cursor_colors.pop().unwrap_or_default();
+        self.caret_width = self.saved_caret_widths.pop().unwrap_or_default();
+        self.caret_shape = self.saved_caret_shapes.pop().unwrap_or_default();
+        self.selection_background = self.saved_selection_backgrounds.pop().unwrap_or_default();
+        self.selection_color = self.saved_selection_colors.pop().unwrap_or_default();
         self.z_index = self.saved_z_indexes.pop().unwrap_or_default();
         self.paint_state.renderer.transform(self.transform);
         if let Some(z_index) = self.z_index {
@@ -963,6 +1315,7 @@ impl<'a> PaintCx<'a> {
         }
         if let Some(rect) = self.clip {
             self.paint_state.renderer.clip(&rect);
+            self.apply_mask();
         } else {
             self.paint_state.renderer.clear_clip();
         }
@@ -1028,6 +1381,41 @@ impl<'a> PaintCx<'a> {
             rect
         };
         self.clip = Some(rect);
+        self.apply_mask();
+    }
+
+    /// Masks everything painted until the next [restore](PaintCx::restore) with `mask`,
+    /// stretched over an area of `size` at the current origin
+    pub fn mask(&mut self, mask: &Mask, size: Size) {
+        let rect = size.to_rect();
+        let rect = if let Some(existing) = self.clip {
+            existing.rect().intersect(rect)
+        } else {
+            rect
+        };
+        self.clip = Some(rect.to_rounded_rect(0.0));
+        self.mask = Some(ActiveMask {
+            mask: mask.clone(),
+            size,
+            transform: self.transform,
+        });
+        self.apply_mask();
+    }
+
+    /// The renderer only keeps one clip, so the mask is applied again on top of every
+    /// clip set while it's active
+    fn apply_mask(&mut self) {
+        if let Some(active) = self.mask.as_ref() {
+            let renderer = &mut self.paint_state.renderer;
+            renderer.transform(active.transform);
+            match &active.mask {
+                Mask::Path(path) => renderer.intersect_clip(&path(active.size)),
+                Mask::Image { data, hash } => {
+                    renderer.intersect_clip_img(Img { data, hash }, active.size.to_rect())
+                }
+            }
+            renderer.transform(self.transform);
+        }
     }
 
     pub fn offset(&mut self, offset: (f64, f64)) {
@@ -1068,6 +1456,31 @@ impl<'a> PaintCx<'a> {
         }
     }
 
+    /// Applies `transform` on top of the current one, in the coordinates of the view being
+    /// painted
+    pub(crate) fn apply_transform(&mut self, transform: Affine) {
+        self.transform *= transform;
+        self.paint_state.renderer.transform(self.transform);
+        if let Some(rect) = self.clip.as_mut() {
+            // the clip is kept as a rectangle, so a rotated one becomes its bounds
+            let radius = rect.radii();
+            *rect = transform
+                .inverse()
+                .transform_rect_bbox(rect.rect())
+                .to_rounded_rect(radius);
+        }
+    }
+
+    /// Paints the `children` in the order of their [z_index](crate::style::Style::z_index),
+    /// and in their own order when they have the same one, so a child with a higher z_index
+    /// is painted over the ones that come after it
+    pub fn paint_children(&mut self, mut children: Vec<&mut dyn View>) {
+        children.sort_by_key(|child| self.app_state.get_z_index(child.id()));
+        for child in children {
+            child.paint_main(self);
+        }
+    }
+
     pub(crate) fn set_z_index(&mut self, z_index: i32) {
         self.z_index = Some(z_index);
         self.paint_state.renderer.set_z_index(z_index);
@@ -1076,6 +1489,18 @@ impl<'a> PaintCx<'a> {
     pub fn is_focused(&self, id: Id) -> bool {
         self.app_state.is_focused(&id)
     }
+
+    /// When painting this frame started. Views animating while they're painted should
+    /// use this instead of `Instant::now`, so they all move in step.
+    pub fn frame_time(&self) -> Instant {
+        self.app_state.frame_time
+    }
+
+    /// Paints `id` again on the next frame, for animations that only change what's painted
+    /// and keep going for as long as the view asks for frames
+    pub fn request_animation_frame(&mut self, id: Id) {
+        self.app_state.frame_requests.insert(id);
+    }
 }
 
 // TODO: should this be private?
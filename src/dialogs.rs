@@ -0,0 +1,153 @@
+//! The file dialogs of the platform, for picking files to open, a path to save to, or a
+//! folder.
+//!
+//! The dialogs don't block the views: the result is passed to the callback once the user is
+//! done with the dialog, on the UI thread like any other event, so the callback can set
+//! signals. On macOS the panels can only be shown from the main thread, so they're shown
+//! there after the current event is handled, and on the other platforms the dialog waits
+//! on a thread of its own.
+//!
+//! ```rust,no_run
+//! use floem::{
+//!     dialogs::open_file,
+//!     file::{FileDialogOptions, FileSpec},
+//!     reactive::create_rw_signal,
+//! };
+//!
+//! let picked = create_rw_signal(Vec::new());
+//! let options = FileDialogOptions::new()
+//!     .allowed_types(vec![FileSpec {
+//!         name: "Images",
+//!         extensions: &["png", "jpg"],
+//!     }])
+//!     .multi_selection();
+//! open_file(options, move |files| picked.set(files));
+//! ```
+
+use std::path::PathBuf;
+
+use floem_reactive::Scope;
+
+use crate::{
+    ext_event::create_ext_action,
+    file::{FileDialogOptions, FileInfo},
+};
+
+/// Shows a dialog for picking a file to open, or several with
+/// [multi_selection](FileDialogOptions::multi_selection), and calls `on_pick` with them.
+/// They're empty if the dialog was cancelled.
+///
+/// With [select_directories](FileDialogOptions::select_directories) this picks a folder
+/// instead, like [pick_folder].
+pub fn open_file(options: FileDialogOptions, on_pick: impl Fn(Vec<FileInfo>) + 'static) {
+    if options.select_directories {
+        pick_folder(options, move |folder| on_pick(folder.into_iter().collect()));
+        return;
+    }
+    run_dialog(
+        move || {
+            let dialog = file_dialog(&options);
+            let paths = if options.multi_selection {
+                dialog.pick_files().unwrap_or_default()
+            } else {
+                dialog.pick_file().into_iter().collect()
+            };
+            paths
+                .into_iter()
+                .map(|path| file_info(path, &options))
+                .collect()
+        },
+        on_pick,
+    );
+}
+
+/// Shows a dialog for picking where to save a file, and calls `on_pick` with the path, or
+/// with `None` if the dialog was cancelled. The file isn't written, that's left to the
+/// callback.
+pub fn save_file(options: FileDialogOptions, on_pick: impl Fn(Option<FileInfo>) + 'static) {
+    run_dialog(
+        move || {
+            file_dialog(&options)
+                .save_file()
+                .map(|path| file_info(path, &options))
+        },
+        on_pick,
+    );
+}
+
+/// Shows a dialog for picking a folder, and calls `on_pick` with it, or with `None` if the
+/// dialog was cancelled. The file types of the options aren't used here.
+pub fn pick_folder(options: FileDialogOptions, on_pick: impl Fn(Option<FileInfo>) + 'static) {
+    run_dialog(
+        move || {
+            file_dialog(&options)
+                .pick_folder()
+                .map(|path| FileInfo { path, format: None })
+        },
+        on_pick,
+    );
+}
+
+/// Shows the dialog with `show` where the platform allows it, and passes the result to
+/// `on_result` back on the UI thread
+fn run_dialog<T: Send + 'static>(
+    show: impl FnOnce() -> T + Send + 'static,
+    on_result: impl Fn(T) + 'static,
+) {
+    let send = create_ext_action(Scope::new(), on_result);
+
+    #[cfg(target_os = "macos")]
+    {
+        // the panel runs modally, so it's not shown in the middle of handling an event
+        crate::action::exec_after(std::time::Duration::ZERO, move |_| send(show()));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        std::thread::spawn(move || send(show()));
+    }
+}
+
+/// The dialog with everything from `options` that rfd supports
+fn file_dialog(options: &FileDialogOptions) -> rfd::FileDialog {
+    let mut dialog = rfd::FileDialog::new();
+    if let Some(path) = options.starting_directory.as_ref() {
+        dialog = dialog.set_directory(path);
+    }
+    if let Some(title) = options.title.as_ref() {
+        dialog = dialog.set_title(title);
+    }
+    if let Some(name) = options.default_name.as_ref() {
+        dialog = dialog.set_file_name(name);
+    }
+    // the first filter is the one that's selected at first
+    let default_type = options
+        .default_type
+        .or_else(|| options.allowed_types.as_ref()?.first().copied());
+    let types = options.allowed_types.iter().flatten();
+    for spec in default_type
+        .iter()
+        .chain(types.filter(|spec| Some(**spec) != default_type))
+    {
+        dialog = dialog.add_filter(spec.name, spec.extensions);
+    }
+    dialog
+}
+
+/// The picked `path`, with the allowed type that its extension belongs to
+fn file_info(path: PathBuf, options: &FileDialogOptions) -> FileInfo {
+    let extension = path.extension().and_then(|extension| extension.to_str());
+    let format = extension.and_then(|extension| {
+        options
+            .allowed_types
+            .iter()
+            .flatten()
+            .find(|spec| {
+                spec.extensions
+                    .iter()
+                    .any(|spec_extension| spec_extension.eq_ignore_ascii_case(extension))
+            })
+            .copied()
+    });
+    FileInfo { path, format }
+}
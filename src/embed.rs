@@ -0,0 +1,197 @@
+//! Running floem views inside an application that owns the event loop, like a game engine
+//! or an editor with its own main loop.
+//!
+//! An [EmbeddedView] renders into a surface the host gives it and only does anything when
+//! the host calls it: [handle_event](EmbeddedView::handle_event) with each event of the
+//! surface's window, [tick](EmbeddedView::tick) once per frame to run timers and reactive
+//! updates, and [render](EmbeddedView::render) when it [needs to](EmbeddedView::needs_render).
+//!
+//! The host has to be a winit application, as events are fed in as winit `WindowEvent`s.
+//! It shouldn't also run an [Application](crate::Application) on the same thread, since
+//! both would handle the same timers and background updates.
+//! ```rust,no_run
+//! # use std::time::Instant;
+//! # use floem::{embed::EmbeddedView, views::label};
+//! # fn run(window: &winit::window::Window, events: Vec<winit::event::WindowEvent>) {
+//! let scale = window.scale_factor();
+//! let size = window.inner_size().to_logical::<f64>(scale);
+//! let mut ui = EmbeddedView::new(
+//!     window,
+//!     window.id(),
+//!     floem::kurbo::Size::new(size.width, size.height),
+//!     scale,
+//!     || label(|| "Hello from floem".to_string()),
+//! );
+//! // in the host's loop
+//! for event in events {
+//!     ui.handle_event(event);
+//! }
+//! ui.tick(Instant::now());
+//! if ui.needs_render() {
+//!     ui.render(window);
+//! }
+//! # }
+//! ```
+
+use std::{collections::HashMap, time::Instant};
+
+use floem_reactive::batch;
+use kurbo::Size;
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle, RawWindowHandle};
+use winit::{event::WindowEvent, window::WindowId};
+
+use crate::{
+    action::{Timer, TimerToken},
+    app::{AppUpdateEvent, APP_UPDATE_EVENTS},
+    context::PaintState,
+    ext_event::EXT_EVENT_HANDLER,
+    modal::remove_modal_layer,
+    overlay::remove_overlay_layer,
+    toast::remove_toast_layer,
+    view::View,
+    window::{ResizeMode, SpatialNavigation, WindowSignals},
+    window_handle::{SurfaceInfo, WindowHandle},
+};
+
+/// A view tree drawn to a surface of the host application. See the [module docs](self)
+pub struct EmbeddedView {
+    handle: WindowHandle,
+    window_id: WindowId,
+    /// The surface the renderer is set up for
+    target: RawWindowHandle,
+    size: Size,
+    timers: HashMap<TimerToken, Timer>,
+}
+
+impl EmbeddedView {
+    /// Builds the view from `view_fn` to draw it to `target`, a window or child surface of
+    /// the host with the logical `size` and `scale` factor. `window_id` is the id of the
+    /// winit window that the host's events come from. The view's
+    /// [WindowSignals](crate::window::WindowSignals) follow the size of the surface.
+    pub fn new<W, V>(
+        target: &W,
+        window_id: WindowId,
+        size: Size,
+        scale: f64,
+        view_fn: impl FnOnce() -> V + 'static,
+    ) -> Self
+    where
+        W: HasRawDisplayHandle + HasRawWindowHandle,
+        V: View + 'static,
+    {
+        let paint_state = PaintState::new(target, scale, size * scale);
+        let surface = SurfaceInfo {
+            window_id,
+            scale,
+            size,
+            outer_size: size,
+            theme: None,
+            is_maximized: false,
+        };
+        let handle = WindowHandle::with_surface(
+            None,
+            surface,
+            paint_state,
+            move |_| Box::new(view_fn()),
+            ResizeMode::default(),
+            SpatialNavigation::default(),
+        );
+        Self {
+            handle,
+            window_id,
+            target: target.raw_window_handle(),
+            size,
+            timers: HashMap::new(),
+        }
+    }
+
+    /// Handles an event of the host's window, with positions relative to the surface.
+    /// Resizes should go through [resize](Self::resize) instead, and closing is up to the
+    /// host, which drops the view.
+    pub fn handle_event(&mut self, event: WindowEvent) {
+        match event {
+            WindowEvent::Resized(_)
+            | WindowEvent::ScaleFactorChanged { .. }
+            | WindowEvent::CloseRequested
+            | WindowEvent::Destroyed => {}
+            event => self.handle.window_event(event),
+        }
+    }
+
+    /// Changes the logical size and scale factor of the surface
+    pub fn resize(&mut self, size: Size, scale: f64) {
+        if scale != self.handle.scale {
+            self.handle.scale(scale);
+        }
+        self.size = size;
+        self.handle.size(size);
+    }
+
+    /// Runs the updates that are due by `frame_time`: results sent from other threads,
+    /// timers and animations, and the relayout and restyle they lead to.
+    ///
+    /// Returns when the next timer is due, for hosts that sleep until there's something to do.
+    pub fn tick(&mut self, frame_time: Instant) -> Option<Instant> {
+        // what several threads sent since the last time is taken in as one update
+        batch(|| {
+            while let Some(trigger) = { EXT_EVENT_HANDLER.queue.lock().pop_front() } {
+                trigger.notify();
+            }
+        });
+
+        let events = APP_UPDATE_EVENTS.with(|events| std::mem::take(&mut *events.borrow_mut()));
+        for event in events {
+            // there's no application to open or close windows for
+            if let AppUpdateEvent::RequestTimer { timer } = event {
+                self.timers.insert(timer.token, timer);
+            }
+        }
+
+        let due: Vec<TimerToken> = self
+            .timers
+            .iter()
+            .filter(|(_, timer)| timer.deadline <= frame_time)
+            .map(|(token, _)| *token)
+            .collect();
+        for token in due {
+            if let Some(timer) = self.timers.remove(&token) {
+                (timer.action)(token);
+            }
+        }
+
+        self.handle.process_update();
+        self.timers.values().map(|timer| timer.deadline).min()
+    }
+
+    /// Whether anything changed since the last [render](Self::render)
+    pub fn needs_render(&self) -> bool {
+        self.handle.needs_paint
+    }
+
+    /// Paints the view to `target`. That's usually the surface it was created with, and when
+    /// it's another one, like after the host recreated its window, the renderer is set up for
+    /// that surface first, which takes a while.
+    pub fn render<W>(&mut self, target: &W)
+    where
+        W: HasRawDisplayHandle + HasRawWindowHandle,
+    {
+        let handle = target.raw_window_handle();
+        if handle != self.target {
+            let scale = self.handle.scale;
+            self.handle
+                .set_paint_state(PaintState::new(target, scale, self.size * scale));
+            self.target = handle;
+        }
+        self.handle.paint();
+    }
+}
+
+impl Drop for EmbeddedView {
+    fn drop(&mut self) {
+        self.handle.destroy();
+        remove_overlay_layer(self.handle.view.id());
+        remove_modal_layer(self.handle.view.id());
+        remove_toast_layer(self.handle.view.id());
+        WindowSignals::remove(self.window_id);
+    }
+}
@@ -1,5 +1,8 @@
-use kurbo::{Point, Size};
-use winit::{keyboard::KeyCode, window::Theme};
+use kurbo::{Affine, Point, Size};
+use winit::{
+    keyboard::{Key, KeyCode, ModifiersState},
+    window::Theme,
+};
 
 use crate::{
     keyboard::KeyEvent,
@@ -127,6 +130,18 @@ impl Event {
         }
     }
 
+    /// The context menu key, or Shift+F10 on keyboards without one, open the context menu
+    pub(crate) fn is_context_menu_trigger(&self) -> bool {
+        match self {
+            Event::KeyDown(key) => {
+                key.key.logical_key == Key::ContextMenu
+                    || (key.key.logical_key == Key::F10
+                        && key.modifiers.contains(ModifiersState::SHIFT))
+            }
+            _ => false,
+        }
+    }
+
     pub fn allow_disabled(&self) -> bool {
         match self {
             Event::PointerDown(_)
@@ -239,6 +254,37 @@ impl Event {
         self
     }
 
+    /// Maps the positions of the event with `transform`
+    pub fn transform(mut self, transform: Affine) -> Event {
+        match &mut self {
+            Event::PointerDown(pointer_event) | Event::PointerUp(pointer_event) => {
+                pointer_event.pos = transform * pointer_event.pos;
+            }
+            Event::PointerMove(pointer_event) => {
+                pointer_event.pos = transform * pointer_event.pos;
+            }
+            Event::PointerWheel(pointer_event) => {
+                pointer_event.pos = transform * pointer_event.pos;
+            }
+            Event::KeyDown(_)
+            | Event::KeyUp(_)
+            | Event::FocusGained
+            | Event::FocusLost
+            | Event::ImeEnabled
+            | Event::ImeDisabled
+            | Event::ImePreedit { .. }
+            | Event::ThemeChanged(_)
+            | Event::ImeCommit(_)
+            | Event::WindowClosed
+            | Event::WindowResized(_)
+            | Event::WindowMoved(_)
+            | Event::WindowMaximizeChanged(_)
+            | Event::WindowGotFocus
+            | Event::WindowLostFocus => {}
+        }
+        self
+    }
+
     pub fn listener(&self) -> Option<EventListener> {
         match self {
             Event::PointerDown(_) => Some(EventListener::PointerDown),
@@ -1,10 +1,27 @@
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    future::Future,
+    pin::pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+    time::Duration,
+};
 
-use floem_reactive::{create_effect, untrack, with_scope, ReadSignal, Scope, Trigger, WriteSignal};
+use floem_reactive::{
+    create_effect, create_rw_signal, create_signal, untrack, with_scope, ReadSignal, RwSignal,
+    Scope, Trigger, WriteSignal,
+};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 
 use crate::{
+    action::exec_after,
     app::UserEvent,
     window_handle::{get_current_view, set_current_view},
     Application,
@@ -142,3 +159,273 @@ pub fn create_signal_from_channel<T: Send + 'static>(
 
     read
 }
+
+/// A handle for sending values to a signal from other threads, made by
+/// [create_signal_from_sender]. It can be cloned and sent to any thread or async task, and
+/// sending doesn't block, so it can be used from inside a tokio task as well.
+pub struct ExtSender<T> {
+    queue: Arc<Mutex<VecDeque<T>>>,
+    trigger: Trigger,
+}
+
+impl<T> Clone for ExtSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+            trigger: self.trigger,
+        }
+    }
+}
+
+impl<T: Send + 'static> ExtSender<T> {
+    /// Sets the signal to `value` on the UI thread, waking the event loop for it
+    pub fn send(&self, value: T) {
+        self.queue.lock().push_back(value);
+        EXT_EVENT_HANDLER.add_trigger(self.trigger);
+    }
+}
+
+/// Creates a signal that is set to each value sent with the returned [ExtSender], which,
+/// unlike [create_signal_from_channel], doesn't need a thread waiting on a channel.
+///
+/// The signal belongs to the current Scope, and the values sent once it's disposed are
+/// dropped.
+pub fn create_signal_from_sender<T: Send + 'static>() -> (ReadSignal<Option<T>>, ExtSender<T>) {
+    let cx = Scope::current();
+    let trigger = cx.create_trigger();
+    let (read, write) = cx.create_signal(None);
+    let queue = Arc::new(Mutex::new(VecDeque::new()));
+
+    {
+        let queue = queue.clone();
+        cx.create_effect(move |_| {
+            trigger.track();
+            while let Some(value) = queue.lock().pop_front() {
+                write.set(Some(value));
+            }
+        });
+    }
+
+    (read, ExtSender { queue, trigger })
+}
+
+/// The state of the data fetched by [create_resource]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Resource<T, E> {
+    /// The fetch hasn't finished yet
+    Loading,
+    Ready(T),
+    Error(E),
+}
+
+impl<T, E> Resource<T, E> {
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Resource::Loading)
+    }
+
+    /// The data, once it's been fetched
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            Resource::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// A fetch started by [create_resource] that can be given up on
+struct Fetch {
+    cancelled: Arc<AtomicBool>,
+    thread: Thread,
+}
+
+impl Fetch {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        // so the future is dropped right away instead of at its next wake up
+        self.thread.unpark();
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Polls `future` to the end on the current thread, or until it's `cancelled`
+fn block_on<F: Future>(future: F, cancelled: &AtomicBool) -> Option<F::Output> {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if cancelled.load(Ordering::SeqCst) {
+            return None;
+        }
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return Some(output);
+        }
+        std::thread::park();
+    }
+}
+
+/// Creates a signal with the data `fetcher` fetches for the value `source` returns.
+///
+/// The future from `fetcher` is run on a thread of its own, and the signal is
+/// [Loading](Resource::Loading) until it finishes, then [Ready](Resource::Ready) or
+/// [Error](Resource::Error) with what it returned. When a signal read by `source` changes,
+/// the fetch that's still running is cancelled and one for the new value is started.
+pub fn create_resource<S, T, E, Fut>(
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fut + 'static,
+) -> ReadSignal<Resource<T, E>>
+where
+    S: 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+{
+    let cx = Scope::current();
+    let (read, write) = cx.create_signal(Resource::Loading);
+
+    create_effect(move |prev: Option<Fetch>| {
+        let source = source();
+        if let Some(prev) = prev {
+            prev.cancel();
+        }
+        let future = untrack(|| fetcher(source));
+        write.set(Resource::Loading);
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let send = {
+            let cancelled = cancelled.clone();
+            create_ext_action(cx, move |result: Option<Result<T, E>>| {
+                // a fetch that was cancelled can still finish before it notices
+                if cancelled.load(Ordering::SeqCst) {
+                    return;
+                }
+                match result {
+                    Some(Ok(value)) => write.set(Resource::Ready(value)),
+                    Some(Err(err)) => write.set(Resource::Error(err)),
+                    None => {}
+                }
+            })
+        };
+        let thread = {
+            let cancelled = cancelled.clone();
+            std::thread::spawn(move || {
+                send(block_on(future, &cancelled));
+            })
+            .thread()
+            .clone()
+        };
+        Fetch { cancelled, thread }
+    });
+
+    read
+}
+
+/// Rate limits for the updates of a signal, run on Floem's timers
+pub trait SignalTiming<T> {
+    /// A signal that takes the value of this one once it has stayed the same for
+    /// `duration`, so a burst of updates, like the keys of a word being typed, ends up as
+    /// a single one
+    fn debounce(self, duration: Duration) -> ReadSignal<T>;
+
+    /// A signal that takes the value of this one at most once every `duration`. The first
+    /// update is passed on right away and the last one of a burst at the end of the
+    /// `duration` it falls in, so updates that keep coming, like the ones of a resize, are
+    /// passed on at a steady pace
+    fn throttle(self, duration: Duration) -> ReadSignal<T>;
+}
+
+impl<T: Clone + 'static> SignalTiming<T> for ReadSignal<T> {
+    fn debounce(self, duration: Duration) -> ReadSignal<T> {
+        debounce_signal(move || self.get(), duration)
+    }
+
+    fn throttle(self, duration: Duration) -> ReadSignal<T> {
+        throttle_signal(move || self.get(), duration)
+    }
+}
+
+impl<T: Clone + 'static> SignalTiming<T> for RwSignal<T> {
+    fn debounce(self, duration: Duration) -> ReadSignal<T> {
+        debounce_signal(move || self.get(), duration)
+    }
+
+    fn throttle(self, duration: Duration) -> ReadSignal<T> {
+        throttle_signal(move || self.get(), duration)
+    }
+}
+
+fn debounce_signal<T: 'static>(
+    source: impl Fn() -> T + 'static,
+    duration: Duration,
+) -> ReadSignal<T> {
+    let (read, write) = create_signal(untrack(&source));
+    // counts the updates, so only the last one is passed on
+    let generation = create_rw_signal(0u64);
+
+    create_effect(move |first_run: Option<()>| {
+        let value = source();
+        if first_run.is_none() {
+            return;
+        }
+        let current = generation.get_untracked() + 1;
+        generation.set(current);
+        exec_after(duration, move |_| {
+            if generation.try_get_untracked() == Some(current) {
+                write.set(value);
+            }
+        });
+    });
+
+    read
+}
+
+fn throttle_signal<T: 'static>(
+    source: impl Fn() -> T + 'static,
+    duration: Duration,
+) -> ReadSignal<T> {
+    let (read, write) = create_signal(untrack(&source));
+    let pending: Rc<RefCell<Option<T>>> = Rc::new(RefCell::new(None));
+    let waiting = Rc::new(Cell::new(false));
+
+    create_effect(move |first_run: Option<()>| {
+        let value = source();
+        if first_run.is_none() {
+            return;
+        }
+        if waiting.get() {
+            *pending.borrow_mut() = Some(value);
+            return;
+        }
+        write.set(value);
+        waiting.set(true);
+        throttle_wait(duration, pending.clone(), waiting.clone(), write);
+    });
+
+    read
+}
+
+/// Passes on the last update that came while waiting for `duration`, and waits again if
+/// there was one
+fn throttle_wait<T: 'static>(
+    duration: Duration,
+    pending: Rc<RefCell<Option<T>>>,
+    waiting: Rc<Cell<bool>>,
+    write: WriteSignal<T>,
+) {
+    exec_after(duration, move |_| {
+        let value = pending.borrow_mut().take();
+        match value {
+            Some(value) => {
+                write.set(value);
+                throttle_wait(duration, pending, waiting, write);
+            }
+            None => waiting.set(false),
+        }
+    });
+}
@@ -0,0 +1,86 @@
+//! # Fonts
+//!
+//! The fonts registered here can be used by their family name in
+//! [font_family](crate::style::Style::font_family), like the fonts installed on the system.
+//! This is how an app ships its own fonts, and icon fonts.
+//! ```rust,no_run
+//! # use floem::{fonts::register_font, style::Style};
+//! register_font(std::fs::read("assets/Inter.ttf").unwrap());
+//! let body = Style::BASE.font_family("Inter, Noto Sans, sans-serif".to_string());
+//! ```
+//!
+//! A font family can be a list, like in CSS, and the first of its families that's available
+//! is used. The generic `sans-serif`, `serif` and `monospace` families are the ones set with
+//! [set_generic_family].
+//!
+//! Fonts can be registered at any time, and the text of the windows that are open is laid
+//! out again with them.
+
+use std::{
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use floem_renderer::cosmic_text::FONT_SYSTEM;
+
+use crate::{app::UserEvent, Application};
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// A generic family, that a font family can name in place of an actual one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenericFamily {
+    SansSerif,
+    Serif,
+    Monospace,
+}
+
+/// Registers the fonts in `data`, the content of a TrueType or OpenType font file or
+/// collection
+pub fn register_font(data: impl Into<Vec<u8>>) {
+    FONT_SYSTEM.lock().db_mut().load_font_data(data.into());
+    fonts_changed();
+}
+
+/// Registers the fonts in the file at `path`
+pub fn register_font_file(path: impl AsRef<Path>) -> std::io::Result<()> {
+    FONT_SYSTEM.lock().db_mut().load_font_file(path)?;
+    fonts_changed();
+    Ok(())
+}
+
+/// Registers the fonts of the files in the directory at `path` and in the directories inside
+/// of it. The files that aren't fonts are skipped.
+pub fn register_fonts_dir(path: impl AsRef<Path>) {
+    FONT_SYSTEM.lock().db_mut().load_fonts_dir(path);
+    fonts_changed();
+}
+
+/// Sets the font family used for the generic `family`, in place of the one the system picks
+pub fn set_generic_family(family: GenericFamily, name: impl Into<String>) {
+    let name = name.into();
+    {
+        let mut font_system = FONT_SYSTEM.lock();
+        let db = font_system.db_mut();
+        match family {
+            GenericFamily::SansSerif => db.set_sans_serif_family(name),
+            GenericFamily::Serif => db.set_serif_family(name),
+            GenericFamily::Monospace => db.set_monospace_family(name),
+        }
+    }
+    fonts_changed();
+}
+
+fn fonts_changed() {
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+    // so the windows lay out their text again
+    Application::with_event_loop_proxy(|proxy| {
+        let _ = proxy.send_event(UserEvent::Idle);
+    });
+}
+
+/// Counts the changes of the registered fonts, so the text laid out with the fonts from
+/// before can be told apart
+pub(crate) fn font_generation() -> u64 {
+    GENERATION.load(Ordering::Relaxed)
+}
@@ -0,0 +1,246 @@
+//! # Gamepad input
+//!
+//! Enabled with the `gamepad` feature. Controllers are polled on a background thread,
+//! and their input is exposed as signals on the main thread:
+//! - [gamepad_state] for the buttons currently held and the latest axis values
+//! - [gamepad_events] for every button press, release and axis change as it happens
+//!
+//! The d-pad and the left stick also move the focus between `keyboard_navigatable` views
+//! in the focused window, so a gamepad can drive the UI without any extra handling.
+//!
+//! ```ignore
+//! let events = gamepad_events();
+//! create_effect(move |_| {
+//!     if let Some(GamepadEvent {
+//!         kind: GamepadEventKind::ButtonPressed(GamepadButton::South),
+//!         ..
+//!     }) = events.get()
+//!     {
+//!         play();
+//!     }
+//! });
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use floem_reactive::{ReadSignal, RwSignal, Scope};
+
+use crate::{
+    app::{Application, UserEvent},
+    view::FocusDirection,
+};
+
+/// How often the background thread checks the controllers for new input
+const POLL_INTERVAL: Duration = Duration::from_millis(8);
+/// How far a stick has to be pushed before it moves the focus
+const NAVIGATION_THRESHOLD: f32 = 0.5;
+
+thread_local! {
+    static GAMEPAD_STATE: RwSignal<GamepadState> =
+        Scope::new().create_rw_signal(GamepadState::default());
+    static GAMEPAD_EVENTS: RwSignal<Option<GamepadEvent>> = Scope::new().create_rw_signal(None);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Unknown,
+}
+
+impl From<gilrs::Button> for GamepadButton {
+    fn from(value: gilrs::Button) -> Self {
+        match value {
+            gilrs::Button::South => Self::South,
+            gilrs::Button::East => Self::East,
+            gilrs::Button::North => Self::North,
+            gilrs::Button::West => Self::West,
+            gilrs::Button::LeftTrigger => Self::LeftTrigger,
+            gilrs::Button::LeftTrigger2 => Self::LeftTrigger2,
+            gilrs::Button::RightTrigger => Self::RightTrigger,
+            gilrs::Button::RightTrigger2 => Self::RightTrigger2,
+            gilrs::Button::Select => Self::Select,
+            gilrs::Button::Start => Self::Start,
+            gilrs::Button::Mode => Self::Mode,
+            gilrs::Button::LeftThumb => Self::LeftThumb,
+            gilrs::Button::RightThumb => Self::RightThumb,
+            gilrs::Button::DPadUp => Self::DPadUp,
+            gilrs::Button::DPadDown => Self::DPadDown,
+            gilrs::Button::DPadLeft => Self::DPadLeft,
+            gilrs::Button::DPadRight => Self::DPadRight,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Stick axes range from -1.0 to 1.0, with positive values pointing right and up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    Unknown,
+}
+
+impl From<gilrs::Axis> for GamepadAxis {
+    fn from(value: gilrs::Axis) -> Self {
+        match value {
+            gilrs::Axis::LeftStickX => Self::LeftStickX,
+            gilrs::Axis::LeftStickY => Self::LeftStickY,
+            gilrs::Axis::RightStickX => Self::RightStickX,
+            gilrs::Axis::RightStickY => Self::RightStickY,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEventKind {
+    Connected,
+    Disconnected,
+    ButtonPressed(GamepadButton),
+    ButtonReleased(GamepadButton),
+    AxisChanged(GamepadAxis, f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GamepadEvent {
+    /// Identifies the controller, stable for as long as it stays connected
+    pub gamepad: usize,
+    pub kind: GamepadEventKind,
+}
+
+impl GamepadEvent {
+    fn from_gilrs(event: gilrs::Event) -> Option<Self> {
+        let kind = match event.event {
+            gilrs::EventType::Connected => GamepadEventKind::Connected,
+            gilrs::EventType::Disconnected => GamepadEventKind::Disconnected,
+            gilrs::EventType::ButtonPressed(button, _) => {
+                GamepadEventKind::ButtonPressed(button.into())
+            }
+            gilrs::EventType::ButtonReleased(button, _) => {
+                GamepadEventKind::ButtonReleased(button.into())
+            }
+            gilrs::EventType::AxisChanged(axis, value, _) => {
+                GamepadEventKind::AxisChanged(axis.into(), value)
+            }
+            _ => return None,
+        };
+        Some(Self {
+            gamepad: event.id.into(),
+            kind,
+        })
+    }
+}
+
+/// The combined input of all connected gamepads
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GamepadState {
+    pub connected: HashSet<usize>,
+    pub pressed: HashSet<GamepadButton>,
+    pub axes: HashMap<GamepadAxis, f32>,
+}
+
+impl GamepadState {
+    pub fn is_pressed(&self, button: GamepadButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+}
+
+/// A signal with the buttons currently held and the latest axis values
+pub fn gamepad_state() -> ReadSignal<GamepadState> {
+    GAMEPAD_STATE.with(|state| state.read_only())
+}
+
+/// A signal that's set to every gamepad event as it arrives
+pub fn gamepad_events() -> ReadSignal<Option<GamepadEvent>> {
+    GAMEPAD_EVENTS.with(|events| events.read_only())
+}
+
+/// Starts polling the controllers. If no gamepad backend is available on this platform,
+/// the signals simply never change.
+pub(crate) fn spawn_gamepad_thread() {
+    std::thread::spawn(|| {
+        let mut gilrs = match gilrs::Gilrs::new() {
+            Ok(gilrs) => gilrs,
+            Err(_) => return,
+        };
+        loop {
+            while let Some(event) = gilrs.next_event() {
+                if let Some(event) = GamepadEvent::from_gilrs(event) {
+                    Application::with_event_loop_proxy(|proxy| {
+                        let _ = proxy.send_event(UserEvent::Gamepad(event));
+                    });
+                }
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+/// Updates the gamepad signals, and returns the direction the focus should move in, if any
+pub(crate) fn update_gamepad(event: GamepadEvent) -> Option<FocusDirection> {
+    let mut direction = None;
+    GAMEPAD_STATE.with(|state| {
+        state.update(|state| match event.kind {
+            GamepadEventKind::Connected => {
+                state.connected.insert(event.gamepad);
+            }
+            GamepadEventKind::Disconnected => {
+                state.connected.remove(&event.gamepad);
+            }
+            GamepadEventKind::ButtonPressed(button) => {
+                state.pressed.insert(button);
+                direction = match button {
+                    GamepadButton::DPadUp => Some(FocusDirection::Up),
+                    GamepadButton::DPadDown => Some(FocusDirection::Down),
+                    GamepadButton::DPadLeft => Some(FocusDirection::Left),
+                    GamepadButton::DPadRight => Some(FocusDirection::Right),
+                    _ => None,
+                };
+            }
+            GamepadEventKind::ButtonReleased(button) => {
+                state.pressed.remove(&button);
+            }
+            GamepadEventKind::AxisChanged(axis, value) => {
+                let old = state.axis(axis);
+                state.axes.insert(axis, value);
+                // Only move once each time the stick is pushed past the threshold
+                if old.abs() < NAVIGATION_THRESHOLD && value.abs() >= NAVIGATION_THRESHOLD {
+                    direction = match (axis, value > 0.0) {
+                        (GamepadAxis::LeftStickX, true) => Some(FocusDirection::Right),
+                        (GamepadAxis::LeftStickX, false) => Some(FocusDirection::Left),
+                        (GamepadAxis::LeftStickY, true) => Some(FocusDirection::Up),
+                        (GamepadAxis::LeftStickY, false) => Some(FocusDirection::Down),
+                        _ => None,
+                    };
+                }
+            }
+        })
+    });
+    GAMEPAD_EVENTS.with(|events| events.set(Some(event)));
+    direction
+}
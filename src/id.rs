@@ -13,12 +13,14 @@ use std::{any::Any, cell::RefCell, collections::HashMap, sync::atomic::AtomicU64
 use kurbo::Point;
 
 use crate::{
-    animate::Animation,
+    animate::{Animation, KeyframeAnimation},
     context::{EventCallback, MenuCallback, ResizeCallback},
     event::EventListener,
-    responsive::ScreenSize,
+    overlay::Tooltip,
+    responsive::{ScreenSize, WidthQuery},
     style::{Style, StyleSelector},
     update::{UpdateMessage, CENTRAL_DEFERRED_UPDATE_MESSAGES, CENTRAL_UPDATE_MESSAGES},
+    views::Mask,
 };
 
 thread_local! {
@@ -148,6 +150,10 @@ impl Id {
         self.add_update_message(UpdateMessage::Style { id: *self, style });
     }
 
+    pub fn update_dyn_style(&self, style: Style) {
+        self.add_update_message(UpdateMessage::DynStyle { id: *self, style });
+    }
+
     pub fn update_style_selector(&self, style: Style, selector: StyleSelector) {
         self.add_update_message(UpdateMessage::StyleSelector {
             id: *self,
@@ -172,6 +178,15 @@ impl Id {
         });
     }
 
+    pub fn update_width_style(&self, key: Id, query: WidthQuery, style: Style) {
+        self.add_update_message(UpdateMessage::WidthStyle {
+            id: *self,
+            key,
+            query,
+            style,
+        });
+    }
+
     pub fn update_event_listener(&self, listener: EventListener, action: Box<EventCallback>) {
         self.add_update_message(UpdateMessage::EventListener {
             id: *self,
@@ -199,6 +214,13 @@ impl Id {
         });
     }
 
+    pub fn update_keyframe_animation(&self, animation: KeyframeAnimation) {
+        self.add_update_message(UpdateMessage::KeyframeAnimation {
+            id: *self,
+            animation,
+        });
+    }
+
     pub fn update_context_menu(&self, menu: Box<MenuCallback>) {
         self.add_update_message(UpdateMessage::ContextMenu { id: *self, menu });
     }
@@ -207,6 +229,30 @@ impl Id {
         self.add_update_message(UpdateMessage::PopoutMenu { id: *self, menu });
     }
 
+    pub(crate) fn update_tooltip(&self, tooltip: Tooltip) {
+        self.add_update_message(UpdateMessage::Tooltip { id: *self, tooltip });
+    }
+
+    pub fn update_mask(&self, mask: Mask) {
+        self.add_update_message(UpdateMessage::Mask { id: *self, mask });
+    }
+
+    /// Gives the view a class that [stylesheet](crate::stylesheet) rules can select it by
+    pub fn add_class(&self, class: impl Into<String>) {
+        self.add_update_message(UpdateMessage::AddClass {
+            id: *self,
+            class: class.into(),
+        });
+    }
+
+    /// Sets the id that [stylesheet](crate::stylesheet) rules can select the view by
+    pub fn update_style_id(&self, style_id: impl Into<String>) {
+        self.add_update_message(UpdateMessage::StyleId {
+            id: *self,
+            style_id: style_id.into(),
+        });
+    }
+
     fn add_update_message(&self, msg: UpdateMessage) {
         CENTRAL_UPDATE_MESSAGES.with(|msgs| {
             msgs.borrow_mut().push((*self, msg));
@@ -5,3 +5,35 @@ pub struct KeyEvent {
     pub key: winit::event::KeyEvent,
     pub modifiers: ModifiersState,
 }
+
+/// A hint describing what kind of text an input expects, passed on to the platform
+/// input method.
+///
+/// winit's input method purposes only tell passwords apart from other text, so
+/// [Password](InputPurpose::Password) is the only hint that reaches the platform. `Email`,
+/// `Number`, `Url` and `Search` are passed on as normal text, and don't change the layout
+/// of a virtual keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum InputPurpose {
+    #[default]
+    Normal,
+    Password,
+    Email,
+    Number,
+    Url,
+    Search,
+}
+
+impl From<InputPurpose> for winit::window::ImePurpose {
+    fn from(purpose: InputPurpose) -> Self {
+        // winit only distinguishes passwords from regular text
+        match purpose {
+            InputPurpose::Password => winit::window::ImePurpose::Password,
+            InputPurpose::Normal
+            | InputPurpose::Email
+            | InputPurpose::Number
+            | InputPurpose::Url
+            | InputPurpose::Search => winit::window::ImePurpose::Normal,
+        }
+    }
+}
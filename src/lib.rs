@@ -39,9 +39,44 @@
 //! will mutate the underlying `String` when the user types in the input box. This will
 //! reactivly update the [label](views::label), which displays the same text, in real time.
 //!
-//! ### Global state
+//! ### Derived state
 //!
-//! Global state can be implemented using [provide_context](floem_reactive::provide_context) and [use_context](floem_reactive::use_context).
+//! State that is computed from other signals can be kept in a [Memo](floem_reactive::Memo),
+//! created with [create_memo](floem_reactive::create_memo). A memo reruns its closure whenever
+//! a signal it reads changes, but only notifies the views and effects that use it when the
+//! computed value is different from the last one, as compared with `PartialEq`.
+//!
+//! ```ignore
+//! let items = create_rw_signal(vec![1, 2, 3]);
+//! let is_empty = create_memo(move |_| items.with(|items| items.is_empty()));
+//!
+//! // only reruns when the list goes from empty to not empty, or back
+//! label(move || if is_empty.get() { "Nothing here" } else { "" }.to_string())
+//! ```
+//!
+//! ### Untracked reads
+//!
+//! Reading a signal inside an effect or a view closure subscribes that closure to it. To read
+//! one without subscribing, use `get_untracked` or `with_untracked` on the signal, or
+//! [untrack](floem_reactive::untrack) for a whole block of code:
+//!
+//! ```ignore
+//! label(move || {
+//!     // reruns when `name` changes, but not when `greeting` does
+//!     let greeting = untrack(|| greeting.get());
+//!     format!("{greeting}, {}", name.get())
+//! })
+//! ```
+//!
+//! Signals and effects belong to the [Scope](floem_reactive::Scope) that was current when they
+//! were created, and are disposed with it. [with_scope](floem_reactive::with_scope) runs code
+//! with another Scope as the current one.
+//!
+//! ### Shared state
+//!
+//! State can be shared with nested views using [provide_context](floem_reactive::provide_context) and [use_context](floem_reactive::use_context).
+//! A value provided while building a view can only be used by the views nested in it. For
+//! global state, provide the value before any window is created, so it can be used from anywhere.
 //!
 //! ## Styling
 //! You can style your views by applying [Styles](style::Style) through the
@@ -94,16 +129,28 @@ pub mod animate;
 mod app;
 mod app_handle;
 pub mod context;
+pub mod dialogs;
+pub mod embed;
 pub mod event;
 pub mod ext_event;
 pub mod file;
+pub mod fonts;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
 pub mod id;
 pub mod keyboard;
 pub mod menu;
+pub mod modal;
+pub mod overlay;
+#[cfg(feature = "persist")]
+pub mod persist;
 pub mod pointer;
 pub mod renderer;
 pub mod responsive;
 pub mod style;
+pub mod stylesheet;
+pub mod theme;
+pub mod toast;
 pub mod unit;
 mod update;
 pub mod view;
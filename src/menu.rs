@@ -15,6 +15,16 @@ pub struct Menu {
     pub(crate) children: Vec<MenuEntry>,
 }
 
+/// A menu with the given title, to add entries to with [Menu::entry] and [Menu::separator]
+pub fn menu(title: impl Into<String>) -> Menu {
+    Menu::new(title)
+}
+
+/// A menu item with the given title, to give an action with [MenuItem::action]
+pub fn menu_item(title: impl Into<String>) -> MenuItem {
+    MenuItem::new(title)
+}
+
 impl From<Menu> for MenuEntry {
     fn from(m: Menu) -> MenuEntry {
         MenuEntry::SubMenu(m)
@@ -81,7 +91,8 @@ pub struct MenuItem {
     pub(crate) id: u64,
     pub(crate) title: String,
     // key: Option<HotKey>,
-    selected: Option<bool>,
+    pub(crate) accelerator: Option<String>,
+    pub(crate) selected: Option<bool>,
     pub(crate) enabled: bool,
     pub(crate) action: Option<Box<dyn Fn()>>,
 }
@@ -100,6 +111,7 @@ impl MenuItem {
             id,
             title: title.into(),
             // key: None,
+            accelerator: None,
             selected: None,
             enabled: true,
             action: None,
@@ -115,4 +127,18 @@ impl MenuItem {
         self.enabled = enabled;
         self
     }
+
+    /// Makes this a checkable item, showing a check mark while `checked` is true
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.selected = Some(checked);
+        self
+    }
+
+    /// The key combination shown at the end of the item in a [menu_bar](crate::views::menu_bar),
+    /// like "Ctrl+S". It's only displayed, the shortcut itself still has to be handled
+    /// with a key listener.
+    pub fn accelerator(mut self, accelerator: impl Into<String>) -> Self {
+        self.accelerator = Some(accelerator.into());
+        self
+    }
 }
@@ -0,0 +1,280 @@
+//! Dialogs that take over a window until they're closed.
+//!
+//! [open_modal] shows a view centered over a dimmed copy of the window's content. While it's
+//! open, the content below doesn't get any pointer input, keyboard focus stays inside the
+//! dialog, and Escape closes it. Modals opened from inside a modal stack on top of it.
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use floem_reactive::RwSignal;
+use kurbo::Rect;
+use taffy::prelude::Node;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    theme::tokens,
+    unit::UnitExt,
+    view::{ChangeFlags, View},
+    views::{list, Decorators},
+    window_handle::get_current_view,
+};
+
+thread_local! {
+    /// The open modals of each window, by the id of the window's root view
+    static MODAL_LAYERS: RefCell<HashMap<Id, RwSignal<Vec<ModalEntry>>>> =
+        RefCell::new(HashMap::new());
+}
+
+#[derive(Clone)]
+pub(crate) struct ModalEntry {
+    /// Also the id of the view holding the dialog
+    id: Id,
+    view_fn: Rc<dyn Fn() -> Box<dyn View>>,
+    cancel: Rc<dyn Fn()>,
+}
+
+/// Closes the modal it was given to by [open_modal], with or without a result
+pub struct ModalHandle<T> {
+    id: Id,
+    on_close: Rc<RefCell<Option<Box<dyn FnOnce(Option<T>)>>>>,
+}
+
+impl<T> Clone for ModalHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            on_close: self.on_close.clone(),
+        }
+    }
+}
+
+impl<T> ModalHandle<T> {
+    /// The id of the view the dialog is in
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Closes the modal, passing `result` to its `on_close` callback
+    pub fn close(&self, result: T) {
+        self.finish(Some(result));
+    }
+
+    /// Closes the modal without a result, the same as pressing Escape
+    pub fn cancel(&self) {
+        self.finish(None);
+    }
+
+    /// Whether the modal hasn't been closed yet
+    pub fn is_open(&self) -> bool {
+        self.on_close.borrow().is_some()
+    }
+
+    fn finish(&self, result: Option<T>) {
+        let on_close = self.on_close.borrow_mut().take();
+        if let Some(on_close) = on_close {
+            remove_modal(self.id);
+            on_close(result);
+        }
+    }
+}
+
+/// Shows the view from `view_fn` as a modal dialog in the current window.
+///
+/// The dialog closes when it calls [ModalHandle::close] with a result or [ModalHandle::cancel],
+/// or when Escape is pressed and nothing in the dialog handles it. `on_close` is called once
+/// when that happens, with `None` if there was no result. Until then, the rest of the
+/// window is dimmed and can't be clicked, and keyboard focus is moved into the dialog and
+/// kept there. Focus goes back to where it was once the dialog closes.
+/// ```rust
+/// # use floem::{modal::open_modal, view::View, views::{Decorators, label, stack}};
+/// fn confirm() {
+///     open_modal(
+///         |modal| {
+///             let (yes, no) = (modal.clone(), modal.clone());
+///             stack((
+///                 label(|| "Yes".to_string()).on_click(move |_| {
+///                     yes.close(true);
+///                     true
+///                 }),
+///                 label(|| "No".to_string()).on_click(move |_| {
+///                     no.close(false);
+///                     true
+///                 }),
+///             ))
+///         },
+///         |confirmed| println!("confirmed: {confirmed:?}"),
+///     );
+/// }
+/// ```
+pub fn open_modal<T: 'static, V: View + 'static>(
+    view_fn: impl Fn(ModalHandle<T>) -> V + 'static,
+    on_close: impl FnOnce(Option<T>) + 'static,
+) -> ModalHandle<T> {
+    let id = Id::next();
+    let handle = ModalHandle {
+        id,
+        on_close: Rc::new(RefCell::new(Some(
+            Box::new(on_close) as Box<dyn FnOnce(Option<T>)>
+        ))),
+    };
+
+    let current = get_current_view();
+    let root = current.root_id().unwrap_or(current);
+    let layer = MODAL_LAYERS.with(|layers| layers.borrow().get(&root).copied());
+    if let Some(layer) = layer {
+        let view_handle = handle.clone();
+        let cancel_handle = handle.clone();
+        let entry = ModalEntry {
+            id,
+            view_fn: Rc::new(move || Box::new(view_fn(view_handle.clone())) as Box<dyn View>),
+            cancel: Rc::new(move || cancel_handle.cancel()),
+        };
+        layer.update(|modals| modals.push(entry));
+    } else {
+        // not in a window, so there's nowhere to show it
+        handle.cancel();
+    }
+    handle
+}
+
+fn remove_modal(id: Id) {
+    let layers: Vec<_> = MODAL_LAYERS.with(|layers| layers.borrow().values().copied().collect());
+    for layer in layers {
+        if layer.with_untracked(|modals| modals.iter().any(|entry| entry.id == id)) {
+            layer.update(|modals| modals.retain(|entry| entry.id != id));
+        }
+    }
+}
+
+/// The layer of a window that modals are shown in, which has to be on top of the window's
+/// content and below its overlay layer, so popups opened from a dialog show above it
+pub(crate) fn modal_layer(modals: RwSignal<Vec<ModalEntry>>) -> impl View {
+    list(
+        move || modals.get(),
+        |entry: &ModalEntry| entry.id,
+        move |entry| modal_view(entry, modals),
+    )
+    .style(|s| s.absolute().size(100.pct(), 100.pct()))
+}
+
+pub(crate) fn register_modal_layer(root: Id, modals: RwSignal<Vec<ModalEntry>>) {
+    MODAL_LAYERS.with(|layers| layers.borrow_mut().insert(root, modals));
+}
+
+pub(crate) fn remove_modal_layer(root: Id) {
+    MODAL_LAYERS.with(|layers| layers.borrow_mut().remove(&root));
+}
+
+/// The ids of the views holding the open modals of the window of `root`, from the bottom up
+pub(crate) fn open_modals(root: Id) -> Vec<Id> {
+    let layer = MODAL_LAYERS.with(|layers| layers.borrow().get(&root).copied());
+    layer
+        .map(|layer| layer.with_untracked(|modals| modals.iter().map(|entry| entry.id).collect()))
+        .unwrap_or_default()
+}
+
+/// Cancels the topmost modal of the window of `root`, returning whether there was one
+pub(crate) fn cancel_top_modal(root: Id) -> bool {
+    let layer = MODAL_LAYERS.with(|layers| layers.borrow().get(&root).copied());
+    let cancel = layer.and_then(|layer| {
+        layer.with_untracked(|modals| modals.last().map(|entry| entry.cancel.clone()))
+    });
+    if let Some(cancel) = cancel {
+        cancel();
+        true
+    } else {
+        false
+    }
+}
+
+fn modal_view(entry: ModalEntry, modals: RwSignal<Vec<ModalEntry>>) -> ModalView {
+    ModalView {
+        id: entry.id,
+        modals,
+        child: (entry.view_fn)(),
+    }
+    .style(|s| {
+        s.absolute()
+            .size(100.pct(), 100.pct())
+            .items_center()
+            .justify_center()
+            .background(tokens().colors.backdrop)
+    })
+}
+
+/// The backdrop of a modal, which keeps pointer events from getting to anything below it
+/// while it's the topmost modal
+struct ModalView {
+    id: Id,
+    modals: RwSignal<Vec<ModalEntry>>,
+    child: Box<dyn View>,
+}
+
+impl View for ModalView {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.child.id() == id {
+            Some(&self.child)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.child.id() == id {
+            Some(&mut self.child)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.child]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.child]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Modal".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, true, |cx| vec![self.child.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        Some(self.child.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        // the layer sends events to the topmost modal first, and the ones below it mustn't
+        // get what it lets through, like the keys it doesn't handle
+        let is_top = self
+            .modals
+            .with_untracked(|modals| modals.last().map(|entry| entry.id) == Some(self.id));
+        if !is_top {
+            return false;
+        }
+        if cx.should_send(self.child.id(), &event)
+            && self.child.event_main(cx, id_path, event.clone())
+        {
+            return true;
+        }
+        event.is_pointer()
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.child.paint_main(cx);
+    }
+}
@@ -0,0 +1,480 @@
+//! Views shown above everything else in a window, positioned next to another view.
+//!
+//! Every window has an overlay layer painted over its content, where [add_overlay] puts views like
+//! tooltips and popovers. An overlay follows the view it's anchored to, and has a placement
+//! function that picks its position from the anchor's rect, its own size and the window size,
+//! so it can move to stay inside the window. [Placement] covers the usual ways of doing that.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    time::Duration,
+};
+
+use floem_reactive::{create_rw_signal, RwSignal};
+use kurbo::{Point, Rect, Size};
+use taffy::prelude::Node;
+
+use crate::{
+    action::exec_after,
+    context::{AppState, EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    theme::{tokens, TextRole},
+    view::{ChangeFlags, View},
+    views::{container_box, list, Decorators},
+};
+
+thread_local! {
+    /// The overlays of each window, by the id of the window's root view
+    static OVERLAY_LAYERS: RefCell<HashMap<Id, RwSignal<Vec<OverlayEntry>>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// How long the pointer has to rest on a view before its tooltip is shown, unless the tooltip
+/// was added with [tooltip_with_delay](crate::views::Decorators::tooltip_with_delay)
+pub(crate) const TOOLTIP_DELAY: Duration = Duration::from_millis(600);
+/// How far a tooltip is shown from the view it belongs to
+const TOOLTIP_GAP: f64 = 4.0;
+
+type PlaceFn = Rc<dyn Fn(Rect, Size, Size) -> Point>;
+
+#[derive(Clone)]
+pub(crate) struct OverlayEntry {
+    /// Also the id of the view holding the overlay
+    id: Id,
+    anchor: Id,
+    place: PlaceFn,
+    view_fn: Rc<dyn Fn() -> Box<dyn View>>,
+    on_outside_click: Option<Rc<dyn Fn()>>,
+}
+
+/// Which side of its anchor an overlay goes on, and how it lines up with the anchor along
+/// that side. `Top` and `Bottom` are centered horizontally, with `Start` lining up the left edges
+/// and `End` the right edges, and `Left` and `Right` work the same way vertically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    Top,
+    TopStart,
+    TopEnd,
+    Bottom,
+    BottomStart,
+    BottomEnd,
+    Left,
+    LeftStart,
+    LeftEnd,
+    Right,
+    RightStart,
+    RightEnd,
+}
+
+impl Placement {
+    /// The placement on the opposite side of the anchor, with the same alignment
+    pub fn flipped(self) -> Self {
+        match self {
+            Placement::Top => Placement::Bottom,
+            Placement::TopStart => Placement::BottomStart,
+            Placement::TopEnd => Placement::BottomEnd,
+            Placement::Bottom => Placement::Top,
+            Placement::BottomStart => Placement::TopStart,
+            Placement::BottomEnd => Placement::TopEnd,
+            Placement::Left => Placement::Right,
+            Placement::LeftStart => Placement::RightStart,
+            Placement::LeftEnd => Placement::RightEnd,
+            Placement::Right => Placement::Left,
+            Placement::RightStart => Placement::LeftStart,
+            Placement::RightEnd => Placement::LeftEnd,
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(
+            self,
+            Placement::Top
+                | Placement::TopStart
+                | Placement::TopEnd
+                | Placement::Bottom
+                | Placement::BottomStart
+                | Placement::BottomEnd
+        )
+    }
+
+    /// The top left corner of an overlay of `size` with this placement, `gap` away from `anchor`
+    pub fn position(self, anchor: Rect, size: Size, gap: f64) -> Point {
+        let start_x = anchor.x0;
+        let center_x = anchor.center().x - size.width / 2.0;
+        let end_x = anchor.x1 - size.width;
+        let start_y = anchor.y0;
+        let center_y = anchor.center().y - size.height / 2.0;
+        let end_y = anchor.y1 - size.height;
+        let top = anchor.y0 - gap - size.height;
+        let bottom = anchor.y1 + gap;
+        let left = anchor.x0 - gap - size.width;
+        let right = anchor.x1 + gap;
+        let (x, y) = match self {
+            Placement::Top => (center_x, top),
+            Placement::TopStart => (start_x, top),
+            Placement::TopEnd => (end_x, top),
+            Placement::Bottom => (center_x, bottom),
+            Placement::BottomStart => (start_x, bottom),
+            Placement::BottomEnd => (end_x, bottom),
+            Placement::Left => (left, center_y),
+            Placement::LeftStart => (left, start_y),
+            Placement::LeftEnd => (left, end_y),
+            Placement::Right => (right, center_y),
+            Placement::RightStart => (right, start_y),
+            Placement::RightEnd => (right, end_y),
+        };
+        Point::new(x, y)
+    }
+
+    /// Like [Placement::position], keeping the overlay inside a window of size `window`.
+    ///
+    /// With `flip`, the overlay goes on the opposite side of the anchor when it doesn't fit
+    /// on this side but does fit on the other. With `shift`, it's moved along the side of
+    /// the anchor as far as it takes to stay inside the window.
+    pub fn place(
+        self,
+        anchor: Rect,
+        size: Size,
+        window: Size,
+        gap: f64,
+        flip: bool,
+        shift: bool,
+    ) -> Point {
+        let overflows = |placement: Placement, position: Point| match placement {
+            Placement::Top | Placement::TopStart | Placement::TopEnd => position.y < 0.0,
+            Placement::Bottom | Placement::BottomStart | Placement::BottomEnd => {
+                position.y + size.height > window.height
+            }
+            Placement::Left | Placement::LeftStart | Placement::LeftEnd => position.x < 0.0,
+            Placement::Right | Placement::RightStart | Placement::RightEnd => {
+                position.x + size.width > window.width
+            }
+        };
+
+        let mut placement = self;
+        let mut position = self.position(anchor, size, gap);
+        if flip && overflows(placement, position) {
+            let flipped = self.flipped().position(anchor, size, gap);
+            if !overflows(self.flipped(), flipped) {
+                placement = self.flipped();
+                position = flipped;
+            }
+        }
+        if shift {
+            if placement.is_vertical() {
+                position.x = position.x.min(window.width - size.width).max(0.0);
+            } else {
+                position.y = position.y.min(window.height - size.height).max(0.0);
+            }
+        }
+        position
+    }
+}
+
+/// Shows the view built by `view_fn` in the overlay layer of the window `anchor` is in,
+/// returning an id to remove it again with [remove_overlay].
+///
+/// `place` is called with the rect of `anchor` and the size of the overlay, both in window
+/// coordinates, along with the size of the window, and returns where the top left corner
+/// of the overlay goes. It's called again whenever any of them change.
+/// Nothing is shown if `anchor` isn't in a window yet.
+pub fn add_overlay<V: View + 'static>(
+    anchor: Id,
+    place: impl Fn(Rect, Size, Size) -> Point + 'static,
+    view_fn: impl Fn() -> V + 'static,
+) -> Id {
+    push_overlay(anchor, Rc::new(place), view_fn, None)
+}
+
+/// Like [add_overlay], calling `on_outside_click` when the pointer goes down anywhere
+/// in the window outside of both the overlay and its anchor
+pub(crate) fn add_dismissable_overlay<V: View + 'static>(
+    anchor: Id,
+    place: impl Fn(Rect, Size, Size) -> Point + 'static,
+    view_fn: impl Fn() -> V + 'static,
+    on_outside_click: impl Fn() + 'static,
+) -> Id {
+    push_overlay(
+        anchor,
+        Rc::new(place),
+        view_fn,
+        Some(Rc::new(on_outside_click)),
+    )
+}
+
+fn push_overlay<V: View + 'static>(
+    anchor: Id,
+    place: PlaceFn,
+    view_fn: impl Fn() -> V + 'static,
+    on_outside_click: Option<Rc<dyn Fn()>>,
+) -> Id {
+    let id = Id::next();
+    let layer = anchor
+        .root_id()
+        .and_then(|root| OVERLAY_LAYERS.with(|layers| layers.borrow().get(&root).copied()));
+    if let Some(layer) = layer {
+        let entry = OverlayEntry {
+            id,
+            anchor,
+            place,
+            view_fn: Rc::new(move || Box::new(view_fn()) as Box<dyn View>),
+            on_outside_click,
+        };
+        layer.update(|overlays| overlays.push(entry));
+    }
+    id
+}
+
+/// Removes an overlay added with [add_overlay], and does nothing if it's already gone
+pub fn remove_overlay(id: Id) {
+    let layers: Vec<_> = OVERLAY_LAYERS.with(|layers| layers.borrow().values().copied().collect());
+    for layer in layers {
+        if layer.with_untracked(|overlays| overlays.iter().any(|entry| entry.id == id)) {
+            layer.update(|overlays| overlays.retain(|entry| entry.id != id));
+        }
+    }
+}
+
+/// The layer of a window, which has to be painted on top of the window's content.
+/// Overlays are only added to it once it's registered with [register_overlay_layer].
+pub(crate) fn overlay_layer(overlays: RwSignal<Vec<OverlayEntry>>) -> impl View {
+    list(
+        move || overlays.get(),
+        |entry: &OverlayEntry| entry.id,
+        overlay_view,
+    )
+    .style(|s| s.absolute())
+}
+
+pub(crate) fn register_overlay_layer(root: Id, overlays: RwSignal<Vec<OverlayEntry>>) {
+    OVERLAY_LAYERS.with(|layers| layers.borrow_mut().insert(root, overlays));
+}
+
+pub(crate) fn remove_overlay_layer(root: Id) {
+    OVERLAY_LAYERS.with(|layers| layers.borrow_mut().remove(&root));
+}
+
+/// Whether `pos` is on the overlay `entry`, or on an overlay anchored to a view inside of it,
+/// like a submenu opened from a menu
+fn overlay_contains(
+    overlays: &[OverlayEntry],
+    entry: &OverlayEntry,
+    contains: &impl Fn(Id) -> bool,
+) -> bool {
+    contains(entry.id)
+        || overlays.iter().any(|other| {
+            other.id != entry.id
+                && other
+                    .anchor
+                    .id_path()
+                    .map(|path| path.0.contains(&entry.id))
+                    .unwrap_or(false)
+                && overlay_contains(overlays, other, contains)
+        })
+}
+
+/// Runs the outside click callback of the overlays in the window of `root` that `pos` isn't on.
+/// A click on the anchor doesn't count, since that's usually what toggles the overlay.
+pub(crate) fn click_outside_overlays(app_state: &AppState, root: Id, pos: Point) {
+    let layer = OVERLAY_LAYERS.with(|layers| layers.borrow().get(&root).copied());
+    if let Some(layer) = layer {
+        let contains = |id: Id| {
+            app_state
+                .view_states
+                .get(&id)
+                .map(|state| state.layout_rect.contains(pos))
+                .unwrap_or(false)
+        };
+        let callbacks: Vec<_> = layer.with_untracked(|overlays| {
+            overlays
+                .iter()
+                .filter(|entry| {
+                    !overlay_contains(overlays, entry, &contains) && !contains(entry.anchor)
+                })
+                .filter_map(|entry| entry.on_outside_click.clone())
+                .collect()
+        });
+        for callback in callbacks {
+            callback();
+        }
+    }
+}
+
+fn overlay_view(entry: OverlayEntry) -> OverlayView {
+    let position = create_rw_signal(None);
+    OverlayView {
+        id: entry.id,
+        anchor: entry.anchor,
+        place: entry.place,
+        position,
+        placed: false,
+        child: (entry.view_fn)(),
+    }
+    .style(move |s| {
+        let position: Point = position.get().unwrap_or_default();
+        s.absolute()
+            .margin_left(position.x as f32)
+            .margin_top(position.y as f32)
+            .z_index(900)
+    })
+}
+
+/// Keeps an overlay where its placement function wants it, and doesn't paint it
+/// until it's been moved there the first time
+struct OverlayView {
+    id: Id,
+    anchor: Id,
+    place: PlaceFn,
+    position: RwSignal<Option<Point>>,
+    placed: bool,
+    child: Box<dyn View>,
+}
+
+impl View for OverlayView {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.child.id() == id {
+            Some(&self.child)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.child.id() == id {
+            Some(&mut self.child)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.child]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.child]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Overlay".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, true, |cx| vec![self.child.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let app_state = cx.app_state();
+        let anchor = app_state
+            .view_states
+            .get(&self.anchor)
+            .map(|state| state.layout_rect)
+            .unwrap_or_default();
+        let size = cx
+            .get_layout(self.id)
+            .map(|layout| Size::new(layout.size.width as f64, layout.size.height as f64))
+            .unwrap_or_default();
+        let window = app_state.root_size / app_state.scale;
+
+        let position = (self.place)(anchor, size, window);
+        if self.position.get_untracked() == Some(position) {
+            self.placed = true;
+        } else {
+            self.position.set(Some(position));
+        }
+
+        Some(self.child.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        if self.placed && cx.should_send(self.child.id(), &event) {
+            self.child.event_main(cx, id_path, event)
+        } else {
+            false
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        if self.placed {
+            self.child.paint_main(cx);
+        }
+    }
+}
+
+/// The tooltip of a view, shown once the pointer has been on the view for `delay`
+pub(crate) struct Tooltip {
+    delay: Duration,
+    view_fn: Rc<dyn Fn() -> Box<dyn View>>,
+    /// Bumped every time the tooltip is hidden, so a show that's still waiting on its delay
+    /// from an earlier hover is dropped
+    generation: Rc<Cell<u64>>,
+    shown: Rc<Cell<Option<Id>>>,
+}
+
+impl Tooltip {
+    pub(crate) fn new<V: View + 'static>(
+        delay: Duration,
+        view_fn: impl Fn() -> V + 'static,
+    ) -> Self {
+        Self {
+            delay,
+            view_fn: Rc::new(move || Box::new(view_fn()) as Box<dyn View>),
+            generation: Rc::new(Cell::new(0)),
+            shown: Rc::new(Cell::new(None)),
+        }
+    }
+
+    pub(crate) fn schedule(&self, anchor: Id) {
+        let generation = self.generation.clone();
+        let expected = generation.get();
+        let shown = self.shown.clone();
+        let view_fn = self.view_fn.clone();
+        exec_after(self.delay, move |_| {
+            if generation.get() != expected || shown.get().is_some() {
+                return;
+            }
+            let view_fn = view_fn.clone();
+            let place = |anchor, size, window| {
+                Placement::BottomStart.place(anchor, size, window, TOOLTIP_GAP, true, true)
+            };
+            let id = add_overlay(anchor, place, move || {
+                container_box(view_fn()).style(|s| {
+                    let tokens = tokens();
+                    s.padding_horiz(tokens.spacing.md)
+                        .padding_vert(tokens.spacing.sm)
+                        .text_role(TextRole::Caption)
+                        .background(tokens.colors.surface)
+                        .border(1.0)
+                        .border_color(tokens.colors.border)
+                        .border_radius(tokens.radius.md)
+                })
+            });
+            shown.set(Some(id));
+        });
+    }
+
+    pub(crate) fn hide(&self) {
+        self.generation.set(self.generation.get() + 1);
+        if let Some(id) = self.shown.take() {
+            remove_overlay(id);
+        }
+    }
+}
+
+impl Drop for Tooltip {
+    /// A removed view doesn't get a pointer leave, so its tooltip is hidden here instead
+    fn drop(&mut self) {
+        self.hide();
+    }
+}
@@ -0,0 +1,164 @@
+//! # Persisted signals
+//!
+//! Signals whose values are kept in a file, so they are restored the next time the
+//! application runs, like the size of a panel or the last opened documents. This module is
+//! behind the `persist` feature.
+//!
+//! The values are stored as JSON under the key each signal was created with, and are
+//! written a moment after they change, and when the application exits.
+
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::action::exec_after;
+
+/// How long after a change the values are written, so a value that changes often, like a
+/// size being dragged, isn't written for each change
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+thread_local! {
+    static STORE: RefCell<Store> = RefCell::new(Store::default());
+}
+
+#[derive(Default)]
+struct Store {
+    path: Option<PathBuf>,
+    values: Option<Map<String, Value>>,
+    save_scheduled: bool,
+}
+
+impl Store {
+    fn path(&mut self) -> Option<&Path> {
+        if self.path.is_none() {
+            self.path = default_path();
+        }
+        self.path.as_deref()
+    }
+
+    /// The stored values, read from the file the first time
+    fn values(&mut self) -> &mut Map<String, Value> {
+        if self.values.is_none() {
+            let values = self
+                .path()
+                .and_then(|path| std::fs::read(path).ok())
+                .and_then(|data| serde_json::from_slice(&data).ok())
+                .unwrap_or_default();
+            self.values = Some(values);
+        }
+        self.values.as_mut().unwrap()
+    }
+}
+
+/// `<config directory>/<name of the executable>/persisted.json`
+fn default_path() -> Option<PathBuf> {
+    let name = std::env::current_exe()
+        .ok()?
+        .file_stem()?
+        .to_string_lossy()
+        .into_owned();
+    Some(config_dir()?.join(name).join("persisted.json"))
+}
+
+fn config_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join("Library")
+                .join("Application Support")
+        })
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }
+}
+
+/// Sets the file the values are kept in, in place of the default one in the user's
+/// configuration directory. This has to be called before the first persisted signal is
+/// created.
+pub fn set_persist_file(path: impl Into<PathBuf>) {
+    STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        store.path = Some(path.into());
+        store.values = None;
+    });
+}
+
+/// Creates a signal holding the value stored under `key` the last time the application ran,
+/// or the one `default` returns if there's none, or if it can't be read as a `T`. The value
+/// is stored again whenever the signal changes.
+pub fn create_persisted_signal<T>(
+    key: impl Into<String>,
+    default: impl FnOnce() -> T,
+) -> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let key = key.into();
+    let stored = STORE.with(|store| {
+        store
+            .borrow_mut()
+            .values()
+            .get(&key)
+            .and_then(|value| T::deserialize(value).ok())
+    });
+    let signal = create_rw_signal(stored.unwrap_or_else(default));
+
+    create_effect(move |first_run: Option<()>| {
+        signal.track();
+        if first_run.is_none() {
+            return;
+        }
+        if let Ok(value) = signal.with_untracked(serde_json::to_value) {
+            STORE.with(|store| {
+                store.borrow_mut().values().insert(key.clone(), value);
+            });
+            schedule_save();
+        }
+    });
+
+    signal
+}
+
+fn schedule_save() {
+    let scheduled =
+        STORE.with(|store| std::mem::replace(&mut store.borrow_mut().save_scheduled, true));
+    if !scheduled {
+        exec_after(SAVE_DELAY, |_| {
+            let _ = save_persisted();
+        });
+    }
+}
+
+/// Writes the values of the persisted signals to the file now, which is also done when the
+/// application exits
+pub fn save_persisted() -> std::io::Result<()> {
+    STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        store.save_scheduled = false;
+        let Some(values) = store.values.as_ref() else {
+            // nothing was read or changed
+            return Ok(());
+        };
+        let data = serde_json::to_vec_pretty(values)?;
+        let Some(path) = store.path.as_deref() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // so a crash while writing doesn't lose the values from before
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, data)?;
+        std::fs::rename(temp, path)
+    })
+}
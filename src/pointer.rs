@@ -1,5 +1,100 @@
+use floem_reactive::{ReadSignal, RwSignal, Scope};
 use kurbo::{Point, Vec2};
-use winit::{event::MouseButton, keyboard::ModifiersState};
+use winit::{
+    event::{Force, MouseButton},
+    keyboard::ModifiersState,
+};
+
+thread_local! {
+    static POINTER_CAPABILITIES: RwSignal<PointerCapabilities> =
+        Scope::new().create_rw_signal(PointerCapabilities::default());
+}
+
+/// The kind of device the user is currently pointing with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PointerType {
+    #[default]
+    Mouse,
+    Touch,
+    /// Only detected on iOS, as it's the only platform winit reports the altitude of a
+    /// stylus on. Pens show up as [PointerType::Touch] or [PointerType::Mouse] elsewhere
+    Pen,
+}
+
+/// What the primary pointing device can do, similar to the `pointer` and `hover`
+/// media features on the web.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointerCapabilities {
+    pub pointer_type: PointerType,
+    /// Whether the pointer can hover views without pressing them
+    pub can_hover: bool,
+}
+
+impl Default for PointerCapabilities {
+    fn default() -> Self {
+        Self::from_pointer_type(PointerType::Mouse)
+    }
+}
+
+impl PointerCapabilities {
+    pub(crate) fn from_pointer_type(pointer_type: PointerType) -> Self {
+        Self {
+            pointer_type,
+            can_hover: pointer_type != PointerType::Touch,
+        }
+    }
+
+    /// True when the pointer is imprecise (a finger), and hit targets should be larger
+    pub fn is_coarse(&self) -> bool {
+        self.pointer_type == PointerType::Touch
+    }
+}
+
+/// A signal tracking the primary pointing device, which changes whenever the user
+/// switches between mouse, touch and pen on hybrid devices.
+/// Reading it in a style closure makes the style adapt automatically:
+/// ```ignore
+/// let pointer = pointer_capabilities();
+/// button.style(move |s| s.apply_if(pointer.get().is_coarse(), |s| s.min_height(44.0)))
+/// ```
+pub fn pointer_capabilities() -> ReadSignal<PointerCapabilities> {
+    POINTER_CAPABILITIES.with(|capabilities| capabilities.read_only())
+}
+
+pub(crate) fn update_pointer_type(pointer_type: PointerType) {
+    POINTER_CAPABILITIES.with(|capabilities| {
+        if capabilities.with_untracked(|c| c.pointer_type) != pointer_type {
+            capabilities.set(PointerCapabilities::from_pointer_type(pointer_type));
+        }
+    });
+}
+
+/// Stylus state reported along with pen pointer events, which only happen on iOS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PenState {
+    /// Normalized pressure, from 0.0 to 1.0
+    pub pressure: f64,
+    /// Angle between the pen and the surface normal in radians,
+    /// 0.0 when the pen is perpendicular to the surface
+    pub tilt: f64,
+}
+
+impl PenState {
+    /// Only styluses report an altitude angle, so that's what tells a pen apart from a finger.
+    /// winit only reports it on iOS, so this is `None` everywhere else
+    pub(crate) fn from_force(force: Force) -> Option<Self> {
+        match force {
+            Force::Calibrated {
+                altitude_angle: Some(altitude_angle),
+                ..
+            } => Some(Self {
+                pressure: force.normalized().clamp(0.0, 1.0),
+                tilt: (std::f64::consts::FRAC_PI_2 - altitude_angle).max(0.0),
+            }),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct PointerWheelEvent {
@@ -59,10 +154,16 @@ pub struct PointerInputEvent {
     pub button: PointerButton,
     pub modifiers: ModifiersState,
     pub count: u8,
+    pub pointer_type: PointerType,
+    /// Only set when `pointer_type` is `PointerType::Pen`
+    pub pen: Option<PenState>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PointerMoveEvent {
     pub pos: Point,
     pub modifiers: ModifiersState,
+    pub pointer_type: PointerType,
+    /// Only set when `pointer_type` is `PointerType::Pen`
+    pub pen: Option<PenState>,
 }
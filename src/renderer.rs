@@ -132,6 +132,28 @@ impl floem_renderer::Renderer for Renderer {
         }
     }
 
+    fn intersect_clip(&mut self, shape: &impl Shape) {
+        match self {
+            Renderer::Vger(v) => {
+                v.intersect_clip(shape);
+            }
+            Renderer::TinySkia(v) => {
+                v.intersect_clip(shape);
+            }
+        }
+    }
+
+    fn intersect_clip_img(&mut self, img: Img<'_>, rect: Rect) {
+        match self {
+            Renderer::Vger(v) => {
+                v.intersect_clip_img(img, rect);
+            }
+            Renderer::TinySkia(v) => {
+                v.intersect_clip_img(img, rect);
+            }
+        }
+    }
+
     fn stroke<'b>(&mut self, shape: &impl Shape, brush: impl Into<BrushRef<'b>>, width: f64) {
         match self {
             Renderer::Vger(v) => {
@@ -159,13 +181,18 @@ impl floem_renderer::Renderer for Renderer {
         }
     }
 
-    fn draw_text(&mut self, layout: &TextLayout, pos: impl Into<kurbo::Point>) {
+    fn draw_text_spaced(
+        &mut self,
+        layout: &TextLayout,
+        pos: impl Into<kurbo::Point>,
+        letter_spacing: f64,
+    ) {
         match self {
             Renderer::Vger(v) => {
-                v.draw_text(layout, pos);
+                v.draw_text_spaced(layout, pos, letter_spacing);
             }
             Renderer::TinySkia(v) => {
-                v.draw_text(layout, pos);
+                v.draw_text_spaced(layout, pos, letter_spacing);
             }
         }
     }
@@ -181,6 +208,17 @@ impl floem_renderer::Renderer for Renderer {
         }
     }
 
+    fn draw_pixels(&mut self, img: Img<'_>, version: u64, width: u32, height: u32, rect: Rect) {
+        match self {
+            Renderer::Vger(v) => {
+                v.draw_pixels(img, version, width, height, rect);
+            }
+            Renderer::TinySkia(v) => {
+                v.draw_pixels(img, version, width, height, rect);
+            }
+        }
+    }
+
     fn draw_svg<'b>(
         &mut self,
         svg: floem_renderer::Svg<'b>,
@@ -219,6 +257,39 @@ impl floem_renderer::Renderer for Renderer {
         }
     }
 
+    fn push_layer(&mut self, alpha: f32, blur: f64) {
+        match self {
+            Renderer::Vger(v) => {
+                v.push_layer(alpha, blur);
+            }
+            Renderer::TinySkia(v) => {
+                v.push_layer(alpha, blur);
+            }
+        }
+    }
+
+    fn pop_layer(&mut self) {
+        match self {
+            Renderer::Vger(v) => {
+                v.pop_layer();
+            }
+            Renderer::TinySkia(v) => {
+                v.pop_layer();
+            }
+        }
+    }
+
+    fn blur_backdrop(&mut self, shape: &impl Shape, radius: f64) {
+        match self {
+            Renderer::Vger(v) => {
+                v.blur_backdrop(shape, radius);
+            }
+            Renderer::TinySkia(v) => {
+                v.blur_backdrop(shape, radius);
+            }
+        }
+    }
+
     fn finish(&mut self) {
         match self {
             Renderer::Vger(r) => {
@@ -172,11 +172,83 @@ impl BitOr for ScreenSize {
     }
 }
 
+/// The width a [WidthQuery] looks at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum QueryTarget {
+    /// The width of the window
+    Window,
+    /// The width of the view the view is in, from its last layout
+    Container,
+}
+
+/// A condition on a width in pixels, for the styles of
+/// [responsive](crate::views::Decorators::responsive). Both of its bounds are inclusive, like
+/// the `min-width` and `max-width` media queries of CSS.
+/// ```rust
+/// # use floem::responsive::{max_width, min_width};
+/// let wide = min_width(800.0);
+/// let tablet = min_width(600.0).max_width(1000.0);
+/// let narrow_panel = max_width(300.0).of_container();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WidthQuery {
+    min: f64,
+    max: f64,
+    target: QueryTarget,
+}
+
+/// Matches when the window is at least `width` wide
+pub fn min_width(width: f64) -> WidthQuery {
+    WidthQuery {
+        min: width,
+        max: f64::INFINITY,
+        target: QueryTarget::Window,
+    }
+}
+
+/// Matches when the window is at most `width` wide
+pub fn max_width(width: f64) -> WidthQuery {
+    WidthQuery {
+        min: f64::NEG_INFINITY,
+        max: width,
+        target: QueryTarget::Window,
+    }
+}
+
+impl WidthQuery {
+    /// Also requires the width to be at least `width`
+    pub fn min_width(mut self, width: f64) -> Self {
+        self.min = width;
+        self
+    }
+
+    /// Also requires the width to be at most `width`
+    pub fn max_width(mut self, width: f64) -> Self {
+        self.max = width;
+        self
+    }
+
+    /// Looks at the width of the view the view is in, rather than the one of the window, so a
+    /// view can adapt to the space it's given
+    pub fn of_container(mut self) -> Self {
+        self.target = QueryTarget::Container;
+        self
+    }
+
+    pub(crate) fn target(&self) -> QueryTarget {
+        self.target
+    }
+
+    pub(crate) fn matches(&self, width: f64) -> bool {
+        width >= self.min && width <= self.max
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::responsive::SizeFlags;
 
-    use super::{range, ScreenSize};
+    use super::{max_width, min_width, range, ScreenSize};
 
     #[test]
     fn range_full() {
@@ -275,4 +347,16 @@ mod tests {
         assert!(range.flags.contains(SizeFlags::XL));
         assert!(range.flags.contains(SizeFlags::XXL));
     }
+
+    #[test]
+    fn width_query() {
+        let query = min_width(600.0).max_width(1000.0);
+        assert!(!query.matches(599.0));
+        assert!(query.matches(600.0));
+        assert!(query.matches(1000.0));
+        assert!(!query.matches(1000.5));
+
+        assert!(max_width(300.0).matches(0.0));
+        assert!(!max_width(300.0).matches(301.0));
+    }
 }
@@ -17,16 +17,19 @@
 //!   
 //! An override [`Style`] is perhaps closest to classes that can be applied to an element, like
 //! `div:hover { color: blue; }`.  
-//! However, we do not actually have 'classes' where you can define a separate collection of styles
-//! in the same way. So, the hover styling is still defined with the view as you construct it, so
-//! perhaps a closer pseudocode analogy is:
+//! The hover styling is usually defined with the view as you construct it, so perhaps a closer
+//! pseudocode analogy is:
 //! ```html
 //! <div hover_style="color: blue;" style="color: red; font-size: 12px;">
 //! ```
+//! Styles shared by many views can also be given by class in a [stylesheet](crate::stylesheet).
 //!
 
+use std::time::Duration;
+
 use floem_renderer::cosmic_text::{LineHeightValue, Style as FontStyle, Weight};
-use peniko::Color;
+use kurbo::{Affine, Point, Rect as KurboRect, Size as KurboSize, Vec2};
+use peniko::{Color, ColorStop, Gradient};
 pub use taffy::style::{
     AlignContent, AlignItems, Dimension, Display, FlexDirection, JustifyContent, Position,
 };
@@ -36,7 +39,10 @@ use taffy::{
     style::{FlexWrap, LengthPercentage, Style as TaffyStyle},
 };
 
-use crate::unit::{Px, PxPct, PxPctAuto, UnitExt};
+use crate::{
+    animate::{Easing, Transition, TransitionProp},
+    unit::{Px, PxPct, PxPctAuto, UnitExt},
+};
 
 pub enum StyleSelector {
     Hover,
@@ -54,10 +60,68 @@ pub enum TextOverflow {
     Ellipsis,
 }
 
+/// How the text caret is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaretShape {
+    /// A thin line before the next character
+    #[default]
+    Bar,
+    /// A box over the next character
+    Block,
+    /// A line under the next character
+    Underline,
+}
+
+/// Lines drawn along the text of a [label](crate::views::label) or a
+/// [rich_text](crate::views::rich_text), set with [Style::underline] and
+/// [Style::line_through]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextDecoration {
+    pub underline: bool,
+    pub line_through: bool,
+    /// The color of the lines, which is the one of the text when it's None
+    pub color: Option<Color>,
+}
+
+/// How the case of the text of a [label](crate::views::label) is changed, like the
+/// `text-transform` of CSS
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextTransform {
+    Uppercase,
+    Lowercase,
+    /// The first letter of each word in uppercase
+    Capitalize,
+}
+
+impl TextTransform {
+    pub(crate) fn apply(self, text: &str) -> String {
+        match self {
+            TextTransform::Uppercase => text.to_uppercase(),
+            TextTransform::Lowercase => text.to_lowercase(),
+            TextTransform::Capitalize => {
+                let mut word_start = true;
+                let mut capitalized = String::with_capacity(text.len());
+                for c in text.chars() {
+                    if word_start {
+                        capitalized.extend(c.to_uppercase());
+                    } else {
+                        capitalized.push(c);
+                    }
+                    word_start = c.is_whitespace();
+                }
+                capitalized
+            }
+        }
+    }
+}
+
+/// The pointer shown over a view, set with [Style::cursor]
 #[derive(Debug, Clone, Copy)]
 pub enum CursorStyle {
     Default,
+    /// A hand, for the views that can be clicked
     Pointer,
+    /// A text beam, for the views whose text can be selected
     Text,
     ColResize,
     RowResize,
@@ -71,6 +135,15 @@ pub enum CursorStyle {
     SeResize,
     NeswResize,
     NwseResize,
+    Move,
+    /// An open hand, for what can be dragged
+    Grab,
+    /// A closed hand, for what's being dragged
+    Grabbing,
+    NotAllowed,
+    Wait,
+    Crosshair,
+    Help,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -94,6 +167,98 @@ impl Default for BoxShadow {
     }
 }
 
+/// A gradient painted as the background of a view, over its background color and inside its
+/// rounded corners. It's laid out over the size of the view when it's painted.
+///
+/// The vger renderer only draws linear gradients, with their first two stops.
+/// ```rust
+/// # use floem::{peniko::Color, style::{BackgroundGradient, Style}};
+/// let header = Style::BASE.background_gradient(
+///     BackgroundGradient::linear(90.0)
+///         .stop(0.0, Color::rgb8(66, 133, 244))
+///         .stop(1.0, Color::rgb8(120, 80, 220)),
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum BackgroundGradient {
+    /// Along a line through the center at `angle` degrees, where 0 goes from the bottom to
+    /// the top and 90 from the left to the right, like in CSS
+    Linear { angle: f64, stops: Vec<ColorStop> },
+    /// Out from the center to the farthest corner
+    Radial { stops: Vec<ColorStop> },
+}
+
+impl BackgroundGradient {
+    pub fn linear(angle: f64) -> Self {
+        Self::Linear {
+            angle,
+            stops: Vec::new(),
+        }
+    }
+
+    pub fn radial() -> Self {
+        Self::Radial { stops: Vec::new() }
+    }
+
+    /// Adds a stop with `color` at `offset`, from 0 at the start of the gradient to 1 at its
+    /// end. The stops are added in order.
+    pub fn stop(mut self, offset: f32, color: Color) -> Self {
+        let (Self::Linear { stops, .. } | Self::Radial { stops }) = &mut self;
+        stops.push(ColorStop { offset, color });
+        self
+    }
+
+    /// The gradient laid out over `rect`
+    pub(crate) fn to_gradient(&self, rect: KurboRect) -> Gradient {
+        let center = rect.center();
+        match self {
+            Self::Linear { angle, stops } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                // the length at which the corners get the colors of the ends
+                let length = (rect.width() * sin).abs() + (rect.height() * cos).abs();
+                let half = Vec2::new(sin, -cos) * (length / 2.0);
+                Gradient::new_linear(center - half, center + half).with_stops(stops.as_slice())
+            }
+            Self::Radial { stops } => {
+                let radius = Point::new(rect.x0, rect.y0).distance(center);
+                Gradient::new_radial(center, radius as f32).with_stops(stops.as_slice())
+            }
+        }
+    }
+}
+
+/// How far a view looks raised above the ones behind it, which [Style::elevation] turns
+/// into a shadow
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Elevation {
+    /// No shadow
+    #[default]
+    Flat,
+    /// Just off the surface, like a card in a list
+    Low,
+    /// Above the content, like a menu or a popover
+    Medium,
+    /// Well above everything, like a dialog
+    High,
+}
+
+impl Elevation {
+    pub fn box_shadow(self) -> Option<BoxShadow> {
+        let (blur_radius, v_offset, alpha) = match self {
+            Elevation::Flat => return None,
+            Elevation::Low => (4.0, 1.0, 40),
+            Elevation::Medium => (10.0, 3.0, 50),
+            Elevation::High => (20.0, 8.0, 60),
+        };
+        Some(BoxShadow {
+            blur_radius,
+            color: Color::rgba8(0, 0, 0, alpha),
+            v_offset,
+            ..Default::default()
+        })
+    }
+}
+
 /// The value for a [`Style`] property
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StyleValue<T> {
@@ -308,7 +473,8 @@ define_styles!(
     cursor cursor_sv nocb: Option<CursorStyle> = None,
     color color_sv nocb: Option<Color> = None,
     background background_sv nocb: Option<Color> = None,
-    box_shadow box_shadow_sv nocb: Option<BoxShadow> = None,
+    background_gradient background_gradient_sv nocb: Option<BackgroundGradient> = None,
+    box_shadows box_shadows_sv nocb: Vec<BoxShadow> = Vec::new(),
     scroll_bar_color scroll_bar_color_sv nocb: Option<Color> = None,
     scroll_bar_rounded scroll_bar_rounded_sv nocb: Option<bool> = None,
     scroll_bar_thickness scroll_bar_thickness_sv nocb: Option<Px> = None,
@@ -318,10 +484,28 @@ define_styles!(
     font_weight font_weight_sv nocb: Option<Weight> = None,
     font_style font_style_sv nocb: Option<FontStyle> = None,
     cursor_color cursor_color_sv nocb: Option<Color> = None,
+    caret_width caret_width_sv nocb: Option<Px> = None,
+    caret_shape caret_shape_sv nocb: Option<CaretShape> = None,
+    selection_background selection_background_sv nocb: Option<Color> = None,
+    selection_color selection_color_sv nocb: Option<Color> = None,
     text_overflow text_overflow_sv: TextOverflow = TextOverflow::Wrap,
     line_height line_height_sv nocb: Option<LineHeightValue> = None,
+    letter_spacing letter_spacing_sv nocb: Option<Px> = None,
+    text_decoration text_decoration_sv nocb: Option<TextDecoration> = None,
+    text_transform text_transform_sv nocb: Option<TextTransform> = None,
     aspect_ratio aspect_ratio_sv: Option<f32> = None,
     gap gap_sv: Size<LengthPercentage> = Size::zero(),
+    transitions transitions_sv nocb: Vec<(TransitionProp, Transition)> = Vec::new(),
+    translate_x translate_x_sv: PxPct = PxPct::Px(0.0),
+    translate_y translate_y_sv: PxPct = PxPct::Px(0.0),
+    rotate rotate_sv nocb: f64 = 0.0,
+    scale_x scale_x_sv: f64 = 1.0,
+    scale_y scale_y_sv: f64 = 1.0,
+    transform_origin_x transform_origin_x_sv: PxPct = PxPct::Pct(50.0),
+    transform_origin_y transform_origin_y_sv: PxPct = PxPct::Pct(50.0),
+    opacity opacity_sv nocb: f32 = 1.0,
+    blur blur_sv nocb: f64 = 0.0,
+    backdrop_blur backdrop_blur_sv nocb: f64 = 0.0,
 );
 
 impl Style {
@@ -578,6 +762,15 @@ impl Style {
             .inset_bottom(inset)
     }
 
+    /// Shows `cursor` while the pointer is over the view, unless a view inside of it under the
+    /// pointer has a cursor of its own. While the view is pressed it keeps its cursor, even
+    /// when the pointer leaves it, and once it's released the cursor goes back to the one of
+    /// the views under the pointer.
+    /// ```rust
+    /// # use floem::style::{CursorStyle, Style};
+    /// let splitter = Style::BASE.width(4.0).cursor(CursorStyle::ColResize);
+    /// let handle = Style::BASE.size(12.0, 12.0).cursor(CursorStyle::Grab);
+    /// ```
     pub fn cursor(mut self, cursor: impl Into<StyleValue<CursorStyle>>) -> Self {
         self.cursor = cursor.into().map(Some);
         self
@@ -593,83 +786,83 @@ impl Style {
         self
     }
 
-    pub fn box_shadow_blur(mut self, blur_radius: f64) -> Self {
-        if let Some(box_shadow) = self.box_shadow.as_mut() {
-            if let Some(box_shadow) = box_shadow.as_mut() {
-                box_shadow.blur_radius = blur_radius;
-                return self;
-            }
-        }
-
-        self.box_shadow = Some(BoxShadow {
-            blur_radius,
-            ..Default::default()
-        })
-        .into();
+    /// Paints `gradient` over the background color
+    pub fn background_gradient(
+        mut self,
+        gradient: impl Into<StyleValue<BackgroundGradient>>,
+    ) -> Self {
+        self.background_gradient = gradient.into().map(Some);
         self
     }
 
-    pub fn box_shadow_color(mut self, color: Color) -> Self {
-        if let Some(box_shadow) = self.box_shadow.as_mut() {
-            if let Some(box_shadow) = box_shadow.as_mut() {
-                box_shadow.color = color;
-                return self;
-            }
-        }
-
-        self.box_shadow = Some(BoxShadow {
+    /// Adds a shadow under the view, `offset` from it and `spread` larger than it, blurred
+    /// by `blur`. The shadows added first are drawn on top of the ones added after them.
+    pub fn box_shadow(
+        mut self,
+        offset: impl Into<Vec2>,
+        blur: f64,
+        spread: f64,
+        color: Color,
+    ) -> Self {
+        let offset = offset.into();
+        let shadow = BoxShadow {
+            blur_radius: blur,
             color,
-            ..Default::default()
-        })
-        .into();
+            spread,
+            h_offset: offset.x,
+            v_offset: offset.y,
+        };
+        let mut shadows = match self.box_shadows {
+            StyleValue::Val(shadows) => shadows,
+            StyleValue::Unset | StyleValue::Base => Vec::new(),
+        };
+        shadows.push(shadow);
+        self.box_shadows = StyleValue::Val(shadows);
         self
     }
 
-    pub fn box_shadow_spread(mut self, spread: f64) -> Self {
-        if let Some(box_shadow) = self.box_shadow.as_mut() {
-            if let Some(box_shadow) = box_shadow.as_mut() {
-                box_shadow.spread = spread;
+    /// Changes the last shadow added, or adds one if there's none
+    fn update_box_shadow(mut self, f: impl FnOnce(&mut BoxShadow)) -> Self {
+        if let Some(shadows) = self.box_shadows.as_mut() {
+            if let Some(shadow) = shadows.last_mut() {
+                f(shadow);
                 return self;
             }
         }
 
-        self.box_shadow = Some(BoxShadow {
-            spread,
-            ..Default::default()
-        })
-        .into();
+        let mut shadow = BoxShadow::default();
+        f(&mut shadow);
+        self.box_shadows = vec![shadow].into();
         self
     }
 
-    pub fn box_shadow_h_offset(mut self, h_offset: f64) -> Self {
-        if let Some(box_shadow) = self.box_shadow.as_mut() {
-            if let Some(box_shadow) = box_shadow.as_mut() {
-                box_shadow.h_offset = h_offset;
-                return self;
-            }
-        }
+    pub fn box_shadow_blur(self, blur_radius: f64) -> Self {
+        self.update_box_shadow(|shadow| shadow.blur_radius = blur_radius)
+    }
 
-        self.box_shadow = Some(BoxShadow {
-            h_offset,
-            ..Default::default()
-        })
-        .into();
-        self
+    pub fn box_shadow_color(self, color: Color) -> Self {
+        self.update_box_shadow(|shadow| shadow.color = color)
     }
 
-    pub fn box_shadow_v_offset(mut self, v_offset: f64) -> Self {
-        if let Some(box_shadow) = self.box_shadow.as_mut() {
-            if let Some(box_shadow) = box_shadow.as_mut() {
-                box_shadow.v_offset = v_offset;
-                return self;
-            }
-        }
+    pub fn box_shadow_spread(self, spread: f64) -> Self {
+        self.update_box_shadow(|shadow| shadow.spread = spread)
+    }
 
-        self.box_shadow = Some(BoxShadow {
-            v_offset,
-            ..Default::default()
-        })
-        .into();
+    pub fn box_shadow_h_offset(self, h_offset: f64) -> Self {
+        self.update_box_shadow(|shadow| shadow.h_offset = h_offset)
+    }
+
+    pub fn box_shadow_v_offset(self, v_offset: f64) -> Self {
+        self.update_box_shadow(|shadow| shadow.v_offset = v_offset)
+    }
+
+    /// The shadow for `elevation`, replacing any other box shadow
+    pub fn elevation(mut self, elevation: Elevation) -> Self {
+        self.box_shadows = elevation
+            .box_shadow()
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into();
         self
     }
 
@@ -717,16 +910,106 @@ impl Style {
         self
     }
 
+    /// The color of the text caret. Like the other caret and selection styles it's inherited,
+    /// so setting it on the root view themes every text input in the window.
     pub fn cursor_color(mut self, color: impl Into<StyleValue<Color>>) -> Self {
         self.cursor_color = color.into().map(Some);
         self
     }
 
+    /// The width of a [CaretShape::Bar] caret, or the thickness of a [CaretShape::Underline] one
+    pub fn caret_width(mut self, width: impl Into<Px>) -> Self {
+        self.caret_width = StyleValue::Val(Some(width.into()));
+        self
+    }
+
+    pub fn caret_shape(mut self, shape: impl Into<StyleValue<CaretShape>>) -> Self {
+        self.caret_shape = shape.into().map(Some);
+        self
+    }
+
+    /// The color drawn behind selected text
+    pub fn selection_background(mut self, color: impl Into<StyleValue<Color>>) -> Self {
+        self.selection_background = color.into().map(Some);
+        self
+    }
+
+    /// The color of selected text, instead of the normal text color
+    pub fn selection_color(mut self, color: impl Into<StyleValue<Color>>) -> Self {
+        self.selection_color = color.into().map(Some);
+        self
+    }
+
     pub fn line_height(mut self, normal: f32) -> Self {
         self.line_height = Some(LineHeightValue::Normal(normal)).into();
         self
     }
 
+    /// Sets the height of the lines of text to `px` pixels, where
+    /// [line_height](Style::line_height) is relative to the font size
+    pub fn line_height_px(mut self, px: f32) -> Self {
+        self.line_height = Some(LineHeightValue::Px(px)).into();
+        self
+    }
+
+    /// Adds `px` pixels of space after each character of the text. Lines still wrap where
+    /// they would without it.
+    pub fn letter_spacing(mut self, px: impl Into<Px>) -> Self {
+        self.letter_spacing = StyleValue::Val(Some(px.into()));
+        self
+    }
+
+    /// Draws a line under the text
+    pub fn underline(mut self) -> Self {
+        self.text_decoration = self.text_decoration_with(|d| d.underline = true);
+        self
+    }
+
+    /// Draws a line through the middle of the text
+    pub fn line_through(mut self) -> Self {
+        self.text_decoration = self.text_decoration_with(|d| d.line_through = true);
+        self
+    }
+
+    /// The color of the lines of [underline](Style::underline) and
+    /// [line_through](Style::line_through), which is the color of the text by default
+    /// ```rust
+    /// # use floem::{peniko::Color, style::Style};
+    /// let misspelled = Style::BASE
+    ///     .underline()
+    ///     .text_decoration_color(Color::rgb8(218, 54, 51));
+    /// ```
+    pub fn text_decoration_color(mut self, color: Color) -> Self {
+        self.text_decoration = self.text_decoration_with(|d| d.color = Some(color));
+        self
+    }
+
+    /// Removes the lines of [underline](Style::underline) and
+    /// [line_through](Style::line_through) that the view would inherit
+    pub fn text_decoration_none(mut self) -> Self {
+        self.text_decoration = StyleValue::Val(Some(TextDecoration::default()));
+        self
+    }
+
+    fn text_decoration_with(
+        &self,
+        f: impl FnOnce(&mut TextDecoration),
+    ) -> StyleValue<Option<TextDecoration>> {
+        let mut decoration = match &self.text_decoration {
+            StyleValue::Val(Some(decoration)) => *decoration,
+            _ => TextDecoration::default(),
+        };
+        f(&mut decoration);
+        StyleValue::Val(Some(decoration))
+    }
+
+    /// Changes the case of the text of a [label](crate::views::label), without changing the
+    /// text it's given
+    pub fn text_transform(mut self, transform: TextTransform) -> Self {
+        self.text_transform = Some(transform).into();
+        self
+    }
+
     pub fn text_ellipsis(self) -> Self {
         self.text_overflow(TextOverflow::Ellipsis)
     }
@@ -785,6 +1068,70 @@ impl Style {
         self.flex_direction(FlexDirection::Column)
     }
 
+    /// Moves the view by `x` and `y` when it's painted, without changing the layout. A
+    /// percentage is of the size of the view.
+    ///
+    /// Like the rotation and the scale, this also applies to where the view receives the
+    /// pointer.
+    pub fn translate(self, x: impl Into<PxPct>, y: impl Into<PxPct>) -> Self {
+        self.translate_x(x).translate_y(y)
+    }
+
+    /// Rotates the view clockwise by `degrees` about its
+    /// [transform origin](Style::transform_origin)
+    /// ```rust
+    /// # use floem::style::Style;
+    /// let badge = Style::BASE.rotate(-12.0).scale(1.1);
+    /// ```
+    pub fn rotate(mut self, degrees: f64) -> Self {
+        self.rotate = StyleValue::Val(degrees);
+        self
+    }
+
+    /// Scales the view by `factor` in both directions about its
+    /// [transform origin](Style::transform_origin)
+    pub fn scale(self, factor: f64) -> Self {
+        self.scale_x(factor).scale_y(factor)
+    }
+
+    /// The point the view is rotated and scaled about, which is its center by default. A
+    /// percentage is of the size of the view.
+    pub fn transform_origin(self, x: impl Into<PxPct>, y: impl Into<PxPct>) -> Self {
+        self.transform_origin_x(x).transform_origin_y(y)
+    }
+
+    /// Paints the view and its children into a layer that's then blended with `opacity`, from
+    /// 0 for transparent to 1 for opaque, so the parts that overlap inside it don't show
+    /// through each other
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = StyleValue::Val(opacity.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Blurs the view and its children by `radius`, the standard deviation of the blur in
+    /// pixels, by painting them into a layer like [Style::opacity] does
+    pub fn blur(mut self, radius: f64) -> Self {
+        self.blur = StyleValue::Val(radius.max(0.0));
+        self
+    }
+
+    /// Blurs by `radius` what's painted behind the view, inside its rounded corners, which
+    /// gives frosted glass with a translucent background
+    /// ```rust
+    /// # use floem::{peniko::Color, style::Style};
+    /// let panel = Style::BASE
+    ///     .backdrop_blur(12.0)
+    ///     .background(Color::rgba8(255, 255, 255, 160))
+    ///     .border_radius(8.0);
+    /// ```
+    pub fn backdrop_blur(mut self, radius: f64) -> Self {
+        self.backdrop_blur = StyleValue::Val(radius.max(0.0));
+        self
+    }
+
+    /// Raises the view over its siblings with a lower z_index, which is 0 by default, even
+    /// the ones that come after it. It's painted after them, and gets the pointer events
+    /// before them.
     pub fn z_index(mut self, z_index: i32) -> Self {
         self.z_index = Some(z_index).into();
         self
@@ -799,6 +1146,37 @@ impl Style {
     ///    .apply_opt(Some(5.0), |s, v| s.border_right(v * 2.0))
     ///    .border_left(5.0); // ran, obviously
     /// ```
+    /// Makes `prop` go from its old value to a new one over `duration` when it changes, like
+    /// when the view gets its hover style, instead of changing at once.
+    ///
+    /// Like the other properties, the transitions of a style replace the ones of the styles
+    /// it's applied over, so a hover style that sets transitions has to set all of them.
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use floem::{animate::{Easing, EasingFn, EasingMode, TransitionProp}, peniko::Color, view::View, views::{Decorators, label}};
+    /// fn view() -> impl View {
+    ///     label(|| "Hover me".to_string())
+    ///         .style(|s| {
+    ///             s.background(Color::WHITE).transition(
+    ///                 TransitionProp::Background,
+    ///                 Duration::from_millis(150),
+    ///                 Easing::new(EasingFn::Quadratic, EasingMode::Out),
+    ///             )
+    ///         })
+    ///         .hover_style(|s| s.background(Color::rgb8(228, 232, 240)))
+    /// }
+    /// ```
+    pub fn transition(mut self, prop: TransitionProp, duration: Duration, easing: Easing) -> Self {
+        let mut transitions = match self.transitions {
+            StyleValue::Val(transitions) => transitions,
+            StyleValue::Unset | StyleValue::Base => Vec::new(),
+        };
+        transitions.retain(|(p, _)| *p != prop);
+        transitions.push((prop, Transition { duration, easing }));
+        self.transitions = StyleValue::Val(transitions);
+        self
+    }
+
     pub fn apply_opt<T>(self, opt: Option<T>, f: impl FnOnce(Self, T) -> Self) -> Self {
         if let Some(t) = opt {
             f(self, t)
@@ -824,6 +1202,60 @@ impl Style {
 }
 
 impl ComputedStyle {
+    /// Whether the properties that children inherit during layout and paint are the same
+    pub(crate) fn inherited_eq(&self, other: &ComputedStyle) -> bool {
+        self.color == other.color
+            && self.font_size == other.font_size
+            && self.font_family == other.font_family
+            && self.font_weight == other.font_weight
+            && self.font_style == other.font_style
+            && self.line_height == other.line_height
+            && self.letter_spacing == other.letter_spacing
+            && self.text_decoration == other.text_decoration
+            && self.text_transform == other.text_transform
+            && self.cursor_color == other.cursor_color
+            && self.caret_width == other.caret_width
+            && self.caret_shape == other.caret_shape
+            && self.selection_background == other.selection_background
+            && self.selection_color == other.selection_color
+            && self.scroll_bar_color == other.scroll_bar_color
+            && self.scroll_bar_rounded == other.scroll_bar_rounded
+            && self.scroll_bar_thickness == other.scroll_bar_thickness
+            && self.scroll_bar_edge_width == other.scroll_bar_edge_width
+            && self.text_overflow == other.text_overflow
+    }
+
+    /// The transform of a view of `size` with this style, or None when it isn't moved,
+    /// rotated or scaled
+    pub(crate) fn transform(&self, size: KurboSize) -> Option<Affine> {
+        if self.translate_x == PxPct::Px(0.0)
+            && self.translate_y == PxPct::Px(0.0)
+            && self.rotate == 0.0
+            && self.scale_x == 1.0
+            && self.scale_y == 1.0
+        {
+            return None;
+        }
+        let resolve = |value: PxPct, length: f64| match value {
+            PxPct::Px(px) => px,
+            PxPct::Pct(pct) => length * pct / 100.0,
+        };
+        let origin = Vec2::new(
+            resolve(self.transform_origin_x, size.width),
+            resolve(self.transform_origin_y, size.height),
+        );
+        let translate = Vec2::new(
+            resolve(self.translate_x, size.width),
+            resolve(self.translate_y, size.height),
+        );
+        Some(
+            Affine::translate(origin + translate)
+                * Affine::rotate(self.rotate.to_radians())
+                * Affine::scale_non_uniform(self.scale_x, self.scale_y)
+                * Affine::translate(-origin),
+        )
+    }
+
     pub fn to_taffy_style(&self) -> TaffyStyle {
         TaffyStyle {
             display: self.display,
@@ -883,7 +1315,9 @@ impl ComputedStyle {
 
 #[cfg(test)]
 mod tests {
-    use super::{Style, StyleValue};
+    use peniko::Color;
+
+    use super::{CaretShape, ComputedStyle, Style, StyleValue, TextDecoration, TextTransform};
     use crate::unit::PxPct;
 
     #[test]
@@ -938,4 +1372,47 @@ mod tests {
         assert_eq!(style.padding_left, StyleValue::Val(PxPct::Px(64.0)));
         assert_eq!(style.padding_bottom, StyleValue::Val(PxPct::Px(100.0)));
     }
+
+    #[test]
+    fn text_decoration_lines_add_up() {
+        let style = Style::BASE.underline().line_through();
+        assert_eq!(
+            style.text_decoration,
+            StyleValue::Val(Some(TextDecoration {
+                underline: true,
+                line_through: true,
+                color: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn inherited_props_are_compared() {
+        let base = ComputedStyle::default();
+        let changes = [
+            Style::BASE.color(Color::RED),
+            Style::BASE.font_size(20.0),
+            Style::BASE.line_height_px(20.0),
+            Style::BASE.letter_spacing(2.0),
+            Style::BASE.underline(),
+            Style::BASE.text_transform(TextTransform::Uppercase),
+            Style::BASE.cursor_color(Color::RED),
+            Style::BASE.caret_width(3.0),
+            Style::BASE.caret_shape(CaretShape::Block),
+            Style::BASE.selection_background(Color::RED),
+            Style::BASE.selection_color(Color::RED),
+        ];
+        for change in changes {
+            assert!(!base.inherited_eq(&change.compute(&base)));
+        }
+        assert!(base.inherited_eq(&Style::BASE.width(10.0).compute(&base)));
+    }
+
+    #[test]
+    fn capitalize_each_word() {
+        assert_eq!(
+            TextTransform::Capitalize.apply("save all files"),
+            "Save All Files"
+        );
+    }
 }
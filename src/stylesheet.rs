@@ -0,0 +1,819 @@
+//! # Stylesheets
+//!
+//! A [Stylesheet] styles views from a CSS-like text, so the styling of an app can live in a
+//! file instead of in the style closures of its views. Once [set](set_stylesheet), it's
+//! applied to the views of every window, over the default style of each view and under
+//! what's set with [style](crate::views::Decorators::style).
+//!
+//! ```css
+//! /* the views of type Button */
+//! Button {
+//!     padding: 4px 12px;
+//!     border-radius: 4px;
+//! }
+//!
+//! /* views with the class "danger", and the view with the style id "delete" */
+//! .danger, #delete {
+//!     color: #c62828;
+//! }
+//!
+//! Button.danger:hover {
+//!     background: rgba(198, 40, 40, 0.1);
+//! }
+//! ```
+//!
+//! A selector is the name of a view type, classes and a style id, all optional, followed by
+//! one of the `:hover`, `:focus`, `:focus-visible`, `:active` and `:disabled` states. Classes
+//! are given to views with [class](crate::views::Decorators::class) and style ids with
+//! [style_id](crate::views::Decorators::style_id). Selectors for nested views, like
+//! `.toolbar Button`, aren't supported.
+//!
+//! When several rules set the same property, the one with the most specific selector wins,
+//! and of those the last one, like in CSS.
+//!
+//! ## Style classes
+//!
+//! A class can also be given a [Style] from code with [define_class], for styling that's
+//! shared by many views without a stylesheet file:
+//! ```rust
+//! # use floem::{peniko::Color, stylesheet::define_class, style::Style, view::View, views::{Decorators, label}};
+//! define_class(
+//!     "danger-button",
+//!     Style::BASE.color(Color::WHITE).background(Color::rgb8(198, 40, 40)),
+//! );
+//!
+//! fn delete_button() -> impl View {
+//!     label(|| "Delete".to_string()).class("danger-button")
+//! }
+//! ```
+//! Defining a class again applies the new style over the one from before, and a class defined
+//! later wins over one defined earlier when a view has both. The stylesheet wins over the
+//! classes defined in code, for selectors as specific.
+
+use std::{cell::RefCell, fmt, path::Path};
+
+use floem_renderer::cosmic_text::{Style as FontStyle, Weight};
+use peniko::Color;
+use taffy::style::{FlexWrap, LengthPercentage};
+
+use crate::{
+    app::UserEvent,
+    style::{
+        AlignItems, CursorStyle, Display, FlexDirection, JustifyContent, Position, Style,
+        StyleSelector, TextOverflow, TextTransform,
+    },
+    unit::{PxPct, PxPctAuto},
+    Application,
+};
+
+thread_local! {
+    static STYLESHEET: RefCell<Sheets> = RefCell::new(Sheets::default());
+}
+
+#[derive(Default)]
+struct Sheets {
+    /// Counts the changes, so a window can tell when it has to restyle its views
+    generation: u64,
+    stylesheet: Option<Stylesheet>,
+    /// The rules of the classes made with [define_class]
+    classes: Vec<Rule>,
+}
+
+/// Rules styling views by their names, classes and style ids. See the
+/// [module documentation](self)
+#[derive(Clone, Debug, Default)]
+pub struct Stylesheet {
+    rules: Vec<Rule>,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    selector: Selector,
+    style: Style,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Selector {
+    name: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    state: Option<SelectorState>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SelectorState {
+    Hover,
+    Focus,
+    FocusVisible,
+    Active,
+    Disabled,
+}
+
+impl Selector {
+    fn matches(&self, name: &str, id: Option<&str>, classes: &[String]) -> bool {
+        self.name.as_deref().map_or(true, |n| n == name)
+            && self.id.as_deref().map_or(true, |i| Some(i) == id)
+            && self.classes.iter().all(|class| classes.contains(class))
+    }
+
+    /// Like in CSS, an id counts more than any number of classes, which count more than a name
+    fn specificity(&self) -> (usize, usize, usize) {
+        (
+            self.id.is_some() as usize,
+            self.classes.len() + self.state.is_some() as usize,
+            self.name.is_some() as usize,
+        )
+    }
+}
+
+/// An error in the text of a [Stylesheet], with the line it's on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StylesheetError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for StylesheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for StylesheetError {}
+
+/// The styles of a stylesheet that match a view, one for each state
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SheetStyles {
+    pub(crate) base: Option<Style>,
+    pub(crate) hover: Option<Style>,
+    pub(crate) focus: Option<Style>,
+    pub(crate) focus_visible: Option<Style>,
+    pub(crate) active: Option<Style>,
+    pub(crate) disabled: Option<Style>,
+}
+
+impl SheetStyles {
+    fn slot(&mut self, state: Option<SelectorState>) -> &mut Option<Style> {
+        match state {
+            None => &mut self.base,
+            Some(SelectorState::Hover) => &mut self.hover,
+            Some(SelectorState::Focus) => &mut self.focus,
+            Some(SelectorState::FocusVisible) => &mut self.focus_visible,
+            Some(SelectorState::Active) => &mut self.active,
+            Some(SelectorState::Disabled) => &mut self.disabled,
+        }
+    }
+
+    pub(crate) fn has_style_for_sel(&self, selector: &StyleSelector) -> bool {
+        match selector {
+            StyleSelector::Hover => self.hover.is_some(),
+            StyleSelector::Focus => self.focus.is_some(),
+            StyleSelector::FocusVisible => self.focus_visible.is_some(),
+            StyleSelector::Disabled => self.disabled.is_some(),
+            StyleSelector::Active => self.active.is_some(),
+            StyleSelector::Dragging => false,
+        }
+    }
+}
+
+impl Stylesheet {
+    pub fn parse(source: &str) -> Result<Self, StylesheetError> {
+        let source = strip_comments(source);
+        let mut rules = Vec::new();
+        let mut rest = source.as_str();
+        let mut line = 1;
+
+        while let Some(open) = rest.find('{') {
+            let selectors = &rest[..open];
+            let selector_line = line + count_lines(selectors.trim_end());
+            let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+                return Err(StylesheetError {
+                    line: selector_line,
+                    message: "missing `}`".to_string(),
+                });
+            };
+            let body = &rest[open + 1..close];
+            let body_line = line + count_lines(&rest[..open + 1]);
+
+            let style = parse_declarations(body, body_line)?;
+            for selector in selectors.split(',') {
+                let selector =
+                    parse_selector(selector.trim()).map_err(|message| StylesheetError {
+                        line: selector_line,
+                        message,
+                    })?;
+                rules.push(Rule {
+                    selector,
+                    style: style.clone(),
+                });
+            }
+
+            line += count_lines(&rest[..close + 1]);
+            rest = &rest[close + 1..];
+        }
+
+        if !rest.trim().is_empty() {
+            return Err(StylesheetError {
+                line: line + count_lines(&rest[..rest.len() - rest.trim_start().len()]),
+                message: "expected a rule, with its properties in `{ }`".to_string(),
+            });
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Reads and [parses](Stylesheet::parse) the stylesheet in the file at `path`
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        Self::parse(&source)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    /// The rules of `other` added after the ones of this stylesheet, so they win over them
+    /// when their selectors are as specific
+    pub fn extend(&mut self, other: Stylesheet) {
+        self.rules.extend(other.rules);
+    }
+
+    #[cfg(test)]
+    fn styles_for(&self, name: &str, id: Option<&str>, classes: &[String]) -> Option<SheetStyles> {
+        matching_styles(self.rules.iter(), name, id, classes)
+    }
+}
+
+/// The `rules` that match a view, applied from the least to the most specific, and in the
+/// order of `rules` for the ones as specific
+fn matching_styles<'a>(
+    rules: impl Iterator<Item = &'a Rule>,
+    name: &str,
+    id: Option<&str>,
+    classes: &[String],
+) -> Option<SheetStyles> {
+    let mut matched: Vec<(usize, &Rule)> = rules
+        .enumerate()
+        .filter(|(_, rule)| rule.selector.matches(name, id, classes))
+        .collect();
+    if matched.is_empty() {
+        return None;
+    }
+    matched.sort_by_key(|(index, rule)| (rule.selector.specificity(), *index));
+
+    let mut styles = SheetStyles::default();
+    for (_, rule) in matched {
+        let slot = styles.slot(rule.selector.state);
+        *slot = Some(match slot.take() {
+            Some(style) => style.apply(rule.style.clone()),
+            None => rule.style.clone(),
+        });
+    }
+    Some(styles)
+}
+
+/// Applies `stylesheet` to the views of every window, in place of the one set before
+pub fn set_stylesheet(stylesheet: Stylesheet) {
+    change_sheets(|sheets| sheets.stylesheet = Some(stylesheet));
+}
+
+/// Stops applying the stylesheet set with [set_stylesheet]
+pub fn clear_stylesheet() {
+    change_sheets(|sheets| sheets.stylesheet = None);
+}
+
+/// Styles the views with the class `name`, given with
+/// [class](crate::views::Decorators::class), with `style`. See the
+/// [module documentation](self#style-classes)
+pub fn define_class(name: impl Into<String>, style: Style) {
+    let selector = Selector {
+        classes: vec![name.into()],
+        ..Selector::default()
+    };
+    change_sheets(|sheets| sheets.classes.push(Rule { selector, style }));
+}
+
+fn change_sheets(f: impl FnOnce(&mut Sheets)) {
+    STYLESHEET.with(|sheets| {
+        let mut sheets = sheets.borrow_mut();
+        f(&mut sheets);
+        sheets.generation += 1;
+    });
+    // so the windows restyle their views
+    Application::with_event_loop_proxy(|proxy| {
+        let _ = proxy.send_event(UserEvent::Idle);
+    });
+}
+
+/// Counts the changes of the stylesheet and classes, so a window can tell when it has to
+/// restyle its views
+pub(crate) fn stylesheet_generation() -> u64 {
+    STYLESHEET.with(|sheets| sheets.borrow().generation)
+}
+
+pub(crate) fn stylesheet_styles(
+    name: &str,
+    id: Option<&str>,
+    classes: &[String],
+) -> Option<SheetStyles> {
+    STYLESHEET.with(|sheets| {
+        let sheets = sheets.borrow();
+        let rules = sheets.classes.iter().chain(
+            sheets
+                .stylesheet
+                .iter()
+                .flat_map(|sheet| sheet.rules.iter()),
+        );
+        matching_styles(rules, name, id, classes)
+    })
+}
+
+/// The name stylesheet rules select a view by, like `Label` for the type
+/// `floem::views::label::Label`
+pub(crate) fn view_type_name(type_name: &str) -> &str {
+    let name = type_name.split('<').next().unwrap_or(type_name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// `source` with the comments replaced by spaces, keeping the line breaks in them so the
+/// line numbers stay the same
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        let end = rest[start + 2..]
+            .find("*/")
+            .map(|end| start + 2 + end + 2)
+            .unwrap_or(rest.len());
+        stripped.extend(
+            rest[start..end]
+                .chars()
+                .map(|c| if c == '\n' { '\n' } else { ' ' }),
+        );
+        rest = &rest[end..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+fn count_lines(text: &str) -> usize {
+    text.matches('\n').count()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn parse_selector(text: &str) -> Result<Selector, String> {
+    if text.is_empty() {
+        return Err("expected a selector".to_string());
+    }
+    if text.contains(char::is_whitespace) {
+        return Err(format!(
+            "`{text}` has more than one part, selectors for nested views aren't supported"
+        ));
+    }
+
+    let mut selector = Selector::default();
+    let mut rest = text;
+    let name_len = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+    if name_len > 0 {
+        selector.name = Some(rest[..name_len].to_string());
+        rest = &rest[name_len..];
+    }
+
+    while let Some(prefix) = rest.chars().next() {
+        let part = &rest[1..];
+        let len = part.find(|c| !is_ident_char(c)).unwrap_or(part.len());
+        if len == 0 {
+            return Err(format!("expected a name after `{prefix}` in `{text}`"));
+        }
+        let value = &part[..len];
+        match prefix {
+            '.' => selector.classes.push(value.to_string()),
+            '#' => selector.id = Some(value.to_string()),
+            ':' if selector.state.is_none() => {
+                selector.state = Some(match value {
+                    "hover" => SelectorState::Hover,
+                    "focus" => SelectorState::Focus,
+                    "focus-visible" => SelectorState::FocusVisible,
+                    "active" => SelectorState::Active,
+                    "disabled" => SelectorState::Disabled,
+                    _ => return Err(format!("unknown state `:{value}`")),
+                })
+            }
+            _ => return Err(format!("unexpected `{prefix}` in `{text}`")),
+        }
+        rest = &part[len..];
+    }
+
+    Ok(selector)
+}
+
+fn parse_declarations(body: &str, first_line: usize) -> Result<Style, StylesheetError> {
+    let mut style = Style::BASE;
+    let mut line = first_line;
+    for declaration in body.split(';') {
+        let declaration_line =
+            line + count_lines(&declaration[..declaration.len() - declaration.trim_start().len()]);
+        line += count_lines(declaration);
+        let declaration = declaration.trim();
+        if declaration.is_empty() {
+            continue;
+        }
+        let error = |message: String| StylesheetError {
+            line: declaration_line,
+            message,
+        };
+        let Some((property, value)) = declaration.split_once(':') else {
+            return Err(error(format!(
+                "expected `property: value`, found `{declaration}`"
+            )));
+        };
+        style = apply_property(style, property.trim(), value.trim()).map_err(error)?;
+    }
+    Ok(style)
+}
+
+fn parse_number(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .map_err(|_| format!("`{value}` isn't a number"))
+}
+
+fn parse_px(value: &str) -> Result<f64, String> {
+    parse_number(value.strip_suffix("px").unwrap_or(value))
+}
+
+fn parse_px_pct(value: &str) -> Result<PxPct, String> {
+    match value.strip_suffix('%') {
+        Some(pct) => parse_number(pct).map(PxPct::Pct),
+        None => parse_px(value).map(PxPct::Px),
+    }
+}
+
+fn parse_px_pct_auto(value: &str) -> Result<PxPctAuto, String> {
+    match value {
+        "auto" => Ok(PxPctAuto::Auto),
+        _ => match parse_px_pct(value)? {
+            PxPct::Px(px) => Ok(PxPctAuto::Px(px)),
+            PxPct::Pct(pct) => Ok(PxPctAuto::Pct(pct)),
+        },
+    }
+}
+
+/// The top, right, bottom and left values of a shorthand like `padding: 4px 8px`
+fn parse_sides<T: Clone>(
+    value: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<[T; 4], String> {
+    let values = value
+        .split_whitespace()
+        .map(parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    match values.as_slice() {
+        [all] => Ok([all.clone(), all.clone(), all.clone(), all.clone()]),
+        [vert, horiz] => Ok([vert.clone(), horiz.clone(), vert.clone(), horiz.clone()]),
+        [top, horiz, bottom] => Ok([top.clone(), horiz.clone(), bottom.clone(), horiz.clone()]),
+        [top, right, bottom, left] => {
+            Ok([top.clone(), right.clone(), bottom.clone(), left.clone()])
+        }
+        _ => Err(format!("expected one to four values, found `{value}`")),
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    let invalid = || format!("`{value}` isn't a color");
+    if let Some(hex) = value.strip_prefix('#') {
+        let digit = |i: usize| {
+            hex.get(i..i + 1)
+                .and_then(|d| u8::from_str_radix(d, 16).ok())
+                .ok_or_else(invalid)
+        };
+        let byte = |i: usize| Ok::<_, String>(digit(i)? * 16 + digit(i + 1)?);
+        return match hex.len() {
+            3 => Ok(Color::rgb8(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+            6 => Ok(Color::rgb8(byte(0)?, byte(2)?, byte(4)?)),
+            8 => Ok(Color::rgba8(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+            _ => Err(invalid()),
+        };
+    }
+    if let Some(args) = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        let args: Vec<&str> = args.split(',').map(str::trim).collect();
+        let channel = |arg: &str| {
+            arg.parse::<u8>()
+                .map_err(|_| format!("`{arg}` isn't a color channel from 0 to 255"))
+        };
+        return match args.as_slice() {
+            [r, g, b] => Ok(Color::rgb8(channel(r)?, channel(g)?, channel(b)?)),
+            [r, g, b, a] => {
+                let alpha = parse_number(a)?.clamp(0.0, 1.0);
+                Ok(Color::rgba8(
+                    channel(r)?,
+                    channel(g)?,
+                    channel(b)?,
+                    (alpha * 255.0).round() as u8,
+                ))
+            }
+            _ => Err(invalid()),
+        };
+    }
+    match value {
+        "transparent" => Ok(Color::TRANSPARENT),
+        "black" => Ok(Color::BLACK),
+        "white" => Ok(Color::WHITE),
+        "gray" | "grey" => Ok(Color::GRAY),
+        "red" => Ok(Color::RED),
+        "green" => Ok(Color::GREEN),
+        "blue" => Ok(Color::BLUE),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_align(value: &str) -> Result<AlignItems, String> {
+    match value {
+        "start" => Ok(AlignItems::Start),
+        "end" => Ok(AlignItems::End),
+        "flex-start" => Ok(AlignItems::FlexStart),
+        "flex-end" => Ok(AlignItems::FlexEnd),
+        "center" => Ok(AlignItems::Center),
+        "baseline" => Ok(AlignItems::Baseline),
+        "stretch" => Ok(AlignItems::Stretch),
+        _ => Err(format!("unknown alignment `{value}`")),
+    }
+}
+
+fn parse_justify(value: &str) -> Result<JustifyContent, String> {
+    match value {
+        "start" => Ok(JustifyContent::Start),
+        "end" => Ok(JustifyContent::End),
+        "flex-start" => Ok(JustifyContent::FlexStart),
+        "flex-end" => Ok(JustifyContent::FlexEnd),
+        "center" => Ok(JustifyContent::Center),
+        "stretch" => Ok(JustifyContent::Stretch),
+        "space-between" => Ok(JustifyContent::SpaceBetween),
+        "space-around" => Ok(JustifyContent::SpaceAround),
+        "space-evenly" => Ok(JustifyContent::SpaceEvenly),
+        _ => Err(format!("unknown alignment `{value}`")),
+    }
+}
+
+fn parse_cursor(value: &str) -> Result<CursorStyle, String> {
+    match value {
+        "default" => Ok(CursorStyle::Default),
+        "pointer" => Ok(CursorStyle::Pointer),
+        "text" => Ok(CursorStyle::Text),
+        "col-resize" => Ok(CursorStyle::ColResize),
+        "row-resize" => Ok(CursorStyle::RowResize),
+        "w-resize" => Ok(CursorStyle::WResize),
+        "e-resize" => Ok(CursorStyle::EResize),
+        "s-resize" => Ok(CursorStyle::SResize),
+        "n-resize" => Ok(CursorStyle::NResize),
+        "nw-resize" => Ok(CursorStyle::NwResize),
+        "ne-resize" => Ok(CursorStyle::NeResize),
+        "sw-resize" => Ok(CursorStyle::SwResize),
+        "se-resize" => Ok(CursorStyle::SeResize),
+        "nesw-resize" => Ok(CursorStyle::NeswResize),
+        "nwse-resize" => Ok(CursorStyle::NwseResize),
+        "move" => Ok(CursorStyle::Move),
+        "grab" => Ok(CursorStyle::Grab),
+        "grabbing" => Ok(CursorStyle::Grabbing),
+        "not-allowed" => Ok(CursorStyle::NotAllowed),
+        "wait" => Ok(CursorStyle::Wait),
+        "crosshair" => Ok(CursorStyle::Crosshair),
+        "help" => Ok(CursorStyle::Help),
+        _ => Err(format!("unknown cursor `{value}`")),
+    }
+}
+
+fn length_percentage(value: PxPct) -> LengthPercentage {
+    match value {
+        PxPct::Px(px) => LengthPercentage::Points(px as f32),
+        PxPct::Pct(pct) => LengthPercentage::Percent((pct / 100.0) as f32),
+    }
+}
+
+fn apply_property(style: Style, property: &str, value: &str) -> Result<Style, String> {
+    let style = match property {
+        "width" => style.width(parse_px_pct_auto(value)?),
+        "height" => style.height(parse_px_pct_auto(value)?),
+        "min-width" => style.min_width(parse_px_pct_auto(value)?),
+        "min-height" => style.min_height(parse_px_pct_auto(value)?),
+        "max-width" => style.max_width(parse_px_pct_auto(value)?),
+        "max-height" => style.max_height(parse_px_pct_auto(value)?),
+
+        "padding" => {
+            let [top, right, bottom, left] = parse_sides(value, parse_px_pct)?;
+            style
+                .padding_top(top)
+                .padding_right(right)
+                .padding_bottom(bottom)
+                .padding_left(left)
+        }
+        "padding-top" => style.padding_top(parse_px_pct(value)?),
+        "padding-right" => style.padding_right(parse_px_pct(value)?),
+        "padding-bottom" => style.padding_bottom(parse_px_pct(value)?),
+        "padding-left" => style.padding_left(parse_px_pct(value)?),
+        "margin" => {
+            let [top, right, bottom, left] = parse_sides(value, parse_px_pct_auto)?;
+            style
+                .margin_top(top)
+                .margin_right(right)
+                .margin_bottom(bottom)
+                .margin_left(left)
+        }
+        "margin-top" => style.margin_top(parse_px_pct_auto(value)?),
+        "margin-right" => style.margin_right(parse_px_pct_auto(value)?),
+        "margin-bottom" => style.margin_bottom(parse_px_pct_auto(value)?),
+        "margin-left" => style.margin_left(parse_px_pct_auto(value)?),
+
+        "position" => style.position(match value {
+            "relative" => Position::Relative,
+            "absolute" => Position::Absolute,
+            _ => return Err(format!("unknown position `{value}`")),
+        }),
+        "top" => style.inset_top(parse_px_pct_auto(value)?),
+        "right" => style.inset_right(parse_px_pct_auto(value)?),
+        "bottom" => style.inset_bottom(parse_px_pct_auto(value)?),
+        "left" => style.inset_left(parse_px_pct_auto(value)?),
+        "z-index" => style.z_index(
+            value
+                .parse()
+                .map_err(|_| format!("`{value}` isn't a whole number"))?,
+        ),
+
+        // `border: 1px` or `border: 1px #ccc`, as there are only solid borders
+        "border" => {
+            let mut parts = value.split_whitespace().filter(|part| *part != "solid");
+            let width = parse_px(parts.next().unwrap_or_default())?;
+            let style = style.border(width);
+            match parts.next() {
+                Some(color) => style.border_color(parse_color(color)?),
+                None => style,
+            }
+        }
+        "border-width" => style.border(parse_px(value)?),
+        "border-top" | "border-top-width" => style.border_top(parse_px(value)?),
+        "border-right" | "border-right-width" => style.border_right(parse_px(value)?),
+        "border-bottom" | "border-bottom-width" => style.border_bottom(parse_px(value)?),
+        "border-left" | "border-left-width" => style.border_left(parse_px(value)?),
+        "border-color" => style.border_color(parse_color(value)?),
+        "border-radius" => style.border_radius(parse_px(value)?),
+        "outline" | "outline-width" => style.outline(parse_px(value)?),
+        "outline-color" => style.outline_color(parse_color(value)?),
+
+        "color" => style.color(parse_color(value)?),
+        "background" | "background-color" => style.background(parse_color(value)?),
+        "cursor" => style.cursor(parse_cursor(value)?),
+
+        "font-size" => style.font_size(parse_px(value)? as f32),
+        "font-family" => {
+            style.font_family(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+        }
+        "font-weight" => style.font_weight(match value {
+            "normal" => Weight::NORMAL,
+            "bold" => Weight::BOLD,
+            _ => Weight(
+                value
+                    .parse()
+                    .map_err(|_| format!("unknown font weight `{value}`"))?,
+            ),
+        }),
+        "font-style" => style.font_style(match value {
+            "normal" => FontStyle::Normal,
+            "italic" => FontStyle::Italic,
+            "oblique" => FontStyle::Oblique,
+            _ => return Err(format!("unknown font style `{value}`")),
+        }),
+        "line-height" => match value.strip_suffix("px") {
+            Some(px) => style.line_height_px(parse_number(px)? as f32),
+            None => style.line_height(parse_number(value)? as f32),
+        },
+        // `text-decoration: underline` or `text-decoration: underline line-through #c62828`
+        "text-decoration" => {
+            let mut style = style;
+            for part in value.split_whitespace() {
+                style = match part {
+                    "underline" => style.underline(),
+                    "line-through" => style.line_through(),
+                    "none" => style.text_decoration_none(),
+                    _ => style.text_decoration_color(parse_color(part)?),
+                };
+            }
+            style
+        }
+        "letter-spacing" => style.letter_spacing(parse_px(value)?),
+        "text-decoration-color" => style.text_decoration_color(parse_color(value)?),
+        "text-transform" => style.text_transform(match value {
+            "uppercase" => TextTransform::Uppercase,
+            "lowercase" => TextTransform::Lowercase,
+            "capitalize" => TextTransform::Capitalize,
+            _ => return Err(format!("unknown text transform `{value}`")),
+        }),
+        "text-overflow" => style.text_overflow(match value {
+            "wrap" => TextOverflow::Wrap,
+            "clip" => TextOverflow::Clip,
+            "ellipsis" => TextOverflow::Ellipsis,
+            _ => return Err(format!("unknown text overflow `{value}`")),
+        }),
+
+        "display" => style.display(match value {
+            "flex" => Display::Flex,
+            "grid" => Display::Grid,
+            "none" => Display::None,
+            _ => return Err(format!("unknown display `{value}`")),
+        }),
+        "flex-direction" => style.flex_direction(match value {
+            "row" => FlexDirection::Row,
+            "column" => FlexDirection::Column,
+            "row-reverse" => FlexDirection::RowReverse,
+            "column-reverse" => FlexDirection::ColumnReverse,
+            _ => return Err(format!("unknown flex direction `{value}`")),
+        }),
+        "flex-wrap" => style.flex_wrap(match value {
+            "nowrap" => FlexWrap::NoWrap,
+            "wrap" => FlexWrap::Wrap,
+            "wrap-reverse" => FlexWrap::WrapReverse,
+            _ => return Err(format!("unknown flex wrap `{value}`")),
+        }),
+        "flex-grow" => style.flex_grow(parse_number(value)? as f32),
+        "flex-shrink" => style.flex_shrink(parse_number(value)? as f32),
+        "flex-basis" => style.flex_basis(parse_px_pct_auto(value)?),
+        "align-items" => style.align_items(Some(parse_align(value)?)),
+        "align-self" => style.align_self(Some(parse_align(value)?)),
+        "justify-content" => style.justify_content(Some(parse_justify(value)?)),
+        "gap" => {
+            let values = value
+                .split_whitespace()
+                .map(parse_px_pct)
+                .collect::<Result<Vec<_>, _>>()?;
+            let (row, column) = match values.as_slice() {
+                [gap] => (*gap, *gap),
+                [row, column] => (*row, *column),
+                _ => return Err(format!("expected one or two values, found `{value}`")),
+            };
+            style.gap(taffy::geometry::Size {
+                width: length_percentage(column),
+                height: length_percentage(row),
+            })
+        }
+        "aspect-ratio" => style.aspect_ratio(Some(parse_number(value)? as f32)),
+
+        _ => return Err(format!("unknown property `{property}`")),
+    };
+    Ok(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::Color;
+
+    use super::Stylesheet;
+    use crate::{style::StyleValue, unit::PxPct};
+
+    #[test]
+    fn more_specific_rules_win() {
+        let sheet = Stylesheet::parse(
+            "
+            #save { color: #00f; }
+            Button.primary { color: #0f0; padding: 4px 8px; }
+            Button { color: #f00; }
+            ",
+        )
+        .unwrap();
+
+        let classes = vec!["primary".to_string()];
+        let styles = sheet.styles_for("Button", None, &classes).unwrap();
+        let base = styles.base.unwrap();
+        assert_eq!(base.color, StyleValue::Val(Some(Color::rgb8(0, 255, 0))));
+        assert_eq!(base.padding_left, StyleValue::Val(PxPct::Px(8.0)));
+
+        let styles = sheet.styles_for("Button", Some("save"), &classes).unwrap();
+        let base = styles.base.unwrap();
+        assert_eq!(base.color, StyleValue::Val(Some(Color::rgb8(0, 0, 255))));
+
+        assert!(sheet.styles_for("Label", None, &[]).is_none());
+    }
+
+    #[test]
+    fn states_are_kept_apart() {
+        let sheet = Stylesheet::parse(".link, .link:hover { color: white }").unwrap();
+        let styles = sheet
+            .styles_for("Label", None, &["link".to_string()])
+            .unwrap();
+        assert!(styles.base.is_some());
+        assert!(styles.hover.is_some());
+        assert!(styles.focus.is_none());
+    }
+
+    #[test]
+    fn errors_have_their_line() {
+        let err = Stylesheet::parse("Label {\n    color: red;\n    colour: red;\n}").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        let err = Stylesheet::parse("/* a\ncomment */\n.toolbar Button {}").unwrap_err();
+        assert_eq!(err.line, 3);
+    }
+}
@@ -0,0 +1,311 @@
+//! Design tokens: the spacing, corner radii, text styles and colors the built-in widgets are
+//! styled with.
+//!
+//! The tokens are shared by the whole app, and reading them with [tokens] inside a style
+//! closure restyles the view whenever they change. So changing a token with [set_tokens]
+//! or [update_tokens] updates every built-in widget, along with any views of the app that
+//! use the tokens the same way, without building them again. For example, switching to
+//! [Tokens::dark] gives the app a dark mode, and [follow_window_theme] does that whenever the
+//! system switches between light and dark.
+//! ```rust
+//! # use floem::{theme::{tokens, TextRole}, view::View, views::{Decorators, label}};
+//! fn caption() -> impl View {
+//!     label(|| "Last saved 5 minutes ago".to_string()).style(|s| {
+//!         s.text_role(TextRole::Caption)
+//!             .color(tokens().colors.text_muted)
+//!             .padding(tokens().spacing.sm)
+//!     })
+//! }
+//! ```
+
+use floem_reactive::{create_effect, RwSignal, Scope};
+use floem_renderer::cosmic_text::Weight;
+use peniko::Color;
+
+use crate::{
+    style::Style,
+    window::{window_signals, Theme, WindowId},
+};
+
+thread_local! {
+    static TOKENS: RwSignal<Tokens> = Scope::new().create_rw_signal(Tokens::default());
+}
+
+/// All of the design tokens
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tokens {
+    pub spacing: Spacing,
+    pub radius: Radius,
+    pub typography: Typography,
+    pub colors: SemanticColors,
+}
+
+impl Tokens {
+    /// The default tokens, with [light](SemanticColors::light) colors
+    pub fn light() -> Self {
+        Self::default()
+    }
+
+    /// The default tokens with [dark](SemanticColors::dark) colors
+    pub fn dark() -> Self {
+        Self {
+            colors: SemanticColors::dark(),
+            ..Self::default()
+        }
+    }
+}
+
+/// The sizes used for padding, margins and gaps, from the smallest to the largest
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spacing {
+    pub xs: f32,
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+    pub xl: f32,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            xs: 2.0,
+            sm: 4.0,
+            md: 8.0,
+            lg: 12.0,
+            xl: 24.0,
+        }
+    }
+}
+
+/// The border radii used for rounded corners, from the smallest to the largest
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Radius {
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+}
+
+impl Default for Radius {
+    fn default() -> Self {
+        Self {
+            sm: 2.0,
+            md: 4.0,
+            lg: 8.0,
+        }
+    }
+}
+
+/// What a piece of text is for, which decides its [TextStyle]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextRole {
+    /// The title of a window or page
+    Title,
+    /// The heading of a section
+    Heading,
+    /// Normal text, and the text of most widgets
+    Body,
+    /// Small text next to other content, like hints and timestamps
+    Caption,
+}
+
+/// How the text with one [TextRole] looks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextStyle {
+    pub font_size: f32,
+    pub font_weight: Weight,
+    /// A multiple of the font's normal line height
+    pub line_height: f32,
+}
+
+/// The [TextStyle] of each [TextRole]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Typography {
+    pub title: TextStyle,
+    pub heading: TextStyle,
+    pub body: TextStyle,
+    pub caption: TextStyle,
+}
+
+impl Typography {
+    pub fn get(&self, role: TextRole) -> TextStyle {
+        match role {
+            TextRole::Title => self.title,
+            TextRole::Heading => self.heading,
+            TextRole::Body => self.body,
+            TextRole::Caption => self.caption,
+        }
+    }
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            title: TextStyle {
+                font_size: 24.0,
+                font_weight: Weight::BOLD,
+                line_height: 1.2,
+            },
+            heading: TextStyle {
+                font_size: 18.0,
+                font_weight: Weight::SEMIBOLD,
+                line_height: 1.2,
+            },
+            body: TextStyle {
+                font_size: 14.0,
+                font_weight: Weight::NORMAL,
+                line_height: 1.0,
+            },
+            caption: TextStyle {
+                font_size: 12.0,
+                font_weight: Weight::NORMAL,
+                line_height: 1.0,
+            },
+        }
+    }
+}
+
+/// Colors named after what they're used for rather than what they look like,
+/// so they can all be changed together
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SemanticColors {
+    /// The background of windows and pages
+    pub background: Color,
+    /// The accent of filled and selected controls, like the on track of a toggle switch
+    pub primary: Color,
+    /// Text and icons drawn on top of [primary](SemanticColors::primary)
+    pub on_primary: Color,
+    /// The background of popups, menus and dialogs
+    pub surface: Color,
+    /// The background of hovered items in lists and menus
+    pub hover: Color,
+    /// The background of the highlighted or selected item in lists and menus
+    pub selected: Color,
+    pub text: Color,
+    /// Less important text, and the text of disabled controls
+    pub text_muted: Color,
+    /// The outlines of controls and popups
+    pub border: Color,
+    /// The part of sliders, switches and progress bars that isn't filled
+    pub track: Color,
+    /// Dims the window behind a modal dialog
+    pub backdrop: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub info: Color,
+}
+
+impl Default for SemanticColors {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl SemanticColors {
+    /// Dark text on light backgrounds, the default
+    pub fn light() -> Self {
+        Self {
+            background: Color::rgb8(248, 248, 248),
+            primary: Color::rgb8(66, 133, 244),
+            on_primary: Color::WHITE,
+            surface: Color::WHITE,
+            hover: Color::rgb8(228, 232, 240),
+            selected: Color::rgb8(220, 230, 250),
+            text: Color::BLACK,
+            text_muted: Color::rgb8(150, 150, 150),
+            border: Color::rgb8(200, 200, 200),
+            track: Color::rgb8(210, 210, 210),
+            backdrop: Color::rgba8(0, 0, 0, 100),
+            success: Color::rgb8(46, 160, 67),
+            warning: Color::rgb8(230, 160, 20),
+            danger: Color::rgb8(218, 54, 51),
+            info: Color::rgb8(66, 133, 244),
+        }
+    }
+
+    /// Light text on dark backgrounds
+    pub fn dark() -> Self {
+        Self {
+            background: Color::rgb8(30, 30, 30),
+            primary: Color::rgb8(110, 160, 250),
+            on_primary: Color::BLACK,
+            surface: Color::rgb8(44, 44, 44),
+            hover: Color::rgb8(65, 65, 65),
+            selected: Color::rgb8(50, 70, 110),
+            text: Color::rgb8(230, 230, 230),
+            text_muted: Color::rgb8(140, 140, 140),
+            border: Color::rgb8(80, 80, 80),
+            track: Color::rgb8(70, 70, 70),
+            backdrop: Color::rgba8(0, 0, 0, 150),
+            success: Color::rgb8(70, 190, 90),
+            warning: Color::rgb8(240, 180, 50),
+            danger: Color::rgb8(240, 90, 85),
+            info: Color::rgb8(110, 160, 250),
+        }
+    }
+}
+
+/// The current tokens. Reading them inside a style closure, or anything else that's tracked
+/// by the reactive system, subscribes to changes of them.
+pub fn tokens() -> Tokens {
+    TOKENS.with(|tokens| tokens.get())
+}
+
+/// Replaces all of the tokens
+pub fn set_tokens(new: Tokens) {
+    TOKENS.with(|tokens| tokens.set(new));
+}
+
+/// Changes some of the tokens, leaving the rest as they are
+/// ```rust
+/// # use floem::{peniko::Color, theme::update_tokens};
+/// update_tokens(|tokens| {
+///     tokens.colors.primary = Color::rgb8(120, 80, 220);
+///     tokens.radius.md = 6.0;
+/// });
+/// ```
+pub fn update_tokens(f: impl FnOnce(&mut Tokens)) {
+    TOKENS.with(|tokens| tokens.update(f));
+}
+
+/// Switches the colors of the tokens between [light](SemanticColors::light) and
+/// [dark](SemanticColors::dark) with the system theme of the window, now and whenever it
+/// changes. It's called from within the window's view function, with the `WindowId` it's
+/// given, and stops with the window.
+///
+/// The tokens are app-wide, so this switches the colors of every window, not only this
+/// one. With several windows, call it from one of them; when more than one is followed,
+/// the one whose theme changed last wins.
+/// ```rust
+/// # use floem::{theme::follow_window_theme, view::View, views::label, window::WindowId};
+/// fn app_view(window_id: WindowId) -> impl View {
+///     follow_window_theme(window_id);
+///     label(|| "Hello".to_string())
+/// }
+/// ```
+pub fn follow_window_theme(window_id: WindowId) {
+    let Some(signals) = window_signals(window_id) else {
+        return;
+    };
+    let theme = signals.theme();
+    create_effect(move |_| {
+        let colors = match theme.get() {
+            Some(Theme::Dark) => SemanticColors::dark(),
+            Some(Theme::Light) | None => SemanticColors::light(),
+        };
+        let changed = TOKENS.with(|tokens| tokens.with_untracked(|t| t.colors != colors));
+        if changed {
+            update_tokens(|tokens| tokens.colors = colors);
+        }
+    });
+}
+
+impl Style {
+    /// Sets the font size, weight and line height to those of `role` in the current tokens
+    pub fn text_role(self, role: TextRole) -> Self {
+        let text = tokens().typography.get(role);
+        self.font_size(text.font_size)
+            .font_weight(text.font_weight)
+            .line_height(text.line_height)
+    }
+}
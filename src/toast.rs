@@ -0,0 +1,366 @@
+//! Short messages shown in a corner of the window, which go away by themselves.
+//!
+//! [show_toast] adds a message to the toast layer of the current window, where it slides in
+//! next to the other toasts and is dismissed again once its duration is up, or when its
+//! close button or one of its actions is clicked. Only a few toasts are shown at a time, see
+//! [set_toast_limit], and the ones after them wait until there's room.
+//!
+//! ```rust
+//! # use floem::toast::{show_toast, ToastKind, ToastOptions};
+//! fn saved() {
+//!     show_toast(
+//!         ToastOptions::new("The file was saved")
+//!             .kind(ToastKind::Success)
+//!             .action("Undo", || println!("undo")),
+//!     );
+//! }
+//! ```
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
+
+use floem_reactive::{create_rw_signal, RwSignal, Scope};
+use floem_renderer::cosmic_text::Weight;
+use peniko::Color;
+
+use crate::{
+    action::exec_after,
+    animate::animation,
+    id::Id,
+    style::CursorStyle,
+    theme::tokens,
+    unit::UnitExt,
+    view::View,
+    views::{clip, container_box, empty, label, list, stack, Decorators},
+    window_handle::get_current_view,
+};
+
+thread_local! {
+    /// The toast layer of each window, by the id of the window's root view
+    static TOAST_LAYERS: RefCell<HashMap<Id, ToastLayer>> = RefCell::new(HashMap::new());
+}
+
+/// How long a toast is shown for, unless it has a [duration](ToastOptions::duration) of
+/// its own
+const DEFAULT_DURATION: Duration = Duration::from_secs(4);
+/// How long a toast takes to slide in or out
+const TRANSITION: Duration = Duration::from_millis(200);
+const TOAST_WIDTH: f64 = 320.0;
+
+/// What a toast is about, which picks the color of the strip at its side
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ToastKind {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl ToastKind {
+    fn color(self) -> Color {
+        let colors = tokens().colors;
+        match self {
+            ToastKind::Info => colors.info,
+            ToastKind::Success => colors.success,
+            ToastKind::Warning => colors.warning,
+            ToastKind::Danger => colors.danger,
+        }
+    }
+}
+
+/// The corner of the window the toasts are shown in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ToastPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// A message for [show_toast]
+#[derive(Clone)]
+pub struct ToastOptions {
+    message: String,
+    title: Option<String>,
+    kind: ToastKind,
+    duration: Option<Duration>,
+    actions: Vec<(String, Rc<dyn Fn()>)>,
+}
+
+impl ToastOptions {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            title: None,
+            kind: ToastKind::default(),
+            duration: Some(DEFAULT_DURATION),
+            actions: Vec::new(),
+        }
+    }
+
+    /// A title shown in bold above the message
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn kind(mut self, kind: ToastKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// How long the toast is shown before it's dismissed, counting from when it's shown
+    /// rather than when it's queued
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Keeps the toast until it's closed or one of its actions is clicked
+    pub fn sticky(mut self) -> Self {
+        self.duration = None;
+        self
+    }
+
+    /// A button on the toast with `text`, which calls `action` and dismisses the toast
+    pub fn action(mut self, text: impl Into<String>, action: impl Fn() + 'static) -> Self {
+        self.actions.push((text.into(), Rc::new(action)));
+        self
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ToastEntry {
+    /// The id [dismiss_toast] takes
+    id: Id,
+    options: ToastOptions,
+}
+
+/// The toasts of a window, and how they're shown
+#[derive(Clone, Copy)]
+pub(crate) struct ToastLayer {
+    toasts: RwSignal<Vec<ToastEntry>>,
+    /// The toasts that are sliding out
+    leaving: RwSignal<Vec<Id>>,
+    position: RwSignal<ToastPosition>,
+    limit: RwSignal<usize>,
+}
+
+impl ToastLayer {
+    pub(crate) fn new(scope: Scope) -> Self {
+        Self {
+            toasts: scope.create_rw_signal(Vec::new()),
+            leaving: scope.create_rw_signal(Vec::new()),
+            position: scope.create_rw_signal(ToastPosition::default()),
+            limit: scope.create_rw_signal(3),
+        }
+    }
+}
+
+/// The toast layer of the window the current view is in
+fn current_layer() -> Option<ToastLayer> {
+    let current = get_current_view();
+    let root = current.root_id().unwrap_or(current);
+    TOAST_LAYERS.with(|layers| layers.borrow().get(&root).copied())
+}
+
+/// Shows a toast in the current window, returning an id to dismiss it early with
+/// [dismiss_toast]. Nothing is shown if there's no window.
+pub fn show_toast(options: ToastOptions) -> Id {
+    let id = Id::next();
+    if let Some(layer) = current_layer() {
+        layer
+            .toasts
+            .update(|toasts| toasts.push(ToastEntry { id, options }));
+    }
+    id
+}
+
+/// Slides out a toast shown with [show_toast], and does nothing if it's already gone
+pub fn dismiss_toast(id: Id) {
+    let layers: Vec<_> = TOAST_LAYERS.with(|layers| layers.borrow().values().copied().collect());
+    for layer in layers {
+        let shown = layer
+            .toasts
+            .with_untracked(|toasts| toasts.iter().any(|entry| entry.id == id));
+        if !shown
+            || layer
+                .leaving
+                .with_untracked(|leaving| leaving.contains(&id))
+        {
+            continue;
+        }
+        layer.leaving.update(|leaving| leaving.push(id));
+        exec_after(TRANSITION, move |_| {
+            layer
+                .toasts
+                .update(|toasts| toasts.retain(|entry| entry.id != id));
+            layer
+                .leaving
+                .update(|leaving| leaving.retain(|leaving| *leaving != id));
+        });
+    }
+}
+
+/// The corner of the current window the toasts are shown in, the bottom right by default
+pub fn set_toast_position(position: ToastPosition) {
+    if let Some(layer) = current_layer() {
+        layer.position.set(position);
+    }
+}
+
+/// How many toasts the current window shows at once, 3 by default. The toasts after them are
+/// shown as the ones before are dismissed.
+pub fn set_toast_limit(limit: usize) {
+    if let Some(layer) = current_layer() {
+        layer.limit.set(limit.max(1));
+    }
+}
+
+/// The layer of a window that toasts are shown in, which has to be on top of the window's
+/// content and its modals
+pub(crate) fn toast_layer(layer: ToastLayer) -> impl View {
+    let toasts = list(
+        move || {
+            let limit = layer.limit.get();
+            let mut toasts: Vec<_> = layer.toasts.get().into_iter().take(limit).collect();
+            // the newest toast is the one closest to the edge of the window
+            if matches!(
+                layer.position.get(),
+                ToastPosition::TopLeft | ToastPosition::TopRight
+            ) {
+                toasts.reverse();
+            }
+            toasts
+        },
+        |entry: &ToastEntry| entry.id,
+        move |entry| toast_view(entry, layer),
+    )
+    .style(|s| s.flex_col().width(TOAST_WIDTH));
+
+    container_box(toasts).style(move |s| {
+        let position = layer.position.get();
+        let s = s
+            .absolute()
+            .size(100.pct(), 100.pct())
+            .flex_col()
+            .padding(tokens().spacing.md);
+        let s = match position {
+            ToastPosition::TopLeft | ToastPosition::TopRight => s.justify_start(),
+            ToastPosition::BottomLeft | ToastPosition::BottomRight => s.justify_end(),
+        };
+        match position {
+            ToastPosition::TopLeft | ToastPosition::BottomLeft => s.items_start(),
+            ToastPosition::TopRight | ToastPosition::BottomRight => s.items_end(),
+        }
+    })
+}
+
+pub(crate) fn register_toast_layer(root: Id, layer: ToastLayer) {
+    TOAST_LAYERS.with(|layers| layers.borrow_mut().insert(root, layer));
+}
+
+pub(crate) fn remove_toast_layer(root: Id) {
+    TOAST_LAYERS.with(|layers| layers.borrow_mut().remove(&root));
+}
+
+fn toast_view(entry: ToastEntry, layer: ToastLayer) -> impl View {
+    let ToastEntry { id, options } = entry;
+    if let Some(duration) = options.duration {
+        // the toast is only built once it's shown, so this counts from then
+        exec_after(duration, move |_| dismiss_toast(id));
+    }
+    let height = create_rw_signal(0.0);
+    let leaving = move || layer.leaving.with(|leaving| leaving.contains(&id));
+    let ToastOptions {
+        message,
+        title,
+        kind,
+        actions,
+        ..
+    } = options;
+
+    let text = {
+        let has_title = title.is_some();
+        stack((
+            label(move || title.clone().unwrap_or_default()).style(move |s| {
+                s.font_weight(Weight::BOLD)
+                    .margin_bottom(tokens().spacing.xs)
+                    .apply_if(!has_title, |s| s.hide())
+            }),
+            label(move || message.clone()),
+        ))
+        .style(|s| s.flex_col().flex_grow(1.0).min_width(0.0))
+    };
+    let actions = list(
+        move || actions.clone().into_iter().enumerate(),
+        |(index, _)| *index,
+        move |(_, (text, action))| {
+            label(move || text.clone())
+                .style(|s| {
+                    let tokens = tokens();
+                    s.margin_left(tokens.spacing.sm)
+                        .padding_horiz(tokens.spacing.sm)
+                        .border_radius(tokens.radius.sm)
+                        .color(tokens.colors.primary)
+                        .cursor(CursorStyle::Pointer)
+                })
+                .hover_style(|s| s.background(tokens().colors.hover))
+                .on_click(move |_| {
+                    action();
+                    dismiss_toast(id);
+                    true
+                })
+        },
+    )
+    .style(|s| s.items_center());
+    let close = label(|| "×".to_string())
+        .style(|s| {
+            let tokens = tokens();
+            s.margin_left(tokens.spacing.sm)
+                .padding_horiz(tokens.spacing.xs)
+                .border_radius(tokens.radius.sm)
+                .color(tokens.colors.text_muted)
+                .cursor(CursorStyle::Pointer)
+        })
+        .hover_style(|s| s.background(tokens().colors.hover))
+        .on_click(move |_| {
+            dismiss_toast(id);
+            true
+        });
+
+    let strip = empty().style(move |s| {
+        s.width(4.0)
+            .margin_right(tokens().spacing.md)
+            .border_radius(2.0)
+            .background(kind.color())
+    });
+    let card = container_box(stack((strip, text, actions, close)).style(|s| s.width_full()))
+        .on_resize(move |rect| {
+            if rect.height() > 0.0 {
+                height.set(rect.height());
+            }
+        })
+        .style(|s| {
+            let tokens = tokens();
+            s.width_full()
+                .flex_shrink(0.0)
+                .margin_vert(tokens.spacing.xs)
+                .padding(tokens.spacing.md)
+                .background(tokens.colors.surface)
+                .border(1.0)
+                .border_color(tokens.colors.border)
+                .border_radius(tokens.radius.md)
+        });
+
+    // the toast grows in from no height, and shrinks back to it when it's dismissed
+    clip(card)
+        .style(|s| s.width_full().items_start())
+        .animation(
+            animation()
+                .height(move || if leaving() { 0.0 } else { height.get() })
+                .duration(TRANSITION)
+                .ease_in_out(),
+        )
+}
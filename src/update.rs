@@ -4,13 +4,16 @@ use kurbo::{Point, Size, Vec2};
 use winit::window::ResizeDirection;
 
 use crate::{
-    animate::{AnimUpdateMsg, Animation},
+    animate::{AnimUpdateMsg, Animation, KeyframeAnimation},
     context::{EventCallback, ResizeCallback},
     event::EventListener,
     id::Id,
+    keyboard::InputPurpose,
     menu::Menu,
-    responsive::ScreenSize,
+    overlay::Tooltip,
+    responsive::{ScreenSize, WidthQuery},
     style::{Style, StyleSelector},
+    views::Mask,
 };
 
 thread_local! {
@@ -53,11 +56,21 @@ pub(crate) enum UpdateMessage {
         id: Id,
         style: Style,
     },
+    DynStyle {
+        id: Id,
+        style: Style,
+    },
     ResponsiveStyle {
         id: Id,
         style: Style,
         size: ScreenSize,
     },
+    WidthStyle {
+        id: Id,
+        key: Id,
+        query: WidthQuery,
+        style: Style,
+    },
     StyleSelector {
         id: Id,
         selector: StyleSelector,
@@ -96,6 +109,10 @@ pub(crate) enum UpdateMessage {
         id: Id,
         animation: Animation,
     },
+    KeyframeAnimation {
+        id: Id,
+        animation: KeyframeAnimation,
+    },
     ContextMenu {
         id: Id,
         menu: Box<dyn Fn() -> Menu>,
@@ -104,6 +121,22 @@ pub(crate) enum UpdateMessage {
         id: Id,
         menu: Box<dyn Fn() -> Menu>,
     },
+    Tooltip {
+        id: Id,
+        tooltip: Tooltip,
+    },
+    Mask {
+        id: Id,
+        mask: Mask,
+    },
+    AddClass {
+        id: Id,
+        class: String,
+    },
+    StyleId {
+        id: Id,
+        style_id: String,
+    },
     ShowContextMenu {
         menu: Menu,
         pos: Option<Point>,
@@ -122,4 +155,11 @@ pub(crate) enum UpdateMessage {
         position: Point,
         size: Size,
     },
+    SetImePurpose {
+        purpose: InputPurpose,
+    },
+    #[cfg(feature = "webview")]
+    WithWindow {
+        action: Box<dyn FnOnce(&winit::window::Window)>,
+    },
 }
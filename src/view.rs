@@ -87,7 +87,7 @@ use std::any::Any;
 
 use bitflags::bitflags;
 use floem_renderer::Renderer;
-use kurbo::{Affine, Circle, Insets, Line, Point, Rect, RoundedRect, Size};
+use kurbo::{Affine, Circle, Line, Point, Rect, RoundedRect, Shape, Size, Vec2};
 use taffy::prelude::Node;
 
 use crate::{
@@ -152,6 +152,12 @@ pub trait View {
         core::any::type_name::<Self>().into()
     }
 
+    /// The type name stylesheet rules select the view by. Views standing in for a type
+    /// of their own, like a [Composite](crate::views::Composite), return the name of that type.
+    fn view_name(&self) -> &'static str {
+        core::any::type_name::<Self>()
+    }
+
     /// Used internally by Floem to send an update to the correct view based on the `Id` path.
     /// It will invoke only once `update` when the correct view is located.
     ///
@@ -193,6 +199,7 @@ pub trait View {
         cx.save();
 
         let view_style = self.view_style();
+        cx.app_state_mut().view_state(self.id()).view_name = self.view_name();
         cx.app_state_mut().compute_style(self.id(), view_style);
         let style = cx.app_state_mut().get_computed_style(self.id()).clone();
 
@@ -226,6 +233,12 @@ pub trait View {
         if style.line_height.is_some() {
             cx.line_height = style.line_height;
         }
+        if style.letter_spacing.is_some() {
+            cx.letter_spacing = style.letter_spacing.map(|v| v.0);
+        }
+        if style.text_transform.is_some() {
+            cx.text_transform = style.text_transform;
+        }
 
         let node = self.layout(cx);
 
@@ -251,6 +264,8 @@ pub trait View {
         if cx.app_state().is_hidden(self.id()) {
             return Rect::ZERO;
         }
+        // with the new layout, the width styles may have to change for the next one
+        cx.app_state_mut().update_width_styles(self.id());
 
         cx.save();
         let style = cx.app_state_mut().get_computed_style(self.id()).clone();
@@ -269,6 +284,12 @@ pub trait View {
         if style.line_height.is_some() {
             cx.line_height = style.line_height;
         }
+        if style.letter_spacing.is_some() {
+            cx.letter_spacing = style.letter_spacing.map(|v| v.0);
+        }
+        if style.text_transform.is_some() {
+            cx.text_transform = style.text_transform;
+        }
 
         let layout = cx
             .app_state()
@@ -617,6 +638,15 @@ pub trait View {
                         (*action)(&event);
                     }
                 }
+                if event.is_context_menu_trigger() {
+                    // The key reaches the focused view first and then its ancestors, so the
+                    // closest one with a context menu opens it, below its top left corner
+                    let layout = cx.app_state.view_state(id).layout_rect;
+                    if let Some(menu) = &cx.app_state.view_state(id).context_menu {
+                        show_context_menu(menu(), Some(Point::new(layout.x0, layout.y1)));
+                        return true;
+                    }
+                }
             }
             Event::WindowResized(_) => {
                 if let Some(view_state) = cx.app_state.view_states.get(&self.id()) {
@@ -664,6 +694,9 @@ pub trait View {
 
         cx.save();
         let size = cx.transform(id);
+        if let Some(transform) = cx.app_state.get_transform(id) {
+            cx.apply_transform(transform);
+        }
         let is_empty = cx
             .clip
             .map(|rect| rect.rect().intersect(size.to_rect()).is_empty())
@@ -675,6 +708,24 @@ pub trait View {
                 cx.set_z_index(z_index);
             }
 
+            if style.backdrop_blur > 0.0 {
+                let rect = size.to_rect().to_rounded_rect(style.border_radius.0);
+                cx.paint_state
+                    .renderer
+                    .blur_backdrop(&rect, style.backdrop_blur);
+            }
+
+            let in_layer = style.opacity < 1.0 || style.blur > 0.0;
+            if in_layer {
+                cx.paint_state
+                    .renderer
+                    .push_layer(style.opacity, style.blur);
+            }
+
+            if let Some(mask) = cx.app_state.view_state(id).mask.clone() {
+                cx.mask(&mask, size);
+            }
+
             paint_bg(cx, &style, size);
 
             if style.color.is_some() {
@@ -707,9 +758,34 @@ pub trait View {
             if style.line_height.is_some() {
                 cx.line_height = style.line_height;
             }
+            if style.letter_spacing.is_some() {
+                cx.letter_spacing = style.letter_spacing.map(|v| v.0);
+            }
+            if style.text_decoration.is_some() {
+                cx.text_decoration = style.text_decoration;
+            }
+            if style.cursor_color.is_some() {
+                cx.cursor_color = style.cursor_color;
+            }
+            if style.caret_width.is_some() {
+                cx.caret_width = style.caret_width.map(|v| v.0);
+            }
+            if style.caret_shape.is_some() {
+                cx.caret_shape = style.caret_shape;
+            }
+            if style.selection_background.is_some() {
+                cx.selection_background = style.selection_background;
+            }
+            if style.selection_color.is_some() {
+                cx.selection_color = style.selection_color;
+            }
             self.paint(cx);
             paint_border(cx, &style, size);
-            paint_outline(cx, &style, size)
+            paint_outline(cx, &style, size);
+
+            if in_layer {
+                cx.paint_state.renderer.pop_layer();
+            }
         }
 
         let mut drag_set_to_none = false;
@@ -789,39 +865,36 @@ fn paint_bg(cx: &mut PaintCx, style: &ComputedStyle, size: Size) {
         if width > 0.0 && height > 0.0 && radius > width.max(height) / 2.0 {
             let radius = width.max(height) / 2.0;
             let circle = Circle::new(rect.center(), radius);
-            let bg = match style.background {
-                Some(color) => color,
-                None => return,
-            };
-            cx.fill(&circle, bg, 0.0);
+            let circle_rect = Rect::from_center_size(circle.center, (radius * 2.0, radius * 2.0));
+            paint_box_shadow(cx, style, circle_rect, Some(radius));
+            fill_bg(cx, style, rect, &circle);
         } else {
             paint_box_shadow(cx, style, rect, Some(radius));
-            let bg = match style.background {
-                Some(color) => color,
-                None => return,
-            };
             let rounded_rect = rect.to_rounded_rect(radius);
-            cx.fill(&rounded_rect, bg, 0.0);
+            fill_bg(cx, style, rect, &rounded_rect);
         }
     } else {
         paint_box_shadow(cx, style, size.to_rect(), None);
-        let bg = match style.background {
-            Some(color) => color,
-            None => return,
-        };
-        cx.fill(&size.to_rect(), bg, 0.0);
+        fill_bg(cx, style, size.to_rect(), &size.to_rect());
+    }
+}
+
+/// Fills `shape` with the background color and then the background gradient, which is laid
+/// out over `rect`
+fn fill_bg(cx: &mut PaintCx, style: &ComputedStyle, rect: Rect, shape: &impl Shape) {
+    if let Some(color) = style.background {
+        cx.fill(shape, color, 0.0);
+    }
+    if let Some(gradient) = style.background_gradient.as_ref() {
+        cx.fill(shape, &gradient.to_gradient(rect), 0.0);
     }
 }
 
 fn paint_box_shadow(cx: &mut PaintCx, style: &ComputedStyle, rect: Rect, rect_radius: Option<f64>) {
-    if let Some(shadow) = style.box_shadow.as_ref() {
-        let inset = Insets::new(
-            -shadow.h_offset / 2.0,
-            -shadow.v_offset / 2.0,
-            shadow.h_offset / 2.0,
-            shadow.v_offset / 2.0,
-        );
-        let rect = rect.inflate(shadow.spread, shadow.spread).inset(inset);
+    // the first shadow is drawn last, so it's on top
+    for shadow in style.box_shadows.iter().rev() {
+        let rect = rect.inflate(shadow.spread, shadow.spread)
+            + Vec2::new(shadow.h_offset, shadow.v_offset);
         if let Some(radii) = rect_radius {
             let rounded_rect = RoundedRect::from_rect(rect, radii + shadow.spread);
             cx.fill(&rounded_rect, shadow.color, shadow.blur_radius);
@@ -927,6 +1000,82 @@ pub(crate) fn view_tab_navigation(root_view: &dyn View, app_state: &mut AppState
     println!("Tab to {new_focus:?}");
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Spatial navigation moves the focus to the nearest view with the `keyboard_navigatable` status
+/// in the given direction, comparing the centers of their layout rects.
+/// Views that are off the direction's axis count as further away than views in line with the focus.
+/// With `wrap`, when there's no view in that direction the furthest view on the opposite side is
+/// picked instead.
+pub(crate) fn view_spatial_navigation(
+    app_state: &mut AppState,
+    direction: FocusDirection,
+    wrap: bool,
+) {
+    let focus = app_state.focus;
+    let ids: Vec<Id> = app_state.keyboard_navigable.iter().copied().collect();
+    let mut candidates = Vec::new();
+    for id in ids {
+        if Some(id) == focus
+            || !id.has_id_path()
+            || app_state.is_disabled(&id)
+            || app_state.is_hidden_recursive(id)
+            || !app_state.in_focus_scope(id)
+        {
+            continue;
+        }
+        candidates.push((id, app_state.get_layout_rect(id)));
+    }
+
+    let new_focus = if let Some(focus) = focus {
+        let origin = app_state.get_layout_rect(focus).center();
+        let scored: Vec<(Id, f64, f64)> = candidates
+            .into_iter()
+            .map(|(id, rect)| {
+                let delta = rect.center() - origin;
+                let (distance, offset) = match direction {
+                    FocusDirection::Up => (-delta.y, delta.x),
+                    FocusDirection::Down => (delta.y, delta.x),
+                    FocusDirection::Left => (-delta.x, delta.y),
+                    FocusDirection::Right => (delta.x, delta.y),
+                };
+                (id, distance, offset.abs() * 2.0)
+            })
+            .collect();
+        let nearest = scored
+            .iter()
+            .filter(|(_, distance, _)| *distance > 0.0)
+            .min_by(|a, b| (a.1 + a.2).total_cmp(&(b.1 + b.2)));
+        // The distance is negative on the opposite side, so the furthest view scores lowest
+        let wrapped = || {
+            scored
+                .iter()
+                .filter(|(_, distance, _)| *distance < 0.0)
+                .min_by(|a, b| (a.1 + a.2).total_cmp(&(b.1 + b.2)))
+        };
+        nearest
+            .or_else(|| if wrap { wrapped() } else { None })
+            .map(|(id, _, _)| *id)
+    } else {
+        // Nothing has focus yet, so start from the top left
+        candidates
+            .into_iter()
+            .min_by(|a, b| a.1.y0.total_cmp(&b.1.y0).then(a.1.x0.total_cmp(&b.1.x0)))
+            .map(|(id, _)| id)
+    };
+
+    if let Some(new_focus) = new_focus {
+        app_state.clear_focus();
+        app_state.update_focus(new_focus, true);
+    }
+}
+
 fn view_children<'a>(view: &'a dyn View, id_path: &[Id]) -> Vec<&'a dyn View> {
     let id = id_path[0];
     let id_path = &id_path[1..];
@@ -1112,6 +1261,10 @@ impl View for Box<dyn View> {
         (**self).view_style()
     }
 
+    fn view_name(&self) -> &'static str {
+        (**self).view_name()
+    }
+
     fn child(&self, id: Id) -> Option<&dyn View> {
         (**self).child(id)
     }
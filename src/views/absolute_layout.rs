@@ -0,0 +1,222 @@
+use floem_reactive::RwSignal;
+use kurbo::{Point, Rect, Size, Vec2};
+use taffy::prelude::Node;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    view::{ChangeFlags, View},
+    views::{container_box, ContainerBox, Decorators},
+};
+
+/// A child being dragged, and where in the child the pointer grabbed it
+struct Drag {
+    index: usize,
+    grab: Vec2,
+}
+
+/// A container placing each child at its own position. See [`absolute_layout`]
+pub struct AbsoluteLayout {
+    id: Id,
+    children: Vec<ContainerBox>,
+    positions: Vec<RwSignal<Point>>,
+    child_sizes: Vec<Size>,
+    size: Size,
+    clamp_to_bounds: bool,
+    drag_to_move: bool,
+    drag: Option<Drag>,
+}
+
+/// A container for freely positioned children, like the items on a whiteboard or panels
+/// that can be moved around like windows. Each child is placed with its top left corner at
+/// the point in its signal, relative to the container, and moves whenever the signal changes.
+///
+/// The children don't take part in the container's flexbox layout, so the container
+/// needs a size from its style or its parent.
+pub fn absolute_layout(children: Vec<(RwSignal<Point>, Box<dyn View>)>) -> AbsoluteLayout {
+    let (positions, children): (Vec<_>, Vec<_>) = children
+        .into_iter()
+        .map(|(position, child)| {
+            let child = container_box(child).style(move |s| {
+                let position = position.get();
+                s.absolute().inset_left(position.x).inset_top(position.y)
+            });
+            (position, child)
+        })
+        .unzip();
+    AbsoluteLayout {
+        id: Id::next(),
+        child_sizes: vec![Size::ZERO; children.len()],
+        children,
+        positions,
+        size: Size::ZERO,
+        clamp_to_bounds: false,
+        drag_to_move: false,
+        drag: None,
+    }
+}
+
+impl AbsoluteLayout {
+    /// Keeps every child inside the container, moving it back in when its position
+    /// would put any of it outside
+    pub fn clamp_to_bounds(mut self, clamp_to_bounds: bool) -> Self {
+        self.clamp_to_bounds = clamp_to_bounds;
+        self
+    }
+
+    /// Lets the children be moved by dragging them with the pointer, which updates their
+    /// position signals. Presses that a child handles itself, like clicking a button in it,
+    /// don't start a drag.
+    pub fn drag_to_move(mut self, drag_to_move: bool) -> Self {
+        self.drag_to_move = drag_to_move;
+        self
+    }
+
+    fn clamp(&self, index: usize, position: Point) -> Point {
+        if !self.clamp_to_bounds {
+            return position;
+        }
+        let child = self.child_sizes[index];
+        Point::new(
+            position.x.min(self.size.width - child.width).max(0.0),
+            position.y.min(self.size.height - child.height).max(0.0),
+        )
+    }
+
+    /// The topmost child under `pos`
+    fn child_at(&self, pos: Point) -> Option<usize> {
+        (0..self.children.len()).rev().find(|&index| {
+            Rect::from_origin_size(
+                self.positions[index].get_untracked(),
+                self.child_sizes[index],
+            )
+            .contains(pos)
+        })
+    }
+}
+
+impl View for AbsoluteLayout {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        self.children
+            .iter()
+            .find(|child| child.id() == id)
+            .map(|child| child as &dyn View)
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        self.children
+            .iter_mut()
+            .find(|child| child.id() == id)
+            .map(|child| child as &mut dyn View)
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.children
+            .iter()
+            .map(|child| child as &dyn View)
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.children
+            .iter_mut()
+            .map(|child| child as &mut dyn View)
+            .collect()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "AbsoluteLayout".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, true, |cx| {
+            self.children
+                .iter_mut()
+                .map(|child| child.layout_main(cx))
+                .collect()
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let size = |layout: taffy::prelude::Layout| {
+            Size::new(layout.size.width as f64, layout.size.height as f64)
+        };
+        self.size = cx.get_layout(self.id).map(size).unwrap_or_default();
+        for (index, child) in self.children.iter().enumerate() {
+            self.child_sizes[index] = cx.get_layout(child.id()).map(size).unwrap_or_default();
+        }
+
+        if self.clamp_to_bounds {
+            for (index, position) in self.positions.iter().enumerate() {
+                let current = position.get_untracked();
+                let clamped = self.clamp(index, current);
+                if clamped != current {
+                    position.set(clamped);
+                }
+            }
+        }
+
+        let mut layout_rect = Rect::ZERO;
+        for child in &mut self.children {
+            layout_rect = layout_rect.union(child.compute_layout_main(cx));
+        }
+        Some(layout_rect)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        let children = self
+            .children
+            .iter_mut()
+            .map(|child| child as &mut dyn View)
+            .collect();
+        if cx.event_children(children, id_path, &event) {
+            return true;
+        }
+        if !self.drag_to_move {
+            return false;
+        }
+
+        match &event {
+            Event::PointerDown(event) if event.button.is_primary() => {
+                if let Some(index) = self.child_at(event.pos) {
+                    let origin = self.positions[index].get_untracked();
+                    self.drag = Some(Drag {
+                        index,
+                        grab: event.pos - origin,
+                    });
+                    cx.update_active(self.id);
+                    return true;
+                }
+                false
+            }
+            Event::PointerMove(event) => {
+                if let Some(drag) = self.drag.as_ref() {
+                    let position = self.clamp(drag.index, event.pos - drag.grab);
+                    self.positions[drag.index].set(position);
+                    return true;
+                }
+                false
+            }
+            Event::PointerUp(_) => self.drag.take().is_some(),
+            _ => false,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let children = self
+            .children
+            .iter_mut()
+            .map(|child| child as &mut dyn View)
+            .collect();
+        cx.paint_children(children);
+    }
+}
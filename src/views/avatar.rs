@@ -0,0 +1,177 @@
+use floem_reactive::{create_memo, create_rw_signal, RwSignal};
+use floem_renderer::cosmic_text::Weight;
+use peniko::Color;
+
+use crate::{
+    theme::tokens,
+    view::View,
+    views::{composite, dyn_container, empty, img, label, stack, svg, Composite, Decorators, Mask},
+};
+
+const PERSON_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><circle cx="12" cy="8" r="4.5" /><path d="M3 22c0-5 4-8.5 9-8.5s9 3.5 9 8.5z" /></svg>"#;
+
+/// How big an [avatar] is
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum AvatarSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+    /// A width and height of its own, in pixels
+    Custom(f64),
+}
+
+impl AvatarSize {
+    fn px(self) -> f64 {
+        match self {
+            AvatarSize::Small => 24.0,
+            AvatarSize::Medium => 32.0,
+            AvatarSize::Large => 48.0,
+            AvatarSize::Custom(size) => size,
+        }
+    }
+}
+
+/// The badge at the bottom right of an [avatar], telling whether the person is around
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AvatarStatus {
+    Online,
+    Away,
+    Busy,
+    Offline,
+}
+
+impl AvatarStatus {
+    fn color(self) -> Color {
+        let colors = tokens().colors;
+        match self {
+            AvatarStatus::Online => colors.success,
+            AvatarStatus::Away => colors.warning,
+            AvatarStatus::Busy => colors.danger,
+            AvatarStatus::Offline => colors.track,
+        }
+    }
+}
+
+/// What an avatar shows in its circle
+#[derive(Clone, PartialEq)]
+enum AvatarContent {
+    Image(Vec<u8>),
+    Initials(String),
+    Placeholder,
+}
+
+/// A picture of a person in a circle. See [`avatar`]
+pub type Avatar = Composite<AvatarState>;
+
+pub struct AvatarState {
+    name: RwSignal<Option<String>>,
+    size: RwSignal<AvatarSize>,
+    status: RwSignal<Option<AvatarStatus>>,
+}
+
+/// The first letters of the first and the last word of `name`
+fn initials(name: &str) -> String {
+    let mut words = name.split_whitespace();
+    let first = words.next().and_then(|word| word.chars().next());
+    let last = words.last().and_then(|word| word.chars().next());
+    first
+        .into_iter()
+        .chain(last)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// The image from `src` cut to a circle, in any format [img] can show. While `src` is
+/// `None`, as it is while the image loads, or when it's not an image that can be decoded,
+/// the avatar shows the initials of its [name](Avatar::name) instead, or an outline of a
+/// person when it has no name.
+pub fn avatar(src: impl Fn() -> Option<Vec<u8>> + 'static) -> Avatar {
+    let name: RwSignal<Option<String>> = create_rw_signal(None);
+    let size = create_rw_signal(AvatarSize::default());
+    let status: RwSignal<Option<AvatarStatus>> = create_rw_signal(None);
+
+    // the image is decoded once here, so one that can't be doesn't get to img
+    let image = create_memo(move |_| src().filter(|data| image::load_from_memory(data).is_ok()));
+    let content = move || match image.get() {
+        Some(data) => AvatarContent::Image(data),
+        None => match name.get().as_deref().map(initials) {
+            Some(initials) if !initials.is_empty() => AvatarContent::Initials(initials),
+            _ => AvatarContent::Placeholder,
+        },
+    };
+
+    let circle = dyn_container(
+        move || (content(), size.get()),
+        |(content, size)| {
+            let px = size.px();
+            match content {
+                // img only takes a size in pixels
+                AvatarContent::Image(data) => {
+                    Box::new(img(move || data.clone()).style(move |s| s.size(px, px)))
+                }
+                AvatarContent::Initials(initials) => {
+                    Box::new(label(move || initials.clone()).style(move |s| {
+                        s.font_size((px * 0.4) as f32)
+                            .font_weight(Weight::SEMIBOLD)
+                            .color(tokens().colors.text)
+                    }))
+                }
+                AvatarContent::Placeholder => {
+                    Box::new(svg(|| PERSON_SVG.to_string()).style(move |s| {
+                        s.size(px * 0.6, px * 0.6).color(tokens().colors.text_muted)
+                    }))
+                }
+            }
+        },
+    )
+    .style(move |s| {
+        let px = size.get().px();
+        s.size(px, px)
+            .justify_center()
+            .items_center()
+            .background(tokens().colors.track)
+    })
+    .mask(Mask::ellipse());
+
+    let badge = empty().style(move |s| {
+        let badge = (size.get().px() / 4.0).max(6.0);
+        match status.get() {
+            Some(status) => s
+                .absolute()
+                .inset_right(0.0)
+                .inset_bottom(0.0)
+                .size(badge, badge)
+                .border(2.0)
+                .border_radius(badge / 2.0)
+                .border_color(tokens().colors.surface)
+                .background(status.color()),
+            None => s.hide(),
+        }
+    });
+
+    composite(
+        "Avatar",
+        stack((circle, badge)).style(|s| s.flex_shrink(0.0)),
+        AvatarState { name, size, status },
+    )
+}
+
+impl Avatar {
+    /// The name of the person, whose initials are shown when there's no image
+    pub fn name(self, name: impl Into<String>) -> Self {
+        self.state().name.set(Some(name.into()));
+        self
+    }
+
+    pub fn size(self, size: AvatarSize) -> Self {
+        self.state().size.set(size);
+        self
+    }
+
+    /// Shows a dot in the color of `status` at the bottom right of the avatar
+    pub fn status(self, status: AvatarStatus) -> Self {
+        self.state().status.set(Some(status));
+        self
+    }
+}
@@ -0,0 +1,194 @@
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+
+use crate::{
+    menu::{Menu, MenuItem},
+    style::CursorStyle,
+    theme::tokens,
+    view::View,
+    views::{clip, composite, label, list, stack, Composite, Decorators},
+};
+
+/// The width the overflow menu is assumed to take before it's been shown
+const DEFAULT_OVERFLOW_WIDTH: f64 = 32.0;
+
+type SelectFn = Rc<RefCell<Option<Box<dyn Fn(usize)>>>>;
+
+/// One of the crumbs shown in the bar
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Crumb {
+    Segment {
+        index: usize,
+        title: String,
+        last: bool,
+    },
+    /// The segments that don't fit, in a menu
+    Overflow(Vec<(usize, String)>),
+}
+
+/// A path of segments, like the folders leading to a file. See [`breadcrumb`]
+pub type Breadcrumb = Composite<BreadcrumbState>;
+
+pub struct BreadcrumbState {
+    separator: RwSignal<String>,
+    on_select: SelectFn,
+}
+
+/// The `segments` in a row with separators between them, where clicking a segment calls
+/// [Breadcrumb::on_select] with its index. The last segment is the current one and can't
+/// be clicked.
+///
+/// When the segments don't fit in the width of the breadcrumb, the ones after the first are
+/// put into an overflow menu until they do, so the first and the last few stay visible.
+pub fn breadcrumb(segments: impl Fn() -> Vec<String> + 'static) -> Breadcrumb {
+    let separator = create_rw_signal("/".to_string());
+    let on_select: SelectFn = Rc::new(RefCell::new(None));
+    let items = create_rw_signal(Vec::new());
+    // the width of each segment with its separator, once it's been laid out
+    let widths: RwSignal<Vec<Option<f64>>> = create_rw_signal(Vec::new());
+    let overflow_width = create_rw_signal(DEFAULT_OVERFLOW_WIDTH);
+    let available = create_rw_signal(0.0);
+
+    create_effect(move |_| {
+        let segments = segments();
+        // the segments are all shown to measure them again
+        widths.set(vec![None; segments.len()]);
+        items.set(segments);
+    });
+
+    let crumbs = move || {
+        let hidden = widths.with(|widths| {
+            let measured: Option<Vec<f64>> = widths.iter().copied().collect();
+            measured
+                .map(|widths| hidden_range(&widths, overflow_width.get(), available.get()))
+                .unwrap_or(0..0)
+        });
+        let len = items.with(|items| items.len());
+        let mut crumbs = Vec::new();
+        for (index, title) in items.get().into_iter().enumerate() {
+            if !hidden.contains(&index) {
+                crumbs.push(Crumb::Segment {
+                    index,
+                    title,
+                    last: index + 1 == len,
+                });
+            } else if let Some(Crumb::Overflow(hidden)) = crumbs.last_mut() {
+                hidden.push((index, title));
+            } else {
+                crumbs.push(Crumb::Overflow(vec![(index, title)]));
+            }
+        }
+        crumbs
+    };
+
+    let select = on_select.clone();
+    let view_fn = move |crumb: Crumb| {
+        let index = match &crumb {
+            Crumb::Segment { index, .. } => Some(*index),
+            Crumb::Overflow(_) => None,
+        };
+        let first = index == Some(0);
+        let separator = label(move || separator.get()).style(move |s| {
+            s.padding_horiz(tokens().spacing.xs)
+                .color(tokens().colors.text_muted)
+                .apply_if(first, |s| s.hide())
+        });
+        let select = select.clone();
+        let crumb = match crumb {
+            Crumb::Segment { index, title, last } => label(move || title.clone())
+                .style(move |s| {
+                    let tokens = tokens();
+                    s.padding_horiz(tokens.spacing.xs)
+                        .border_radius(tokens.radius.sm)
+                        .apply_if(last, |s| s.font_bold())
+                        .apply_if(!last, |s| s.cursor(CursorStyle::Pointer))
+                })
+                .hover_style(move |s| s.apply_if(!last, |s| s.background(tokens().colors.hover)))
+                .keyboard_navigatable()
+                .on_click(move |_| {
+                    if !last {
+                        if let Some(on_select) = select.borrow().as_ref() {
+                            on_select(index);
+                        }
+                    }
+                    !last
+                }),
+            Crumb::Overflow(hidden) => label(|| "…".to_string())
+                .style(|s| {
+                    let tokens = tokens();
+                    s.padding_horiz(tokens.spacing.xs)
+                        .border_radius(tokens.radius.sm)
+                        .cursor(CursorStyle::Pointer)
+                })
+                .hover_style(|s| s.background(tokens().colors.hover))
+                .keyboard_navigatable()
+                .popout_menu(move || {
+                    let mut menu = Menu::new("");
+                    for (index, title) in hidden.clone() {
+                        let select = select.clone();
+                        menu = menu.entry(MenuItem::new(title).action(move || {
+                            if let Some(on_select) = select.borrow().as_ref() {
+                                on_select(index);
+                            }
+                        }));
+                    }
+                    menu
+                }),
+        };
+        stack((separator, crumb))
+            .style(|s| s.items_center().flex_shrink(0.0))
+            .on_resize(move |rect| match index {
+                Some(index) => widths.update(|widths| {
+                    if let Some(width) = widths.get_mut(index) {
+                        *width = Some(rect.width());
+                    }
+                }),
+                None => overflow_width.set(rect.width()),
+            })
+    };
+
+    let child = clip(list(crumbs, |crumb| crumb.clone(), view_fn).style(|s| s.items_center()))
+        .on_resize(move |rect| available.set(rect.width()))
+        .style(|s| s.width_full().min_width(0.0));
+
+    composite(
+        "Breadcrumb",
+        child,
+        BreadcrumbState {
+            separator,
+            on_select,
+        },
+    )
+    .base_style(|s| s.width_full().min_width(0.0))
+}
+
+/// The segments to hide so the rest fit in `available`, next to an overflow menu that's
+/// `overflow_width` wide. The first and the last are never hidden.
+fn hidden_range(widths: &[f64], overflow_width: f64, available: f64) -> Range<usize> {
+    let total: f64 = widths.iter().sum();
+    if total <= available || widths.len() < 3 {
+        return 0..0;
+    }
+    let mut width = total + overflow_width;
+    let mut end = 1;
+    while end < widths.len() - 1 && width > available {
+        width -= widths[end];
+        end += 1;
+    }
+    1..end
+}
+
+impl Breadcrumb {
+    /// The text between the segments, `/` by default
+    pub fn separator(self, separator: impl Into<String>) -> Self {
+        self.state().separator.set(separator.into());
+        self
+    }
+
+    /// Called with the index of a segment when it's clicked or picked from the overflow menu
+    pub fn on_select(self, on_select: impl Fn(usize) + 'static) -> Self {
+        *self.state().on_select.borrow_mut() = Some(Box::new(on_select));
+        self
+    }
+}
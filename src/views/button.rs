@@ -0,0 +1,111 @@
+use std::rc::Rc;
+
+use floem_reactive::{create_rw_signal, RwSignal};
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    style::CursorStyle,
+    theme::tokens,
+    view::View,
+    views::{composite, label, Composite, Decorators},
+};
+
+/// How a [button] looks, which tells how important its action is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ButtonVariant {
+    /// An outlined button, for most actions
+    #[default]
+    Default,
+    /// A button filled with the primary color, for the main action of a view
+    Primary,
+}
+
+/// A clickable label. See [`button`]
+pub type Button = Composite<ButtonState>;
+
+pub struct ButtonState {
+    variant: RwSignal<ButtonVariant>,
+}
+
+/// A button showing `text`, which calls `on_press` when it's clicked, or when Enter or
+/// Space is pressed while it has focus.
+///
+/// The button is highlighted while it's hovered and while it's held down, is outlined in
+/// the primary color when it's focused with the keyboard, and is greyed out when it's
+/// [disabled](Decorators::disabled), which also keeps it from being pressed.
+pub fn button(text: impl Fn() -> String + 'static, on_press: impl Fn() + 'static) -> Button {
+    let variant = create_rw_signal(ButtonVariant::default());
+    let on_press = Rc::new(on_press);
+    let key_press = on_press.clone();
+    let primary = move || variant.get() == ButtonVariant::Primary;
+
+    composite("Button", label(text), ButtonState { variant })
+        .base_style(move |s| {
+            let tokens = tokens();
+            s.padding_horiz(tokens.spacing.md)
+                .padding_vert(tokens.spacing.xs)
+                .justify_center()
+                .items_center()
+                .border(1.0)
+                .border_radius(tokens.radius.md)
+                .border_color(tokens.colors.border)
+                .cursor(CursorStyle::Pointer)
+                .apply_if(primary(), |s| {
+                    s.border_color(tokens.colors.primary)
+                        .background(tokens.colors.primary)
+                        .color(tokens.colors.on_primary)
+                })
+        })
+        .hover_style(move |s| {
+            let colors = tokens().colors;
+            if primary() {
+                s.background(colors.primary.with_alpha_factor(0.85))
+            } else {
+                s.background(colors.hover)
+            }
+        })
+        .active_style(move |s| {
+            let colors = tokens().colors;
+            if primary() {
+                s.background(colors.primary.with_alpha_factor(0.7))
+            } else {
+                s.background(colors.selected)
+            }
+        })
+        .focus_visible_style(|s| s.border_color(tokens().colors.primary))
+        .disabled_style(move |s| {
+            let colors = tokens().colors;
+            s.cursor(CursorStyle::Default)
+                .color(colors.text_muted)
+                .border_color(colors.border)
+                .apply_if(primary(), |s| s.background(colors.track))
+        })
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            on_press();
+            true
+        })
+        .on_event(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                if matches!(key.logical_key, Key::Enter | Key::Space) {
+                    key_press();
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+impl Button {
+    pub fn variant(self, variant: ButtonVariant) -> Self {
+        self.state().variant.set(variant);
+        self
+    }
+
+    /// Shorthand for the [primary](ButtonVariant::Primary) variant
+    pub fn primary(self) -> Self {
+        self.state().variant(ButtonVariant::Primary)
+    }
+}
@@ -0,0 +1,98 @@
+use floem_reactive::SignalTracker;
+use kurbo::{Rect, Size};
+use taffy::prelude::Node;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    view::{ChangeFlags, View},
+};
+
+/// A view drawn by a closure. See [`canvas`]
+pub struct Canvas {
+    id: Id,
+    paint_fn: Box<dyn Fn(&mut PaintCx, Size)>,
+    tracker: SignalTracker,
+    size: Size,
+}
+
+/// A view that's drawn by calling `paint` with the size of the view, for drawing shapes,
+/// text and images with the [Renderer](crate::Renderer) through the [PaintCx]
+/// without implementing a view. The origin is the top left corner of the view, and what's
+/// drawn is clipped to it.
+///
+/// The view is painted again whenever a signal that `paint` read the last time it ran
+/// changes. Its size comes from its style, as it has no content to size it.
+/// ```rust
+/// # use floem::{kurbo::Circle, peniko::Color, reactive::RwSignal, view::View, Renderer};
+/// # use floem::views::{canvas, Decorators};
+/// fn dot(radius: RwSignal<f64>) -> impl View {
+///     canvas(move |cx, size| {
+///         let center = size.to_rect().center();
+///         cx.fill(&Circle::new(center, radius.get()), Color::ROYAL_BLUE, 0.0);
+///     })
+///     .style(|s| s.size(100.0, 100.0))
+/// }
+/// ```
+pub fn canvas(paint: impl Fn(&mut PaintCx, Size) + 'static) -> Canvas {
+    let id = Id::next();
+    Canvas {
+        id,
+        paint_fn: Box::new(paint),
+        tracker: SignalTracker::new(move || id.request_paint()),
+        size: Size::ZERO,
+    }
+}
+
+impl View for Canvas {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Canvas".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        None
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        cx.save();
+        cx.clip(&self.size.to_rect());
+        let size = self.size;
+        self.tracker.track(|| (self.paint_fn)(cx, size));
+        cx.restore();
+    }
+}
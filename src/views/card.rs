@@ -0,0 +1,143 @@
+use kurbo::Rect;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::Elevation,
+    theme::tokens,
+    view::{ChangeFlags, View},
+    views::{container_box, ContainerBox, Decorators},
+};
+
+/// A raised panel around a view. See [`card`]
+pub struct Card {
+    id: Id,
+    header: Option<ContainerBox>,
+    body: ContainerBox,
+    footer: Option<ContainerBox>,
+}
+
+/// A panel with rounded corners and a border around `child`, raised with a
+/// [low elevation](Elevation::Low). Another elevation can be picked with
+/// [Style::elevation](crate::style::Style::elevation) in the card's style, which also
+/// takes `Elevation::Flat` for no shadow at all.
+///
+/// The card can have a [header](Card::header) above `child` and a [footer](Card::footer)
+/// below it, both set apart from it by a line.
+pub fn card(child: impl View + 'static) -> Card {
+    Card {
+        id: Id::next(),
+        header: None,
+        body: container_box(child).style(|s| s.padding(tokens().spacing.md).flex_grow(1.0)),
+        footer: None,
+    }
+    .base_style(|s| {
+        let tokens = tokens();
+        s.flex_col()
+            .border(1.0)
+            .border_color(tokens.colors.border)
+            .border_radius(tokens.radius.lg)
+            .background(tokens.colors.surface)
+            .elevation(Elevation::Low)
+    })
+}
+
+impl Card {
+    /// A view above the card's content, like its title
+    pub fn header(mut self, header: impl View + 'static) -> Self {
+        self.header = Some(container_box(header).style(|s| {
+            let tokens = tokens();
+            s.padding_horiz(tokens.spacing.md)
+                .padding_vert(tokens.spacing.sm)
+                .border_bottom(1.0)
+                .border_color(tokens.colors.border)
+        }));
+        self
+    }
+
+    /// A view below the card's content, like the buttons for its actions
+    pub fn footer(mut self, footer: impl View + 'static) -> Self {
+        self.footer = Some(container_box(footer).style(|s| {
+            let tokens = tokens();
+            s.padding_horiz(tokens.spacing.md)
+                .padding_vert(tokens.spacing.sm)
+                .border_top(1.0)
+                .border_color(tokens.colors.border)
+        }));
+        self
+    }
+}
+
+impl View for Card {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        self.children().into_iter().find(|child| child.id() == id)
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        self.children_mut()
+            .into_iter()
+            .find(|child| child.id() == id)
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        let mut children: Vec<&dyn View> = Vec::new();
+        if let Some(header) = self.header.as_ref() {
+            children.push(header);
+        }
+        children.push(&self.body);
+        if let Some(footer) = self.footer.as_ref() {
+            children.push(footer);
+        }
+        children
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        let mut children: Vec<&mut dyn View> = Vec::new();
+        if let Some(header) = self.header.as_mut() {
+            children.push(header);
+        }
+        children.push(&mut self.body);
+        if let Some(footer) = self.footer.as_mut() {
+            children.push(footer);
+        }
+        children
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Card".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, true, |cx| {
+            self.children_mut()
+                .into_iter()
+                .map(|child| child.layout_main(cx))
+                .collect()
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let mut layout_rect = Rect::ZERO;
+        for child in self.children_mut() {
+            layout_rect = layout_rect.union(child.compute_layout_main(cx));
+        }
+        Some(layout_rect)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        cx.event_children(self.children_mut(), id_path, &event)
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        cx.paint_children(self.children_mut());
+    }
+}
@@ -0,0 +1,273 @@
+use std::{cell::RefCell, time::Duration};
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use winit::keyboard::Key;
+
+use crate::{
+    action::exec_after,
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    style::CursorStyle,
+    theme::tokens,
+    view::View,
+    views::{clip, composite, container_box, empty, list, stack, Composite, Decorators},
+};
+
+/// How often the pages move while they snap to a page
+const SNAP_INTERVAL: Duration = Duration::from_millis(16);
+/// The part of the remaining distance to the page that's moved on each tick of the snap
+const SNAP_SPEED: f64 = 0.25;
+/// How much of a page the pages have to be dragged for the next or the previous one
+const SWIPE_THRESHOLD: f64 = 0.2;
+/// How far the pointer moves before it's dragging the pages rather than clicking a page
+const DRAG_THRESHOLD: f64 = 4.0;
+const DOT_SIZE: f64 = 8.0;
+
+/// Where the pages of a carousel are, and where they're snapping to, in pages
+#[derive(Clone, Copy)]
+struct Motion {
+    position: RwSignal<f64>,
+    target: RwSignal<f64>,
+    scheduled: RwSignal<bool>,
+}
+
+impl Motion {
+    fn snap_to(self, target: f64) {
+        self.target.set(target);
+        if !self.scheduled.get_untracked() {
+            self.schedule();
+        }
+    }
+
+    fn schedule(self) {
+        self.scheduled.set(true);
+        exec_after(SNAP_INTERVAL, move |_| {
+            self.scheduled.set(false);
+            let position = self.position.get_untracked();
+            let target = self.target.get_untracked();
+            if (target - position).abs() < 0.001 {
+                self.position.set(target);
+            } else {
+                self.position
+                    .set(position + (target - position) * SNAP_SPEED);
+                self.schedule();
+            }
+        });
+    }
+
+    fn stop(self) {
+        self.target.set(self.position.get_untracked());
+    }
+}
+
+/// Pages shown one at a time, which are swiped between. See [`carousel`]
+pub type Carousel = Composite<CarouselState>;
+
+pub struct CarouselState {
+    active_index: RwSignal<usize>,
+    looping: RwSignal<bool>,
+    dots: RwSignal<bool>,
+}
+
+/// Shows one of the `children` at a time, as pages that are dragged sideways to go to the
+/// next or the previous one, snapping to the page they're dragged towards once they're let
+/// go. The dots below the pages show which page is shown, and go to a page when clicked,
+/// and the arrow keys move between the pages while the carousel has focus.
+///
+/// The page that's shown is [active_index](Carousel::active_index), and setting it slides
+/// the pages to that page.
+pub fn carousel(children: Vec<Box<dyn View>>) -> Carousel {
+    let len = children.len();
+    let pages = RefCell::new(children.into_iter().map(Some).collect::<Vec<_>>());
+    let active_index = create_rw_signal(0);
+    let looping = create_rw_signal(false);
+    let dots = create_rw_signal(true);
+    let width = create_rw_signal(0.0);
+    let motion = Motion {
+        position: create_rw_signal(0.0),
+        target: create_rw_signal(0.0),
+        scheduled: create_rw_signal(false),
+    };
+    // where the drag started, and the position of the pages then
+    let drag: RwSignal<Option<(f64, f64)>> = create_rw_signal(None);
+
+    // a looping carousel snaps to the page the shortest way round, so the first page comes
+    // after the last
+    let nearest = move |index: usize| {
+        let index = index as f64;
+        if !looping.get_untracked() || len == 0 {
+            return index;
+        }
+        let len = len as f64;
+        let position = motion.position.get_untracked();
+        index + ((position - index) / len).round() * len
+    };
+    create_effect(move |_| {
+        let index = active_index.get().min(len.saturating_sub(1));
+        motion.snap_to(nearest(index));
+    });
+    let go_to = move |page: isize| {
+        if len == 0 {
+            return;
+        }
+        let index = if looping.get_untracked() {
+            page.rem_euclid(len as isize) as usize
+        } else {
+            page.clamp(0, len as isize - 1) as usize
+        };
+        // set even when it's the same page, so a short drag snaps back
+        active_index.set(index);
+    };
+
+    let slides = list(
+        move || 0..len,
+        |index| *index,
+        move |index| {
+            // each page is only built once, as the pages don't change
+            let page = pages.borrow_mut()[index]
+                .take()
+                .unwrap_or_else(|| Box::new(empty()));
+            container_box(page).style(move |s| {
+                let len = len as f64;
+                let mut offset = index as f64 - motion.position.get();
+                if looping.get() {
+                    offset = (offset + len / 2.0).rem_euclid(len) - len / 2.0;
+                }
+                s.absolute()
+                    .inset_left(offset * width.get())
+                    .inset_top(0.0)
+                    .width(width.get())
+                    .height_full()
+            })
+        },
+    )
+    .style(|s| s.size_full());
+
+    let viewport = clip(slides);
+    let viewport_id = viewport.id();
+    let viewport = viewport
+        .on_resize(move |rect| width.set(rect.width()))
+        .style(|s| s.width_full().flex_grow(1.0).min_height(0.0))
+        .on_event(EventListener::PointerDown, move |event| {
+            if let Event::PointerDown(event) = event {
+                if event.button.is_primary() {
+                    motion.stop();
+                    drag.set(Some((event.pos.x, motion.position.get_untracked())));
+                    return false;
+                }
+            }
+            false
+        })
+        .on_event(EventListener::PointerMove, move |event| {
+            if let (Event::PointerMove(event), Some((start, from))) = (event, drag.get_untracked())
+            {
+                if (event.pos.x - start).abs() > DRAG_THRESHOLD {
+                    viewport_id.request_active();
+                }
+                let width = width.get_untracked();
+                if width > 0.0 {
+                    let mut position = from - (event.pos.x - start) / width;
+                    if !looping.get_untracked() {
+                        position = position.clamp(0.0, len.saturating_sub(1) as f64);
+                    }
+                    motion.position.set(position);
+                    motion.target.set(position);
+                }
+            }
+            false
+        })
+        .on_event(EventListener::PointerUp, move |_| {
+            if let Some((_, from)) = drag.get_untracked() {
+                drag.set(None);
+                let from = from.round();
+                let moved = motion.position.get_untracked() - from;
+                let page = if moved > SWIPE_THRESHOLD {
+                    from + 1.0
+                } else if moved < -SWIPE_THRESHOLD {
+                    from - 1.0
+                } else {
+                    from
+                };
+                go_to(page as isize);
+            }
+            false
+        });
+
+    let indicator = list(
+        move || 0..len,
+        |index| *index,
+        move |index| {
+            empty()
+                .style(move |s| {
+                    let colors = tokens().colors;
+                    let active = active_index.get() == index;
+                    s.size(DOT_SIZE, DOT_SIZE)
+                        .margin_horiz(DOT_SIZE / 2.0)
+                        .border_radius(DOT_SIZE / 2.0)
+                        .background(if active {
+                            colors.primary
+                        } else {
+                            colors.border
+                        })
+                        .cursor(CursorStyle::Pointer)
+                })
+                .on_click(move |_| {
+                    active_index.set(index);
+                    true
+                })
+        },
+    )
+    .style(move |s| {
+        s.justify_center()
+            .width_full()
+            .padding_top(tokens().spacing.sm)
+            .apply_if(!dots.get(), |s| s.hide())
+    });
+
+    let child = stack((viewport, indicator))
+        .style(|s| s.flex_col().size_full())
+        .keyboard_navigatable()
+        .on_event(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                let current = active_index.get_untracked() as isize;
+                match key.logical_key {
+                    Key::ArrowRight => go_to(current + 1),
+                    Key::ArrowLeft => go_to(current - 1),
+                    Key::Home => go_to(0),
+                    Key::End => go_to(len as isize - 1),
+                    _ => return false,
+                }
+                return true;
+            }
+            false
+        });
+
+    composite(
+        "Carousel",
+        child,
+        CarouselState {
+            active_index,
+            looping,
+            dots,
+        },
+    )
+}
+
+impl Carousel {
+    /// The index of the page that's shown, which slides to another page when it's set
+    pub fn active_index(&self) -> RwSignal<usize> {
+        self.state().active_index
+    }
+
+    /// Goes from the last page to the first one and back, instead of stopping at them
+    pub fn looping(self, looping: bool) -> Self {
+        self.state().looping.set(looping);
+        self
+    }
+
+    /// Whether the dots showing the pages are shown below them, which they are by default
+    pub fn dots(self, dots: bool) -> Self {
+        self.state().dots.set(dots);
+        self
+    }
+}
@@ -0,0 +1,568 @@
+//! Line, bar and pie charts of reactive data.
+//!
+//! Each chart gets its data from a closure, and is drawn again whenever a signal read by
+//! the closure changes. The charts fill the size their style gives them, and hovering a
+//! point, bar or slice shows its value.
+//! ```rust
+//! # use floem::{reactive::RwSignal, view::View, views::{chart::bar_chart, Decorators}};
+//! fn sales(months: RwSignal<Vec<(String, f64)>>) -> impl View {
+//!     bar_chart(move || months.get()).style(|s| s.size(400.0, 240.0))
+//! }
+//! ```
+
+use std::f64::consts::PI;
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal, SignalTracker};
+use floem_renderer::{
+    cosmic_text::{Attrs, AttrsList, TextLayout},
+    Renderer,
+};
+use kurbo::{Arc, BezPath, Circle, Line, Point, Rect, Size, Vec2};
+use peniko::Color;
+use taffy::prelude::Node;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::{Event, EventListener},
+    id::Id,
+    theme::{tokens, Tokens},
+    view::{ChangeFlags, View},
+    views::Decorators,
+};
+
+/// The space between the labels of the axes and the plot
+const LABEL_GAP: f64 = 6.0;
+/// How close the pointer has to be to a point of a line chart to hover it
+const HOVER_DISTANCE: f64 = 16.0;
+const DEFAULT_TICKS: usize = 5;
+
+enum ChartData {
+    Line(Vec<(f64, f64)>),
+    Bar(Vec<(String, f64)>),
+    Pie(Vec<(String, f64)>),
+}
+
+/// Where each value was drawn, to find the one under the pointer
+enum Target {
+    Point(Point),
+    Bar(Rect),
+    /// The angles of a slice, clockwise from the top
+    Slice {
+        start: f64,
+        sweep: f64,
+    },
+}
+
+/// A chart of some data. See [`line_chart`], [`bar_chart`] and [`pie_chart`]
+pub struct Chart {
+    id: Id,
+    data_fn: Box<dyn Fn() -> ChartData>,
+    tracker: SignalTracker,
+    colors: Option<Vec<Color>>,
+    ticks: usize,
+    size: Size,
+    hovered: RwSignal<Option<usize>>,
+    targets: Vec<Target>,
+    pie: Circle,
+}
+
+fn new_chart(data_fn: impl Fn() -> ChartData + 'static) -> Chart {
+    let id = Id::next();
+    let hovered = create_rw_signal(None);
+    create_effect(move |_| {
+        hovered.with(|_| ());
+        id.request_paint();
+    });
+    Chart {
+        id,
+        data_fn: Box::new(data_fn),
+        tracker: SignalTracker::new(move || id.request_paint()),
+        colors: None,
+        ticks: DEFAULT_TICKS,
+        size: Size::ZERO,
+        hovered,
+        targets: Vec::new(),
+        pie: Circle::new(Point::ZERO, 0.0),
+    }
+    .base_style(|s| s.size(300.0, 200.0))
+    .on_event(EventListener::PointerLeave, move |_| {
+        hovered.set(None);
+        false
+    })
+}
+
+/// A line through the `(x, y)` points, in the order they're given, with the x axis at the
+/// bottom and the y axis on the left
+pub fn line_chart(points: impl Fn() -> Vec<(f64, f64)> + 'static) -> Chart {
+    new_chart(move || ChartData::Line(points()))
+}
+
+/// A bar for each `(label, value)`, from left to right, with the labels below the bars.
+/// Negative values go down from zero.
+pub fn bar_chart(bars: impl Fn() -> Vec<(String, f64)> + 'static) -> Chart {
+    new_chart(move || ChartData::Bar(bars()))
+}
+
+/// A circle split into a slice for each `(label, value)`, by their part of the total,
+/// clockwise from the top. Values below zero are left out.
+pub fn pie_chart(slices: impl Fn() -> Vec<(String, f64)> + 'static) -> Chart {
+    new_chart(move || ChartData::Pie(slices()))
+}
+
+impl Chart {
+    /// The colors of the line, of the bars, or of the slices in turn. The defaults are the
+    /// primary color followed by the status colors of the [tokens](crate::theme::tokens).
+    pub fn colors(mut self, colors: impl Into<Vec<Color>>) -> Self {
+        let colors = colors.into();
+        self.colors = if colors.is_empty() {
+            None
+        } else {
+            Some(colors)
+        };
+        self
+    }
+
+    /// About how many values are marked along each axis. The marks are put on round numbers,
+    /// so there can be a few more or less.
+    pub fn ticks(mut self, ticks: usize) -> Self {
+        self.ticks = ticks.max(1);
+        self
+    }
+
+    fn color(&self, tokens: &Tokens, index: usize) -> Color {
+        let colors = &tokens.colors;
+        let defaults = [
+            colors.primary,
+            colors.success,
+            colors.warning,
+            colors.danger,
+            colors.info,
+        ];
+        match self.colors.as_ref() {
+            Some(colors) => colors[index % colors.len()],
+            None => defaults[index % defaults.len()],
+        }
+    }
+
+    fn hit(&self, pos: Point) -> Option<usize> {
+        let offset = pos - self.pie.center;
+        // clockwise from the top, like the slices
+        let angle = (offset.y.atan2(offset.x) + PI / 2.0).rem_euclid(2.0 * PI);
+        let mut nearest = None;
+        for (index, target) in self.targets.iter().enumerate() {
+            match target {
+                Target::Point(point) => {
+                    let distance = point.distance(pos);
+                    if distance <= HOVER_DISTANCE
+                        && nearest
+                            .map(|(_, nearest)| distance < nearest)
+                            .unwrap_or(true)
+                    {
+                        nearest = Some((index, distance));
+                    }
+                }
+                Target::Bar(rect) => {
+                    if rect.contains(pos) {
+                        return Some(index);
+                    }
+                }
+                Target::Slice { start, sweep } => {
+                    if offset.hypot() <= self.pie.radius && angle >= *start && angle < start + sweep
+                    {
+                        return Some(index);
+                    }
+                }
+            }
+        }
+        nearest.map(|(index, _)| index)
+    }
+
+    /// Draws the two axes with their ticks and returns the area inside of them
+    fn paint_axes(
+        &self,
+        cx: &mut PaintCx,
+        tokens: &Tokens,
+        y_ticks: &[f64],
+        x_labels: &[(f64, String)],
+    ) -> Rect {
+        let y_labels: Vec<TextLayout> = y_ticks
+            .iter()
+            .map(|tick| text_layout(&format_value(*tick), tokens.colors.text_muted))
+            .collect();
+        let x_labels: Vec<(f64, TextLayout)> = x_labels
+            .iter()
+            .map(|(at, label)| (*at, text_layout(label, tokens.colors.text_muted)))
+            .collect();
+        let label_width = y_labels
+            .iter()
+            .map(|label| label.size().width)
+            .fold(0.0, f64::max);
+        let label_height = text_layout("0", tokens.colors.text).size().height;
+        let plot = Rect::new(
+            label_width + LABEL_GAP,
+            label_height / 2.0,
+            self.size.width - LABEL_GAP,
+            self.size.height - label_height - LABEL_GAP,
+        );
+        if plot.width() <= 0.0 || plot.height() <= 0.0 {
+            return plot;
+        }
+
+        let (first, last) = (y_ticks[0], y_ticks[y_ticks.len() - 1]);
+        for (tick, label) in y_ticks.iter().zip(y_labels.iter()) {
+            let y = plot.y1 - (tick - first) / (last - first) * plot.height();
+            cx.stroke(
+                &Line::new((plot.x0, y), (plot.x1, y)),
+                tokens.colors.border,
+                1.0,
+            );
+            let size = label.size();
+            cx.draw_text(
+                label,
+                (plot.x0 - LABEL_GAP - size.width, y - size.height / 2.0),
+            );
+        }
+        for (at, label) in &x_labels {
+            let x = plot.x0 + at * plot.width();
+            let size = label.size();
+            cx.draw_text(label, (x - size.width / 2.0, plot.y1 + LABEL_GAP));
+        }
+        cx.stroke(
+            &Line::new((plot.x0, plot.y0), (plot.x0, plot.y1)),
+            tokens.colors.text_muted,
+            1.0,
+        );
+        plot
+    }
+
+    fn paint_line(
+        &self,
+        cx: &mut PaintCx,
+        tokens: &Tokens,
+        points: &[(f64, f64)],
+        hovered: Option<usize>,
+    ) -> Vec<Target> {
+        let points: Vec<(f64, f64)> = points
+            .iter()
+            .copied()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+        if points.is_empty() {
+            return Vec::new();
+        }
+        let (x_min, x_max) = min_max(points.iter().map(|(x, _)| *x));
+        let (y_min, y_max) = min_max(points.iter().map(|(_, y)| *y));
+        let x_ticks = nice_ticks(x_min, x_max, self.ticks);
+        let y_ticks = nice_ticks(y_min, y_max, self.ticks);
+        let (x_first, x_last) = (x_ticks[0], x_ticks[x_ticks.len() - 1]);
+        let (y_first, y_last) = (y_ticks[0], y_ticks[y_ticks.len() - 1]);
+        let x_labels: Vec<(f64, String)> = x_ticks
+            .iter()
+            .map(|tick| ((tick - x_first) / (x_last - x_first), format_value(*tick)))
+            .collect();
+        let plot = self.paint_axes(cx, tokens, &y_ticks, &x_labels);
+
+        let targets: Vec<Target> = points
+            .iter()
+            .map(|(x, y)| {
+                Target::Point(Point::new(
+                    plot.x0 + (x - x_first) / (x_last - x_first) * plot.width(),
+                    plot.y1 - (y - y_first) / (y_last - y_first) * plot.height(),
+                ))
+            })
+            .collect();
+        let color = self.color(tokens, 0);
+        let mut path = BezPath::new();
+        for (index, target) in targets.iter().enumerate() {
+            if let Target::Point(point) = target {
+                if index == 0 {
+                    path.move_to(*point);
+                } else {
+                    path.line_to(*point);
+                }
+            }
+        }
+        cx.stroke(&path, color, 2.0);
+        for (index, target) in targets.iter().enumerate() {
+            if let Target::Point(point) = target {
+                let radius = if hovered == Some(index) { 5.0 } else { 3.0 };
+                cx.fill(&Circle::new(*point, radius), color, 0.0);
+            }
+        }
+
+        if let Some((Target::Point(point), (x, y))) =
+            hovered.and_then(|index| targets.get(index).zip(points.get(index)))
+        {
+            let text = format!("{}, {}", format_value(*x), format_value(*y));
+            paint_tooltip(cx, tokens, *point, &text, self.size);
+        }
+        targets
+    }
+
+    fn paint_bars(
+        &self,
+        cx: &mut PaintCx,
+        tokens: &Tokens,
+        bars: &[(String, f64)],
+        hovered: Option<usize>,
+    ) -> Vec<Target> {
+        if bars.is_empty() {
+            return Vec::new();
+        }
+        let values = bars
+            .iter()
+            .map(|(_, value)| if value.is_finite() { *value } else { 0.0 });
+        let (min, max) = min_max(values.chain([0.0]));
+        let y_ticks = nice_ticks(min, max, self.ticks);
+        let (first, last) = (y_ticks[0], y_ticks[y_ticks.len() - 1]);
+        let slot = 1.0 / bars.len() as f64;
+        let x_labels: Vec<(f64, String)> = bars
+            .iter()
+            .enumerate()
+            .map(|(index, (label, _))| ((index as f64 + 0.5) * slot, label.clone()))
+            .collect();
+        let plot = self.paint_axes(cx, tokens, &y_ticks, &x_labels);
+
+        let y = |value: f64| plot.y1 - (value - first) / (last - first) * plot.height();
+        let width = plot.width() * slot;
+        let targets: Vec<Target> = bars
+            .iter()
+            .enumerate()
+            .map(|(index, (_, value))| {
+                let value = if value.is_finite() { *value } else { 0.0 };
+                let x = plot.x0 + index as f64 * width;
+                Target::Bar(Rect::new(
+                    x + width * 0.15,
+                    y(value.max(0.0)),
+                    x + width * 0.85,
+                    y(value.min(0.0)),
+                ))
+            })
+            .collect();
+        for (index, target) in targets.iter().enumerate() {
+            if let Target::Bar(rect) = target {
+                let color = self.color(tokens, 0);
+                let color = if hovered == Some(index) {
+                    color.with_alpha_factor(0.8)
+                } else {
+                    color
+                };
+                cx.fill(rect, color, 0.0);
+            }
+        }
+
+        if let Some((Target::Bar(rect), (label, value))) =
+            hovered.and_then(|index| targets.get(index).zip(bars.get(index)))
+        {
+            let text = format!("{label}: {}", format_value(*value));
+            paint_tooltip(
+                cx,
+                tokens,
+                Point::new(rect.center().x, rect.y0),
+                &text,
+                self.size,
+            );
+        }
+        targets
+    }
+
+    fn paint_pie(
+        &mut self,
+        cx: &mut PaintCx,
+        tokens: &Tokens,
+        slices: &[(String, f64)],
+        hovered: Option<usize>,
+    ) -> Vec<Target> {
+        let values: Vec<f64> = slices
+            .iter()
+            .map(|(_, value)| {
+                if value.is_finite() {
+                    value.max(0.0)
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let total: f64 = values.iter().sum();
+        self.pie = Circle::new(
+            self.size.to_rect().center(),
+            (self.size.width.min(self.size.height) / 2.0 - LABEL_GAP).max(0.0),
+        );
+        if total <= 0.0 || self.pie.radius <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut start = 0.0;
+        let mut targets = Vec::new();
+        for (index, value) in values.iter().enumerate() {
+            let sweep = value / total * 2.0 * PI;
+            // the hovered slice stands out from the rest
+            let radius = if hovered == Some(index) {
+                self.pie.radius + LABEL_GAP / 2.0
+            } else {
+                self.pie.radius
+            };
+            if sweep > 0.0 {
+                let center = self.pie.center;
+                let start_angle = start - PI / 2.0;
+                let arc = Arc {
+                    center,
+                    radii: Vec2::new(radius, radius),
+                    start_angle,
+                    sweep_angle: sweep,
+                    x_rotation: 0.0,
+                };
+                let mut path = BezPath::new();
+                path.move_to(center);
+                path.line_to(center + Vec2::from_angle(start_angle) * radius);
+                path.extend(arc.append_iter(0.1));
+                path.close_path();
+                cx.fill(&path, self.color(tokens, index), 0.0);
+            }
+            targets.push(Target::Slice { start, sweep });
+            start += sweep;
+        }
+
+        if let Some((index, Target::Slice { start, sweep })) =
+            hovered.and_then(|index| Some(index).zip(targets.get(index)))
+        {
+            let (label, value) = (&slices[index].0, values[index]);
+            let text = format!(
+                "{label}: {} ({:.0}%)",
+                format_value(value),
+                value / total * 100.0
+            );
+            let angle = start + sweep / 2.0 - PI / 2.0;
+            let anchor = self.pie.center + Vec2::from_angle(angle) * (self.pie.radius / 2.0);
+            paint_tooltip(cx, tokens, anchor, &text, self.size);
+        }
+        targets
+    }
+}
+
+fn text_layout(text: &str, color: Color) -> TextLayout {
+    let mut layout = TextLayout::new();
+    layout.set_text(
+        text,
+        AttrsList::new(
+            Attrs::new()
+                .color(color)
+                .font_size(tokens().typography.caption.font_size),
+        ),
+    );
+    layout
+}
+
+/// A box with `text` above and to the right of `anchor`, kept inside of the chart
+fn paint_tooltip(cx: &mut PaintCx, tokens: &Tokens, anchor: Point, text: &str, bounds: Size) {
+    let layout = text_layout(text, tokens.colors.text);
+    let size = layout.size();
+    let padding = tokens.spacing.sm as f64;
+    let width = size.width + padding * 2.0;
+    let height = size.height + padding * 2.0;
+    let x = (anchor.x + LABEL_GAP).min(bounds.width - width).max(0.0);
+    let y = (anchor.y - LABEL_GAP - height).max(0.0);
+    let rect = Rect::new(x, y, x + width, y + height).to_rounded_rect(tokens.radius.sm as f64);
+    cx.fill(&rect, tokens.colors.surface, 0.0);
+    cx.stroke(&rect, tokens.colors.border, 1.0);
+    cx.draw_text(&layout, (x + padding, y + padding));
+}
+
+fn min_max(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
+
+/// Round numbers from at most `min` to at least `max`, about `count` steps apart
+fn nice_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    let (min, max) = if max > min {
+        (min, max)
+    } else {
+        (min - 1.0, min + 1.0)
+    };
+    let raw_step = (max - min) / count.max(1) as f64;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(10.0 * magnitude);
+    let first = (min / step).floor() * step;
+    let last = (max / step).ceil() * step;
+    let steps = ((last - first) / step).round() as usize;
+    (0..=steps)
+        .map(|index| first + index as f64 * step)
+        .collect()
+}
+
+fn format_value(value: f64) -> String {
+    let text = format!("{value:.2}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+impl View for Chart {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Chart".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        None
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, event: Event) -> bool {
+        if let Event::PointerMove(event) = &event {
+            let hovered = self.hit(event.pos);
+            if hovered != self.hovered.get_untracked() {
+                self.hovered.set(hovered);
+            }
+        }
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        // the tokens are read along with the data, so the chart follows changes to both
+        let (tokens, data) = self.tracker.track(|| (tokens(), (self.data_fn)()));
+        let hovered = self.hovered.get_untracked();
+        self.targets = match &data {
+            ChartData::Line(points) => self.paint_line(cx, &tokens, points, hovered),
+            ChartData::Bar(bars) => self.paint_bars(cx, &tokens, bars, hovered),
+            ChartData::Pie(slices) => self.paint_pie(cx, &tokens, slices, hovered),
+        };
+    }
+}
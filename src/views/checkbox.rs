@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+use crate::{
+    theme::tokens,
+    views::{self, label, stack, Decorators, Label, Stack, Svg},
+};
+
+const CHECKBOX_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 16 16"><polygon points="5.19,11.83 0.18,7.44 1.82,5.56 4.81,8.17 10,1.25 12,2.75" /></svg>"#;
+const INDETERMINATE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 16 16"><rect x="1" y="5" width="10" height="2" /></svg>"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CheckboxState {
+    #[default]
+    Unchecked,
+    Checked,
+    /// Neither checked nor unchecked, e.g. a "select all" box when only some items are selected
+    Indeterminate,
+}
+
+impl From<bool> for CheckboxState {
+    fn from(checked: bool) -> Self {
+        if checked {
+            Self::Checked
+        } else {
+            Self::Unchecked
+        }
+    }
+}
+
+fn checkbox_svg(state: impl Fn() -> CheckboxState + 'static) -> Svg {
+    let svg_str = move || {
+        match state() {
+            CheckboxState::Unchecked => "",
+            CheckboxState::Checked => CHECKBOX_SVG,
+            CheckboxState::Indeterminate => INDETERMINATE_SVG,
+        }
+        .to_string()
+    };
+
+    views::svg(svg_str).base_style(|s| {
+        s.width(20.)
+            .height(20.)
+            .border_color(tokens().colors.text)
+            .border(1.)
+            .border_radius(5.)
+            .margin_right(5.)
+    })
+}
+
+/// Renders a checkbox using an svg and the provided `checked` function.
+/// The check mark is drawn with the `color` style, so it can be restyled like text.
+///
+/// The checkbox doesn't toggle itself, toggle your state in `on_click`, which also fires
+/// for Space and Enter while the checkbox has keyboard focus.
+pub fn checkbox(checked: impl Fn() -> bool + 'static) -> Svg {
+    tristate_checkbox(move || checked().into())
+}
+
+/// A checkbox that can also show the indeterminate state
+pub fn tristate_checkbox(state: impl Fn() -> CheckboxState + 'static) -> Svg {
+    checkbox_svg(state).keyboard_navigatable()
+}
+
+/// A checkbox followed by a label, where the whole row is a single click target.
+/// Put `on_click` on the returned view, so clicking the label toggles the checkbox too.
+pub fn labeled_checkbox<S: Display + 'static>(
+    checked: impl Fn() -> bool + 'static,
+    label_text: impl Fn() -> S + 'static,
+) -> Stack<(Svg, Label)> {
+    labeled_tristate_checkbox(move || checked().into(), label_text)
+}
+
+/// Like `labeled_checkbox`, but can also show the indeterminate state
+pub fn labeled_tristate_checkbox<S: Display + 'static>(
+    state: impl Fn() -> CheckboxState + 'static,
+    label_text: impl Fn() -> S + 'static,
+) -> Stack<(Svg, Label)> {
+    stack((checkbox_svg(state), label(label_text)))
+        .base_style(|s| s.items_center())
+        .keyboard_navigatable()
+}
@@ -0,0 +1,744 @@
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use clipboard::{ClipboardContext, ClipboardProvider};
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use floem_renderer::{
+    cosmic_text::{Attrs, AttrsList, FamilyOwned, TextLayout},
+    Renderer,
+};
+use kurbo::{Line, Point, Rect};
+use peniko::Color;
+use taffy::prelude::Node;
+use unicode_segmentation::UnicodeSegmentation;
+use winit::keyboard::{Key, ModifiersState};
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    keyboard::KeyEvent,
+    style::{ComputedStyle, CursorStyle, Style},
+    theme::tokens,
+    view::{ChangeFlags, View},
+    views::{composite, scroll, Composite, Decorators},
+};
+
+const DEFAULT_FONT_SIZE: f32 = 14.0;
+const DEFAULT_TAB_WIDTH: usize = 4;
+/// The space between the gutter and the text, and after the longest line
+const TEXT_PADDING: f64 = 6.0;
+const MONOSPACE: &[FamilyOwned] = &[FamilyOwned::Monospace];
+
+type HighlightFn = Rc<RefCell<Option<Box<dyn Fn(&str) -> Vec<Highlight>>>>>;
+
+/// A color for a range of the text of a [`code_editor`], from its highlighter
+#[derive(Clone, Debug, PartialEq)]
+pub struct Highlight {
+    /// The bytes of the text the color is for, which can span lines
+    pub range: Range<usize>,
+    pub color: Color,
+}
+
+/// A multi-line editor for source code. See [`code_editor`]
+pub type CodeEditor = Composite<CodeEditorState>;
+
+pub struct CodeEditorState {
+    highlighter: HighlightFn,
+    line_numbers: RwSignal<bool>,
+    tab_width: RwSignal<usize>,
+    content_id: Id,
+}
+
+/// An editor for the text in `buffer`, in a monospaced font, with the line numbers in a
+/// gutter on the left. It scrolls both ways, and only the lines that are scrolled into view
+/// are laid out and painted, so it keeps up with long files.
+///
+/// It does the basic editing: typing, Enter keeping the indentation of the line, Tab
+/// inserting spaces up to the next tab stop, deleting, selecting with the pointer or with
+/// Shift and the arrow keys, and cut, copy and paste. The text is colored by a
+/// [highlighter](CodeEditor::highlight), which can be built on syntect or tree-sitter.
+pub fn code_editor(buffer: RwSignal<String>) -> CodeEditor {
+    let content_id = Id::next();
+    let highlighter: HighlightFn = Rc::new(RefCell::new(None));
+    let line_numbers = create_rw_signal(true);
+    let tab_width = create_rw_signal(DEFAULT_TAB_WIDTH);
+    let cursor_rect = create_rw_signal(Rect::ZERO);
+
+    create_effect(move |_| {
+        let text = buffer.get();
+        content_id.update_state(text, false);
+    });
+    create_effect(move |_| {
+        line_numbers.track();
+        content_id.update_state(EditorState::Relayout, false);
+    });
+
+    let content = EditorContent {
+        id: content_id,
+        buffer,
+        text: String::new(),
+        lines: vec![0],
+        max_columns: 0,
+        line_highlights: Vec::new(),
+        highlighter: highlighter.clone(),
+        line_numbers,
+        tab_width,
+        cursor: 0,
+        anchor: 0,
+        column: None,
+        selecting: false,
+        cursor_rect,
+        font_size: DEFAULT_FONT_SIZE,
+        char_width: 0.0,
+        line_height: 0.0,
+        content_node: None,
+    }
+    .base_style(|s| s.min_size_full().cursor(CursorStyle::Text))
+    .keyboard_navigatable();
+
+    let child = scroll(content)
+        .on_ensure_visible(move || cursor_rect.get())
+        .style(|s| s.size_full());
+
+    composite(
+        "CodeEditor",
+        child,
+        CodeEditorState {
+            highlighter,
+            line_numbers,
+            tab_width,
+            content_id,
+        },
+    )
+    .base_style(|s| {
+        let tokens = tokens();
+        s.size(480.0, 320.0)
+            .background(tokens.colors.surface)
+            .color(tokens.colors.text)
+            .border(1.0)
+            .border_color(tokens.colors.border)
+            .border_radius(tokens.radius.md)
+    })
+}
+
+impl CodeEditor {
+    /// Colors the text with the [`Highlight`]s that `highlighter` returns for it, which is
+    /// called with the whole text each time it changes. The rest of the text has the color
+    /// of the editor.
+    pub fn highlight(self, highlighter: impl Fn(&str) -> Vec<Highlight> + 'static) -> Self {
+        *self.state().highlighter.borrow_mut() = Some(Box::new(highlighter));
+        self.state()
+            .content_id
+            .update_state(EditorState::Rehighlight, false);
+        self
+    }
+
+    /// Whether the gutter shows the line numbers, which it does by default
+    pub fn line_numbers(self, line_numbers: bool) -> Self {
+        self.state().line_numbers.set(line_numbers);
+        self
+    }
+
+    /// How many columns apart the tab stops are, 4 by default
+    pub fn tab_width(self, tab_width: usize) -> Self {
+        self.state().tab_width.set(tab_width.max(1));
+        self
+    }
+}
+
+enum EditorState {
+    Relayout,
+    Rehighlight,
+}
+
+/// The lines of the editor, inside of its scroll
+struct EditorContent {
+    id: Id,
+    buffer: RwSignal<String>,
+    text: String,
+    /// Where each line starts in the text
+    lines: Vec<usize>,
+    /// The number of characters in the longest line
+    max_columns: usize,
+    /// The highlights of each line, with their ranges in the line
+    line_highlights: Vec<Vec<(Range<usize>, Color)>>,
+    highlighter: HighlightFn,
+    line_numbers: RwSignal<bool>,
+    tab_width: RwSignal<usize>,
+    cursor: usize,
+    /// Where the selection started, which is the cursor when nothing is selected
+    anchor: usize,
+    /// The column to go back to when moving up and down through shorter lines
+    column: Option<usize>,
+    selecting: bool,
+    cursor_rect: RwSignal<Rect>,
+    font_size: f32,
+    char_width: f64,
+    line_height: f64,
+    content_node: Option<Node>,
+}
+
+impl EditorContent {
+    fn attrs(&self, color: Color) -> Attrs<'static> {
+        Attrs::new()
+            .color(color)
+            .family(MONOSPACE)
+            .font_size(self.font_size)
+    }
+
+    fn measure(&mut self) {
+        let mut layout = TextLayout::new();
+        layout.set_text("0", AttrsList::new(self.attrs(Color::BLACK)));
+        self.char_width = layout.size().width;
+        self.line_height = (self.font_size as f64 * 1.5).round();
+    }
+
+    fn text_changed(&mut self) {
+        self.lines = std::iter::once(0)
+            .chain(self.text.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        self.max_columns = (0..self.lines.len())
+            .map(|line| self.text[self.line_range(line)].chars().count())
+            .max()
+            .unwrap_or(0);
+        self.cursor = self.clamp(self.cursor);
+        self.anchor = self.clamp(self.anchor);
+        self.highlight();
+    }
+
+    fn highlight(&mut self) {
+        let mut line_highlights = vec![Vec::new(); self.lines.len()];
+        if let Some(highlighter) = self.highlighter.borrow().as_ref() {
+            for highlight in highlighter(&self.text) {
+                let range = highlight.range.start.min(self.text.len())
+                    ..highlight.range.end.min(self.text.len());
+                if range.is_empty() {
+                    continue;
+                }
+                for line in self.line_of(range.start)..=self.line_of(range.end - 1) {
+                    let line_range = self.line_range(line);
+                    let start = range.start.max(line_range.start);
+                    let end = range.end.min(line_range.end);
+                    if start < end {
+                        line_highlights[line].push((
+                            start - line_range.start..end - line_range.start,
+                            highlight.color,
+                        ));
+                    }
+                }
+            }
+        }
+        self.line_highlights = line_highlights;
+    }
+
+    /// The nearest character boundary at or before `offset`
+    fn clamp(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    fn line_of(&self, offset: usize) -> usize {
+        self.lines.partition_point(|start| *start <= offset) - 1
+    }
+
+    /// The bytes of a line, without its newline
+    fn line_range(&self, line: usize) -> Range<usize> {
+        let start = self.lines[line];
+        let end = self
+            .lines
+            .get(line + 1)
+            .map(|next| next - 1)
+            .unwrap_or(self.text.len());
+        start..end
+    }
+
+    fn column_of(&self, offset: usize) -> usize {
+        let start = self.lines[self.line_of(offset)];
+        self.text[start..offset].chars().count()
+    }
+
+    fn offset_at(&self, line: usize, column: usize) -> usize {
+        let range = self.line_range(line);
+        self.text[range.clone()]
+            .char_indices()
+            .nth(column)
+            .map(|(index, _)| range.start + index)
+            .unwrap_or(range.end)
+    }
+
+    fn gutter_width(&self) -> f64 {
+        if self.line_numbers.get_untracked() {
+            let digits = self.lines.len().to_string().len().max(2);
+            (digits + 2) as f64 * self.char_width
+        } else {
+            self.char_width
+        }
+    }
+
+    fn text_x(&self) -> f64 {
+        self.gutter_width() + TEXT_PADDING
+    }
+
+    fn offset_at_point(&self, point: Point) -> usize {
+        let line =
+            ((point.y / self.line_height).floor().max(0.0) as usize).min(self.lines.len() - 1);
+        let column = ((point.x - self.text_x()) / self.char_width)
+            .round()
+            .max(0.0) as usize;
+        self.offset_at(line, column)
+    }
+
+    fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    fn move_to(&mut self, offset: usize, extend: bool) {
+        self.cursor = offset;
+        if !extend {
+            self.anchor = offset;
+        }
+    }
+
+    fn replace(&mut self, range: Range<usize>, text: &str) {
+        self.text.replace_range(range.clone(), text);
+        self.cursor = range.start + text.len();
+        self.anchor = self.cursor;
+        self.column = None;
+        self.text_changed();
+        self.buffer.set(self.text.clone());
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.replace(self.selection(), text);
+    }
+
+    fn update_cursor_rect(&self) {
+        let line = self.line_of(self.cursor);
+        let x = self.text_x() + self.column_of(self.cursor) as f64 * self.char_width;
+        let y = line as f64 * self.line_height;
+        // the gutter is drawn over the left of the text, so the caret has to be right of it
+        self.cursor_rect.set(Rect::new(
+            x - self.text_x(),
+            y,
+            x + self.char_width,
+            y + self.line_height,
+        ));
+    }
+
+    fn copy(&self) {
+        let selection = self.selection();
+        if !selection.is_empty() {
+            let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
+            let _ = ctx.set_contents(self.text[selection].to_string());
+        }
+    }
+
+    fn handle_command(&mut self, event: &KeyEvent, ch: &str) -> bool {
+        #[cfg(target_os = "macos")]
+        let command = event.modifiers == ModifiersState::SUPER;
+        #[cfg(not(target_os = "macos"))]
+        let command = event.modifiers == ModifiersState::CONTROL;
+        if !command {
+            return false;
+        }
+        match ch {
+            "a" => {
+                self.anchor = 0;
+                self.cursor = self.text.len();
+            }
+            "c" => self.copy(),
+            "x" => {
+                self.copy();
+                self.insert("");
+            }
+            "v" => {
+                let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
+                if let Ok(content) = ctx.get_contents() {
+                    self.insert(&content.replace("\r\n", "\n"));
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_key_down(&mut self, cx: &mut EventCx, event: &KeyEvent) -> bool {
+        let shift = event.modifiers.contains(ModifiersState::SHIFT);
+        let word = event.modifiers.contains(ModifiersState::CONTROL)
+            || event.modifiers.contains(ModifiersState::ALT);
+        let line = self.line_of(self.cursor);
+        match event.key.logical_key {
+            Key::Character(ref ch) => {
+                if !self.handle_command(event, ch) {
+                    self.insert(ch);
+                }
+            }
+            Key::Space => self.insert(" "),
+            Key::Enter => {
+                let range = self.line_range(line);
+                let indent: String = self.text[range]
+                    .chars()
+                    .take_while(|c| *c == ' ' || *c == '\t')
+                    .collect();
+                self.insert(&format!("\n{indent}"));
+            }
+            Key::Tab => {
+                let tab_width = self.tab_width.get_untracked();
+                let column = self.column_of(self.selection().start);
+                self.insert(&" ".repeat(tab_width - column % tab_width));
+            }
+            Key::Backspace => {
+                if self.selection().is_empty() {
+                    let start = prev_boundary(&self.text, self.cursor, word);
+                    self.replace(start..self.cursor, "");
+                } else {
+                    self.insert("");
+                }
+            }
+            Key::Delete => {
+                if self.selection().is_empty() {
+                    let end = next_boundary(&self.text, self.cursor, word);
+                    self.replace(self.cursor..end, "");
+                } else {
+                    self.insert("");
+                }
+            }
+            Key::ArrowLeft => {
+                let selection = self.selection();
+                let offset = if !shift && !selection.is_empty() {
+                    selection.start
+                } else {
+                    prev_boundary(&self.text, self.cursor, word)
+                };
+                self.move_to(offset, shift);
+                self.column = None;
+            }
+            Key::ArrowRight => {
+                let selection = self.selection();
+                let offset = if !shift && !selection.is_empty() {
+                    selection.end
+                } else {
+                    next_boundary(&self.text, self.cursor, word)
+                };
+                self.move_to(offset, shift);
+                self.column = None;
+            }
+            Key::ArrowUp | Key::ArrowDown | Key::PageUp | Key::PageDown => {
+                let page = cx
+                    .app_state
+                    .view_states
+                    .get(&self.id)
+                    .and_then(|state| state.viewport)
+                    .map(|viewport| (viewport.height() / self.line_height) as usize)
+                    .unwrap_or(1)
+                    .max(1);
+                let target = match event.key.logical_key {
+                    Key::ArrowUp => line.checked_sub(1),
+                    Key::ArrowDown => Some(line + 1).filter(|line| *line < self.lines.len()),
+                    Key::PageUp => Some(line.saturating_sub(page)),
+                    _ => Some((line + page).min(self.lines.len() - 1)),
+                };
+                let column = self.column.unwrap_or_else(|| self.column_of(self.cursor));
+                let offset = match target {
+                    Some(target) => self.offset_at(target, column),
+                    None if line == 0 => 0,
+                    None => self.text.len(),
+                };
+                self.move_to(offset, shift);
+                self.column = Some(column);
+            }
+            Key::Home => {
+                let offset = if word { 0 } else { self.line_range(line).start };
+                self.move_to(offset, shift);
+                self.column = None;
+            }
+            Key::End => {
+                let offset = if word {
+                    self.text.len()
+                } else {
+                    self.line_range(line).end
+                };
+                self.move_to(offset, shift);
+                self.column = None;
+            }
+            Key::Escape => {
+                cx.app_state.clear_focus();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn paint_line(&self, cx: &mut PaintCx, line: usize, x: f64, y: f64, color: Color) {
+        let range = self.line_range(line);
+        let attrs = self.attrs(color);
+        let mut attrs_list = AttrsList::new(attrs);
+        for (range, color) in &self.line_highlights[line] {
+            attrs_list.add_span(range.clone(), attrs.color(*color));
+        }
+        let mut layout = TextLayout::new();
+        layout.set_text(&self.text[range], attrs_list);
+        let height = layout.size().height;
+        cx.draw_text(
+            &layout,
+            Point::new(x, y + (self.line_height - height) / 2.0),
+        );
+    }
+}
+
+/// Where the cursor goes from `offset` when moving left, by a grapheme or a word
+fn prev_boundary(text: &str, offset: usize, word: bool) -> usize {
+    if word {
+        text[..offset]
+            .split_word_bound_indices()
+            .rev()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map(|(index, _)| index)
+            .unwrap_or(0)
+    } else {
+        text[..offset]
+            .grapheme_indices(true)
+            .next_back()
+            .map(|(index, _)| index)
+            .unwrap_or(0)
+    }
+}
+
+/// Where the cursor goes from `offset` when moving right, by a grapheme or a word
+fn next_boundary(text: &str, offset: usize, word: bool) -> usize {
+    if word {
+        text[offset..]
+            .split_word_bound_indices()
+            .find(|(_, word)| !word.trim().is_empty())
+            .map(|(index, word)| offset + index + word.len())
+            .unwrap_or(text.len())
+    } else {
+        text[offset..]
+            .graphemes(true)
+            .next()
+            .map(|grapheme| offset + grapheme.len())
+            .unwrap_or(text.len())
+    }
+}
+
+impl View for EditorContent {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "CodeEditor content".into()
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn std::any::Any>) -> ChangeFlags {
+        let state = match state.downcast::<String>() {
+            Ok(text) => {
+                if *text != self.text {
+                    self.text = *text;
+                    self.text_changed();
+                }
+                cx.request_layout(self.id);
+                return ChangeFlags::LAYOUT;
+            }
+            Err(state) => state,
+        };
+        match state.downcast::<EditorState>().map(|state| *state) {
+            Ok(EditorState::Relayout) => {
+                cx.request_layout(self.id);
+                ChangeFlags::LAYOUT
+            }
+            Ok(EditorState::Rehighlight) => {
+                self.highlight();
+                ChangeFlags::PAINT
+            }
+            Err(_) => ChangeFlags::empty(),
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, true, |cx| {
+            let font_size = cx.current_font_size().unwrap_or(DEFAULT_FONT_SIZE);
+            if font_size != self.font_size || self.char_width == 0.0 {
+                self.font_size = font_size;
+                self.measure();
+            }
+            if self.content_node.is_none() {
+                self.content_node = Some(
+                    cx.app_state_mut()
+                        .taffy
+                        .new_leaf(taffy::style::Style::DEFAULT)
+                        .unwrap(),
+                );
+            }
+            let content_node = self.content_node.unwrap();
+            let width = self.text_x() + self.max_columns as f64 * self.char_width + TEXT_PADDING;
+            let height = self.lines.len() as f64 * self.line_height;
+            let style = Style::BASE
+                .width(width)
+                .height(height)
+                .compute(&ComputedStyle::default())
+                .to_taffy_style();
+            let _ = cx.app_state_mut().taffy.set_style(content_node, style);
+            vec![content_node]
+        })
+    }
+
+    fn event(&mut self, cx: &mut EventCx, _id_path: Option<&[Id]>, event: Event) -> bool {
+        let text = self.text.len();
+        let handled = match &event {
+            Event::PointerDown(event) => {
+                // the event is handled here, so the view is focused here too
+                cx.app_state.update_focus(self.id, false);
+                let offset = self.offset_at_point(event.pos);
+                if event.count == 2 {
+                    let line = self.line_range(self.line_of(offset));
+                    if let Some((index, word)) = self.text[line.clone()]
+                        .split_word_bound_indices()
+                        .find(|(index, word)| line.start + index + word.len() > offset)
+                    {
+                        self.anchor = line.start + index;
+                        self.cursor = line.start + index + word.len();
+                    }
+                } else {
+                    self.move_to(offset, event.modifiers.contains(ModifiersState::SHIFT));
+                    self.selecting = true;
+                    self.id.request_active();
+                }
+                self.column = None;
+                true
+            }
+            Event::PointerMove(event) if self.selecting => {
+                let offset = self.offset_at_point(event.pos);
+                self.move_to(offset, true);
+                true
+            }
+            Event::PointerUp(_) => {
+                self.selecting = false;
+                false
+            }
+            Event::KeyDown(event) => self.handle_key_down(cx, event),
+            _ => false,
+        };
+        if handled {
+            self.update_cursor_rect();
+            if self.text.len() != text {
+                cx.app_state.request_layout(self.id);
+            }
+            self.id.request_paint();
+        }
+        handled
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let tokens = tokens();
+        let layout = cx.get_layout(self.id).unwrap();
+        let viewport = cx
+            .app_state
+            .view_states
+            .get(&self.id)
+            .and_then(|state| state.viewport)
+            .unwrap_or_else(|| {
+                Rect::new(
+                    0.0,
+                    0.0,
+                    layout.size.width as f64,
+                    layout.size.height as f64,
+                )
+            });
+        let focused = cx.app_state.is_focused(&self.id);
+        let color = cx.color.unwrap_or(tokens.colors.text);
+        let text_x = self.text_x();
+        let gutter_width = self.gutter_width();
+        let cursor_line = self.line_of(self.cursor);
+        let selection = self.selection();
+
+        // only the lines in view are laid out
+        let first = (viewport.y0 / self.line_height).floor().max(0.0) as usize;
+        let last = ((viewport.y1 / self.line_height).ceil() as usize).min(self.lines.len());
+        for line in first..last {
+            let y = line as f64 * self.line_height;
+            let range = self.line_range(line);
+            if focused && selection.is_empty() && line == cursor_line {
+                let rect = Rect::new(viewport.x0, y, viewport.x1, y + self.line_height);
+                cx.fill(&rect, tokens.colors.hover, 0.0);
+            }
+            let start = selection.start.max(range.start);
+            // the newline of a selected line is shown as a bit more selection
+            let end = selection.end.min(range.end + 1);
+            if start < end {
+                let x0 = text_x + self.column_of(start) as f64 * self.char_width;
+                let x1 = if end > range.end {
+                    text_x + self.column_of(range.end) as f64 * self.char_width + self.char_width
+                } else {
+                    text_x + self.column_of(end) as f64 * self.char_width
+                };
+                let rect = Rect::new(x0, y, x1, y + self.line_height);
+                cx.fill(&rect, tokens.colors.selected, 0.0);
+            }
+            self.paint_line(cx, line, text_x, y, color);
+        }
+
+        if focused {
+            let x = text_x + self.column_of(self.cursor) as f64 * self.char_width;
+            let y = cursor_line as f64 * self.line_height;
+            let caret = Rect::new(x, y, x + 2.0, y + self.line_height);
+            cx.fill(&caret, color, 0.0);
+        }
+
+        // the gutter stays on the left when the text is scrolled sideways
+        let gutter = Rect::new(
+            viewport.x0,
+            viewport.y0,
+            viewport.x0 + gutter_width,
+            viewport.y1,
+        );
+        cx.fill(&gutter, tokens.colors.surface, 0.0);
+        cx.stroke(
+            &Line::new((gutter.x1, gutter.y0), (gutter.x1, gutter.y1)),
+            tokens.colors.border,
+            1.0,
+        );
+        if self.line_numbers.get_untracked() {
+            for line in first..last {
+                let number_color = if line == cursor_line {
+                    color
+                } else {
+                    tokens.colors.text_muted
+                };
+                let mut layout = TextLayout::new();
+                layout.set_text(
+                    &(line + 1).to_string(),
+                    AttrsList::new(self.attrs(number_color)),
+                );
+                let size = layout.size();
+                let y = line as f64 * self.line_height;
+                cx.draw_text(
+                    &layout,
+                    Point::new(
+                        gutter.x1 - self.char_width - size.width,
+                        y + (self.line_height - size.height) / 2.0,
+                    ),
+                );
+            }
+        }
+    }
+}
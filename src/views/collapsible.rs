@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use kurbo::Rect;
+use peniko::Color;
+
+use crate::{
+    action::exec_after,
+    animate::animation,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::CursorStyle,
+    theme::tokens,
+    view::{ChangeFlags, View},
+    views::{clip, composite, container_box, label, stack, Composite, Decorators},
+};
+
+/// How long the content takes to open or close
+const TRANSITION: Duration = Duration::from_millis(200);
+
+/// A header that shows or hides the content below it when clicked. See [`collapsible`]
+pub type Collapsible = Composite<CollapsibleState>;
+
+pub struct CollapsibleState {
+    open: RwSignal<bool>,
+}
+
+/// The `header` with an arrow in front of it, which opens and closes the `content` below it
+/// when clicked, or when Enter or Space is pressed while it has focus. The content grows
+/// and shrinks to its height as it opens and closes. It starts closed.
+pub fn collapsible(header: impl View + 'static, content: impl View + 'static) -> Collapsible {
+    let open = create_rw_signal(false);
+    let content_height = create_rw_signal(0.0);
+    // whether the content has finished closing, so it's hidden instead of being clipped away
+    // and can't be clicked or focused
+    let collapsed = create_rw_signal(true);
+    create_effect(move |_| {
+        if open.get() {
+            collapsed.set(false);
+        } else {
+            exec_after(TRANSITION, move |_| {
+                if !open.get_untracked() {
+                    collapsed.set(true);
+                }
+            });
+        }
+    });
+
+    let arrow = label(move || {
+        let arrow = if open.get() { "▾" } else { "▸" };
+        arrow.to_string()
+    })
+    .style(|s| s.width(16.0).justify_center());
+    let header = stack((arrow, header))
+        .style(|s| {
+            let tokens = tokens();
+            s.width_full()
+                .items_center()
+                .padding(tokens.spacing.sm)
+                .border(1.0)
+                .border_color(Color::TRANSPARENT)
+                .border_radius(tokens.radius.sm)
+                .cursor(CursorStyle::Pointer)
+        })
+        .hover_style(|s| s.background(tokens().colors.hover))
+        .focus_visible_style(|s| s.border_color(tokens().colors.primary))
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            open.update(|open| *open = !*open);
+            true
+        });
+
+    let content = container_box(content)
+        .on_resize(move |rect| {
+            // a hidden content is laid out with no height, which isn't the height to open to
+            if rect.height() > 0.0 {
+                content_height.set(rect.height());
+            }
+        })
+        .style(|s| s.width_full().flex_shrink(0.0));
+    let body = clip(content)
+        .style(move |s| {
+            s.width_full()
+                .items_start()
+                .apply_if(collapsed.get(), |s| s.hide())
+        })
+        .animation(
+            animation()
+                .height(move || {
+                    if open.get() {
+                        content_height.get()
+                    } else {
+                        0.0
+                    }
+                })
+                .duration(TRANSITION)
+                .ease_in_out(),
+        );
+
+    composite(
+        "Collapsible",
+        stack((header, body)).style(|s| s.flex_col().width_full()),
+        CollapsibleState { open },
+    )
+}
+
+impl Collapsible {
+    /// Opens or closes the content
+    pub fn open(self, open: bool) -> Self {
+        self.state().open.set(open);
+        self
+    }
+
+    /// Whether the content is open, which can be set to open or close it
+    pub fn open_signal(&self) -> RwSignal<bool> {
+        self.state().open
+    }
+}
+
+/// Collapsible sections of which only one is open at a time. See [`accordion`]
+pub struct Accordion {
+    id: Id,
+    sections: Vec<Collapsible>,
+}
+
+/// The `sections` one below the other, where opening one closes the one that was open
+pub fn accordion(sections: impl IntoIterator<Item = Collapsible>) -> Accordion {
+    let sections: Vec<Collapsible> = sections.into_iter().collect();
+    let opens: Vec<RwSignal<bool>> = sections
+        .iter()
+        .map(|section| section.open_signal())
+        .collect();
+    for (index, open) in opens.iter().copied().enumerate() {
+        let others = opens.clone();
+        create_effect(move |_| {
+            if open.get() {
+                for (other_index, other) in others.iter().enumerate() {
+                    if other_index != index && other.get_untracked() {
+                        other.set(false);
+                    }
+                }
+            }
+        });
+    }
+    Accordion {
+        id: Id::next(),
+        sections,
+    }
+    .base_style(|s| s.flex_col())
+}
+
+impl View for Accordion {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        self.sections
+            .iter()
+            .find(|section| section.id() == id)
+            .map(|section| section as &dyn View)
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        self.sections
+            .iter_mut()
+            .find(|section| section.id() == id)
+            .map(|section| section as &mut dyn View)
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.sections
+            .iter()
+            .map(|section| section as &dyn View)
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.sections
+            .iter_mut()
+            .map(|section| section as &mut dyn View)
+            .collect()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Accordion".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, true, |cx| {
+            self.sections
+                .iter_mut()
+                .map(|section| section.layout_main(cx))
+                .collect()
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let mut layout_rect = Rect::ZERO;
+        for section in &mut self.sections {
+            layout_rect = layout_rect.union(section.compute_layout_main(cx));
+        }
+        Some(layout_rect)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        for section in self.sections.iter_mut().rev() {
+            let id = section.id();
+            if cx.should_send(id, &event) && section.event_main(cx, id_path, event.clone()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        for section in &mut self.sections {
+            section.paint_main(cx);
+        }
+    }
+}
@@ -0,0 +1,493 @@
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use floem_renderer::Renderer;
+use kurbo::{Circle, Point, Rect, Size};
+use peniko::{Color, Gradient};
+use taffy::{prelude::Node, style::FlexWrap};
+use winit::keyboard::Key;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    theme::tokens,
+    view::{ChangeFlags, View},
+    views::{composite, empty, list, stack, text_input, Composite, Decorators},
+};
+
+const PICKER_WIDTH: f32 = 200.0;
+const STRIP_HEIGHT: f32 = 12.0;
+const SWATCH_SIZE: f32 = 20.0;
+/// The size of the squares of the checkerboard shown through transparent colors
+const CHECKER_SIZE: f64 = 6.0;
+
+/// A color as hue, saturation and value, which keeps its hue while the color is gray
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Hsva {
+    /// In degrees, from 0 to 360
+    h: f64,
+    s: f64,
+    v: f64,
+    a: f64,
+}
+
+impl Hsva {
+    fn from_color(color: Color) -> Self {
+        let (r, g, b) = (
+            color.r as f64 / 255.0,
+            color.g as f64 / 255.0,
+            color.b as f64 / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        Self {
+            h,
+            s,
+            v: max,
+            a: color.a as f64 / 255.0,
+        }
+    }
+
+    fn to_color(self) -> Color {
+        let c = self.v * self.s;
+        let h = (self.h / 60.0).rem_euclid(6.0);
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = self.v - c;
+        let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+        Color::rgba8(
+            channel(r),
+            channel(g),
+            channel(b),
+            (self.a * 255.0).round() as u8,
+        )
+    }
+
+    /// The fully saturated and opaque color of the hue
+    fn hue_color(h: f64) -> Color {
+        Hsva {
+            h,
+            s: 1.0,
+            v: 1.0,
+            a: 1.0,
+        }
+        .to_color()
+    }
+}
+
+/// `#RRGGBB`, or `#RRGGBBAA` for colors that aren't opaque
+fn to_hex(color: Color) -> String {
+    if color.a == 255 {
+        format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b)
+    } else {
+        format!(
+            "#{:02X}{:02X}{:02X}{:02X}",
+            color.r, color.g, color.b, color.a
+        )
+    }
+}
+
+/// Parses `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA`, with or without the `#`
+fn parse_hex(hex: &str) -> Option<Color> {
+    let hex = hex.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+    let byte = |i: usize| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok();
+    match hex.len() {
+        3 => Some(Color::rgb8(digit(0)?, digit(1)?, digit(2)?)),
+        4 => Some(Color::rgba8(digit(0)?, digit(1)?, digit(2)?, digit(3)?)),
+        6 => Some(Color::rgb8(byte(0)?, byte(1)?, byte(2)?)),
+        8 => Some(Color::rgba8(byte(0)?, byte(1)?, byte(2)?, byte(3)?)),
+        _ => None,
+    }
+}
+
+/// A picker for a color with any hue, saturation, value and alpha. See [`color_picker`]
+pub type ColorPicker = Composite<ColorPickerState>;
+
+pub struct ColorPickerState {
+    palette: RwSignal<Vec<Color>>,
+}
+
+/// Picks the color in `color` with a square for the saturation and value, strips for the
+/// hue and alpha, and a field for typing the color as hex. Each of them can be dragged
+/// or clicked, and changed with the arrow keys while it has focus.
+///
+/// [ColorPicker::palette] adds swatches that set the color when they're clicked.
+pub fn color_picker(color: RwSignal<Color>) -> ColorPicker {
+    let hsva = create_rw_signal(Hsva::from_color(color.get_untracked()));
+    // only follow changes that actually change the color, so the hue isn't lost when
+    // the color becomes gray
+    create_effect(move |_| {
+        let color = color.get();
+        if hsva.get_untracked().to_color() != color {
+            hsva.set(Hsva::from_color(color));
+        }
+    });
+    create_effect(move |_| {
+        let new = hsva.get().to_color();
+        if color.get_untracked() != new {
+            color.set(new);
+        }
+    });
+
+    let hex = create_rw_signal(to_hex(color.get_untracked()));
+    // leave the text alone while it's an unfinished or differently written version of the color
+    create_effect(move |_| {
+        let color = color.get();
+        if parse_hex(&hex.get_untracked()) != Some(color) {
+            hex.set(to_hex(color));
+        }
+    });
+    create_effect(move |_| {
+        if let Some(new) = parse_hex(&hex.get()) {
+            if color.get_untracked() != new {
+                color.set(new);
+            }
+        }
+    });
+
+    let palette = create_rw_signal(Vec::new());
+    let swatches = list(
+        move || palette.get().into_iter().enumerate().collect::<Vec<_>>(),
+        |(index, swatch)| (*index, swatch.r, swatch.g, swatch.b, swatch.a),
+        move |(_, swatch)| {
+            empty()
+                .style(move |s| {
+                    let tokens = tokens();
+                    s.size(SWATCH_SIZE, SWATCH_SIZE)
+                        .margin_right(tokens.spacing.sm)
+                        .margin_top(tokens.spacing.sm)
+                        .background(swatch)
+                        .border(1.0)
+                        .border_color(tokens.colors.border)
+                        .border_radius(tokens.radius.sm)
+                        .apply_if(color.get() == swatch, |s| {
+                            s.border(2.0).border_color(tokens.colors.primary)
+                        })
+                })
+                .hover_style(|s| s.border_color(tokens().colors.text_muted))
+                .on_click(move |_| {
+                    color.set(swatch);
+                    true
+                })
+        },
+    )
+    .style(move |s| {
+        s.width(PICKER_WIDTH)
+            .flex_wrap(FlexWrap::Wrap)
+            .apply_if(palette.get().is_empty(), |s| s.hide())
+    });
+
+    let hex_row = stack((
+        empty().style(move |s| {
+            let tokens = tokens();
+            s.size(SWATCH_SIZE, SWATCH_SIZE)
+                .margin_right(tokens.spacing.md)
+                .background(color.get())
+                .border(1.0)
+                .border_color(tokens.colors.border)
+                .border_radius(tokens.radius.sm)
+        }),
+        text_input(hex).style(|s| s.flex_grow(1.0)),
+    ))
+    .style(|s| {
+        s.width(PICKER_WIDTH)
+            .items_center()
+            .margin_top(tokens().spacing.md)
+    });
+
+    let child = stack((
+        color_area(AreaKind::SaturationValue, hsva).style(|s| s.size(PICKER_WIDTH, 150.0)),
+        color_area(AreaKind::Hue, hsva).style(|s| {
+            s.size(PICKER_WIDTH, STRIP_HEIGHT)
+                .margin_top(tokens().spacing.md)
+        }),
+        color_area(AreaKind::Alpha, hsva).style(|s| {
+            s.size(PICKER_WIDTH, STRIP_HEIGHT)
+                .margin_top(tokens().spacing.md)
+        }),
+        hex_row,
+        swatches,
+    ))
+    .style(|s| {
+        let tokens = tokens();
+        s.flex_col()
+            .padding(tokens.spacing.md)
+            .background(tokens.colors.surface)
+            .border(1.0)
+            .border_color(tokens.colors.border)
+            .border_radius(tokens.radius.md)
+    });
+
+    composite("ColorPicker", child, ColorPickerState { palette })
+}
+
+impl ColorPicker {
+    /// Shows `colors` as swatches below the picker, which set the color when clicked
+    pub fn palette(self, colors: impl Into<Vec<Color>>) -> Self {
+        self.state().palette.set(colors.into());
+        self
+    }
+}
+
+/// Which part of the color an area of the picker changes
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AreaKind {
+    /// Saturation from left to right, and value from top to bottom
+    SaturationValue,
+    Hue,
+    Alpha,
+}
+
+/// A part of the picker that picks by where it's clicked or dragged
+struct ColorArea {
+    id: Id,
+    kind: AreaKind,
+    hsva: RwSignal<Hsva>,
+    current: Hsva,
+    size: Size,
+    held: bool,
+}
+
+fn color_area(kind: AreaKind, hsva: RwSignal<Hsva>) -> ColorArea {
+    let id = Id::next();
+    create_effect(move |_| {
+        id.update_state(hsva.get(), false);
+    });
+    ColorArea {
+        id,
+        kind,
+        hsva,
+        current: hsva.get_untracked(),
+        size: Size::ZERO,
+        held: false,
+    }
+    .keyboard_navigatable()
+}
+
+impl ColorArea {
+    fn set(&mut self, hsva: Hsva) {
+        let hsva = Hsva {
+            h: hsva.h.clamp(0.0, 360.0),
+            s: hsva.s.clamp(0.0, 1.0),
+            v: hsva.v.clamp(0.0, 1.0),
+            a: hsva.a.clamp(0.0, 1.0),
+        };
+        if hsva != self.current {
+            self.current = hsva;
+            self.hsva.set(hsva);
+            self.id.request_paint();
+        }
+    }
+
+    fn set_from_pos(&mut self, pos: Point) {
+        let x = if self.size.width > 0.0 {
+            (pos.x / self.size.width).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let y = if self.size.height > 0.0 {
+            (pos.y / self.size.height).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let current = self.current;
+        self.set(match self.kind {
+            AreaKind::SaturationValue => Hsva {
+                s: x,
+                v: 1.0 - y,
+                ..current
+            },
+            AreaKind::Hue => Hsva {
+                h: x * 360.0,
+                ..current
+            },
+            AreaKind::Alpha => Hsva { a: x, ..current },
+        });
+    }
+
+    /// Changes the picked value by a step in the direction of an arrow key
+    fn step(&mut self, dx: f64, dy: f64) {
+        let current = self.current;
+        self.set(match self.kind {
+            AreaKind::SaturationValue => Hsva {
+                s: current.s + dx * 0.01,
+                v: current.v - dy * 0.01,
+                ..current
+            },
+            AreaKind::Hue => Hsva {
+                h: current.h + (dx - dy),
+                ..current
+            },
+            AreaKind::Alpha => Hsva {
+                a: current.a + (dx - dy) * 0.01,
+                ..current
+            },
+        });
+    }
+
+    fn paint_checkerboard(&self, cx: &mut PaintCx) {
+        let columns = (self.size.width / CHECKER_SIZE).ceil() as usize;
+        let rows = (self.size.height / CHECKER_SIZE).ceil() as usize;
+        cx.fill(&self.size.to_rect(), Color::WHITE, 0.0);
+        for row in 0..rows {
+            for column in (row % 2..columns).step_by(2) {
+                let origin = Point::new(column as f64 * CHECKER_SIZE, row as f64 * CHECKER_SIZE);
+                let square = Rect::from_origin_size(origin, (CHECKER_SIZE, CHECKER_SIZE))
+                    .intersect(self.size.to_rect());
+                cx.fill(&square, Color::rgb8(204, 204, 204), 0.0);
+            }
+        }
+    }
+}
+
+impl View for ColorArea {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "ColorArea".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) -> ChangeFlags {
+        if let Ok(hsva) = state.downcast::<Hsva>() {
+            self.current = *hsva;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        None
+    }
+
+    fn event(&mut self, cx: &mut EventCx, _id_path: Option<&[Id]>, event: Event) -> bool {
+        match event {
+            Event::PointerDown(event) => {
+                if event.button.is_primary() {
+                    self.held = true;
+                    cx.update_active(self.id);
+                    cx.update_focus(self.id, false);
+                    self.set_from_pos(event.pos);
+                    return true;
+                }
+                false
+            }
+            Event::PointerMove(event) => {
+                if self.held {
+                    self.set_from_pos(event.pos);
+                    return true;
+                }
+                false
+            }
+            Event::PointerUp(_) => {
+                self.held = false;
+                false
+            }
+            Event::KeyDown(event) => {
+                match event.key.logical_key {
+                    Key::ArrowLeft => self.step(-1.0, 0.0),
+                    Key::ArrowRight => self.step(1.0, 0.0),
+                    Key::ArrowUp => self.step(0.0, -1.0),
+                    Key::ArrowDown => self.step(0.0, 1.0),
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = self.size.to_rect();
+        let current = self.current;
+        let marker = match self.kind {
+            AreaKind::SaturationValue => {
+                let hue = Hsva::hue_color(current.h);
+                let saturation = Gradient::new_linear(rect.origin(), Point::new(rect.x1, 0.0))
+                    .with_stops([Color::WHITE, hue]);
+                cx.fill(&rect, &saturation, 0.0);
+                let value = Gradient::new_linear(rect.origin(), Point::new(0.0, rect.y1))
+                    .with_stops([Color::rgba8(0, 0, 0, 0), Color::BLACK]);
+                cx.fill(&rect, &value, 0.0);
+                Point::new(current.s * rect.x1, (1.0 - current.v) * rect.y1)
+            }
+            AreaKind::Hue => {
+                // one gradient for each sixth of the hues, as not every renderer
+                // can draw gradients with more than two stops
+                let width = rect.width() / 6.0;
+                for sixth in 0..6 {
+                    let x0 = sixth as f64 * width;
+                    let gradient = Gradient::new_linear((x0, 0.0), (x0 + width, 0.0)).with_stops([
+                        Hsva::hue_color(sixth as f64 * 60.0),
+                        Hsva::hue_color((sixth + 1) as f64 * 60.0),
+                    ]);
+                    cx.fill(&Rect::new(x0, 0.0, x0 + width, rect.y1), &gradient, 0.0);
+                }
+                Point::new(current.h / 360.0 * rect.x1, rect.y1 / 2.0)
+            }
+            AreaKind::Alpha => {
+                self.paint_checkerboard(cx);
+                let color = Hsva { a: 1.0, ..current }.to_color();
+                let gradient = Gradient::new_linear(rect.origin(), Point::new(rect.x1, 0.0))
+                    .with_stops([Color::rgba8(color.r, color.g, color.b, 0), color]);
+                cx.fill(&rect, &gradient, 0.0);
+                Point::new(current.a * rect.x1, rect.y1 / 2.0)
+            }
+        };
+
+        let radius = match self.kind {
+            AreaKind::SaturationValue => 5.0,
+            _ => rect.height() / 2.0,
+        };
+        cx.stroke(&Circle::new(marker, radius), Color::WHITE, 2.0);
+        cx.stroke(
+            &Circle::new(marker, radius + 1.5),
+            Color::rgba8(0, 0, 0, 120),
+            1.0,
+        );
+    }
+}
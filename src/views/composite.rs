@@ -0,0 +1,113 @@
+use kurbo::Rect;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    view::{ChangeFlags, View},
+};
+
+/// A widget made out of other views, like an [Avatar](crate::views::Avatar) or a
+/// [Popover](crate::views::Popover). The views are held like in a [ContainerBox](crate::views::ContainerBox),
+/// and `S` is what the widget's builder methods work with, usually the signals the views read.
+///
+/// Each widget is a type alias for a composite with a state type of its own, so its methods
+/// are on `Composite<ItsState>`, and it goes by its own name in stylesheets and debug output.
+pub struct Composite<S> {
+    id: Id,
+    name: &'static str,
+    child: Box<dyn View>,
+    state: S,
+}
+
+/// A composite called `name` showing `child`
+pub(crate) fn composite<S>(
+    name: &'static str,
+    child: impl View + 'static,
+    state: S,
+) -> Composite<S> {
+    composite_with_id(Id::next(), name, child, state)
+}
+
+/// Like [composite], with an id made beforehand for the views inside to refer to it by
+pub(crate) fn composite_with_id<S>(
+    id: Id,
+    name: &'static str,
+    child: impl View + 'static,
+    state: S,
+) -> Composite<S> {
+    Composite {
+        id,
+        name,
+        child: Box::new(child),
+        state,
+    }
+}
+
+impl<S> Composite<S> {
+    pub(crate) fn state(&self) -> &S {
+        &self.state
+    }
+}
+
+impl<S> View for Composite<S> {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.child.id() == id {
+            Some(&*self.child)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.child.id() == id {
+            Some(&mut *self.child)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&*self.child]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut *self.child]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        self.name.into()
+    }
+
+    fn view_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, true, |cx| vec![self.child.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        Some(self.child.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        if cx.should_send(self.child.id(), &event) {
+            self.child.event_main(cx, id_path, event)
+        } else {
+            false
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.child.paint_main(cx);
+    }
+}
@@ -23,7 +23,7 @@ pub struct ContainerBox {
 ///
 /// container(|| {
 ///     if check == true {
-///         checkbox(create_rw_signal(true).read_only())
+///         checkbox(|| true)
 ///     } else {
 ///         label(|| "no check".to_string())
 ///     }
@@ -40,7 +40,7 @@ pub struct ContainerBox {
 /// let check = true;
 ///
 /// if check == true {
-///     container_box(checkbox(create_rw_signal(true).read_only()))
+///     container_box(checkbox(|| true))
 /// } else {
 ///     container_box(label(|| "no check".to_string()))
 /// };
@@ -0,0 +1,260 @@
+use std::rc::Rc;
+
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use floem_reactive::{create_rw_signal, RwSignal};
+use peniko::Color;
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    overlay::Placement,
+    theme::tokens,
+    view::View,
+    views::{composite, label, list, popover_with_open, stack, Composite, Decorators},
+};
+
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+const DAY_SIZE: f32 = 32.0;
+
+/// A field showing a date, with a calendar to pick another one from. See [`date_picker`]
+pub type DatePicker = Composite<DatePickerState>;
+
+/// The signals behind a [DatePicker]
+pub struct DatePickerState {
+    open: RwSignal<bool>,
+    min: RwSignal<Option<NaiveDate>>,
+    max: RwSignal<Option<NaiveDate>>,
+}
+
+/// Shows the date in `date` and opens a month calendar below it when clicked, or with
+/// Enter, Space or the down arrow while it has focus. Picking a day in the calendar sets `date` and closes it again.
+///
+/// While the calendar is open, the arrow keys move the highlighted day by a day or a week,
+/// Page Up and Page Down by a month, and Enter or Space picks it. Escape, or a click outside
+/// the calendar, closes it without changing the date. Days outside of the range set with
+/// [DatePicker::min_date] and [DatePicker::max_date] can't be picked.
+pub fn date_picker(date: RwSignal<NaiveDate>) -> DatePicker {
+    let min = create_rw_signal(None);
+    let max = create_rw_signal(None);
+    let open = create_rw_signal(false);
+
+    let anchor = label(move || date.get().format("%Y-%m-%d").to_string())
+        .base_style(|s| {
+            let tokens = tokens();
+            s.padding_horiz(tokens.spacing.md)
+                .padding_vert(tokens.spacing.sm)
+                .border(1.0)
+                .border_color(tokens.colors.border)
+                .border_radius(tokens.radius.md)
+        })
+        .keyboard_navigatable()
+        .on_event(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                if matches!(key.logical_key, Key::ArrowDown | Key::Enter | Key::Space) {
+                    open.set(true);
+                    return true;
+                }
+            }
+            false
+        });
+    let child = popover_with_open(
+        anchor,
+        open,
+        move || calendar(date, min, max, move || open.set(false)),
+        Placement::BottomStart,
+    )
+    .on_click(move |_| {
+        open.update(|open| *open = !*open);
+        true
+    });
+
+    composite("DatePicker", child, DatePickerState { open, min, max })
+}
+
+impl DatePicker {
+    /// The earliest date that can be picked
+    pub fn min_date(self, min: NaiveDate) -> Self {
+        self.state().min.set(Some(min));
+        self
+    }
+
+    /// The latest date that can be picked
+    pub fn max_date(self, max: NaiveDate) -> Self {
+        self.state().max.set(Some(max));
+        self
+    }
+
+    /// Whether the calendar is open, which can be set to open or close it
+    pub fn open_signal(&self) -> RwSignal<bool> {
+        self.state().open
+    }
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn clamp_date(date: NaiveDate, min: Option<NaiveDate>, max: Option<NaiveDate>) -> NaiveDate {
+    let date = min.map(|min| date.max(min)).unwrap_or(date);
+    max.map(|max| date.min(max)).unwrap_or(date)
+}
+
+/// The 42 days shown for the month starting at `month`, from the Monday on or before the
+/// first of the month, as six rows of a week each
+fn month_grid(month: NaiveDate) -> Vec<Vec<NaiveDate>> {
+    let start = month - Days::new(month.weekday().num_days_from_monday() as u64);
+    (0..6)
+        .map(|week| {
+            (0..7)
+                .map(|day| start + Days::new(week * 7 + day))
+                .collect()
+        })
+        .collect()
+}
+
+fn calendar(
+    date: RwSignal<NaiveDate>,
+    min: RwSignal<Option<NaiveDate>>,
+    max: RwSignal<Option<NaiveDate>>,
+    close: impl Fn() + 'static,
+) -> impl View {
+    let selected = date.get_untracked();
+    let month = create_rw_signal(first_of_month(selected));
+    let highlighted = create_rw_signal(selected);
+    let today = Local::now().date_naive();
+    let close = Rc::new(close);
+
+    let in_range = move |day: NaiveDate| {
+        min.get().map(|min| day >= min).unwrap_or(true)
+            && max.get().map(|max| day <= max).unwrap_or(true)
+    };
+    let pick = {
+        let close = close.clone();
+        move |day: NaiveDate| {
+            if in_range(day) {
+                date.set(day);
+                close();
+            }
+        }
+    };
+    let highlight = move |day: NaiveDate| {
+        let day = clamp_date(day, min.get_untracked(), max.get_untracked());
+        highlighted.set(day);
+        month.set(first_of_month(day));
+    };
+
+    let header = stack((
+        label(|| "‹".to_string())
+            .style(|s| s.padding_horiz(tokens().spacing.md))
+            .hover_style(|s| s.background(tokens().colors.hover))
+            .on_click(move |_| {
+                month.update(|month| *month = *month - Months::new(1));
+                true
+            }),
+        label(move || month.get().format("%B %Y").to_string())
+            .style(|s| s.flex_grow(1.0).justify_center().font_bold()),
+        label(|| "›".to_string())
+            .style(|s| s.padding_horiz(tokens().spacing.md))
+            .hover_style(|s| s.background(tokens().colors.hover))
+            .on_click(move |_| {
+                month.update(|month| *month = *month + Months::new(1));
+                true
+            }),
+    ))
+    .style(|s| s.items_center().padding_bottom(tokens().spacing.sm));
+
+    let weekdays = list(
+        || WEEKDAYS.into_iter().collect::<Vec<_>>(),
+        |weekday| *weekday,
+        |weekday| {
+            label(move || weekday.to_string()).style(|s| {
+                s.width(DAY_SIZE)
+                    .justify_center()
+                    .color(tokens().colors.text_muted)
+            })
+        },
+    );
+
+    let key_pick = pick.clone();
+    let weeks = list(
+        move || month_grid(month.get()),
+        |week| week[0],
+        move |week| {
+            let pick = pick.clone();
+            list(
+                move || week.clone(),
+                |day| *day,
+                move |day| {
+                    let pick = pick.clone();
+                    label(move || day.day().to_string())
+                        .style(move |s| {
+                            let tokens = tokens();
+                            let is_selected = date.get() == day;
+                            s.width(DAY_SIZE)
+                                .height(DAY_SIZE)
+                                .items_center()
+                                .justify_center()
+                                .border(1.0)
+                                .border_color(Color::TRANSPARENT)
+                                .border_radius(tokens.radius.md)
+                                .apply_if(day.month() != month.get().month(), |s| {
+                                    s.color(tokens.colors.text_muted)
+                                })
+                                .apply_if(day == today, |s| s.font_bold())
+                                .apply_if(highlighted.get() == day, |s| {
+                                    s.border_color(tokens.colors.primary)
+                                })
+                                .apply_if(is_selected, |s| {
+                                    s.background(tokens.colors.primary)
+                                        .color(tokens.colors.on_primary)
+                                })
+                        })
+                        .hover_style(move |s| {
+                            s.apply_if(date.get() != day, |s| s.background(tokens().colors.hover))
+                        })
+                        .disabled_style(|s| s.color(tokens().colors.border))
+                        .disabled(move || !in_range(day))
+                        .on_click(move |_| {
+                            pick(day);
+                            true
+                        })
+                },
+            )
+        },
+    )
+    .style(|s| s.flex_col());
+
+    let view = stack((header, weekdays, weeks))
+        .style(|s| {
+            let tokens = tokens();
+            s.flex_col()
+                .padding(tokens.spacing.md)
+                .background(tokens.colors.surface)
+                .border(1.0)
+                .border_color(tokens.colors.border)
+                .border_radius(tokens.radius.md)
+        })
+        .keyboard_navigatable()
+        .on_event(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                let day = highlighted.get_untracked();
+                match key.logical_key {
+                    Key::ArrowLeft => highlight(day - Days::new(1)),
+                    Key::ArrowRight => highlight(day + Days::new(1)),
+                    Key::ArrowUp => highlight(day - Days::new(7)),
+                    Key::ArrowDown => highlight(day + Days::new(7)),
+                    Key::PageUp => highlight(day - Months::new(1)),
+                    Key::PageDown => highlight(day + Months::new(1)),
+                    Key::Enter | Key::Space => key_pick(day),
+                    Key::Escape => close(),
+                    _ => return false,
+                }
+                true
+            } else {
+                false
+            }
+        });
+    view.id().request_focus();
+    view
+}
@@ -1,14 +1,19 @@
+use std::time::Duration;
+
 use floem_reactive::create_effect;
 use kurbo::{Point, Rect};
 
 use crate::{
     action::{set_window_menu, set_window_title, update_window_scale},
-    animate::Animation,
+    animate::{Animation, KeyframeAnimation},
     event::{Event, EventListener},
+    id::Id,
     menu::Menu,
-    responsive::ScreenSize,
+    overlay::{Tooltip, TOOLTIP_DELAY},
+    responsive::{ScreenSize, WidthQuery},
     style::{Style, StyleSelector},
     view::View,
+    views::Mask,
 };
 
 pub trait Decorators: View + Sized {
@@ -43,6 +48,19 @@ pub trait Decorators: View + Sized {
         self
     }
 
+    /// A style applied on top of [`style`](Decorators::style) that lives in its own effect,
+    /// so it can track signals without re-running the main style closure.
+    /// When a change only affects how the view is painted, such as its background or
+    /// border color, the view is repainted without a layout pass.
+    fn dyn_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        let id = self.id();
+        create_effect(move |_| {
+            let style = style(Style::BASE);
+            id.update_dyn_style(style);
+        });
+        self
+    }
+
     /// Alter the base style of the view.  
     /// This is applied before `style`, and so serves as a good place to set defaults.  
     /// ```rust
@@ -69,7 +87,20 @@ pub trait Decorators: View + Sized {
         self
     }
 
-    /// The visual style to apply when the mouse hovers over the element
+    /// The visual style to apply when the mouse hovers over the element, applied over `style`
+    /// without any pointer listeners or signals of your own. Disabled views don't get it.
+    /// ```rust
+    /// # use floem::{peniko::Color, style::CursorStyle, view::View, views::{Decorators, label}};
+    /// fn view() -> impl View {
+    ///     label(|| "Open".to_string())
+    ///         .style(|s| s.padding(4.0).border(1.0).border_radius(4.0))
+    ///         .hover_style(|s| {
+    ///             s.background(Color::rgb8(228, 232, 240))
+    ///                 .border_color(Color::rgb8(66, 133, 244))
+    ///                 .cursor(CursorStyle::Pointer)
+    ///         })
+    /// }
+    /// ```
     fn hover_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
         let id = self.id();
         create_effect(move |_| {
@@ -89,6 +120,7 @@ pub trait Decorators: View + Sized {
         self
     }
 
+    /// The visual style to apply while the view has keyboard focus, however it got it
     fn focus_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
         let id = self.id();
         create_effect(move |_| {
@@ -99,6 +131,16 @@ pub trait Decorators: View + Sized {
     }
 
     /// Similar to the `:focus-visible` css selector, this style only activates when tab navigation is used.
+    /// It's applied over [focus_style](Decorators::focus_style), and isn't shown when the view
+    /// was focused by a click, which makes it the place for focus rings.
+    /// ```rust
+    /// # use floem::{peniko::Color, view::View, views::{Decorators, label}};
+    /// fn view() -> impl View {
+    ///     label(|| "Save".to_string())
+    ///         .keyboard_navigatable()
+    ///         .focus_visible_style(|s| s.outline(2.0).outline_color(Color::rgb8(66, 133, 244)))
+    /// }
+    /// ```
     fn focus_visible_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
         let id = self.id();
         create_effect(move |_| {
@@ -115,12 +157,24 @@ pub trait Decorators: View + Sized {
         self
     }
 
+    /// Lets the view be dragged with the pointer, within its window. Views can't be dragged
+    /// out into other applications: winit has no way to start a drag on the OS side.
     fn draggable(self) -> Self {
         let id = self.id();
         id.draggable();
         self
     }
 
+    /// The visual style to apply while the view is pressed, from the pointer going down on it
+    /// until it's released, or while it's activated with the keyboard when it has focus.
+    /// ```rust
+    /// # use floem::{peniko::Color, view::View, views::{Decorators, label}};
+    /// fn view() -> impl View {
+    ///     label(|| "Press".to_string())
+    ///         .hover_style(|s| s.background(Color::rgb8(228, 232, 240)))
+    ///         .active_style(|s| s.background(Color::rgb8(200, 208, 224)))
+    /// }
+    /// ```
     fn active_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
         let id = self.id();
         create_effect(move |_| {
@@ -148,6 +202,46 @@ pub trait Decorators: View + Sized {
         self
     }
 
+    /// Applies `style` on top of the view's style while the width `query` looks at matches,
+    /// and takes it off again when a resize makes it stop matching, so the same view can be
+    /// laid out differently in a narrow and a wide window.
+    /// ```rust
+    /// # use floem::{responsive::{max_width, min_width}, view::View, views::{Decorators, label, stack}};
+    /// fn toolbar() -> impl View {
+    ///     stack((label(|| "Open".to_string()), label(|| "Save".to_string())))
+    ///         .style(|s| s.flex_col())
+    ///         .responsive(min_width(800.0), |s| s.flex_row())
+    ///         .responsive(max_width(300.0).of_container(), |s| s.font_size(12.0))
+    /// }
+    /// ```
+    /// A query [of the container](crate::responsive::WidthQuery::of_container) looks at the
+    /// width the view's parent had in the last layout, so that parent shouldn't get its width
+    /// from the view's content.
+    fn responsive(self, query: WidthQuery, style: impl Fn(Style) -> Style + 'static) -> Self {
+        let id = self.id();
+        let key = Id::next();
+        create_effect(move |_| {
+            let style = style(Style::BASE);
+            id.update_width_style(key, query, style);
+        });
+        self
+    }
+
+    /// Disables the view and the views inside it while `disabled_fn` returns true. They get
+    /// no pointer or keyboard events, can't be focused or reached with tab, and get their
+    /// [disabled_style](Decorators::disabled_style) instead of their hover style.
+    /// ```rust
+    /// # use floem::{peniko::Color, reactive::create_rw_signal, view::View, views::{Decorators, label, stack}};
+    /// fn form() -> impl View {
+    ///     let saving = create_rw_signal(false);
+    ///     stack((
+    ///         label(|| "Name".to_string()),
+    ///         label(|| "Save".to_string())
+    ///             .disabled_style(|s| s.color(Color::rgb8(150, 150, 150))),
+    ///     ))
+    ///     .disabled(move || saving.get())
+    /// }
+    /// ```
     fn disabled(self, disabled_fn: impl Fn() -> bool + 'static) -> Self {
         let id = self.id();
 
@@ -209,6 +303,14 @@ pub trait Decorators: View + Sized {
         self
     }
 
+    /// Plays `animation` on the view, over its style. Unlike [animation](Decorators::animation),
+    /// it can go through any number of frames.
+    fn keyframe_animation(self, animation: KeyframeAnimation) -> Self {
+        let id = self.id();
+        id.update_keyframe_animation(animation);
+        self
+    }
+
     fn window_scale(self, scale_fn: impl Fn() -> f64 + 'static) -> Self {
         create_effect(move |_| {
             let window_scale = scale_fn();
@@ -234,6 +336,8 @@ pub trait Decorators: View + Sized {
     }
 
     /// Adds a secondary-click context menu to the view, which opens at the mouse position.
+    /// It also opens below the view with the context menu key or Shift+F10 while the view,
+    /// or a view inside it, has focus.
     fn context_menu(self, menu: impl Fn() -> Menu + 'static) -> Self {
         let id = self.id();
         id.update_context_menu(Box::new(menu));
@@ -246,6 +350,64 @@ pub trait Decorators: View + Sized {
         id.update_popout_menu(Box::new(menu));
         self
     }
+
+    /// Shows the view from `tooltip` in a small popup below this view once the pointer has
+    /// rested on it for a moment. The popup moves above the view when there isn't room below,
+    /// and hides again when the pointer leaves the view or something is scrolled.
+    /// ```rust
+    /// # use floem::{view::View, views::{Decorators, label}};
+    /// fn view() -> impl View {
+    ///     label(|| "Save".to_string()).tooltip(|| label(|| "Save the file (Ctrl+S)".to_string()))
+    /// }
+    /// ```
+    fn tooltip<V: View + 'static>(self, tooltip: impl Fn() -> V + 'static) -> Self {
+        self.tooltip_with_delay(TOOLTIP_DELAY, tooltip)
+    }
+
+    /// Like [`tooltip`](Decorators::tooltip), showing the tooltip once the pointer has been on
+    /// the view for `delay`
+    fn tooltip_with_delay<V: View + 'static>(
+        self,
+        delay: Duration,
+        tooltip: impl Fn() -> V + 'static,
+    ) -> Self {
+        let id = self.id();
+        id.update_tooltip(Tooltip::new(delay, tooltip));
+        self
+    }
+
+    /// Cuts what the view and its children paint to the shape of `mask`, like a circle
+    /// for an avatar.
+    /// ```rust
+    /// # use floem::{view::View, views::{Decorators, Mask, label}};
+    /// fn view() -> impl View {
+    ///     label(|| "AB".to_string())
+    ///         .style(|s| s.size(40.0, 40.0))
+    ///         .mask(Mask::ellipse())
+    /// }
+    /// ```
+    fn mask(self, mask: Mask) -> Self {
+        let id = self.id();
+        id.update_mask(mask);
+        self
+    }
+
+    /// Gives the view a class, so it gets the style of the class made with
+    /// [define_class](crate::stylesheet::define_class), and of the rules of the
+    /// [stylesheet](crate::stylesheet) that select `.name`. A view can have several classes.
+    fn class(self, name: impl Into<String>) -> Self {
+        let id = self.id();
+        id.add_class(name);
+        self
+    }
+
+    /// Sets the id the rules of the [stylesheet](crate::stylesheet) can select the view by,
+    /// as `#name`
+    fn style_id(self, name: impl Into<String>) -> Self {
+        let id = self.id();
+        id.update_style_id(name);
+        self
+    }
 }
 
 impl<V: View> Decorators for V {}
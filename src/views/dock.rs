@@ -0,0 +1,456 @@
+use std::{collections::HashMap, rc::Rc};
+
+use floem_reactive::{create_rw_signal, RwSignal};
+use peniko::Color;
+
+use crate::{
+    event::{Event, EventListener},
+    style::{CursorStyle, Style},
+    theme::tokens,
+    view::View,
+    views::{composite, empty, label, list, stack, tab, Composite, Decorators},
+};
+
+const DEFAULT_SIDE_SIZE: f64 = 240.0;
+const DEFAULT_BOTTOM_SIZE: f64 = 200.0;
+const MIN_ZONE_SIZE: f64 = 48.0;
+const GUTTER_SIZE: f64 = 4.0;
+
+/// Where in a [DockArea] a panel is docked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DockZone {
+    Left,
+    Right,
+    Bottom,
+    Center,
+}
+
+impl DockZone {
+    const ALL: [DockZone; 4] = [
+        DockZone::Left,
+        DockZone::Right,
+        DockZone::Bottom,
+        DockZone::Center,
+    ];
+}
+
+/// The panels docked in one zone, shown as tabs
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DockTabs {
+    /// The ids of the panels, in the order of their tabs
+    pub panels: Vec<String>,
+    /// The id of the panel whose tab is open
+    pub active: Option<String>,
+}
+
+/// Which panels are docked where in a [DockArea], and how big the zones around the center
+/// are. With the `serde` feature it can be serialized, to restore the layout the next time
+/// the application runs.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DockLayout {
+    pub left: DockTabs,
+    pub right: DockTabs,
+    pub bottom: DockTabs,
+    pub center: DockTabs,
+    pub left_size: f64,
+    pub right_size: f64,
+    pub bottom_size: f64,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        Self {
+            left: DockTabs::default(),
+            right: DockTabs::default(),
+            bottom: DockTabs::default(),
+            center: DockTabs::default(),
+            left_size: DEFAULT_SIDE_SIZE,
+            right_size: DEFAULT_SIDE_SIZE,
+            bottom_size: DEFAULT_BOTTOM_SIZE,
+        }
+    }
+}
+
+impl DockLayout {
+    pub fn tabs(&self, zone: DockZone) -> &DockTabs {
+        match zone {
+            DockZone::Left => &self.left,
+            DockZone::Right => &self.right,
+            DockZone::Bottom => &self.bottom,
+            DockZone::Center => &self.center,
+        }
+    }
+
+    pub fn tabs_mut(&mut self, zone: DockZone) -> &mut DockTabs {
+        match zone {
+            DockZone::Left => &mut self.left,
+            DockZone::Right => &mut self.right,
+            DockZone::Bottom => &mut self.bottom,
+            DockZone::Center => &mut self.center,
+        }
+    }
+
+    /// The zone the panel with the id `panel` is docked in
+    pub fn zone_of(&self, panel: &str) -> Option<DockZone> {
+        DockZone::ALL
+            .into_iter()
+            .find(|zone| self.tabs(*zone).panels.iter().any(|p| p == panel))
+    }
+
+    /// Takes the panel out of the zone it's in, opening the tab next to it if it was open
+    pub fn remove_panel(&mut self, panel: &str) {
+        for zone in DockZone::ALL {
+            let tabs = self.tabs_mut(zone);
+            if let Some(index) = tabs.panels.iter().position(|p| p == panel) {
+                tabs.panels.remove(index);
+                if tabs.active.as_deref() == Some(panel) {
+                    let next = index.min(tabs.panels.len().saturating_sub(1));
+                    tabs.active = tabs.panels.get(next).cloned();
+                }
+            }
+        }
+    }
+
+    /// Docks the panel in `zone`, in front of the tab of the panel `before`, or after the
+    /// last tab if that isn't in the zone, and opens its tab
+    pub fn move_panel(&mut self, panel: &str, zone: DockZone, before: Option<&str>) {
+        if before == Some(panel) {
+            return;
+        }
+        self.remove_panel(panel);
+        let tabs = self.tabs_mut(zone);
+        let index = before
+            .and_then(|before| tabs.panels.iter().position(|p| p == before))
+            .unwrap_or(tabs.panels.len());
+        tabs.panels.insert(index, panel.to_string());
+        tabs.active = Some(panel.to_string());
+    }
+
+    /// Drops the panels that aren't in `panels` and docks the ones that are missing in
+    /// their default zone, so a saved layout still fits when the panels have changed
+    fn reconcile(&mut self, panels: &[DockPanel]) {
+        for zone in DockZone::ALL {
+            let tabs = self.tabs_mut(zone);
+            tabs.panels
+                .retain(|id| panels.iter().any(|panel| &panel.id == id));
+            if !tabs
+                .active
+                .as_ref()
+                .map(|active| tabs.panels.contains(active))
+                .unwrap_or(false)
+            {
+                tabs.active = tabs.panels.first().cloned();
+            }
+        }
+        for panel in panels {
+            if self.zone_of(&panel.id).is_none() {
+                let tabs = self.tabs_mut(panel.zone);
+                tabs.panels.push(panel.id.clone());
+                if tabs.active.is_none() {
+                    tabs.active = Some(panel.id.clone());
+                }
+            }
+        }
+    }
+}
+
+/// A panel that can be docked in a [DockArea]. See [`dock_panel`]
+pub struct DockPanel {
+    id: String,
+    title: String,
+    zone: DockZone,
+    view_fn: Box<dyn Fn() -> Box<dyn View>>,
+}
+
+/// A panel for a [dock_area] with a tab titled `title` and the view from `view_fn`.
+/// `id` identifies the panel in the [DockLayout], so it should stay the same between runs
+/// of the application for a saved layout to find it.
+pub fn dock_panel<V: View + 'static>(
+    id: impl Into<String>,
+    title: impl Into<String>,
+    view_fn: impl Fn() -> V + 'static,
+) -> DockPanel {
+    DockPanel {
+        id: id.into(),
+        title: title.into(),
+        zone: DockZone::Center,
+        view_fn: Box::new(move || Box::new(view_fn())),
+    }
+}
+
+impl DockPanel {
+    /// The zone the panel is docked in when the layout doesn't have it yet
+    pub fn zone(mut self, zone: DockZone) -> Self {
+        self.zone = zone;
+        self
+    }
+}
+
+/// Panels docked around a center area, like the side bars and bottom panel of an IDE.
+/// See [`dock_area`]
+pub type DockArea = Composite<DockAreaState>;
+
+pub struct DockAreaState {
+    layout: RwSignal<DockLayout>,
+    panels: Rc<Vec<DockPanel>>,
+}
+
+/// Docks each of the `panels` in its zone: left, right, bottom, or the center which takes
+/// the rest of the space. The panels in a zone are shown as tabs, and dragging a tab onto
+/// another zone's tabs or panel moves it there. Zones without panels are hidden, and while
+/// a tab is dragged they're shown as places to drop it. The zones at the sides and bottom
+/// are resized by dragging their edge facing the center.
+///
+/// The layout can be saved from [DockArea::layout_signal] and restored with
+/// [DockArea::layout].
+pub fn dock_area(panels: Vec<DockPanel>) -> DockArea {
+    let mut initial = DockLayout::default();
+    initial.reconcile(&panels);
+    let layout = create_rw_signal(initial);
+    // the id of the panel whose tab is being dragged
+    let dragged: RwSignal<Option<String>> = create_rw_signal(None);
+    let panels = Rc::new(panels);
+
+    let shown = move |zone: DockZone| {
+        dragged.with(|dragged| dragged.is_some())
+            || layout.with(|layout| !layout.tabs(zone).panels.is_empty())
+    };
+
+    let left = zone_view(DockZone::Left, layout, dragged, panels.clone(), move |s| {
+        s.width(layout.with(|layout| layout.left_size))
+            .flex_shrink(0.0)
+            .apply_if(!shown(DockZone::Left), |s| s.hide())
+    });
+    let right = zone_view(DockZone::Right, layout, dragged, panels.clone(), move |s| {
+        s.width(layout.with(|layout| layout.right_size))
+            .flex_shrink(0.0)
+            .apply_if(!shown(DockZone::Right), |s| s.hide())
+    });
+    let bottom = zone_view(
+        DockZone::Bottom,
+        layout,
+        dragged,
+        panels.clone(),
+        move |s| {
+            s.width_full()
+                .height(layout.with(|layout| layout.bottom_size))
+                .flex_shrink(0.0)
+                .apply_if(!shown(DockZone::Bottom), |s| s.hide())
+        },
+    );
+    let center = zone_view(DockZone::Center, layout, dragged, panels.clone(), |s| {
+        s.flex_grow(1.0).flex_basis(0.0).min_width(0.0)
+    });
+
+    let resize = move |zone: DockZone, delta: f64| {
+        layout.update(|layout| {
+            let size = match zone {
+                DockZone::Left => &mut layout.left_size,
+                DockZone::Right => &mut layout.right_size,
+                _ => &mut layout.bottom_size,
+            };
+            *size = (*size + delta).max(MIN_ZONE_SIZE);
+        });
+    };
+    // the right and bottom zones grow as their edge is dragged towards the center
+    let left_gutter = gutter(
+        false,
+        move || shown(DockZone::Left),
+        move |delta| resize(DockZone::Left, delta),
+    );
+    let right_gutter = gutter(
+        false,
+        move || shown(DockZone::Right),
+        move |delta| resize(DockZone::Right, -delta),
+    );
+    let bottom_gutter = gutter(
+        true,
+        move || shown(DockZone::Bottom),
+        move |delta| resize(DockZone::Bottom, -delta),
+    );
+
+    let child = stack((
+        stack((left, left_gutter, center, right_gutter, right)).style(|s| {
+            s.width_full()
+                .flex_grow(1.0)
+                .flex_basis(0.0)
+                .min_height(0.0)
+        }),
+        bottom_gutter,
+        bottom,
+    ))
+    .style(|s| s.flex_col().size_full());
+
+    composite("DockArea", child, DockAreaState { layout, panels })
+}
+
+impl DockArea {
+    /// Docks the panels as in `layout`, like one saved from [DockArea::layout_signal].
+    /// Panels that aren't in it are docked in their default zone.
+    pub fn layout(self, mut layout: DockLayout) -> Self {
+        layout.reconcile(&self.state().panels);
+        self.state().layout.set(layout);
+        self
+    }
+
+    /// The current layout, which changes as panels are moved and zones resized
+    pub fn layout_signal(&self) -> RwSignal<DockLayout> {
+        self.state().layout
+    }
+}
+
+/// The tabs and the open panel of one zone, with `style` for its size
+fn zone_view(
+    zone: DockZone,
+    layout: RwSignal<DockLayout>,
+    dragged: RwSignal<Option<String>>,
+    panels: Rc<Vec<DockPanel>>,
+    style: impl Fn(Style) -> Style + 'static,
+) -> impl View {
+    let titles: HashMap<String, String> = panels
+        .iter()
+        .map(|panel| (panel.id.clone(), panel.title.clone()))
+        .collect();
+    let titles = Rc::new(titles);
+    let drop_on = move |before: Option<&str>| {
+        if let Some(panel) = dragged.get_untracked() {
+            dragged.set(None);
+            layout.update(|layout| layout.move_panel(&panel, zone, before));
+            true
+        } else {
+            false
+        }
+    };
+
+    let tabs = list(
+        move || layout.with(|layout| layout.tabs(zone).panels.clone()),
+        |panel| panel.clone(),
+        move |panel| {
+            let title = titles.get(&panel).cloned().unwrap_or_default();
+            let (active_panel, drag_panel, drop_panel) =
+                (panel.clone(), panel.clone(), panel.clone());
+            label(move || title.clone())
+                .style(move |s| {
+                    let tokens = tokens();
+                    let active = layout
+                        .with(|layout| layout.tabs(zone).active.as_ref() == Some(&active_panel));
+                    s.padding_horiz(tokens.spacing.md)
+                        .padding_vert(tokens.spacing.xs)
+                        .border_bottom(2.0)
+                        .border_color(Color::TRANSPARENT)
+                        .apply_if(active, |s| s.border_color(tokens.colors.primary))
+                })
+                .hover_style(|s| s.background(tokens().colors.hover))
+                .on_click(move |_| {
+                    layout.update(|layout| layout.tabs_mut(zone).active = Some(panel.clone()));
+                    true
+                })
+                .draggable()
+                .dragging_style(|s| s.background(tokens().colors.surface))
+                .on_event(EventListener::DragStart, move |_| {
+                    dragged.set(Some(drag_panel.clone()));
+                    false
+                })
+                .on_event(EventListener::DragEnd, move |_| {
+                    dragged.set(None);
+                    false
+                })
+                .on_event(EventListener::Drop, move |_| drop_on(Some(&drop_panel)))
+        },
+    )
+    .style(|s| {
+        s.width_full()
+            .flex_shrink(0.0)
+            .background(tokens().colors.surface)
+    });
+
+    let content_panels = panels.clone();
+    let content = tab(
+        move || {
+            layout.with(|layout| {
+                let tabs = layout.tabs(zone);
+                tabs.active
+                    .as_ref()
+                    .and_then(|active| tabs.panels.iter().position(|p| p == active))
+                    .unwrap_or(0)
+            })
+        },
+        move || layout.with(|layout| layout.tabs(zone).panels.clone()),
+        |panel| panel.clone(),
+        move |panel| {
+            content_panels
+                .iter()
+                .find(|p| p.id == panel)
+                .map(|panel| (panel.view_fn)())
+                .unwrap_or_else(|| Box::new(empty()))
+        },
+    )
+    .style(|s| s.size_full().min_height(0.0));
+
+    stack((tabs, content))
+        .style(move |s| {
+            let tokens = tokens();
+            let dragging = dragged.with(|dragged| dragged.is_some());
+            s.flex_col()
+                .height_full()
+                .min_width(MIN_ZONE_SIZE)
+                .min_height(MIN_ZONE_SIZE)
+                .border(1.0)
+                .border_color(tokens.colors.border)
+                .apply_if(dragging, |s| s.border_color(tokens.colors.primary))
+                .apply(style(Style::BASE))
+        })
+        .on_event(EventListener::Drop, move |_| drop_on(None))
+}
+
+/// The edge between a zone and the center, which calls `on_drag` with how far it's dragged
+fn gutter(
+    vertical: bool,
+    shown: impl Fn() -> bool + 'static,
+    on_drag: impl Fn(f64) + 'static,
+) -> impl View {
+    // where in the gutter it was grabbed while it's being dragged, so it stays under the
+    // pointer
+    let grab: RwSignal<Option<f64>> = create_rw_signal(None);
+    let gutter = empty();
+    let gutter_id = gutter.id();
+    gutter
+        .style(move |s| {
+            let s = if vertical {
+                s.width_full()
+                    .height(GUTTER_SIZE)
+                    .cursor(CursorStyle::RowResize)
+            } else {
+                s.height_full()
+                    .width(GUTTER_SIZE)
+                    .cursor(CursorStyle::ColResize)
+            };
+            s.flex_shrink(0.0).apply_if(!shown(), |s| s.hide())
+        })
+        .hover_style(|s| s.background(tokens().colors.primary))
+        .on_event(EventListener::PointerDown, move |event| {
+            if let Event::PointerDown(event) = event {
+                if event.button.is_primary() {
+                    grab.set(Some(if vertical { event.pos.y } else { event.pos.x }));
+                    gutter_id.request_active();
+                    return true;
+                }
+            }
+            false
+        })
+        .on_event(EventListener::PointerMove, move |event| {
+            if let (Event::PointerMove(event), Some(grab)) = (event, grab.get_untracked()) {
+                on_drag(if vertical { event.pos.y } else { event.pos.x } - grab);
+                true
+            } else {
+                false
+            }
+        })
+        .on_event(EventListener::PointerUp, move |_| {
+            grab.set(None);
+            false
+        })
+}
@@ -0,0 +1,319 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::Display,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use floem_reactive::{create_rw_signal, RwSignal};
+use kurbo::Rect;
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    overlay::Placement,
+    theme::tokens,
+    view::View,
+    views::{
+        label, list, popover_with_open, scroll, text_input, Decorators, Label, List, Popover,
+        Scroll,
+    },
+};
+
+/// Typing again after this long starts a new type-ahead search instead of extending the last one
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+const POPUP_MAX_HEIGHT: f64 = 200.0;
+/// How close to the top or bottom of a long list the pointer has to be to scroll it
+const POPUP_AUTO_SCROLL_ZONE: f64 = 24.0;
+
+type DropdownPopup = Scroll<List<Label, (usize, String)>>;
+
+/// Moves the highlight one item up or down, starting from the top when nothing is highlighted
+fn move_highlight(highlighted: RwSignal<Option<usize>>, len: usize, down: bool) {
+    if len == 0 {
+        highlighted.set(None);
+        return;
+    }
+    let next = match highlighted.get_untracked() {
+        Some(current) if down => (current + 1).min(len - 1),
+        Some(current) => current.saturating_sub(1),
+        None => 0,
+    };
+    highlighted.set(Some(next));
+}
+
+/// The list shown under the header while it's open, at least as wide as the header
+/// and hidden while there's nothing to show, calling `on_pick` with the index of
+/// the item that's clicked. A list too long to fit scrolls while the pointer rests
+/// near its top or bottom, like a native menu.
+fn popup<T: Display + 'static>(
+    items: Rc<dyn Fn() -> Vec<T>>,
+    header_width: RwSignal<f64>,
+    highlighted: RwSignal<Option<usize>>,
+    on_pick: Rc<dyn Fn(usize)>,
+) -> DropdownPopup {
+    let item_rects: Rc<RefCell<HashMap<usize, Rect>>> = Rc::new(RefCell::new(HashMap::new()));
+
+    let view_item_rects = item_rects.clone();
+    let style_items = items.clone();
+    scroll(
+        list(
+            move || {
+                items()
+                    .iter()
+                    .map(|item| item.to_string())
+                    .enumerate()
+                    .collect::<Vec<_>>()
+            },
+            |item| item.clone(),
+            move |(index, text)| {
+                let item_rects = view_item_rects.clone();
+                let on_pick = on_pick.clone();
+                label(move || text.clone())
+                    .style(move |s| {
+                        let tokens = tokens();
+                        s.width_pct(100.0)
+                            .padding_horiz(tokens.spacing.md)
+                            .padding_vert(tokens.spacing.sm)
+                            .apply_if(highlighted.get() == Some(index), |s| {
+                                s.background(tokens.colors.selected)
+                            })
+                    })
+                    .hover_style(|s| s.background(tokens().colors.hover))
+                    .on_resize(move |rect| {
+                        item_rects.borrow_mut().insert(index, rect);
+                    })
+                    .on_click(move |_| {
+                        on_pick(index);
+                        true
+                    })
+            },
+        )
+        .style(|s| s.flex_col().width_pct(100.0)),
+    )
+    .auto_scroll_on_hover(POPUP_AUTO_SCROLL_ZONE)
+    .on_ensure_visible(move || {
+        highlighted
+            .get()
+            .and_then(|index| item_rects.borrow().get(&index).copied())
+            .unwrap_or_default()
+    })
+    .style(move |s| {
+        s.min_width(header_width.get() as f32)
+            .max_height(POPUP_MAX_HEIGHT)
+            .background(tokens().colors.surface)
+            .border(1.0)
+            .border_color(tokens().colors.border)
+            .apply_if(style_items().is_empty(), |s| s.hide())
+    })
+}
+
+/// Shows the popup below `header` while `open` is set, closing it on a click outside of both
+fn with_popup<V: View + 'static, T: Display + 'static>(
+    header: V,
+    items: Rc<dyn Fn() -> Vec<T>>,
+    open: RwSignal<bool>,
+    highlighted: RwSignal<Option<usize>>,
+    on_pick: impl Fn(usize) + 'static,
+) -> Popover {
+    let header_width = create_rw_signal(0.0);
+    let on_pick: Rc<dyn Fn(usize)> = Rc::new(on_pick);
+    let header = header.on_resize(move |rect| header_width.set(rect.width()));
+    popover_with_open(
+        header,
+        open,
+        move || popup(items.clone(), header_width, highlighted, on_pick.clone()),
+        Placement::BottomStart,
+    )
+}
+
+/// Shows the item that `selected` holds, and opens a list of all the `items` below it
+/// when clicked. Picking an item from the list sets `selected` and closes it again,
+/// as does clicking anywhere else. The list is shown in the window's overlay layer,
+/// so it isn't clipped by the views the dropdown is in.
+///
+/// While the dropdown has focus, the up and down arrows move the highlight through the list,
+/// Enter or Space picks the highlighted item, and Escape closes the list without changing
+/// the selection. Typing the start of an item highlights it, or selects it straight away
+/// if the list isn't open.
+pub fn dropdown<T: Display + Clone + PartialEq + 'static>(
+    items: impl Fn() -> Vec<T> + 'static,
+    selected: RwSignal<T>,
+) -> Popover {
+    let items: Rc<dyn Fn() -> Vec<T>> = Rc::new(items);
+    let open = create_rw_signal(false);
+    let highlighted = create_rw_signal(None);
+    let type_ahead = Rc::new(RefCell::new((String::new(), Instant::now())));
+
+    let pick = {
+        let items = items.clone();
+        move |index: usize| {
+            if let Some(item) = items().get(index) {
+                selected.set(item.clone());
+            }
+            open.set(false);
+        }
+    };
+    let selected_index = {
+        let items = items.clone();
+        move || {
+            items()
+                .iter()
+                .position(|item| selected.with_untracked(|selected| selected == item))
+        }
+    };
+
+    let header_pick = pick.clone();
+    let header_selected_index = selected_index.clone();
+    let key_items = items.clone();
+    let header = label(move || format!("{} ▾", selected.get()))
+        .base_style(|s| {
+            let tokens = tokens();
+            s.padding_horiz(tokens.spacing.md)
+                .padding_vert(tokens.spacing.sm)
+                .border(1.0)
+                .border_color(tokens.colors.border)
+                .border_radius(tokens.radius.md)
+        })
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            if open.get_untracked() {
+                match highlighted.get_untracked() {
+                    Some(index) => header_pick(index),
+                    None => open.set(false),
+                }
+            } else {
+                highlighted.set(header_selected_index());
+                open.set(true);
+            }
+            true
+        })
+        .on_event(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                match &key.logical_key {
+                    Key::ArrowDown | Key::ArrowUp => {
+                        if !open.get_untracked() {
+                            highlighted.set(selected_index());
+                            open.set(true);
+                        } else {
+                            let down = key.logical_key == Key::ArrowDown;
+                            move_highlight(highlighted, key_items().len(), down);
+                        }
+                        true
+                    }
+                    Key::Escape if open.get_untracked() => {
+                        open.set(false);
+                        true
+                    }
+                    Key::Character(c) => {
+                        let mut type_ahead = type_ahead.borrow_mut();
+                        if type_ahead.1.elapsed() > TYPE_AHEAD_TIMEOUT {
+                            type_ahead.0.clear();
+                        }
+                        type_ahead.0.push_str(&c.to_lowercase());
+                        type_ahead.1 = Instant::now();
+
+                        let items = key_items();
+                        let found = items.iter().position(|item| {
+                            item.to_string().to_lowercase().starts_with(&type_ahead.0)
+                        });
+                        if let Some(index) = found {
+                            if open.get_untracked() {
+                                highlighted.set(Some(index));
+                            } else {
+                                selected.set(items[index].clone());
+                            }
+                        }
+                        true
+                    }
+                    _ => false,
+                }
+            } else {
+                false
+            }
+        });
+
+    with_popup(header, items, open, highlighted, pick)
+}
+
+/// A text input bound to `text`, with a list of the `items` containing what's been typed
+/// shown below it. Picking an item from the list, by clicking it or by highlighting it with
+/// the up and down arrows and pressing Enter, replaces `text` with it.
+/// Anything else can still be typed in, the items are only suggestions.
+pub fn combobox<T: Display + 'static>(
+    items: impl Fn() -> Vec<T> + 'static,
+    text: RwSignal<String>,
+) -> Popover {
+    let open = create_rw_signal(false);
+    let highlighted = create_rw_signal(None);
+    let matching: Rc<dyn Fn() -> Vec<String>> = Rc::new(move || {
+        let filter = text.with(|text| text.to_lowercase());
+        items()
+            .iter()
+            .map(|item| item.to_string())
+            .filter(|item| item.to_lowercase().contains(&filter))
+            .collect()
+    });
+
+    let pick = {
+        let matching = matching.clone();
+        move |index: usize| {
+            if let Some(item) = matching().into_iter().nth(index) {
+                text.set(item);
+            }
+            open.set(false);
+        }
+    };
+
+    // the list is only opened by typing, not when the app sets the text
+    let focused = create_rw_signal(false);
+    let submit_pick = pick.clone();
+    let input = text_input(text)
+        .keyboard_navigatable()
+        .on_focus(move || focused.set(true))
+        .on_blur(move |_| focused.set(false))
+        .on_change(move |_| {
+            if focused.get_untracked() {
+                highlighted.set(None);
+                open.set(true);
+            }
+        })
+        .on_submit(move |_| {
+            if open.get_untracked() {
+                if let Some(index) = highlighted.get_untracked() {
+                    submit_pick(index);
+                }
+                open.set(false);
+            }
+        });
+
+    let key_matching = matching.clone();
+    with_popup(input, matching, open, highlighted, pick).on_event(
+        EventListener::KeyDown,
+        move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                match key.logical_key {
+                    Key::ArrowDown | Key::ArrowUp => {
+                        let down = key.logical_key == Key::ArrowDown;
+                        if open.get_untracked() {
+                            move_highlight(highlighted, key_matching().len(), down);
+                        } else {
+                            highlighted.set(None);
+                            open.set(true);
+                        }
+                        true
+                    }
+                    Key::Escape => {
+                        open.set(false);
+                        false
+                    }
+                    _ => false,
+                }
+            } else {
+                false
+            }
+        },
+    )
+}
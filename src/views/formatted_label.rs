@@ -0,0 +1,159 @@
+use std::{
+    rc::Rc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use floem_reactive::{create_effect, create_rw_signal, untrack};
+
+use crate::{
+    action::exec_after,
+    views::{label, Label},
+};
+
+const BYTE_UNITS: [&str; 7] = ["B", "KB", "MB", "GB", "TB", "PB", "EB"];
+
+/// How a [formatted_label] shows its number
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Formatter {
+    /// With `decimals` digits after the point and the thousands grouped with commas, like
+    /// `1,234,567.89`
+    Number { decimals: usize },
+    /// With `decimals` digits after the point and nothing else, like `1234567.89`
+    Fixed(usize),
+    /// A fraction as a percentage with `decimals` digits after the point, so `0.256` with
+    /// one decimal is `25.6%`
+    Percent { decimals: usize },
+    /// A number of bytes in the largest unit it's at least one of, counting 1024 of each
+    /// unit as one of the next, like `1.5 MB`
+    Bytes,
+    /// A time in seconds since the Unix epoch, as how long ago it was or how long until it
+    /// is, like `3 min ago`. The label is refreshed as the time goes by.
+    RelativeTime,
+}
+
+impl Formatter {
+    /// The text for `value`, with a relative time being relative to now
+    pub fn format(&self, value: f64) -> String {
+        match *self {
+            Formatter::Number { decimals } => group_thousands(&format!("{value:.decimals$}")),
+            Formatter::Fixed(decimals) => format!("{value:.decimals$}"),
+            Formatter::Percent { decimals } => format!("{:.decimals$}%", value * 100.0),
+            Formatter::Bytes => format_bytes(value),
+            Formatter::RelativeTime => format_relative_time(value - now_seconds()),
+        }
+    }
+}
+
+/// A label showing `value` as `formatter` formats it, updated whenever `value` changes
+pub fn formatted_label(value: impl Fn() -> f64 + 'static, formatter: Formatter) -> Label {
+    if formatter != Formatter::RelativeTime {
+        return label(move || formatter.format(value()));
+    }
+
+    // "3 min ago" becomes "4 min ago" without the time changing
+    let refresh = create_rw_signal(());
+    let text = {
+        let value = Rc::new(value);
+        let schedule_value = value.clone();
+        create_effect(move |_| {
+            refresh.track();
+            let age = (now_seconds() - untrack(|| schedule_value())).abs();
+            exec_after(refresh_interval(age), move |_| {
+                // stops once the label is gone
+                if refresh.try_get_untracked().is_some() {
+                    refresh.set(());
+                }
+            });
+        });
+        move || {
+            refresh.track();
+            formatter.format(value())
+        }
+    };
+    label(text)
+}
+
+fn now_seconds() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+/// How often a relative time this far from now changes its text
+fn refresh_interval(age: f64) -> Duration {
+    if age < 60.0 * 60.0 {
+        Duration::from_secs(15)
+    } else {
+        Duration::from_secs(60)
+    }
+}
+
+/// `number` with commas between the groups of three digits before the point
+fn group_thousands(number: &str) -> String {
+    let (sign, number) = match number.strip_prefix('-') {
+        Some(number) => ("-", number),
+        None => ("", number),
+    };
+    let (integer, fraction) = match number.find('.') {
+        Some(point) => number.split_at(point),
+        None => (number, ""),
+    };
+    if !integer.bytes().all(|byte| byte.is_ascii_digit()) {
+        // inf and NaN
+        return format!("{sign}{number}");
+    }
+    let mut grouped = String::with_capacity(number.len() + integer.len() / 3);
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{sign}{grouped}{fraction}")
+}
+
+fn format_bytes(bytes: f64) -> String {
+    let mut size = bytes.abs();
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < BYTE_UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    let sign = if bytes < 0.0 { "-" } else { "" };
+    if unit == 0 {
+        format!("{sign}{size:.0} {}", BYTE_UNITS[unit])
+    } else {
+        format!("{sign}{size:.1} {}", BYTE_UNITS[unit])
+    }
+}
+
+/// `offset` seconds from now, being in the past when it's negative
+fn format_relative_time(offset: f64) -> String {
+    let age = offset.abs();
+    if age < 45.0 {
+        return "just now".to_string();
+    }
+    let (count, unit) = if age < 60.0 * 60.0 {
+        ((age / 60.0).round().max(1.0), "min")
+    } else if age < 60.0 * 60.0 * 24.0 {
+        ((age / (60.0 * 60.0)).round(), "h")
+    } else if age < 60.0 * 60.0 * 24.0 * 30.0 {
+        ((age / (60.0 * 60.0 * 24.0)).round(), "day")
+    } else if age < 60.0 * 60.0 * 24.0 * 365.0 {
+        ((age / (60.0 * 60.0 * 24.0 * 30.0)).round(), "month")
+    } else {
+        ((age / (60.0 * 60.0 * 24.0 * 365.0)).round(), "year")
+    };
+    // min and h aren't words, so they're the same for one and for more
+    let plural = if count != 1.0 && matches!(unit, "day" | "month" | "year") {
+        "s"
+    } else {
+        ""
+    };
+    if offset < 0.0 {
+        format!("{count} {unit}{plural} ago")
+    } else {
+        format!("in {count} {unit}{plural}")
+    }
+}
@@ -2,7 +2,8 @@ use std::{any::Any, fmt::Display};
 
 use crate::{
     cosmic_text::{Attrs, AttrsList, FamilyOwned, TextLayout},
-    style::{ComputedStyle, TextOverflow},
+    fonts::font_generation,
+    style::{ComputedStyle, TextDecoration, TextOverflow, TextTransform},
     unit::PxPct,
 };
 use floem_reactive::create_effect;
@@ -24,7 +25,11 @@ use crate::{
 
 pub struct Label {
     id: Id,
+    /// The text the label was given
+    source: String,
+    /// The text that's shown, which is `source` with the text transform of the style
     label: String,
+    text_transform: Option<TextTransform>,
     text_layout: Option<TextLayout>,
     text_node: Option<Node>,
     available_text: Option<String>,
@@ -36,9 +41,26 @@ pub struct Label {
     font_weight: Option<Weight>,
     font_style: Option<FontStyle>,
     line_height: Option<LineHeightValue>,
+    letter_spacing: Option<f64>,
     text_overflow: TextOverflow,
+    auto_fit: Option<AutoFit>,
+    /// The font size picked by [Label::auto_fit_text], replacing the inherited one
+    fitted_font_size: Option<f32>,
+    /// The available width and height the font size was last fitted to
+    fitted_for: Option<(f32, f32)>,
+    /// The fonts the text was laid out with, see [font_generation]
+    font_generation: u64,
 }
 
+#[derive(Clone, Copy)]
+struct AutoFit {
+    min_size: f32,
+    max_size: f32,
+}
+
+/// Auto fitting stops narrowing down the font size once it's within this many pixels
+const AUTO_FIT_PRECISION: f32 = 0.5;
+
 pub fn text<S: Display>(text: S) -> Label {
     let text = text.to_string();
     label(move || text.clone())
@@ -52,7 +74,9 @@ pub fn label<S: Display + 'static>(label: impl Fn() -> S + 'static) -> Label {
     });
     Label {
         id,
+        source: "".to_string(),
         label: "".to_string(),
+        text_transform: None,
         text_layout: None,
         text_node: None,
         available_text: None,
@@ -64,14 +88,46 @@ pub fn label<S: Display + 'static>(label: impl Fn() -> S + 'static) -> Label {
         font_weight: None,
         font_style: None,
         line_height: None,
+        letter_spacing: None,
         text_overflow: TextOverflow::Wrap,
+        auto_fit: None,
+        fitted_font_size: None,
+        fitted_for: None,
+        font_generation: font_generation(),
     }
 }
 
 impl Label {
+    fn transformed(&self) -> String {
+        match self.text_transform {
+            Some(transform) => transform.apply(&self.source),
+            None => self.source.clone(),
+        }
+    }
+
+    /// Picks the largest font size between `min_size` and `max_size` that lets the text fit
+    /// the label, instead of using the `font_size` style, and picks it again when the label
+    /// is resized.
+    ///
+    /// The label's size has to come from its style or its parent, since it no longer follows
+    /// the text. Text that wraps is fit to both the width and the height of the label,
+    /// while text set to clip or use an ellipsis is fit to a single line of the width.
+    pub fn auto_fit_text(mut self, min_size: f32, max_size: f32) -> Self {
+        self.auto_fit = Some(AutoFit {
+            min_size,
+            max_size: max_size.max(min_size),
+        });
+        self.fitted_for = None;
+        self
+    }
+
     fn get_attrs_list(&self) -> AttrsList {
+        self.attrs_list_with_font_size(self.fitted_font_size.or(self.font_size))
+    }
+
+    fn attrs_list_with_font_size(&self, font_size: Option<f32>) -> AttrsList {
         let mut attrs = Attrs::new().color(self.color.unwrap_or(Color::BLACK));
-        if let Some(font_size) = self.font_size {
+        if let Some(font_size) = font_size {
             attrs = attrs.font_size(font_size);
         }
         if let Some(font_style) = self.font_style {
@@ -97,6 +153,11 @@ impl Label {
         let mut text_layout = TextLayout::new();
         let attrs_list = self.get_attrs_list();
         text_layout.set_text(self.label.as_str(), attrs_list.clone());
+        if let (Some(_), Some((width, _))) = (self.auto_fit, self.fitted_for) {
+            if self.text_overflow == TextOverflow::Wrap {
+                text_layout.set_size(width, f32::MAX);
+            }
+        }
         self.text_layout = Some(text_layout);
 
         if let Some(new_text) = self.available_text.as_ref() {
@@ -105,6 +166,43 @@ impl Label {
             self.available_text_layout = Some(text_layout);
         }
     }
+
+    /// Finds the font size for [Label::auto_fit_text] with a binary search, returning it
+    /// together with the text laid out at that size
+    fn fit_text(&self, auto_fit: AutoFit, width: f32, height: f32) -> (f32, TextLayout) {
+        let wrap = self.text_overflow == TextOverflow::Wrap;
+        let text_at = |font_size: f32| {
+            let mut text_layout = TextLayout::new();
+            text_layout.set_text(
+                self.label.as_str(),
+                self.attrs_list_with_font_size(Some(font_size)),
+            );
+            if wrap {
+                text_layout.set_size(width, f32::MAX);
+            }
+            text_layout
+        };
+        let fits = |text_layout: &TextLayout| {
+            let size = text_layout.size();
+            size.width as f32 <= width && (!wrap || size.height as f32 <= height)
+        };
+
+        let largest = text_at(auto_fit.max_size);
+        if fits(&largest) {
+            return (auto_fit.max_size, largest);
+        }
+        // the smallest size is used even if the text doesn't fit at it
+        let (mut low, mut high) = (auto_fit.min_size, auto_fit.max_size);
+        while high - low > AUTO_FIT_PRECISION {
+            let mid = (low + high) / 2.0;
+            if fits(&text_at(mid)) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low, text_at(low))
+    }
 }
 
 impl View for Label {
@@ -129,13 +227,15 @@ impl View for Label {
     }
 
     fn debug_name(&self) -> std::borrow::Cow<'static, str> {
-        format!("Label: {:?}", self.label).into()
+        format!("Label: {:?}", self.source).into()
     }
 
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) -> ChangeFlags {
         if let Ok(state) = state.downcast() {
-            self.label = *state;
+            self.source = *state;
+            self.label = self.transformed();
             self.text_layout = None;
+            self.fitted_for = None;
             self.available_text = None;
             self.available_width = None;
             self.available_text_layout = None;
@@ -152,9 +252,19 @@ impl View for Label {
 
     fn layout(&mut self, cx: &mut crate::context::LayoutCx) -> taffy::prelude::Node {
         cx.layout_node(self.id, true, |cx| {
+            if self.text_transform != cx.text_transform {
+                self.text_transform = cx.text_transform;
+                self.label = self.transformed();
+                self.text_layout = None;
+                self.available_text = None;
+                self.available_width = None;
+                self.available_text_layout = None;
+                self.fitted_for = None;
+            }
             let (width, height) = if self.label.is_empty() {
                 (0.0, cx.current_font_size().unwrap_or(14.0))
             } else {
+                self.letter_spacing = cx.letter_spacing;
                 let text_overflow = cx.app_state_mut().get_computed_style(self.id).text_overflow;
                 if self.color != cx.color
                     || self.font_size != cx.font_size
@@ -163,6 +273,7 @@ impl View for Label {
                     || self.font_style != cx.font_style
                     || self.line_height != cx.line_height
                     || self.text_overflow != text_overflow
+                    || self.font_generation != font_generation()
                 {
                     self.color = cx.color;
                     self.font_size = cx.font_size;
@@ -171,10 +282,12 @@ impl View for Label {
                     self.font_style = cx.font_style;
                     self.line_height = cx.line_height;
                     self.text_overflow = text_overflow;
+                    self.font_generation = font_generation();
                     self.text_layout = None;
                     self.available_text = None;
                     self.available_width = None;
                     self.available_text_layout = None;
+                    self.fitted_for = None;
                     self.set_text_layout();
                 }
                 if self.text_layout.is_none() {
@@ -182,7 +295,12 @@ impl View for Label {
                 }
                 let text_layout = self.text_layout.as_ref().unwrap();
                 let size = text_layout.size();
-                let width = size.width.ceil() as f32;
+                // an auto fit label gets its width from outside, and the text follows it
+                let width = if self.auto_fit.is_some() {
+                    0.0
+                } else {
+                    spaced_width(text_layout, self.letter_spacing).ceil() as f32
+                };
                 let mut height = size.height as f32;
 
                 if text_overflow == TextOverflow::Wrap {
@@ -242,8 +360,36 @@ impl View for Label {
         };
         let padding = padding_left + padding_right;
 
+        if let Some(auto_fit) = self.auto_fit {
+            let padding_top = match style.padding_top {
+                PxPct::Px(padding) => padding as f32,
+                PxPct::Pct(pct) => pct as f32 * layout.size.width,
+            };
+            let padding_bottom = match style.padding_bottom {
+                PxPct::Px(padding) => padding as f32,
+                PxPct::Pct(pct) => pct as f32 * layout.size.width,
+            };
+            let available = (
+                layout.size.width - padding,
+                layout.size.height - padding_top - padding_bottom,
+            );
+            if self.fitted_for != Some(available) {
+                let (font_size, text_layout) = self.fit_text(auto_fit, available.0, available.1);
+                self.fitted_for = Some(available);
+                self.text_layout = Some(text_layout);
+                self.available_text = None;
+                self.available_width = None;
+                self.available_text_layout = None;
+                if self.fitted_font_size != Some(font_size) {
+                    self.fitted_font_size = Some(font_size);
+                    cx.app_state_mut().request_layout(self.id());
+                }
+            }
+            return None;
+        }
+
         let text_layout = self.text_layout.as_ref().unwrap();
-        let width = text_layout.size().width as f32;
+        let width = spaced_width(text_layout, self.letter_spacing) as f32;
         let available_width = layout.size.width - padding;
         if text_overflow == TextOverflow::Ellipsis {
             if width > available_width {
@@ -251,10 +397,9 @@ impl View for Label {
                     let mut dots_text = TextLayout::new();
                     dots_text.set_text("...", self.get_attrs_list());
 
-                    let dots_width = dots_text.size().width as f32;
+                    let dots_width = spaced_width(&dots_text, self.letter_spacing) as f32;
                     let width_left = available_width - dots_width;
-                    let hit_point = text_layout.hit_point(Point::new(width_left as f64, 0.0));
-                    let index = hit_point.index;
+                    let index = spaced_index(text_layout, width_left as f64, self.letter_spacing);
 
                     let new_text = if index > 0 {
                         format!("{}...", &self.label[..index])
@@ -314,10 +459,85 @@ impl View for Label {
         let text_node = self.text_node.unwrap();
         let location = cx.app_state.taffy.layout(text_node).unwrap().location;
         let point = Point::new(location.x as f64, location.y as f64);
-        if let Some(text_layout) = self.available_text_layout.as_ref() {
-            cx.draw_text(text_layout, point);
-        } else {
-            cx.draw_text(self.text_layout.as_ref().unwrap(), point);
+        let text_layout = self
+            .available_text_layout
+            .as_ref()
+            .unwrap_or_else(|| self.text_layout.as_ref().unwrap());
+        paint_text(cx, text_layout, point);
+    }
+}
+
+/// The width of `text_layout` with `letter_spacing` after each of its glyphs
+pub(crate) fn spaced_width(text_layout: &TextLayout, letter_spacing: Option<f64>) -> f64 {
+    let width = text_layout.size().width;
+    match letter_spacing {
+        Some(letter_spacing) => {
+            let glyphs = text_layout
+                .layout_runs()
+                .map(|run| run.glyphs.len())
+                .max()
+                .unwrap_or(0);
+            width + letter_spacing * glyphs as f64
+        }
+        None => width,
+    }
+}
+
+/// The index in the text of `text_layout` of the first glyph of its first line that ends
+/// after `x`, when it's drawn with `letter_spacing`
+fn spaced_index(text_layout: &TextLayout, x: f64, letter_spacing: Option<f64>) -> usize {
+    let Some(letter_spacing) = letter_spacing else {
+        return text_layout.hit_point(Point::new(x, 0.0)).index;
+    };
+    let Some(run) = text_layout.layout_runs().next() else {
+        return 0;
+    };
+    run.glyphs
+        .iter()
+        .enumerate()
+        .find(|(i, glyph)| (glyph.x + glyph.w) as f64 + letter_spacing * (*i + 1) as f64 > x)
+        .map(|(_, glyph)| glyph.start)
+        .unwrap_or(run.text.len())
+}
+
+/// Draws `text_layout` at `point` with the letter spacing and text decoration the view
+/// inherits
+pub(crate) fn paint_text(cx: &mut crate::context::PaintCx, text_layout: &TextLayout, point: Point) {
+    let letter_spacing = cx.letter_spacing.unwrap_or(0.0);
+    cx.draw_text_spaced(text_layout, point, letter_spacing);
+    let decoration = cx.text_decoration.unwrap_or_default();
+    paint_text_decoration(cx, text_layout, point, decoration, letter_spacing);
+}
+
+/// Draws the lines of `decoration` along the text of `text_layout`, drawn at `point`
+fn paint_text_decoration(
+    cx: &mut crate::context::PaintCx,
+    text_layout: &TextLayout,
+    point: Point,
+    decoration: TextDecoration,
+    letter_spacing: f64,
+) {
+    if !decoration.underline && !decoration.line_through {
+        return;
+    }
+    for run in text_layout.layout_runs() {
+        let (Some(first), Some(last)) = (run.glyphs.first(), run.glyphs.last()) else {
+            continue;
+        };
+        let font_size = first.font_size as f64;
+        let color = decoration.color.unwrap_or(first.color);
+        let thickness = (font_size / 14.0).max(1.0);
+        let spacing = letter_spacing * (run.glyphs.len() - 1) as f64;
+        let x0 = point.x + first.x as f64;
+        let x1 = point.x + (last.x + last.w) as f64 + spacing;
+        let baseline = point.y + run.line_y as f64;
+        if decoration.underline {
+            let y = baseline + font_size * 0.1;
+            cx.fill(&Rect::new(x0, y, x1, y + thickness), color, 0.0);
+        }
+        if decoration.line_through {
+            let y = baseline - font_size * 0.3;
+            cx.fill(&Rect::new(x0, y, x1, y + thickness), color, 0.0);
         }
     }
 }
@@ -0,0 +1,72 @@
+use std::rc::Rc;
+
+use floem_reactive::{create_rw_signal, RwSignal};
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    style::CursorStyle,
+    theme::tokens,
+    views::{composite, label, Composite, Decorators},
+};
+
+/// Text that's clicked like a hyperlink. See [`link`]
+pub type Link = Composite<LinkState>;
+
+pub struct LinkState {
+    visited: RwSignal<bool>,
+}
+
+/// Text in the primary color showing `text`, which calls `on_activate` when it's clicked,
+/// or when Enter is pressed while it has focus. It's underlined while it's hovered, and
+/// once it's been activated it's [visited](Link::visited) and shown in a lighter color.
+///
+/// To open a web page, `on_activate` can call [open_url](crate::action::open_url).
+pub fn link(text: impl Fn() -> String + 'static, on_activate: impl Fn() + 'static) -> Link {
+    let visited = create_rw_signal(false);
+    let on_activate = Rc::new(on_activate);
+    let key_activate = on_activate.clone();
+
+    composite("Link", label(text), LinkState { visited })
+        .base_style(move |s| {
+            let primary = tokens().colors.primary;
+            s.cursor(CursorStyle::Pointer)
+                .color(primary)
+                .apply_if(visited.get(), |s| s.color(primary.with_alpha_factor(0.7)))
+        })
+        .hover_style(|s| s.cursor(CursorStyle::Pointer).underline())
+        .focus_visible_style(|s| {
+            s.border(1.0)
+                .border_radius(tokens().radius.sm)
+                .border_color(tokens().colors.primary)
+        })
+        .disabled_style(|s| {
+            s.cursor(CursorStyle::Default)
+                .color(tokens().colors.text_muted)
+        })
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            visited.set(true);
+            on_activate();
+            true
+        })
+        .on_event(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                if key.logical_key == Key::Enter {
+                    visited.set(true);
+                    key_activate();
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+impl Link {
+    /// Whether the link has been activated, which can be set to show links that were
+    /// visited before
+    pub fn visited(&self) -> RwSignal<bool> {
+        self.state().visited
+    }
+}
@@ -1,9 +1,11 @@
 use std::{
+    cell::Cell,
     hash::{BuildHasherDefault, Hash},
     marker::PhantomData,
+    rc::Rc,
 };
 
-use floem_reactive::{as_child_of_current_scope, create_effect, Scope};
+use floem_reactive::{as_child_of_current_scope, create_effect, RwSignalVec, Scope, VecChange};
 use kurbo::Rect;
 use rustc_hash::FxHasher;
 use smallvec::SmallVec;
@@ -78,6 +80,49 @@ where
     }
 }
 
+/// A [list] of the items of `items`. As `items` is edited, the views of the items that were
+/// inserted, removed or swapped are changed one by one, without comparing all the items with
+/// the ones before, so the items don't need a key.
+pub fn signal_vec_list<T, VF, V>(items: RwSignalVec<T>, view_fn: VF) -> List<V, T>
+where
+    T: Clone + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: View + 'static,
+{
+    let id = Id::next();
+    let initial = items.with_untracked(|items| items.clone());
+    let len = Rc::new(Cell::new(initial.len()));
+    id.update_state(Diff::reset(initial), false);
+
+    items.on_change(move |change| {
+        let diff = match change {
+            VecChange::Insert { at, value } => {
+                let diff = Diff::insert(*at, len.get(), value.clone());
+                len.set(len.get() + 1);
+                diff
+            }
+            VecChange::Remove { at } => {
+                len.set(len.get() - 1);
+                Diff::remove(*at)
+            }
+            VecChange::Swap { a, b } => Diff::swap(*a, *b),
+            VecChange::Reset(values) => {
+                len.set(values.len());
+                Diff::reset(values.clone())
+            }
+        };
+        id.update_state(diff, false);
+    });
+
+    let view_fn = Box::new(as_child_of_current_scope(view_fn));
+    List {
+        id,
+        children: Vec::new(),
+        view_fn,
+        phantom: PhantomData,
+    }
+}
+
 impl<V: View + 'static, T> View for List<V, T> {
     fn id(&self) -> Id {
         self.id
@@ -174,23 +219,23 @@ impl<V: View + 'static, T> View for List<V, T> {
         id_path: Option<&[Id]>,
         event: crate::event::Event,
     ) -> bool {
-        for child in self.children.iter_mut() {
-            if let Some((child, _)) = child.as_mut() {
-                let id = child.id();
-                if cx.should_send(id, &event) && child.event_main(cx, id_path, event.clone()) {
-                    return true;
-                }
-            }
-        }
-        false
+        let children = self
+            .children
+            .iter_mut()
+            .filter_map(|child| child.as_mut())
+            .map(|(child, _)| child as &mut dyn View)
+            .collect();
+        cx.event_children(children, id_path, &event)
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        for child in self.children.iter_mut() {
-            if let Some((child, _)) = child.as_mut() {
-                child.paint_main(cx);
-            }
-        }
+        let children = self
+            .children
+            .iter_mut()
+            .filter_map(|child| child.as_mut())
+            .map(|(child, _)| child as &mut dyn View)
+            .collect();
+        cx.paint_children(children);
     }
 }
 
@@ -217,6 +262,53 @@ impl<V> Diff<V> {
     pub fn is_empty(&self) -> bool {
         self.removed.is_empty() && self.moved.is_empty() && self.added.is_empty() && !self.clear
     }
+
+    /// Inserts `view` at `at` into `len` items, moving the ones after it
+    fn insert(at: usize, len: usize, view: V) -> Self {
+        Self {
+            moved: (at..len)
+                .map(|from| DiffOpMove { from, to: from + 1 })
+                .collect(),
+            added: SmallVec::from_iter([DiffOpAdd {
+                at,
+                view: Some(view),
+            }]),
+            ..Default::default()
+        }
+    }
+
+    fn remove(at: usize) -> Self {
+        Self {
+            removed: SmallVec::from_iter([DiffOpRemove { at }]),
+            ..Default::default()
+        }
+    }
+
+    fn swap(a: usize, b: usize) -> Self {
+        Self {
+            moved: SmallVec::from_iter([
+                DiffOpMove { from: a, to: b },
+                DiffOpMove { from: b, to: a },
+            ]),
+            ..Default::default()
+        }
+    }
+
+    /// Replaces all the items with `views`
+    fn reset(views: Vec<V>) -> Self {
+        Self {
+            added: views
+                .into_iter()
+                .enumerate()
+                .map(|(at, view)| DiffOpAdd {
+                    at,
+                    view: Some(view),
+                })
+                .collect(),
+            clear: true,
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Debug)]
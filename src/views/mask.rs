@@ -0,0 +1,70 @@
+use std::{f64::consts::PI, rc::Rc};
+
+use kurbo::{Affine, BezPath, Ellipse, Point, Shape, Size};
+use sha2::{Digest, Sha256};
+
+/// How many line segments make up the outline of [Mask::squircle]
+const SQUIRCLE_SEGMENTS: usize = 64;
+
+/// A shape that a view's painting is cut to with [mask](crate::views::Decorators::mask).
+/// Everything painted by the view and its children outside of the shape is left out,
+/// including its background and borders.
+#[derive(Clone)]
+pub enum Mask {
+    /// A path in the view's own coordinates, built from the view's size so it follows
+    /// the view when it's resized
+    Path(Rc<dyn Fn(Size) -> BezPath>),
+    /// An encoded image stretched over the view, where how opaque each pixel is decides
+    /// how much of the view shows through it
+    Image { data: Rc<[u8]>, hash: Rc<[u8]> },
+}
+
+impl Mask {
+    /// A mask in the shape that `path` returns for the size of the view
+    pub fn path(path: impl Fn(Size) -> BezPath + 'static) -> Self {
+        Mask::Path(Rc::new(path))
+    }
+
+    /// An ellipse filling the view, which makes a circle of a square view like an avatar
+    pub fn ellipse() -> Self {
+        Mask::path(|size| Ellipse::from_rect(size.to_rect()).to_path(0.1))
+    }
+
+    /// A superellipse filling the view, the rounded square shape of app icons,
+    /// with flatter sides and smoother corners than a rounded rect
+    pub fn squircle() -> Self {
+        Mask::path(|size| {
+            let (rx, ry) = (size.width / 2.0, size.height / 2.0);
+            let mut path = BezPath::new();
+            for i in 0..SQUIRCLE_SEGMENTS {
+                let angle = 2.0 * PI * i as f64 / SQUIRCLE_SEGMENTS as f64;
+                let (sin, cos) = angle.sin_cos();
+                // |x|^4 + |y|^4 = 1 in the unit square
+                let point = Point::new(
+                    cos.signum() * cos.abs().sqrt(),
+                    sin.signum() * sin.abs().sqrt(),
+                );
+                let point = Affine::new([rx, 0.0, 0.0, ry, rx, ry]) * point;
+                if i == 0 {
+                    path.move_to(point);
+                } else {
+                    path.line_to(point);
+                }
+            }
+            path.close_path();
+            path
+        })
+    }
+
+    /// An alpha mask from an encoded image, in any format [img](crate::views::img) can show.
+    /// An image that can't be decoded leaves the view unmasked.
+    pub fn image(data: Vec<u8>) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(&data);
+        let hash = hasher.finalize().to_vec();
+        Mask::Image {
+            data: data.into(),
+            hash: hash.into(),
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use std::{collections::HashMap, hash::Hash, rc::Rc};
+
+use floem_reactive::{create_memo, create_rw_signal, RwSignal};
+use kurbo::Rect;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    view::{ChangeFlags, View},
+    views::{container_box, dyn_container, empty, list, ContainerBox, Decorators},
+};
+
+/// How far outside of the viewport the items of a virtualized masonry are still built, so
+/// they're there before they're scrolled in
+const OVERSCAN: f64 = 200.0;
+
+/// Items of different heights packed into columns. See [`masonry`]
+pub struct Masonry {
+    id: Id,
+    child: ContainerBox,
+    gap: RwSignal<f64>,
+    virtualize: RwSignal<bool>,
+    /// The part of the masonry that's shown by the enclosing scroll
+    viewport: RwSignal<Rect>,
+}
+
+/// Lays out a view from `view_fn` for each item from `each_fn` in `column_count` columns of
+/// the same width, putting each item at the bottom of the shortest column so far. This is
+/// how a gallery of images of different heights is usually laid out.
+///
+/// The items are measured once they're laid out, so they're packed again whenever their
+/// heights change, which includes every time the masonry changes width.
+///
+/// With [virtualize](Masonry::virtualize), the items that have been measured and aren't in
+/// the viewport of the enclosing [scroll](crate::views::scroll()) are left out, keeping
+/// their place.
+pub fn masonry<IF, I, T, KF, K, VF, V>(
+    each_fn: IF,
+    key_fn: KF,
+    view_fn: VF,
+    column_count: impl Fn() -> usize + 'static,
+) -> Masonry
+where
+    IF: Fn() -> I + 'static,
+    I: IntoIterator<Item = T>,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + Clone + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: View + 'static,
+    T: Clone + 'static,
+{
+    let each_fn = Rc::new(each_fn);
+    let key_fn = Rc::new(key_fn);
+    let view_fn = Rc::new(view_fn);
+    let gap = create_rw_signal(0.0);
+    let virtualize = create_rw_signal(false);
+    let viewport = create_rw_signal(Rect::ZERO);
+    let width = create_rw_signal(0.0);
+    let heights: RwSignal<HashMap<K, f64>> = create_rw_signal(HashMap::new());
+
+    let columns = create_memo(move |_| column_count().max(1));
+    let column_width = create_memo(move |_| {
+        let columns = columns.get() as f64;
+        ((width.get() - gap.get() * (columns - 1.0)) / columns).max(0.0)
+    });
+    // the column and the top of each item, and the height of the tallest column
+    let placement = {
+        let each_fn = each_fn.clone();
+        let key_fn = key_fn.clone();
+        create_memo(move |_| {
+            let gap = gap.get();
+            let mut bottoms = vec![0.0; columns.get()];
+            let mut places = HashMap::new();
+            heights.with(|heights| {
+                for item in each_fn() {
+                    let key = key_fn(&item);
+                    let (column, bottom) = bottoms
+                        .iter()
+                        .copied()
+                        .enumerate()
+                        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                        .unwrap_or((0, 0.0));
+                    let height = heights.get(&key).copied().unwrap_or(0.0);
+                    bottoms[column] = bottom + height + gap;
+                    places.insert(key, (column, bottom));
+                }
+            });
+            let height = bottoms
+                .into_iter()
+                .map(|bottom| bottom - gap)
+                .fold(0.0, f64::max);
+            (places, height)
+        })
+    };
+
+    let item_key_fn = key_fn.clone();
+    let items = list(
+        move || each_fn(),
+        move |item| key_fn(item),
+        move |item| {
+            let key = item_key_fn(&item);
+            let place = {
+                let key = key.clone();
+                create_memo(move |_| placement.with(|(places, _)| places.get(&key).copied()))
+            };
+            let height = {
+                let key = key.clone();
+                create_memo(move |_| heights.with(|heights| heights.get(&key).copied()))
+            };
+            let shown = create_memo(move |_| {
+                if !virtualize.get() {
+                    return true;
+                }
+                match (place.get(), height.get()) {
+                    (Some((_, top)), Some(height)) => {
+                        let viewport = viewport.get().inflate(0.0, OVERSCAN);
+                        top < viewport.y1 && top + height > viewport.y0
+                    }
+                    _ => true,
+                }
+            });
+            let view_fn = view_fn.clone();
+            let content = dyn_container(
+                move || shown.get(),
+                move |shown| {
+                    if shown {
+                        Box::new(view_fn(item.clone()))
+                    } else {
+                        Box::new(empty())
+                    }
+                },
+            );
+            container_box(content)
+                .on_resize(move |rect| {
+                    // the items that are left out keep the height they had
+                    if shown.get_untracked() {
+                        heights.update(|heights| {
+                            heights.insert(key.clone(), rect.height());
+                        });
+                    }
+                })
+                .style(move |s| {
+                    let (column, top) = place.get().unwrap_or((0, 0.0));
+                    let column_width = column_width.get();
+                    s.absolute()
+                        .inset_left(column as f64 * (column_width + gap.get()))
+                        .inset_top(top)
+                        .width(column_width)
+                        .apply_if(!shown.get(), |s| s.height(height.get().unwrap_or(0.0)))
+                })
+        },
+    )
+    .style(move |s| s.width_full().height(placement.with(|(_, height)| *height)));
+
+    Masonry {
+        id: Id::next(),
+        child: container_box(items)
+            .on_resize(move |rect| width.set(rect.width()))
+            .style(|s| s.width_full()),
+        gap,
+        virtualize,
+        viewport,
+    }
+}
+
+impl Masonry {
+    /// The space between the columns, and between the items in a column
+    pub fn gap(self, gap: f64) -> Self {
+        self.gap.set(gap);
+        self
+    }
+
+    /// Leaves out the items that aren't near the viewport of the enclosing scroll, once
+    /// they've been measured. They're built again when they're scrolled back in.
+    pub fn virtualize(self, virtualize: bool) -> Self {
+        self.virtualize.set(virtualize);
+        self
+    }
+}
+
+impl View for Masonry {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.child.id() == id {
+            Some(&self.child)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.child.id() == id {
+            Some(&mut self.child)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.child]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.child]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Masonry".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, true, |cx| vec![self.child.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let viewport = cx.viewport.unwrap_or_default();
+        if self.virtualize.get_untracked() && self.viewport.get_untracked() != viewport {
+            self.viewport.set(viewport);
+        }
+        Some(self.child.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        if cx.should_send(self.child.id(), &event) {
+            self.child.event_main(cx, id_path, event)
+        } else {
+            false
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.child.paint_main(cx);
+    }
+}
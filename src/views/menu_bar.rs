@@ -0,0 +1,293 @@
+use std::rc::Rc;
+
+use floem_reactive::{create_effect, create_rw_signal, untrack, RwSignal};
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    menu::{Menu, MenuEntry},
+    overlay::Placement,
+    style::Style,
+    theme::tokens,
+    view::View,
+    views::{
+        container_box, dyn_container, empty, list, popover_with_open, scroll, stack, text,
+        ContainerBox, Decorators,
+    },
+};
+
+/// Menus taller than this scroll, and hovering near their top or bottom edge scrolls them
+const PANEL_MAX_HEIGHT: f64 = 480.0;
+/// How close to the top or bottom of a long menu the pointer has to be to scroll it
+const PANEL_AUTO_SCROLL_ZONE: f64 = 24.0;
+
+/// A menu entry with its action shared, so the views can be rebuilt when the menus change
+#[derive(Clone)]
+enum MenuNode {
+    Separator,
+    Item {
+        title: String,
+        accelerator: Option<String>,
+        checked: Option<bool>,
+        enabled: bool,
+        action: Option<Rc<dyn Fn()>>,
+    },
+    SubMenu {
+        title: String,
+        enabled: bool,
+        children: Rc<Vec<MenuNode>>,
+    },
+}
+
+fn menu_nodes(menu: Menu) -> Vec<MenuNode> {
+    menu.children
+        .into_iter()
+        .map(|entry| match entry {
+            MenuEntry::Separator => MenuNode::Separator,
+            MenuEntry::Item(item) => MenuNode::Item {
+                title: item.title,
+                accelerator: item.accelerator,
+                checked: item.selected,
+                enabled: item.enabled,
+                action: item.action.map(Rc::from),
+            },
+            MenuEntry::SubMenu(menu) => MenuNode::SubMenu {
+                title: menu.item.title.clone(),
+                enabled: menu.item.enabled,
+                children: Rc::new(menu_nodes(menu)),
+            },
+        })
+        .collect()
+}
+
+/// Whether the panel of an entry is shown, which is set while `is_open` holds. The panels close
+/// on a click outside of them, which clears it, and `close` is called then for `is_open` to follow.
+fn panel_open(is_open: impl Fn() -> bool + 'static, close: impl Fn() + 'static) -> RwSignal<bool> {
+    let is_open = Rc::new(is_open);
+    let shown = create_rw_signal(false);
+    {
+        let is_open = is_open.clone();
+        create_effect(move |_| shown.set(is_open()));
+    }
+    create_effect(move |_| {
+        if !shown.get() && untrack(|| is_open()) {
+            close();
+        }
+    });
+    shown
+}
+
+/// The dropdown of a menu. Submenus cascade out to the right of their entry while it's hovered,
+/// and picking an item anywhere below closes the whole menu by clearing `open`.
+/// Like the dropdown, they're shown in the overlay layer so they can go past the menu bar.
+fn menu_panel(entries: Rc<Vec<MenuNode>>, open: RwSignal<Option<usize>>) -> Box<dyn View> {
+    let hovered = create_rw_signal(None);
+    // start with all submenus closed every time a menu opens
+    create_effect(move |_| {
+        open.with(|_| ());
+        hovered.set(None);
+    });
+
+    // the submenus are overlays of their own, so the scroll doesn't clip them
+    Box::new(
+        scroll(
+            list(
+                move || entries.iter().cloned().enumerate().collect::<Vec<_>>(),
+                |(index, _)| *index,
+                move |(index, entry)| menu_entry(index, entry, hovered, open),
+            )
+            .style(|s| {
+                s.flex_col()
+                    .width_pct(100.0)
+                    .padding_vert(tokens().spacing.sm)
+            }),
+        )
+        .auto_scroll_on_hover(PANEL_AUTO_SCROLL_ZONE)
+        .style(|s| {
+            let tokens = tokens();
+            s.min_width(180.0)
+                .max_height(PANEL_MAX_HEIGHT)
+                .background(tokens.colors.surface)
+                .border(1.0)
+                .border_color(tokens.colors.border)
+                .border_radius(tokens.radius.md)
+        }),
+    )
+}
+
+fn row_style(s: Style) -> Style {
+    let spacing = tokens().spacing;
+    s.items_center()
+        .padding_vert(spacing.sm)
+        .padding_right(spacing.lg)
+}
+
+fn menu_entry(
+    index: usize,
+    entry: MenuNode,
+    hovered: RwSignal<Option<usize>>,
+    open: RwSignal<Option<usize>>,
+) -> Box<dyn View> {
+    match entry {
+        MenuNode::Separator => Box::new(empty().style(|s| {
+            s.height(1.0)
+                .margin_vert(tokens().spacing.sm)
+                .background(tokens().colors.border)
+        })),
+        MenuNode::Item {
+            title,
+            accelerator,
+            checked,
+            enabled,
+            action,
+        } => Box::new(
+            stack((
+                text(if checked == Some(true) { "✓" } else { "" })
+                    .style(|s| s.width(24.0).justify_center()),
+                text(title).style(|s| s.flex_grow(1.0)),
+                text(accelerator.unwrap_or_default())
+                    .style(|s| s.margin_left(24.0).color(tokens().colors.text_muted)),
+            ))
+            .style(row_style)
+            .hover_style(|s| s.background(tokens().colors.hover))
+            .disabled_style(|s| s.color(tokens().colors.text_muted))
+            .disabled(move || !enabled)
+            .on_event(EventListener::PointerEnter, move |_| {
+                hovered.set(Some(index));
+                false
+            })
+            .on_click(move |_| {
+                if let Some(action) = action.as_ref() {
+                    action();
+                }
+                open.set(None);
+                true
+            }),
+        ),
+        MenuNode::SubMenu {
+            title,
+            enabled,
+            children,
+        } => {
+            let row = stack((
+                text("").style(|s| s.width(24.0)),
+                text(title).style(|s| s.flex_grow(1.0)),
+                text("▸").style(|s| s.margin_left(24.0)),
+            ))
+            .style(row_style)
+            .hover_style(|s| s.background(tokens().colors.hover))
+            .disabled_style(|s| s.color(tokens().colors.text_muted))
+            .disabled(move || !enabled);
+            let shown = panel_open(
+                move || hovered.get() == Some(index),
+                move || hovered.set(None),
+            );
+            Box::new(
+                popover_with_open(
+                    row,
+                    shown,
+                    move || menu_panel(children.clone(), open),
+                    Placement::RightStart,
+                )
+                .on_event(EventListener::PointerEnter, move |_| {
+                    hovered.set(Some(index));
+                    false
+                }),
+            )
+        }
+    }
+}
+
+/// A row of menus like the File/Edit/View menus of a desktop app, drawn by Floem itself
+/// instead of by the platform.
+///
+/// Clicking a title opens its menu, and while one is open, hovering another title opens that
+/// one instead. Items can have a check mark with [MenuItem::checked](crate::menu::MenuItem::checked)
+/// and a shortcut shown on the right with [MenuItem::accelerator](crate::menu::MenuItem::accelerator).
+/// `menus` is called again whenever a signal it reads changes, so checked items can follow
+/// the app's state. The open menu closes when an item is picked, when its title is clicked again,
+/// or with Escape.
+pub fn menu_bar(menus: impl Fn() -> Vec<Menu> + 'static) -> ContainerBox {
+    let open = create_rw_signal(None);
+    let tree = create_rw_signal(Rc::new(Vec::new()));
+    create_effect(move |_| {
+        let menus = menus()
+            .into_iter()
+            .map(|menu| (menu.item.title.clone(), Rc::new(menu_nodes(menu))))
+            .collect::<Vec<_>>();
+        tree.set(Rc::new(menus));
+    });
+
+    container_box(dyn_container(
+        move || tree.get(),
+        move |menus| {
+            Box::new(
+                list(
+                    move || menus.iter().cloned().enumerate().collect::<Vec<_>>(),
+                    |(index, _)| *index,
+                    move |(index, (title, entries))| menu_bar_title(index, title, entries, open),
+                )
+                .style(|s| s.items_center()),
+            )
+        },
+    ))
+    .on_event(EventListener::KeyDown, move |event| {
+        if let Event::KeyDown(KeyEvent { key, .. }) = event {
+            let count = tree.with_untracked(|tree| tree.len());
+            match (open.get_untracked(), &key.logical_key) {
+                (Some(_), Key::Escape) => open.set(None),
+                (Some(index), Key::ArrowLeft) if count > 0 => {
+                    open.set(Some((index + count - 1) % count))
+                }
+                (Some(index), Key::ArrowRight) if count > 0 => open.set(Some((index + 1) % count)),
+                _ => return false,
+            }
+            true
+        } else {
+            false
+        }
+    })
+}
+
+fn menu_bar_title(
+    index: usize,
+    title: String,
+    entries: Rc<Vec<MenuNode>>,
+    open: RwSignal<Option<usize>>,
+) -> impl View {
+    let shown = panel_open(move || open.get() == Some(index), move || open.set(None));
+    popover_with_open(
+        text(title)
+            .style(move |s| {
+                let tokens = tokens();
+                s.padding_horiz(10.0)
+                    .padding_vert(tokens.spacing.sm)
+                    .border_radius(tokens.radius.md)
+                    .apply_if(open.get() == Some(index), |s| {
+                        s.background(tokens.colors.hover)
+                    })
+            })
+            .hover_style(|s| s.background(tokens().colors.hover))
+            .keyboard_navigatable()
+            .on_click(move |_| {
+                open.update(|open| {
+                    *open = if *open == Some(index) {
+                        None
+                    } else {
+                        Some(index)
+                    }
+                });
+                true
+            })
+            .on_event(EventListener::PointerEnter, move |_| {
+                if matches!(open.get_untracked(), Some(open) if open != index) {
+                    open.set(Some(index));
+                }
+                false
+            }),
+        shown,
+        move || menu_panel(entries.clone(), open),
+        Placement::BottomStart,
+    )
+}
@@ -6,6 +6,15 @@
 mod label;
 pub use label::*;
 
+mod formatted_label;
+pub use formatted_label::*;
+
+mod button;
+pub use button::*;
+
+mod link;
+pub use link::*;
+
 mod rich_text;
 pub use rich_text::*;
 
@@ -24,30 +33,97 @@ pub use container::*;
 mod container_box;
 pub use container_box::*;
 
+mod composite;
+pub use composite::*;
+
 mod dyn_container;
 pub use dyn_container::*;
 
 mod decorator;
 pub use decorator::*;
 
+mod mask;
+pub use mask::*;
+
 mod virtual_list;
 pub use virtual_list::*;
 
+mod virtual_grid;
+pub use virtual_grid::*;
+
+mod masonry;
+pub use masonry::*;
+
+mod carousel;
+pub use carousel::*;
+
+mod reorderable_list;
+pub use reorderable_list::*;
+
+mod tree;
+pub use tree::*;
+
+mod table;
+pub use table::*;
+
 mod scroll;
 pub use scroll::*;
 
 mod tab;
 pub use tab::*;
 
+mod wizard;
+pub use wizard::*;
+
+mod split;
+pub use split::*;
+
+mod dock;
+pub use dock::*;
+
+mod card;
+pub use card::*;
+
+mod collapsible;
+pub use collapsible::*;
+
+mod breadcrumb;
+pub use breadcrumb::*;
+
+mod pagination;
+pub use pagination::*;
+
 mod stack;
 pub use stack::*;
 
+mod absolute_layout;
+pub use absolute_layout::*;
+
 mod text_input;
 pub use text_input::*;
 
+mod tag_input;
+pub use tag_input::*;
+
+mod search_input;
+pub use search_input::*;
+
+mod code_editor;
+pub use code_editor::*;
+
+#[cfg(feature = "terminal")]
+mod terminal;
+#[cfg(feature = "terminal")]
+pub use terminal::*;
+
 mod empty;
 pub use empty::*;
 
+mod canvas;
+pub use canvas::*;
+
+pub mod chart;
+
 mod drag_window_area;
 pub use drag_window_area::*;
 
@@ -56,3 +132,59 @@ pub use drag_resize_window_area::*;
 
 mod img;
 pub use img::*;
+
+mod avatar;
+pub use avatar::*;
+
+#[cfg(feature = "video")]
+mod video;
+#[cfg(feature = "video")]
+pub use video::*;
+
+#[cfg(feature = "webview")]
+mod webview;
+#[cfg(feature = "webview")]
+pub use webview::*;
+
+mod checkbox;
+pub use checkbox::*;
+
+mod radio_button;
+pub use radio_button::*;
+
+mod slider;
+pub use slider::*;
+
+mod rating;
+pub use rating::*;
+
+mod progress_bar;
+pub use progress_bar::*;
+
+mod spinner;
+pub use spinner::*;
+
+mod toggle_switch;
+pub use toggle_switch::*;
+
+mod dropdown;
+pub use dropdown::*;
+
+mod menu_bar;
+pub use menu_bar::*;
+
+mod popover;
+pub use popover::*;
+
+mod color_picker;
+pub use color_picker::*;
+
+#[cfg(feature = "chrono")]
+mod date_picker;
+#[cfg(feature = "chrono")]
+pub use date_picker::*;
+
+#[cfg(feature = "chrono")]
+mod time_picker;
+#[cfg(feature = "chrono")]
+pub use time_picker::*;
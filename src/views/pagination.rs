@@ -0,0 +1,205 @@
+use std::{collections::BTreeSet, rc::Rc};
+
+use floem_reactive::{create_effect, create_memo, create_rw_signal, RwSignal};
+
+use crate::{
+    style::CursorStyle,
+    theme::tokens,
+    view::View,
+    views::{
+        composite, dropdown, dyn_container, empty, label, list, stack, Composite, Decorators, Label,
+    },
+};
+
+/// The width and minimum height of each page button
+const BUTTON_SIZE: f64 = 28.0;
+
+/// A button in the row of pages
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum PageItem {
+    Page(usize),
+    /// The pages that are left out after the given one
+    Gap(usize),
+}
+
+/// The pages to show when on `current` of `total`: the first and the last page, and the
+/// ones up to `siblings` away from the current page. The pages in between are left out,
+/// unless there's just one, which takes the same room as the ellipsis would.
+fn page_items(current: usize, total: usize, siblings: usize) -> Vec<PageItem> {
+    if total == 0 {
+        return Vec::new();
+    }
+    let last = total - 1;
+    let current = current.min(last);
+    let mut shown = BTreeSet::new();
+    shown.insert(0);
+    shown.insert(last);
+    shown.extend(current.saturating_sub(siblings)..=(current + siblings).min(last));
+
+    let mut items = Vec::new();
+    let mut previous: Option<usize> = None;
+    for page in shown {
+        if let Some(previous) = previous {
+            match page - previous {
+                1 => {}
+                2 => items.push(PageItem::Page(previous + 1)),
+                _ => items.push(PageItem::Gap(previous)),
+            }
+        }
+        items.push(PageItem::Page(page));
+        previous = Some(page);
+    }
+    items
+}
+
+/// Buttons for moving between pages. See [`pagination`]
+pub type Pagination = Composite<PaginationState>;
+
+pub struct PaginationState {
+    siblings: RwSignal<usize>,
+    page_sizes: RwSignal<Option<(RwSignal<usize>, Vec<usize>)>>,
+}
+
+/// A row of buttons for going to the first, the previous, the next and the last page, with
+/// the numbers of the pages around `current_page` between them. `current_page` is the index
+/// of the page, starting at 0, and is kept below `total_pages` when that shrinks.
+///
+/// When there are many pages, the ones far from the current page are collapsed into an
+/// ellipsis, showing the first and the last page and
+/// [the pages next to the current one](Pagination::siblings).
+pub fn pagination(
+    current_page: RwSignal<usize>,
+    total_pages: impl Fn() -> usize + 'static,
+) -> Pagination {
+    let total = create_memo(move |_| total_pages());
+    let siblings = create_rw_signal(1);
+    let page_sizes: RwSignal<Option<(RwSignal<usize>, Vec<usize>)>> = create_rw_signal(None);
+
+    create_effect(move |_| {
+        let last = total.get().saturating_sub(1);
+        if current_page.get() > last {
+            current_page.set(last);
+        }
+    });
+
+    let go_to = move |page: usize| {
+        let last = total.get_untracked().saturating_sub(1);
+        current_page.set(page.min(last));
+    };
+    let at_first = move || current_page.get() == 0;
+    let at_last = move || current_page.get() + 1 >= total.get();
+
+    let first = page_button(|| "«".to_string(), || false, move || go_to(0)).disabled(at_first);
+    let previous = page_button(
+        || "‹".to_string(),
+        || false,
+        move || go_to(current_page.get_untracked().saturating_sub(1)),
+    )
+    .disabled(at_first);
+    let next = page_button(
+        || "›".to_string(),
+        || false,
+        move || go_to(current_page.get_untracked() + 1),
+    )
+    .disabled(at_last);
+    let last = page_button(
+        || "»".to_string(),
+        || false,
+        move || go_to(total.get_untracked().saturating_sub(1)),
+    )
+    .disabled(at_last);
+
+    let pages = list(
+        move || page_items(current_page.get(), total.get(), siblings.get()),
+        |item| *item,
+        move |item| match item {
+            PageItem::Page(page) => page_button(
+                move || (page + 1).to_string(),
+                move || current_page.get() == page,
+                move || go_to(page),
+            ),
+            PageItem::Gap(_) => label(|| "…".to_string()).style(|s| {
+                s.width(BUTTON_SIZE)
+                    .justify_center()
+                    .color(tokens().colors.text_muted)
+            }),
+        },
+    )
+    .style(|s| s.items_center());
+
+    let page_size = dyn_container(
+        move || page_sizes.get(),
+        |page_sizes| match page_sizes {
+            Some((page_size, sizes)) => Box::new(
+                stack((
+                    dropdown(move || sizes.clone(), page_size),
+                    label(|| "per page".to_string()).style(|s| s.margin_left(tokens().spacing.xs)),
+                ))
+                .style(|s| s.items_center().margin_left(tokens().spacing.md)),
+            ),
+            None => Box::new(empty()),
+        },
+    );
+
+    composite(
+        "Pagination",
+        stack((first, previous, pages, next, last, page_size)).style(|s| s.items_center()),
+        PaginationState {
+            siblings,
+            page_sizes,
+        },
+    )
+}
+
+fn page_button(
+    text: impl Fn() -> String + 'static,
+    selected: impl Fn() -> bool + 'static,
+    action: impl Fn() + 'static,
+) -> Label {
+    let selected = Rc::new(selected);
+    let hover_selected = selected.clone();
+    label(text)
+        .style(move |s| {
+            let tokens = tokens();
+            s.min_width(BUTTON_SIZE)
+                .min_height(BUTTON_SIZE)
+                .margin_horiz(tokens.spacing.xs / 2.0)
+                .padding_horiz(tokens.spacing.xs)
+                .justify_center()
+                .items_center()
+                .border_radius(tokens.radius.md)
+                .cursor(CursorStyle::Pointer)
+                .apply_if(selected(), |s| {
+                    s.background(tokens.colors.primary)
+                        .color(tokens.colors.surface)
+                })
+        })
+        .hover_style(move |s| {
+            s.apply_if(!hover_selected(), |s| s.background(tokens().colors.hover))
+        })
+        .disabled_style(|s| {
+            s.cursor(CursorStyle::Default)
+                .color(tokens().colors.text_muted)
+        })
+        .on_click(move |_| {
+            action();
+            true
+        })
+        .keyboard_navigatable()
+}
+
+impl Pagination {
+    /// How many pages are shown on each side of the current page before the rest are
+    /// collapsed, 1 by default
+    pub fn siblings(self, siblings: usize) -> Self {
+        self.state().siblings.set(siblings);
+        self
+    }
+
+    /// Shows a selector after the pages for picking how many items there are on each page
+    /// from `sizes`, bound to `page_size`
+    pub fn page_sizes(self, page_size: RwSignal<usize>, sizes: impl Into<Vec<usize>>) -> Self {
+        self.state().page_sizes.set(Some((page_size, sizes.into())));
+        self
+    }
+}
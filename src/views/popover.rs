@@ -0,0 +1,117 @@
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+
+use crate::{
+    id::Id,
+    overlay::{add_dismissable_overlay, remove_overlay, Placement},
+    view::View,
+    views::{composite, Composite, Decorators},
+};
+
+/// How far a popover is shown from its anchor
+const POPOVER_GAP: f64 = 4.0;
+
+/// A view with a popup anchored to it. See [`popover`]
+pub type Popover = Composite<PopoverState>;
+
+/// What a [Popover] keeps track of, which removes the popup when it's dropped along with the view
+pub struct PopoverState {
+    open: RwSignal<bool>,
+    flip: Rc<Cell<bool>>,
+    shift: Rc<Cell<bool>>,
+    shown: Rc<Cell<Option<Id>>>,
+}
+
+/// Shows the view from `content` next to `anchor` while the popover is open, in the window's
+/// overlay layer so it's above all other views and isn't clipped by the anchor's parents.
+///
+/// Clicking the anchor opens and closes the popover, and a click anywhere outside of both
+/// the popup and the anchor closes it. It can also be opened and closed through
+/// [Popover::open_signal]. The popup follows the anchor when it moves, and by default
+/// flips to the other side of the anchor and shifts along it to stay inside the window,
+/// which can be turned off with [Popover::flip] and [Popover::shift].
+pub fn popover<V: View + 'static, C: View + 'static>(
+    anchor: V,
+    content: impl Fn() -> C + 'static,
+    placement: Placement,
+) -> Popover {
+    let open = create_rw_signal(false);
+    popover_with_open(anchor, open, content, placement).on_click(move |_| {
+        open.update(|open| *open = !*open);
+        true
+    })
+}
+
+/// Like [`popover`], with the popup shown while `open` is set. Clicking the anchor doesn't
+/// open or close it, that's left to the views setting `open`, but a click outside of both the
+/// popup and the anchor still closes it.
+pub fn popover_with_open<V: View + 'static, C: View + 'static>(
+    anchor: V,
+    open: RwSignal<bool>,
+    content: impl Fn() -> C + 'static,
+    placement: Placement,
+) -> Popover {
+    let state = PopoverState {
+        open,
+        flip: Rc::new(Cell::new(true)),
+        shift: Rc::new(Cell::new(true)),
+        shown: Rc::new(Cell::new(None)),
+    };
+    let flip = state.flip.clone();
+    let shift = state.shift.clone();
+    let shown = state.shown.clone();
+    let popover = composite("Popover", anchor, state);
+    let id = popover.id();
+    let content = Rc::new(content);
+
+    create_effect(move |_| {
+        if open.get() {
+            if shown.get().is_none() {
+                let flip = flip.clone();
+                let shift = shift.clone();
+                let content = content.clone();
+                let overlay = add_dismissable_overlay(
+                    id,
+                    move |anchor, size, window| {
+                        placement.place(anchor, size, window, POPOVER_GAP, flip.get(), shift.get())
+                    },
+                    move || content(),
+                    move || open.set(false),
+                );
+                shown.set(Some(overlay));
+            }
+        } else if let Some(overlay) = shown.take() {
+            remove_overlay(overlay);
+        }
+    });
+
+    popover
+}
+
+impl Popover {
+    /// Whether the popover is open, which can be set to open or close it
+    pub fn open_signal(&self) -> RwSignal<bool> {
+        self.state().open
+    }
+
+    /// Whether the popup goes on the other side of the anchor when there isn't room for it
+    pub fn flip(self, flip: bool) -> Self {
+        self.state().flip.set(flip);
+        self
+    }
+
+    /// Whether the popup moves along the side of the anchor to stay inside the window
+    pub fn shift(self, shift: bool) -> Self {
+        self.state().shift.set(shift);
+        self
+    }
+}
+
+impl Drop for PopoverState {
+    fn drop(&mut self) {
+        if let Some(overlay) = self.shown.take() {
+            remove_overlay(overlay);
+        }
+    }
+}
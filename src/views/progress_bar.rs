@@ -0,0 +1,277 @@
+use std::{
+    f64::consts::PI,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use floem_renderer::Renderer;
+use kurbo::{Arc, Rect, RoundedRect, Size, Vec2};
+use taffy::prelude::Node;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::{ComputedStyle, Style},
+    theme::tokens,
+    view::{ChangeFlags, View},
+    views::Decorators,
+};
+
+/// How long the moving part of an indeterminate bar takes to go across once
+const INDETERMINATE_PERIOD: Duration = Duration::from_millis(1500);
+/// How much of the track the moving part of an indeterminate bar covers
+const INDETERMINATE_LENGTH: f64 = 0.3;
+
+type StyleFn = Rc<dyn Fn(Style) -> Style>;
+
+enum ProgressState {
+    Progress(f64),
+    Indeterminate(bool),
+    TrackStyle(ComputedStyle),
+    FillStyle(ComputedStyle),
+}
+
+/// Shows how far along some work is. See [`progress_bar`] and [`circular_progress`]
+pub struct ProgressBar {
+    id: Id,
+    circular: bool,
+    progress: f64,
+    indeterminate: bool,
+    started: Instant,
+    size: Size,
+    track_style: ComputedStyle,
+    fill_style: ComputedStyle,
+    track_style_fn: RwSignal<StyleFn>,
+    fill_style_fn: RwSignal<StyleFn>,
+}
+
+fn default_track_style(s: Style) -> Style {
+    let tokens = tokens();
+    s.background(tokens.colors.track)
+        .border_radius(tokens.radius.sm)
+        .border(4.0)
+}
+
+fn default_fill_style(s: Style) -> Style {
+    s.background(tokens().colors.primary)
+}
+
+/// The style of one part of the bar, which is computed and sent to it whenever
+/// the style or the tokens its default is made from change
+fn part_style(
+    id: Id,
+    default: fn(Style) -> Style,
+    state: fn(ComputedStyle) -> ProgressState,
+) -> RwSignal<StyleFn> {
+    let style: RwSignal<StyleFn> = create_rw_signal(Rc::new(|s| s));
+    create_effect(move |_| {
+        let computed = style.get()(default(Style::BASE)).compute(&ComputedStyle::default());
+        id.update_state(state(computed), false);
+    });
+    style
+}
+
+fn new_progress(progress: impl Fn() -> f64 + 'static, circular: bool) -> ProgressBar {
+    let id = Id::next();
+    create_effect(move |_| {
+        id.update_state(ProgressState::Progress(progress()), false);
+    });
+    ProgressBar {
+        id,
+        circular,
+        progress: 0.0,
+        indeterminate: false,
+        started: Instant::now(),
+        size: Size::ZERO,
+        track_style: ComputedStyle::default(),
+        fill_style: ComputedStyle::default(),
+        track_style_fn: part_style(id, default_track_style, ProgressState::TrackStyle),
+        fill_style_fn: part_style(id, default_fill_style, ProgressState::FillStyle),
+    }
+}
+
+/// A horizontal bar filled up to `progress`, from 0.0 for none of the work done
+/// to 1.0 for all of it. Values outside of that are clamped.
+///
+/// For work without a known amount left, [ProgressBar::indeterminate] shows a part of the
+/// bar sliding across instead. The track and the filled part are drawn with the
+/// `background` of [ProgressBar::track_style] and [ProgressBar::fill_style],
+/// both with the border radius of the track.
+pub fn progress_bar(progress: impl Fn() -> f64 + 'static) -> ProgressBar {
+    new_progress(progress, false).base_style(|s| s.width(100.0).height(6.0))
+}
+
+/// A ring filled clockwise from the top up to `progress`, like [progress_bar].
+/// The width of the ring is the `border` width of the track style.
+pub fn circular_progress(progress: impl Fn() -> f64 + 'static) -> ProgressBar {
+    new_progress(progress, true).base_style(|s| s.size(32.0, 32.0))
+}
+
+impl ProgressBar {
+    /// Switches between showing the progress and an animation for work that's going on
+    /// without a known end, whenever `indeterminate` changes
+    pub fn indeterminate(self, indeterminate: impl Fn() -> bool + 'static) -> Self {
+        let id = self.id;
+        create_effect(move |_| {
+            id.update_state(ProgressState::Indeterminate(indeterminate()), false);
+        });
+        self
+    }
+
+    /// The part of the bar that isn't filled is drawn with the `background` of this style,
+    /// with its border radius for the corners of both the track and the fill
+    pub fn track_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.track_style_fn.set(Rc::new(style));
+        self
+    }
+
+    /// The filled part of the bar is drawn with the `background` of this style
+    pub fn fill_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.fill_style_fn.set(Rc::new(style));
+        self
+    }
+
+    /// How far the animation of an indeterminate bar is through its loop, from 0.0 to 1.0
+    fn phase(&self, frame_time: Instant) -> f64 {
+        let period = INDETERMINATE_PERIOD.as_secs_f64();
+        let elapsed = frame_time.saturating_duration_since(self.started);
+        (elapsed.as_secs_f64() % period) / period
+    }
+
+    fn paint_bar(&self, cx: &mut PaintCx) {
+        let track = self.size.to_rect();
+        let radius = (self.track_style.border_radius.0).min(track.height() / 2.0);
+        if let Some(background) = self.track_style.background {
+            cx.fill(&RoundedRect::from_rect(track, radius), background, 0.0);
+        }
+
+        let (start, end) = if self.indeterminate {
+            // slide in from the left and out on the right
+            let phase = self.phase(cx.frame_time());
+            let start = -INDETERMINATE_LENGTH + phase * (1.0 + INDETERMINATE_LENGTH);
+            (start.max(0.0), (start + INDETERMINATE_LENGTH).min(1.0))
+        } else {
+            (0.0, self.progress)
+        };
+        if let Some(background) = self.fill_style.background {
+            if end > start {
+                let fill = Rect::new(
+                    track.width() * start,
+                    0.0,
+                    track.width() * end,
+                    track.height(),
+                );
+                cx.fill(&RoundedRect::from_rect(fill, radius), background, 0.0);
+            }
+        }
+    }
+
+    fn paint_ring(&self, cx: &mut PaintCx) {
+        let width = self.track_style.border_left.0;
+        let radii = (self.size.width.min(self.size.height) - width) / 2.0;
+        if radii <= 0.0 {
+            return;
+        }
+        let center = self.size.to_rect().center();
+        let ring = |start: f64, sweep: f64| Arc {
+            center,
+            radii: Vec2::new(radii, radii),
+            // clockwise from the top
+            start_angle: -PI / 2.0 + start * 2.0 * PI,
+            sweep_angle: sweep * 2.0 * PI,
+            x_rotation: 0.0,
+        };
+        if let Some(background) = self.track_style.background {
+            cx.stroke(&ring(0.0, 1.0), background, width);
+        }
+
+        let (start, sweep) = if self.indeterminate {
+            (self.phase(cx.frame_time()), 0.25)
+        } else {
+            (0.0, self.progress)
+        };
+        if let Some(background) = self.fill_style.background {
+            if sweep > 0.0 {
+                cx.stroke(&ring(start, sweep), background, width);
+            }
+        }
+    }
+}
+
+impl View for ProgressBar {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "ProgressBar".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) -> ChangeFlags {
+        if let Ok(state) = state.downcast::<ProgressState>() {
+            match *state {
+                ProgressState::Progress(progress) => {
+                    self.progress = if progress.is_nan() {
+                        0.0
+                    } else {
+                        progress.clamp(0.0, 1.0)
+                    }
+                }
+                ProgressState::Indeterminate(indeterminate) => {
+                    if indeterminate && !self.indeterminate {
+                        self.started = Instant::now();
+                    }
+                    self.indeterminate = indeterminate;
+                }
+                ProgressState::TrackStyle(style) => self.track_style = style,
+                ProgressState::FillStyle(style) => self.fill_style = style,
+            }
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        None
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        if self.circular {
+            self.paint_ring(cx);
+        } else {
+            self.paint_bar(cx);
+        }
+        if self.indeterminate {
+            cx.request_animation_frame(self.id);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::{cell::RefCell, collections::HashMap, fmt::Display, rc::Rc};
+
+use floem_reactive::RwSignal;
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    id::Id,
+    keyboard::KeyEvent,
+    theme::tokens,
+    view::View,
+    views::{self, label, list, stack, Decorators, Label, List, Stack, Svg},
+};
+
+const RADIO_BUTTON_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 20"><circle cx="10" cy="10" r="5" /></svg>"#;
+
+fn radio_button_svg<T: PartialEq + 'static>(value: T, selected: RwSignal<T>) -> Svg {
+    let svg_str = move || {
+        if selected.with(|selected| selected == &value) {
+            RADIO_BUTTON_SVG
+        } else {
+            ""
+        }
+        .to_string()
+    };
+
+    views::svg(svg_str).base_style(|s| {
+        s.width(20.)
+            .height(20.)
+            .border_color(tokens().colors.text)
+            .border(1.)
+            .border_radius(10.)
+            .margin_right(5.)
+    })
+}
+
+/// Renders a radio button that shows whether `selected` holds `value`, and sets it
+/// to `value` when clicked. Radio buttons sharing the same signal are mutually exclusive.
+/// The dot is drawn with the `color` style.
+pub fn radio_button<T: PartialEq + Clone + 'static>(value: T, selected: RwSignal<T>) -> Svg {
+    let on_click_value = value.clone();
+    radio_button_svg(value, selected)
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            selected.set(on_click_value.clone());
+            true
+        })
+}
+
+/// A radio button followed by a label, where clicking the label selects the value too
+pub fn labeled_radio_button<T: PartialEq + Clone + 'static, S: Display + 'static>(
+    value: T,
+    selected: RwSignal<T>,
+    label_text: impl Fn() -> S + 'static,
+) -> Stack<(Svg, Label)> {
+    let on_click_value = value.clone();
+    stack((radio_button_svg(value, selected), label(label_text)))
+        .base_style(|s| s.items_center())
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            selected.set(on_click_value.clone());
+            true
+        })
+}
+
+/// A column of labeled radio buttons, one for each of the `options`.
+/// While one of the options has focus, the arrow keys select and focus the previous or next
+/// option, wrapping around at the ends.
+pub fn radio_group<T: PartialEq + Clone + 'static, S: Display + 'static>(
+    options: Vec<T>,
+    selected: RwSignal<T>,
+    label_fn: impl Fn(&T) -> S + 'static,
+) -> List<Stack<(Svg, Label)>, (usize, T)> {
+    let options = Rc::new(options);
+    let option_ids: Rc<RefCell<HashMap<usize, Id>>> = Rc::new(RefCell::new(HashMap::new()));
+    let label_fn = Rc::new(label_fn);
+
+    let each_options = options.clone();
+    let view_option_ids = option_ids.clone();
+    list(
+        move || each_options.iter().cloned().enumerate().collect::<Vec<_>>(),
+        |(index, _)| *index,
+        move |(index, value)| {
+            let label_text = label_fn(&value).to_string();
+            let option = labeled_radio_button(value, selected, move || label_text.clone());
+            view_option_ids.borrow_mut().insert(index, option.id());
+            option
+        },
+    )
+    .base_style(|s| s.flex_col())
+    .on_event(EventListener::KeyDown, move |event| {
+        if let Event::KeyDown(KeyEvent { key, .. }) = event {
+            if options.is_empty() {
+                return false;
+            }
+            let step = match key.logical_key {
+                Key::ArrowUp | Key::ArrowLeft => options.len() - 1,
+                Key::ArrowDown | Key::ArrowRight => 1,
+                _ => return false,
+            };
+            let current = options
+                .iter()
+                .position(|option| selected.with_untracked(|selected| selected == option));
+            let next = match current {
+                Some(current) => (current + step) % options.len(),
+                None => 0,
+            };
+            selected.set(options[next].clone());
+            if let Some(id) = option_ids.borrow().get(&next) {
+                id.request_focus();
+            }
+            true
+        } else {
+            false
+        }
+    })
+}
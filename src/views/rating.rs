@@ -0,0 +1,131 @@
+use floem_reactive::{create_rw_signal, RwSignal};
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    style::CursorStyle,
+    theme::tokens,
+    view::View,
+    views::{clip, composite, list, stack, svg, Composite, Decorators},
+};
+
+const STAR_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><polygon points="12,2 14.94,8.26 21.8,9.06 16.7,13.74 18.06,20.54 12,17.1 5.94,20.54 7.3,13.74 2.2,9.06 9.06,8.26" /></svg>"#;
+
+/// The width and height of each star
+const STAR_SIZE: f64 = 20.0;
+
+/// A row of stars for picking a rating. See [`rating`]
+pub type Rating = Composite<RatingState>;
+
+pub struct RatingState {
+    half_stars: RwSignal<bool>,
+    icons: RwSignal<(String, String)>,
+}
+
+/// A row of `max` stars showing `value`, which is set to the star that's clicked. While the
+/// pointer is over the stars, the rating it would set is shown instead.
+///
+/// With focus, the arrow keys change the rating by a star, or by half a star with
+/// [half_stars](Rating::half_stars), and Home and End set it to none or to all of them.
+/// A star that's only partly filled is drawn with the filled icon cut off, so a value
+/// like `3.7` shows too.
+pub fn rating(value: RwSignal<f64>, max: usize) -> Rating {
+    let half_stars = create_rw_signal(false);
+    let icons = create_rw_signal((STAR_SVG.to_string(), STAR_SVG.to_string()));
+    let hovered: RwSignal<Option<f64>> = create_rw_signal(None);
+    let max_value = max as f64;
+
+    // the rating a pointer at `x` in star `index` picks
+    let rating_at = move |index: usize, x: f64| {
+        if half_stars.get_untracked() && x < STAR_SIZE / 2.0 {
+            index as f64 + 0.5
+        } else {
+            index as f64 + 1.0
+        }
+    };
+
+    let stars = list(
+        move || 0..max,
+        |index| *index,
+        move |index| {
+            let fill = move || {
+                let shown = hovered.get().unwrap_or_else(|| value.get());
+                (shown - index as f64).clamp(0.0, 1.0)
+            };
+            let empty = svg(move || icons.with(|(_, empty)| empty.clone()))
+                .style(|s| s.size(STAR_SIZE, STAR_SIZE).color(tokens().colors.track));
+            let filled = svg(move || icons.with(|(filled, _)| filled.clone())).style(|s| {
+                s.size(STAR_SIZE, STAR_SIZE)
+                    .min_width(STAR_SIZE)
+                    .color(tokens().colors.warning)
+            });
+            stack((
+                empty,
+                clip(filled).style(move |s| {
+                    s.absolute()
+                        .inset_left(0.0)
+                        .inset_top(0.0)
+                        .height_full()
+                        .width_pct(fill() * 100.0)
+                }),
+            ))
+            .style(|s| s.cursor(CursorStyle::Pointer))
+            .on_event(EventListener::PointerMove, move |event| {
+                if let Event::PointerMove(pointer_event) = event {
+                    hovered.set(Some(rating_at(index, pointer_event.pos.x)));
+                }
+                false
+            })
+            .on_click(move |event| {
+                if let Some(pos) = event.point() {
+                    value.set(rating_at(index, pos.x));
+                }
+                true
+            })
+        },
+    )
+    .on_event(EventListener::PointerLeave, move |_| {
+        hovered.set(None);
+        false
+    });
+
+    composite("Rating", stars, RatingState { half_stars, icons })
+        .keyboard_navigatable()
+        .on_event(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                let step = if half_stars.get_untracked() { 0.5 } else { 1.0 };
+                let current = value.get_untracked();
+                let rated = match key.logical_key {
+                    Key::ArrowRight | Key::ArrowUp => current + step,
+                    Key::ArrowLeft | Key::ArrowDown => current - step,
+                    Key::Home => 0.0,
+                    Key::End => max_value,
+                    _ => return false,
+                };
+                // a value set from outside might not be on a step yet
+                let rated = ((rated / step).round() * step).clamp(0.0, max_value);
+                if rated != current {
+                    value.set(rated);
+                }
+                return true;
+            }
+            false
+        })
+}
+
+impl Rating {
+    /// Lets the left half of a star pick half of it
+    pub fn half_stars(self, half_stars: bool) -> Self {
+        self.state().half_stars.set(half_stars);
+        self
+    }
+
+    /// The SVGs drawn for the filled and the empty part of the stars, in place of the star
+    /// outline. Like [svg](crate::views::svg) they're drawn with the `color` style, the
+    /// warning color of the theme when filled and the track color when empty.
+    pub fn icons(self, filled: impl Into<String>, empty: impl Into<String>) -> Self {
+        self.state().icons.set((filled.into(), empty.into()));
+        self
+    }
+}
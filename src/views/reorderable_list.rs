@@ -0,0 +1,322 @@
+use std::{
+    cell::{Cell, RefCell},
+    hash::Hash,
+    rc::Rc,
+    time::Duration,
+};
+
+use floem_reactive::{create_rw_signal, RwSignal};
+use kurbo::{Point, Rect, Vec2};
+
+use crate::{
+    action::exec_after,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::{Event, EventListener},
+    id::Id,
+    style::CursorStyle,
+    theme::tokens,
+    view::{ChangeFlags, View},
+    views::{container_box, empty, list, stack, ContainerBox, Decorators},
+};
+
+use super::scroll::ScrollState;
+
+/// How close to the edge of the enclosing scroll an item has to be dragged to scroll it
+const AUTO_SCROLL_ZONE: f64 = 40.0;
+/// How often the scroll moves while an item is held in its edge zone
+const AUTO_SCROLL_INTERVAL: Duration = Duration::from_millis(16);
+/// The distance scrolled per tick when the item is right at the edge
+const AUTO_SCROLL_MAX_SPEED: f64 = 10.0;
+/// The thickness of the line showing where the dragged item goes
+const INDICATOR_WIDTH: f64 = 2.0;
+
+type ReorderFn = Rc<RefCell<Option<Box<dyn Fn(usize, usize)>>>>;
+
+/// A list whose items are dragged to reorder them. See [`reorderable_list`]
+pub struct ReorderableList {
+    id: Id,
+    child: ContainerBox,
+    /// The index of the item that's being dragged
+    dragged: RwSignal<Option<usize>>,
+    on_reorder: ReorderFn,
+    auto_scroll: Rc<AutoScroll>,
+}
+
+#[derive(Default)]
+struct AutoScroll {
+    delta: Cell<Vec2>,
+    scheduled: Cell<bool>,
+}
+
+/// A column with a view from `view_fn` for each of the `items`, which can be dragged to
+/// other places in the list. While an item is dragged, a line shows where it would be
+/// dropped, and dropping it there moves it in `items` and calls
+/// [on_reorder](ReorderableList::on_reorder).
+///
+/// When the list is in a [scroll](crate::views::scroll()), holding an item near the top
+/// or the bottom edge of the scroll scrolls it, faster the closer it is to the edge.
+pub fn reorderable_list<T, K, V>(
+    items: RwSignal<Vec<T>>,
+    key_fn: impl Fn(&T) -> K + 'static,
+    view_fn: impl Fn(T) -> V + 'static,
+) -> ReorderableList
+where
+    T: Clone + 'static,
+    K: Eq + Hash + 'static,
+    V: View + 'static,
+{
+    let key_fn = Rc::new(key_fn);
+    let dragged: RwSignal<Option<usize>> = create_rw_signal(None);
+    // where the dragged item would be inserted, from 0 to the number of items
+    let target: RwSignal<Option<usize>> = create_rw_signal(None);
+    let on_reorder: ReorderFn = Rc::new(RefCell::new(None));
+    let auto_scroll = Rc::new(AutoScroll::default());
+
+    let drop_item: Rc<dyn Fn() -> bool> = {
+        let on_reorder = on_reorder.clone();
+        let auto_scroll = auto_scroll.clone();
+        Rc::new(move || {
+            auto_scroll.delta.set(Vec2::ZERO);
+            let moved = dragged.get_untracked().zip(target.get_untracked());
+            dragged.set(None);
+            target.set(None);
+            if let Some((from, to)) = moved {
+                // the items after the dragged one move up once it's taken out
+                let to = if to > from { to - 1 } else { to };
+                let len = items.with_untracked(|items| items.len());
+                if from != to && from < len && to < len {
+                    items.update(|items| {
+                        let item = items.remove(from);
+                        items.insert(to, item);
+                    });
+                    if let Some(on_reorder) = on_reorder.borrow().as_ref() {
+                        on_reorder(from, to);
+                    }
+                }
+            }
+            true
+        })
+    };
+
+    let item_key_fn = key_fn.clone();
+    let item_drop = drop_item.clone();
+    let item_auto_scroll = auto_scroll.clone();
+    let rows = list(
+        move || items.get(),
+        move |item| key_fn(item),
+        move |item| {
+            let key = item_key_fn(&item);
+            let index: Rc<dyn Fn() -> Option<usize>> = {
+                let key_fn = item_key_fn.clone();
+                Rc::new(move || {
+                    items.with(|items| items.iter().position(|item| key_fn(item) == key))
+                })
+            };
+            let height = create_rw_signal(0.0);
+
+            let line = |shown: Rc<dyn Fn() -> bool>, top: bool| {
+                empty().style(move |s| {
+                    let s = s
+                        .absolute()
+                        .inset_left(0.0)
+                        .width_full()
+                        .height(INDICATOR_WIDTH)
+                        .background(tokens().colors.primary)
+                        .z_index(1)
+                        .apply_if(!shown(), |s| s.hide());
+                    if top {
+                        s.inset_top(-INDICATOR_WIDTH / 2.0)
+                    } else {
+                        s.inset_bottom(-INDICATOR_WIDTH / 2.0)
+                    }
+                })
+            };
+            let before: Rc<dyn Fn() -> bool> = {
+                let index = index.clone();
+                Rc::new(move || index().is_some() && target.get() == index())
+            };
+            // the line after the last item, as the others have the line of the next item
+            let after: Rc<dyn Fn() -> bool> = {
+                let index = index.clone();
+                Rc::new(move || {
+                    let len = items.with(|items| items.len());
+                    index().map(|index| index + 1) == Some(len) && target.get() == Some(len)
+                })
+            };
+
+            let drag_index = index.clone();
+            let over_index = index;
+            let drop_item = item_drop.clone();
+            let auto_scroll = item_auto_scroll.clone();
+            stack((line(before, true), view_fn(item), line(after, false)))
+                .style(|s| s.width_full().cursor(CursorStyle::Pointer))
+                .draggable()
+                .on_resize(move |rect| height.set(rect.height()))
+                .on_event(EventListener::DragStart, move |_| {
+                    dragged.set(drag_index());
+                    false
+                })
+                .on_event(EventListener::DragOver, move |event| {
+                    if dragged.get_untracked().is_some() {
+                        if let (Some(index), Some(pos)) = (over_index(), event.point()) {
+                            let after = pos.y >= height.get_untracked() / 2.0;
+                            target.set(Some(if after { index + 1 } else { index }));
+                        }
+                    }
+                    false
+                })
+                .on_event(EventListener::Drop, move |_| drop_item())
+                .on_event(EventListener::DragEnd, move |_| {
+                    auto_scroll.delta.set(Vec2::ZERO);
+                    dragged.set(None);
+                    target.set(None);
+                    false
+                })
+        },
+    )
+    .style(|s| s.flex_col().width_full());
+
+    ReorderableList {
+        id: Id::next(),
+        child: container_box(rows).style(|s| s.width_full()),
+        dragged,
+        on_reorder,
+        auto_scroll,
+    }
+    .on_event(EventListener::Drop, move |_| {
+        // dropped next to the items, before any of them was dragged over
+        if dragged.get_untracked().is_some() && target.get_untracked().is_none() {
+            target.set(Some(items.with_untracked(|items| items.len())));
+        }
+        drop_item()
+    })
+}
+
+impl ReorderableList {
+    /// Called with the index an item was dragged from and the index it was moved to,
+    /// after it's been moved in the items
+    pub fn on_reorder(self, on_reorder: impl Fn(usize, usize) + 'static) -> Self {
+        *self.on_reorder.borrow_mut() = Some(Box::new(on_reorder));
+        self
+    }
+
+    /// Scrolls the scroll the list is in while the dragged item is near its top or bottom
+    /// edge, `pos` being the pointer in the list
+    fn update_auto_scroll(&self, cx: &mut EventCx, pos: Point) {
+        let view_states = &cx.app_state.view_states;
+        // the child of a scroll is the view with a viewport
+        let mut scroll_child = Some(self.id);
+        while let Some(id) = scroll_child {
+            if view_states
+                .get(&id)
+                .and_then(|state| state.viewport)
+                .is_some()
+            {
+                break;
+            }
+            scroll_child = id.parent();
+        }
+        let (scroll, child) = match scroll_child.and_then(|child| Some((child.parent()?, child))) {
+            Some(found) => found,
+            None => return,
+        };
+        let child_state = &view_states[&child];
+        let visible =
+            child_state.viewport.unwrap_or_default() + child_state.layout_rect.origin().to_vec2();
+        let origin = view_states
+            .get(&self.id)
+            .map(|state| state.layout_rect.origin())
+            .unwrap_or_default();
+        let pointer = origin + pos.to_vec2();
+
+        let zone = AUTO_SCROLL_ZONE.min(visible.height() / 2.0);
+        let speed = if pointer.y < visible.y0 + zone {
+            -AUTO_SCROLL_MAX_SPEED * (visible.y0 + zone - pointer.y).min(zone) / zone
+        } else if pointer.y > visible.y1 - zone {
+            AUTO_SCROLL_MAX_SPEED * (pointer.y - (visible.y1 - zone)).min(zone) / zone
+        } else {
+            0.0
+        };
+        self.auto_scroll.delta.set(Vec2::new(0.0, speed));
+        if speed != 0.0 && !self.auto_scroll.scheduled.get() {
+            schedule_auto_scroll(scroll, self.auto_scroll.clone());
+        }
+    }
+}
+
+fn schedule_auto_scroll(scroll: Id, auto_scroll: Rc<AutoScroll>) {
+    auto_scroll.scheduled.set(true);
+    exec_after(AUTO_SCROLL_INTERVAL, move |_| {
+        auto_scroll.scheduled.set(false);
+        let delta = auto_scroll.delta.get();
+        if delta != Vec2::ZERO {
+            scroll.update_state(ScrollState::ScrollDelta(delta), false);
+            schedule_auto_scroll(scroll, auto_scroll);
+        }
+    });
+}
+
+impl View for ReorderableList {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.child.id() == id {
+            Some(&self.child)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.child.id() == id {
+            Some(&mut self.child)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.child]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.child]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "ReorderableList".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, true, |cx| vec![self.child.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        Some(self.child.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        match &event {
+            Event::PointerMove(pointer_event) if self.dragged.get_untracked().is_some() => {
+                self.update_auto_scroll(cx, pointer_event.pos);
+            }
+            Event::PointerUp(_) => self.auto_scroll.delta.set(Vec2::ZERO),
+            _ => {}
+        }
+        if cx.should_send(self.child.id(), &event) {
+            self.child.event_main(cx, id_path, event)
+        } else {
+            false
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.child.paint_main(cx);
+    }
+}
@@ -1,7 +1,7 @@
 use std::any::Any;
 
 use floem_reactive::create_effect;
-use floem_renderer::{cosmic_text::TextLayout, Renderer};
+use floem_renderer::cosmic_text::TextLayout;
 use kurbo::{Point, Rect};
 use taffy::prelude::Node;
 
@@ -14,6 +14,8 @@ use crate::{
     view::{ChangeFlags, View},
 };
 
+use super::label::{paint_text, spaced_width};
+
 pub struct RichText {
     id: Id,
     text_layout: TextLayout,
@@ -93,7 +95,7 @@ impl View for RichText {
     fn layout(&mut self, cx: &mut crate::context::LayoutCx) -> taffy::prelude::Node {
         cx.layout_node(self.id, true, |cx| {
             let size = self.text_layout.size();
-            let width = size.width as f32;
+            let width = spaced_width(&self.text_layout, cx.letter_spacing) as f32;
             let height = size.height as f32;
 
             if self.text_node.is_none() {
@@ -143,6 +145,6 @@ impl View for RichText {
         let text_node = self.text_node.unwrap();
         let location = cx.app_state.taffy.layout(text_node).unwrap().location;
         let point = Point::new(location.x as f64, location.y as f64);
-        cx.draw_text(&self.text_layout, point);
+        paint_text(cx, &self.text_layout, point);
     }
 }
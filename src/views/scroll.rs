@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use floem_reactive::create_effect;
 use floem_renderer::Renderer;
 use kurbo::{Point, Rect, Size, Vec2};
@@ -5,26 +7,51 @@ use peniko::Color;
 use taffy::{prelude::Node, style::Position};
 
 use crate::{
+    action::exec_after,
     context::{AppState, LayoutCx, PaintCx},
     event::Event,
     id::Id,
+    pointer::pointer_capabilities,
     style::{ComputedStyle, Style, StyleValue},
     unit::PxPct,
     view::{ChangeFlags, View},
 };
 
-enum ScrollState {
+pub(crate) enum ScrollState {
     EnsureVisible(Rect),
     ScrollDelta(Vec2),
     ScrollTo(Point),
     HiddenBar(bool),
     PropagatePointerWheel(bool),
     VerticalScrollAsHorizontal(bool),
+    AutoScrollTick,
 }
 
 /// Minimum length for any scrollbar to be when measured on that
 /// scrollbar's primary axis.
 const SCROLLBAR_MIN_SIZE: f64 = 10.0;
+/// The thinnest the bars get while the pointer is a finger, so they're big enough to grab
+const COARSE_SCROLLBAR_THICKNESS: f64 = 16.0;
+
+/// How often the content moves while the pointer rests in an auto scroll zone.
+pub(crate) const AUTO_SCROLL_INTERVAL_MS: u64 = 16;
+/// The distance scrolled per tick when the pointer is right at the edge of the viewport.
+const AUTO_SCROLL_MAX_SPEED: f64 = 10.0;
+
+/// The distance to auto scroll per tick with the pointer at `pos` on an axis that goes from
+/// `min` to `max`, which is negative towards `min` and zero outside of the zones at the ends
+pub(crate) fn auto_scroll_speed(pos: f64, min: f64, max: f64, zone: f64) -> f64 {
+    let zone = zone.min((max - min) / 2.0);
+    if zone <= 0.0 {
+        0.0
+    } else if pos < min + zone {
+        -AUTO_SCROLL_MAX_SPEED * (min + zone - pos).min(zone) / zone
+    } else if pos > max - zone {
+        AUTO_SCROLL_MAX_SPEED * (pos - (max - zone)).min(zone) / zone
+    } else {
+        0.0
+    }
+}
 
 /// Denotes which scrollbar, if any, is currently being dragged.
 #[derive(Debug, Copy, Clone)]
@@ -89,11 +116,26 @@ pub struct Scroll<V: View> {
     propagate_pointer_wheel: bool,
     vertical_scroll_as_horizontal: bool,
     scroll_bar_style: ScrollBarStyle,
+    // the size of the edge regions that scroll the content when hovered
+    auto_scroll_zone: Option<f64>,
+    auto_scroll_delta: Vec2,
+    auto_scroll_scheduled: bool,
+    // whether the pointer was a finger in the last layout
+    coarse_pointer: bool,
 }
 
 pub fn scroll<V: View>(child: V) -> Scroll<V> {
+    let id = Id::next();
+    // the bars are thicker for a finger, so the view is laid out again when that changes
+    create_effect(move |last: Option<bool>| {
+        let coarse = pointer_capabilities().with(|pointer| pointer.is_coarse());
+        if last.is_some() {
+            id.request_layout();
+        }
+        coarse
+    });
     Scroll {
-        id: Id::next(),
+        id,
         child,
         size: Size::ZERO,
         actual_rect: Rect::ZERO,
@@ -105,6 +147,10 @@ pub fn scroll<V: View>(child: V) -> Scroll<V> {
         propagate_pointer_wheel: false,
         vertical_scroll_as_horizontal: false,
         scroll_bar_style: ScrollBarStyle::BASE,
+        auto_scroll_zone: None,
+        auto_scroll_delta: Vec2::ZERO,
+        auto_scroll_scheduled: false,
+        coarse_pointer: false,
     }
 }
 
@@ -169,6 +215,44 @@ impl<V: View> Scroll<V> {
         self
     }
 
+    /// Scroll the content while the pointer hovers within `zone` of an edge of the
+    /// viewport, with a speed proportional to how close the pointer is to the edge.
+    /// This matches how native menus behave when they overflow.
+    pub fn auto_scroll_on_hover(mut self, zone: f64) -> Self {
+        self.auto_scroll_zone = Some(zone);
+        self
+    }
+
+    fn update_auto_scroll(&mut self, pos: Point) {
+        let zone = if let Some(zone) = self.auto_scroll_zone.filter(|zone| *zone > 0.0) {
+            zone
+        } else {
+            return;
+        };
+
+        let rect = self.actual_rect;
+        let mut delta = Vec2::ZERO;
+        if self.child_size.width > rect.width() {
+            delta.x = auto_scroll_speed(pos.x, rect.x0, rect.x1, zone);
+        }
+        if self.child_size.height > rect.height() {
+            delta.y = auto_scroll_speed(pos.y, rect.y0, rect.y1, zone);
+        }
+        self.auto_scroll_delta = delta;
+
+        if delta != Vec2::ZERO && !self.auto_scroll_scheduled {
+            self.schedule_auto_scroll();
+        }
+    }
+
+    fn schedule_auto_scroll(&mut self) {
+        self.auto_scroll_scheduled = true;
+        let id = self.id;
+        exec_after(Duration::from_millis(AUTO_SCROLL_INTERVAL_MS), move |_| {
+            id.update_state(ScrollState::AutoScrollTick, false);
+        });
+    }
+
     fn scroll_delta(&mut self, app_state: &mut AppState, delta: Vec2) {
         let new_origin = self.child_viewport.origin() + delta;
         self.clamp_child_viewport(app_state, self.child_viewport.with_origin(new_origin));
@@ -358,6 +442,15 @@ impl<V: View> Scroll<V> {
         }
     }
 
+    fn bar_thickness(&self) -> f64 {
+        let thickness = self.scroll_bar_style.thickness as f64;
+        if self.coarse_pointer {
+            thickness.max(COARSE_SCROLLBAR_THICKNESS)
+        } else {
+            thickness
+        }
+    }
+
     fn calc_vertical_bar_bounds(&self, _app_state: &mut AppState) -> Option<Rect> {
         let viewport_size = self.child_viewport.size();
         let content_size = self.child_size;
@@ -367,7 +460,7 @@ impl<V: View> Scroll<V> {
             return None;
         }
 
-        let bar_width = self.scroll_bar_style.thickness as f64;
+        let bar_width = self.bar_thickness();
         let bar_pad = 0.0;
 
         let percent_visible = viewport_size.height / content_size.height;
@@ -375,7 +468,7 @@ impl<V: View> Scroll<V> {
 
         let length = (percent_visible * viewport_size.height).ceil();
         // Vertical scroll bar must have ast least the same height as it's width
-        let length = length.max(self.scroll_bar_style.thickness as f64);
+        let length = length.max(bar_width);
 
         let top_y_offset = ((viewport_size.height - length) * percent_scrolled).ceil();
         let bottom_y_offset = top_y_offset + length;
@@ -398,7 +491,7 @@ impl<V: View> Scroll<V> {
             return None;
         }
 
-        let bar_width = self.scroll_bar_style.thickness as f64;
+        let bar_width = self.bar_thickness();
         let bar_pad = 0.0;
 
         let percent_visible = viewport_size.width / content_size.width;
@@ -558,6 +651,21 @@ impl<V: View> View for Scroll<V> {
                 ScrollState::VerticalScrollAsHorizontal(value) => {
                     self.vertical_scroll_as_horizontal = value;
                 }
+                ScrollState::AutoScrollTick => {
+                    self.auto_scroll_scheduled = false;
+                    // stop once the pointer left the view or the held bars took over
+                    if !cx.app_state.is_hovered(&self.id) || self.are_bars_held() {
+                        self.auto_scroll_delta = Vec2::ZERO;
+                    }
+                    if self.auto_scroll_delta == Vec2::ZERO {
+                        return ChangeFlags::empty();
+                    }
+                    let viewport = self.child_viewport;
+                    self.scroll_delta(cx.app_state, self.auto_scroll_delta);
+                    if self.child_viewport != viewport {
+                        self.schedule_auto_scroll();
+                    }
+                }
             }
             cx.request_layout(self.id());
             ChangeFlags::LAYOUT
@@ -580,6 +688,7 @@ impl<V: View> View for Scroll<V> {
             if let Some(edge_width) = cx.scroll_bar_edge_width {
                 self.scroll_bar_style.edge_width = edge_width;
             }
+            self.coarse_pointer = pointer_capabilities().with_untracked(|p| p.is_coarse());
 
             let child_id = self.child.id();
             let child_view = cx.app_state_mut().view_state(child_id);
@@ -678,6 +787,9 @@ impl<V: View> View for Scroll<V> {
             }
             Event::PointerUp(_event) => self.held = BarHeldState::None,
             Event::PointerMove(event) => {
+                if !self.are_bars_held() {
+                    self.update_auto_scroll(event.pos);
+                }
                 if !self.scroll_bar_style.hide {
                     if self.are_bars_held() {
                         match self.held {
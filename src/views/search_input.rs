@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use peniko::Color;
+
+use crate::{
+    action::exec_after,
+    style::CursorStyle,
+    theme::tokens,
+    views::{composite, label, stack, svg, text_input, Composite, Decorators},
+};
+
+const MAGNIFIER_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round"><circle cx="11" cy="11" r="7" /><line x1="16.5" y1="16.5" x2="21" y2="21" /></svg>"#;
+
+const ICON_SIZE: f64 = 14.0;
+
+/// How long the text has to stay the same before it's passed on to the query, unless the
+/// search input has a [debounce](SearchInput::debounce) of its own
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// A text field for a search query. See [`search_input`]
+pub type SearchInput = Composite<SearchInputState>;
+
+pub struct SearchInputState {
+    debounce: RwSignal<Duration>,
+}
+
+/// A text field with a magnifier in front of it, which sets `query` to what's typed once
+/// the typing [pauses](SearchInput::debounce), so a search isn't run for every key.
+///
+/// While there's text, a × button clears it, as does Escape while the field has focus.
+/// Clearing sets `query` right away, and setting `query` from outside replaces the text.
+pub fn search_input(query: RwSignal<String>) -> SearchInput {
+    let text = create_rw_signal(query.get_untracked());
+    let debounce = create_rw_signal(DEFAULT_DEBOUNCE);
+    let generation = create_rw_signal(0u64);
+
+    create_effect(move |_| {
+        let text = text.get();
+        if query.with_untracked(|query| *query == text) {
+            return;
+        }
+        let current = generation.get_untracked() + 1;
+        generation.set(current);
+        let debounce = debounce.get_untracked();
+        if debounce.is_zero() {
+            query.set(text);
+            return;
+        }
+        exec_after(debounce, move |_| {
+            // typed again in the meantime
+            if generation.get_untracked() == current {
+                query.set(text);
+            }
+        });
+    });
+    create_effect(move |_| {
+        let query = query.get();
+        if text.with_untracked(|text| *text != query) {
+            text.set(query);
+        }
+    });
+
+    let icon = svg(|| MAGNIFIER_SVG.to_string()).style(|s| {
+        let tokens = tokens();
+        s.size(ICON_SIZE, ICON_SIZE)
+            .flex_shrink(0.0)
+            .margin_horiz(tokens.spacing.xs)
+            .color(tokens.colors.text_muted)
+    });
+    // Escape clears the text, and only gives up focus once there's no text left
+    let input = text_input(text)
+        .on_escape(move |current| {
+            if current.is_empty() {
+                return false;
+            }
+            clear_text(text, query, generation);
+            true
+        })
+        .style(|s| {
+            s.flex_grow(1.0)
+                .min_width(0.0)
+                .border(0.0)
+                .background(Color::TRANSPARENT)
+        });
+    let clear = label(|| "×".to_string())
+        .style(move |s| {
+            let tokens = tokens();
+            s.padding_horiz(tokens.spacing.xs)
+                .border_radius(tokens.radius.sm)
+                .color(tokens.colors.text_muted)
+                .cursor(CursorStyle::Pointer)
+                .apply_if(text.with(|text| text.is_empty()), |s| s.hide())
+        })
+        .hover_style(|s| s.background(tokens().colors.hover))
+        .on_click(move |_| {
+            clear_text(text, query, generation);
+            true
+        });
+
+    composite(
+        "SearchInput",
+        stack((icon, input, clear)).style(|s| s.width_full().items_center()),
+        SearchInputState { debounce },
+    )
+    .base_style(|s| {
+        let tokens = tokens();
+        s.min_width(160.0)
+            .padding(tokens.spacing.xs)
+            .border(1.0)
+            .border_color(tokens.colors.border)
+            .border_radius(tokens.radius.md)
+    })
+}
+
+/// Empties the text and the query at once, dropping a query that's still waiting
+fn clear_text(text: RwSignal<String>, query: RwSignal<String>, generation: RwSignal<u64>) {
+    generation.update(|generation| *generation += 1);
+    query.set(String::new());
+    text.set(String::new());
+}
+
+impl SearchInput {
+    /// How long the text has to stay the same before `query` is set to it, 300ms by
+    /// default. With a zero duration it's set on every change.
+    pub fn debounce(self, debounce: Duration) -> Self {
+        self.state().debounce.set(debounce);
+        self
+    }
+}
@@ -0,0 +1,359 @@
+use std::rc::Rc;
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use floem_renderer::Renderer;
+use kurbo::{Circle, Line, Point, Rect, RoundedRect, Size};
+use peniko::Color;
+use taffy::prelude::Node;
+use winit::keyboard::Key;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::{ComputedStyle, Style},
+    theme::tokens,
+    unit::PxPctAuto,
+    view::{ChangeFlags, View},
+    views::Decorators,
+};
+
+/// Tick marks are skipped when there would be more than this many, since they'd just blur together
+const MAX_TICK_MARKS: f64 = 200.0;
+
+type StyleFn = Rc<dyn Fn(Style) -> Style>;
+
+enum SliderState {
+    Value(f64),
+    TrackStyle(ComputedStyle),
+    FillStyle(ComputedStyle),
+    ThumbStyle(ComputedStyle),
+}
+
+pub struct Slider {
+    id: Id,
+    value: RwSignal<f64>,
+    current: f64,
+    min: f64,
+    max: f64,
+    step: Option<f64>,
+    tick_marks: bool,
+    held: bool,
+    size: Size,
+    track_style: ComputedStyle,
+    fill_style: ComputedStyle,
+    thumb_style: ComputedStyle,
+    track_style_fn: RwSignal<StyleFn>,
+    fill_style_fn: RwSignal<StyleFn>,
+    thumb_style_fn: RwSignal<StyleFn>,
+}
+
+fn default_track_style(s: Style) -> Style {
+    let tokens = tokens();
+    s.height(4.0)
+        .border_radius(tokens.radius.sm)
+        .background(tokens.colors.track)
+        .color(tokens.colors.text_muted)
+}
+
+fn default_fill_style(s: Style) -> Style {
+    s.background(tokens().colors.primary)
+}
+
+fn default_thumb_style(s: Style) -> Style {
+    let colors = tokens().colors;
+    s.width(16.0)
+        .background(colors.surface)
+        .border(1.0)
+        .border_color(colors.text_muted)
+}
+
+/// The style of one part of the slider, which is computed and sent to it whenever
+/// the style or the tokens its default is made from change
+fn part_style(
+    id: Id,
+    default: fn(Style) -> Style,
+    state: fn(ComputedStyle) -> SliderState,
+) -> RwSignal<StyleFn> {
+    let style: RwSignal<StyleFn> = create_rw_signal(Rc::new(|s| s));
+    create_effect(move |_| {
+        let computed = style.get()(default(Style::BASE)).compute(&ComputedStyle::default());
+        id.update_state(state(computed), false);
+    });
+    style
+}
+
+/// A horizontal slider bound to `value`, which is set while the thumb is dragged,
+/// when the track is clicked, and with the arrow keys while the slider has focus.
+///
+/// The track, the filled part of the track and the thumb each have their own style,
+/// set with [Slider::track_style], [Slider::fill_style] and [Slider::thumb_style].
+pub fn slider(value: RwSignal<f64>) -> Slider {
+    let id = Id::next();
+    create_effect(move |_| {
+        id.update_state(SliderState::Value(value.get()), false);
+    });
+    Slider {
+        id,
+        value,
+        current: value.get_untracked(),
+        min: 0.0,
+        max: 100.0,
+        step: None,
+        tick_marks: false,
+        held: false,
+        size: Size::ZERO,
+        track_style: ComputedStyle::default(),
+        fill_style: ComputedStyle::default(),
+        thumb_style: ComputedStyle::default(),
+        track_style_fn: part_style(id, default_track_style, SliderState::TrackStyle),
+        fill_style_fn: part_style(id, default_fill_style, SliderState::FillStyle),
+        thumb_style_fn: part_style(id, default_thumb_style, SliderState::ThumbStyle),
+    }
+    .base_style(|s| s.width(100.0).height(20.0))
+    .keyboard_navigatable()
+}
+
+impl Slider {
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Snaps the value to multiples of `step` from `min`, and is how far the arrow keys move it
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = Some(step).filter(|step| *step > 0.0);
+        self
+    }
+
+    /// Draws a tick mark at every step, using the `color` of the track style
+    pub fn tick_marks(mut self, tick_marks: bool) -> Self {
+        self.tick_marks = tick_marks;
+        self
+    }
+
+    /// The track is drawn with its `height`, `background` and `border_radius`
+    pub fn track_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.track_style_fn.set(Rc::new(style));
+        self
+    }
+
+    /// The part of the track before the thumb is drawn with the `background` of this style
+    pub fn fill_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.fill_style_fn.set(Rc::new(style));
+        self
+    }
+
+    /// The thumb is a circle with the `width` of this style as its diameter,
+    /// drawn with its `background`, `border_color` and left border width.
+    pub fn thumb_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.thumb_style_fn.set(Rc::new(style));
+        self
+    }
+
+    fn thumb_radius(&self) -> f64 {
+        match self.thumb_style.width {
+            PxPctAuto::Px(width) => width / 2.0,
+            PxPctAuto::Pct(pct) => self.size.height * pct / 100.0 / 2.0,
+            PxPctAuto::Auto => self.size.height / 2.0,
+        }
+    }
+
+    fn track_height(&self) -> f64 {
+        match self.track_style.height {
+            PxPctAuto::Px(height) => height,
+            PxPctAuto::Pct(pct) => self.size.height * pct / 100.0,
+            PxPctAuto::Auto => 4.0,
+        }
+    }
+
+    /// The horizontal range the thumb center moves along
+    fn track_span(&self) -> (f64, f64) {
+        let radius = self.thumb_radius();
+        (radius, (self.size.width - radius).max(radius))
+    }
+
+    fn fraction(&self) -> f64 {
+        if self.max > self.min {
+            ((self.current - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn snap(&self, value: f64) -> f64 {
+        let value = match self.step {
+            Some(step) => self.min + ((value - self.min) / step).round() * step,
+            None => value,
+        };
+        value.clamp(self.min, self.max.max(self.min))
+    }
+
+    fn set_value(&mut self, value: f64) {
+        let value = self.snap(value);
+        if value != self.current {
+            self.current = value;
+            self.value.set(value);
+            self.id.request_paint();
+        }
+    }
+
+    fn set_value_from_x(&mut self, x: f64) {
+        let (start, end) = self.track_span();
+        let fraction = if end > start {
+            ((x - start) / (end - start)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.set_value(self.min + fraction * (self.max - self.min));
+    }
+}
+
+impl View for Slider {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Slider".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) -> ChangeFlags {
+        if let Ok(state) = state.downcast::<SliderState>() {
+            match *state {
+                SliderState::Value(value) => self.current = value,
+                SliderState::TrackStyle(style) => self.track_style = style,
+                SliderState::FillStyle(style) => self.fill_style = style,
+                SliderState::ThumbStyle(style) => self.thumb_style = style,
+            }
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        None
+    }
+
+    fn event(&mut self, cx: &mut EventCx, _id_path: Option<&[Id]>, event: Event) -> bool {
+        match event {
+            Event::PointerDown(event) => {
+                if event.button.is_primary() {
+                    self.held = true;
+                    cx.update_active(self.id);
+                    cx.update_focus(self.id, false);
+                    self.set_value_from_x(event.pos.x);
+                    return true;
+                }
+                false
+            }
+            Event::PointerMove(event) => {
+                if self.held {
+                    self.set_value_from_x(event.pos.x);
+                    return true;
+                }
+                false
+            }
+            Event::PointerUp(_) => {
+                self.held = false;
+                false
+            }
+            Event::KeyDown(event) => {
+                let step = self.step.unwrap_or((self.max - self.min) / 100.0);
+                match event.key.logical_key {
+                    Key::ArrowLeft | Key::ArrowDown => self.set_value(self.current - step),
+                    Key::ArrowRight | Key::ArrowUp => self.set_value(self.current + step),
+                    Key::Home => self.set_value(self.min),
+                    Key::End => self.set_value(self.max),
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let (start, end) = self.track_span();
+        let center_y = self.size.height / 2.0;
+        let track_height = self.track_height();
+        let track_radius = self.track_style.border_radius.0;
+        let track = Rect::new(
+            start,
+            center_y - track_height / 2.0,
+            end,
+            center_y + track_height / 2.0,
+        );
+        if let Some(background) = self.track_style.background {
+            cx.fill(
+                &RoundedRect::from_rect(track, track_radius),
+                background,
+                0.0,
+            );
+        }
+
+        let thumb_x = start + (end - start) * self.fraction();
+        if let Some(background) = self.fill_style.background {
+            let fill = track.with_size(Size::new(thumb_x - start, track.height()));
+            cx.fill(&RoundedRect::from_rect(fill, track_radius), background, 0.0);
+        }
+
+        if self.tick_marks {
+            if let Some(step) = self
+                .step
+                .filter(|step| (self.max - self.min) / step <= MAX_TICK_MARKS)
+            {
+                let color = self.track_style.color.unwrap_or(Color::GRAY);
+                let count = ((self.max - self.min) / step).floor() as usize;
+                for i in 0..=count {
+                    let fraction = (i as f64 * step) / (self.max - self.min);
+                    let x = start + (end - start) * fraction;
+                    let line = Line::new(
+                        Point::new(x, track.y1 + 2.0),
+                        Point::new(x, track.y1 + 2.0 + track_height),
+                    );
+                    cx.stroke(&line, color, 1.0);
+                }
+            }
+        }
+
+        let radius = self.thumb_radius();
+        let thumb = Circle::new(Point::new(thumb_x, center_y), radius);
+        if let Some(background) = self.thumb_style.background {
+            cx.fill(&thumb, background, 0.0);
+        }
+        let border = self.thumb_style.border_left.0;
+        if border > 0.0 {
+            let thumb = Circle::new(thumb.center, radius - border / 2.0);
+            cx.stroke(&thumb, self.thumb_style.border_color, border);
+        }
+    }
+}
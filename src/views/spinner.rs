@@ -0,0 +1,148 @@
+use std::{
+    f64::consts::PI,
+    time::{Duration, Instant},
+};
+
+use floem_reactive::create_effect;
+use floem_renderer::Renderer;
+use kurbo::{Arc, Rect, Size, Vec2};
+use peniko::Color;
+use taffy::prelude::Node;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    theme::tokens,
+    view::{ChangeFlags, View},
+    views::Decorators,
+};
+
+/// How long the spinner takes to turn once
+const ROTATION_PERIOD: Duration = Duration::from_millis(1000);
+/// How much of the circle the spinning arc covers
+const ARC_LENGTH: f64 = 0.75;
+
+enum SpinnerState {
+    DefaultColor(Color),
+}
+
+/// A spinning arc that shows that something is loading. See [`spinner`]
+pub struct Spinner {
+    id: Id,
+    stroke_width: f64,
+    color: Option<Color>,
+    default_color: Color,
+    started: Instant,
+    size: Size,
+}
+
+/// A spinning arc for showing that some work, like a request, is still going on.
+/// It animates by itself for as long as it's shown, and is 24 by 24 pixels unless it's
+/// given another size with [Spinner::diameter] or a style.
+pub fn spinner() -> Spinner {
+    let id = Id::next();
+    create_effect(move |_| {
+        id.update_state(SpinnerState::DefaultColor(tokens().colors.primary), false);
+    });
+    Spinner {
+        id,
+        stroke_width: 3.0,
+        color: None,
+        default_color: Color::TRANSPARENT,
+        started: Instant::now(),
+        size: Size::ZERO,
+    }
+    .base_style(|s| s.size(24.0, 24.0))
+}
+
+impl Spinner {
+    /// Sets both the width and the height of the spinner
+    pub fn diameter(self, diameter: f32) -> Self {
+        self.base_style(move |s| s.size(diameter, diameter))
+    }
+
+    pub fn stroke_width(mut self, stroke_width: f64) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    /// The color of the arc, instead of the primary color of the [tokens](crate::theme)
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl View for Spinner {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Spinner".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) -> ChangeFlags {
+        if let Ok(state) = state.downcast::<SpinnerState>() {
+            match *state {
+                SpinnerState::DefaultColor(color) => self.default_color = color,
+            }
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        None
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let radius = (self.size.width.min(self.size.height) - self.stroke_width) / 2.0;
+        if radius > 0.0 {
+            let period = ROTATION_PERIOD.as_secs_f64();
+            let elapsed = cx
+                .frame_time()
+                .saturating_duration_since(self.started)
+                .as_secs_f64();
+            let turn = (elapsed % period) / period;
+            let arc = Arc {
+                center: self.size.to_rect().center(),
+                radii: Vec2::new(radius, radius),
+                start_angle: turn * 2.0 * PI,
+                sweep_angle: ARC_LENGTH * 2.0 * PI,
+                x_rotation: 0.0,
+            };
+            let color = self.color.unwrap_or(self.default_color);
+            cx.stroke(&arc, color, self.stroke_width);
+        }
+        cx.request_animation_frame(self.id);
+    }
+}
@@ -0,0 +1,200 @@
+use std::rc::Rc;
+
+use floem_reactive::{create_rw_signal, RwSignal};
+use kurbo::Rect;
+
+use crate::{
+    event::{Event, EventListener},
+    style::{CursorStyle, Style},
+    theme::tokens,
+    view::View,
+    views::{composite, container_box, empty, stack, Composite, Decorators},
+};
+
+type StyleFn = Rc<dyn Fn(Style) -> Style>;
+
+/// How much space the first pane of a [Split] takes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitPosition {
+    /// A part of the space the panes share, from 0.0 to 1.0, so both panes grow with the split
+    Ratio(f64),
+    /// A size in pixels, so only the second pane grows with the split
+    Fixed(f64),
+}
+
+impl SplitPosition {
+    /// The size of the first pane with `available` space for both panes
+    fn first_size(self, available: f64, min_sizes: (f64, f64)) -> f64 {
+        let size = match self {
+            SplitPosition::Ratio(ratio) => available * ratio,
+            SplitPosition::Fixed(size) => size,
+        };
+        // when there isn't space for both, the first pane keeps its minimum
+        size.min(available - min_sizes.1).max(min_sizes.0).max(0.0)
+    }
+}
+
+/// Two panes next to each other with a divider between them that can be dragged to
+/// resize them. See [`h_split`] and [`v_split`]
+pub type Split = Composite<SplitState>;
+
+pub struct SplitState {
+    position: RwSignal<SplitPosition>,
+    default_position: RwSignal<SplitPosition>,
+    min_sizes: RwSignal<(f64, f64)>,
+    gutter_style: RwSignal<StyleFn>,
+}
+
+fn default_gutter_style(s: Style) -> Style {
+    s.background(tokens().colors.border)
+}
+
+/// The pane `a` on the left and `b` on the right, split in the middle
+pub fn h_split(a: impl View + 'static, b: impl View + 'static) -> Split {
+    split(a, b, false)
+}
+
+/// The pane `a` on the top and `b` on the bottom, split in the middle
+pub fn v_split(a: impl View + 'static, b: impl View + 'static) -> Split {
+    split(a, b, true)
+}
+
+fn split(a: impl View + 'static, b: impl View + 'static, vertical: bool) -> Split {
+    let position = create_rw_signal(SplitPosition::Ratio(0.5));
+    let default_position = create_rw_signal(SplitPosition::Ratio(0.5));
+    let min_sizes = create_rw_signal((0.0, 0.0));
+    let gutter_style: RwSignal<StyleFn> = create_rw_signal(Rc::new(default_gutter_style));
+    // the size of the split along the axis it's split on, and of the gutter
+    let total = create_rw_signal(0.0);
+    let gutter_size = create_rw_signal(0.0);
+    let available = move || (total.get() - gutter_size.get()).max(0.0);
+    let axis = move |rect: Rect| {
+        if vertical {
+            rect.height()
+        } else {
+            rect.width()
+        }
+    };
+
+    let first = container_box(a).style(move |s| {
+        let size = position.get().first_size(available(), min_sizes.get());
+        let s = s.flex_shrink(0.0).min_width(0.0).min_height(0.0);
+        if vertical {
+            s.width_full().height(size)
+        } else {
+            s.height_full().width(size)
+        }
+    });
+    let second = container_box(b).style(move |s| {
+        s.flex_grow(1.0)
+            .flex_basis(0.0)
+            .min_width(0.0)
+            .min_height(0.0)
+    });
+
+    // where in the gutter it was grabbed while it's being dragged, so it stays under the
+    // pointer
+    let grab: RwSignal<Option<f64>> = create_rw_signal(None);
+    let gutter = empty();
+    let gutter_id = gutter.id();
+    let gutter = gutter
+        .style(move |s| {
+            let s = s.flex_shrink(0.0);
+            let s = if vertical {
+                s.width_full().height(4.0).cursor(CursorStyle::RowResize)
+            } else {
+                s.height_full().width(4.0).cursor(CursorStyle::ColResize)
+            };
+            gutter_style.get()(s)
+        })
+        .hover_style(|s| s.background(tokens().colors.primary))
+        .on_resize(move |rect| gutter_size.set(axis(rect)))
+        .on_event(EventListener::PointerDown, move |event| {
+            if let Event::PointerDown(event) = event {
+                if event.button.is_primary() {
+                    grab.set(Some(if vertical { event.pos.y } else { event.pos.x }));
+                    gutter_id.request_active();
+                    return true;
+                }
+            }
+            false
+        })
+        .on_event(EventListener::PointerMove, move |event| {
+            if let (Event::PointerMove(event), Some(grab)) = (event, grab.get_untracked()) {
+                let delta = if vertical { event.pos.y } else { event.pos.x } - grab;
+                let available = total.get_untracked() - gutter_size.get_untracked();
+                let current = position
+                    .get_untracked()
+                    .first_size(available, min_sizes.get_untracked());
+                let size = (current + delta)
+                    .min(available - min_sizes.get_untracked().1)
+                    .max(min_sizes.get_untracked().0)
+                    .max(0.0);
+                // keep the kind of position, so a ratio still grows with the split
+                position.set(match position.get_untracked() {
+                    SplitPosition::Ratio(_) if available > 0.0 => {
+                        SplitPosition::Ratio(size / available)
+                    }
+                    SplitPosition::Ratio(ratio) => SplitPosition::Ratio(ratio),
+                    SplitPosition::Fixed(_) => SplitPosition::Fixed(size),
+                });
+                true
+            } else {
+                false
+            }
+        })
+        .on_event(EventListener::PointerUp, move |_| {
+            grab.set(None);
+            false
+        })
+        .on_double_click(move |_| {
+            position.set(default_position.get_untracked());
+            true
+        });
+
+    let child = stack((first, gutter, second))
+        .on_resize(move |rect| total.set(axis(rect)))
+        .style(move |s| s.size_full().apply_if(vertical, |s| s.flex_col()));
+
+    composite(
+        "Split",
+        child,
+        SplitState {
+            position,
+            default_position,
+            min_sizes,
+            gutter_style,
+        },
+    )
+}
+
+impl Split {
+    /// Where the panes are split at first and after double clicking the divider
+    pub fn position(self, position: SplitPosition) -> Self {
+        self.state().position.set(position);
+        self.state().default_position.set(position);
+        self
+    }
+
+    /// Where the panes are split, which changes while the divider is dragged. It can be
+    /// read to save the split, and set to restore it.
+    pub fn position_signal(&self) -> RwSignal<SplitPosition> {
+        self.state().position
+    }
+
+    /// The smallest the first and second pane can be dragged to. If the split is too small
+    /// for both, the second pane gets less than its minimum.
+    pub fn min_sizes(self, first: f64, second: f64) -> Self {
+        self.state().min_sizes.set((first, second));
+        self
+    }
+
+    /// The style of the divider between the panes, whose width or height along the split
+    /// is the space between them
+    pub fn gutter_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.state()
+            .gutter_style
+            .set(Rc::new(move |s| style(default_gutter_style(s))));
+        self
+    }
+}
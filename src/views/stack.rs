@@ -58,18 +58,7 @@ impl<VT: ViewTuple + 'static> View for Stack<VT> {
         id_path: Option<&[Id]>,
         event: crate::event::Event,
     ) -> bool {
-        let mut handled = false;
-        self.children.foreach_rev(&mut |view| {
-            let id = view.id();
-            if cx.should_send(id, &event) {
-                handled = view.event_main(cx, id_path, event.clone());
-                if handled {
-                    return true;
-                }
-            }
-            false
-        });
-        handled
+        cx.event_children(self.children.children_mut(), id_path, &event)
     }
 
     fn layout(&mut self, cx: &mut crate::context::LayoutCx) -> taffy::prelude::Node {
@@ -94,9 +83,6 @@ impl<VT: ViewTuple + 'static> View for Stack<VT> {
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        self.children.foreach_mut(&mut |view| {
-            view.paint_main(cx);
-            false
-        });
+        cx.paint_children(self.children.children_mut());
     }
 }
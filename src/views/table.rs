@@ -0,0 +1,381 @@
+use std::{hash::Hash, rc::Rc};
+
+use floem_reactive::{create_effect, create_rw_signal, untrack, RwSignal};
+
+use crate::{
+    event::{Event, EventListener},
+    style::CursorStyle,
+    theme::tokens,
+    view::View,
+    views::{
+        clip, composite, container_box, empty, label, list, scroll, stack, virtual_list, Composite,
+        Decorators, VirtualListDirection, VirtualListItemSize, VirtualListVector,
+    },
+};
+
+const DEFAULT_COLUMN_WIDTH: f64 = 120.0;
+const DEFAULT_MIN_COLUMN_WIDTH: f64 = 24.0;
+const DEFAULT_ROW_HEIGHT: f64 = 28.0;
+const RESIZE_HANDLE_WIDTH: f64 = 6.0;
+
+/// Which way a column of a [Table] is sorted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// A column of a [Table], with its header title and the view of each row's cell.
+/// See [`table_column`]
+pub struct TableColumn<T> {
+    title: String,
+    width: f64,
+    min_width: f64,
+    sortable: bool,
+    cell: Box<dyn Fn(&T) -> Box<dyn View>>,
+}
+
+/// A column titled `title`, showing the view from `cell` for each row
+pub fn table_column<T, V: View + 'static>(
+    title: impl Into<String>,
+    cell: impl Fn(&T) -> V + 'static,
+) -> TableColumn<T> {
+    TableColumn {
+        title: title.into(),
+        width: DEFAULT_COLUMN_WIDTH,
+        min_width: DEFAULT_MIN_COLUMN_WIDTH,
+        sortable: false,
+        cell: Box::new(move |item| Box::new(cell(item))),
+    }
+}
+
+impl<T> TableColumn<T> {
+    /// The width the column starts with, before it's resized
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self.min_width = self.min_width.min(width);
+        self
+    }
+
+    /// How narrow the column can be resized to
+    pub fn min_width(mut self, min_width: f64) -> Self {
+        self.min_width = min_width;
+        self.width = self.width.max(min_width);
+        self
+    }
+
+    /// Lets the column be sorted by clicking its header. See [Table::on_sort]
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+/// Rows of items shown in columns under a header. See [`table`]
+pub type Table<K> = Composite<TableState<K>>;
+
+pub struct TableState<K: 'static> {
+    selected: RwSignal<Option<K>>,
+    sort: RwSignal<Option<(usize, SortDirection)>>,
+    widths: RwSignal<Vec<f64>>,
+    order: RwSignal<Vec<usize>>,
+    row_height: RwSignal<f64>,
+}
+
+/// A table with a row for each item from `each_fn` and the `columns` for the parts of it.
+/// Only the rows in view are built, like with a [virtual_list], so the rows all have the
+/// same height, set with [Table::row_height]. `key_fn` tells the rows apart, to keep the
+/// views of rows that are still there when the items change and to keep track of the
+/// selected row.
+///
+/// Columns are resized by dragging the right edge of their header and moved by dragging
+/// the header onto another one. Clicking the header of a [sortable](TableColumn::sortable)
+/// column sorts by it, or flips the direction if it's already sorted by it. The table
+/// doesn't sort the items itself: [Table::on_sort] is told which column to sort by, so
+/// the items from `each_fn` can be sorted to match. Clicking a row selects it.
+pub fn table<T, IF, I, KF, K>(columns: Vec<TableColumn<T>>, each_fn: IF, key_fn: KF) -> Table<K>
+where
+    T: 'static,
+    IF: Fn() -> I + 'static,
+    I: VirtualListVector<T>,
+    KF: Fn(&T) -> K + 'static,
+    K: Clone + Eq + Hash + 'static,
+{
+    let widths = create_rw_signal(columns.iter().map(|column| column.width).collect());
+    let order = create_rw_signal((0..columns.len()).collect::<Vec<_>>());
+    let sort: RwSignal<Option<(usize, SortDirection)>> = create_rw_signal(None);
+    let selected: RwSignal<Option<K>> = create_rw_signal(None);
+    let row_height = create_rw_signal(DEFAULT_ROW_HEIGHT);
+    // how far the rows are scrolled sideways, for the header to follow
+    let scroll_x = create_rw_signal(0.0);
+    // the column whose header is being dragged to move it
+    let dragged: RwSignal<Option<usize>> = create_rw_signal(None);
+    let columns = Rc::new(columns);
+    let key_fn = Rc::new(key_fn);
+
+    let header_columns = columns.clone();
+    let header = list(
+        move || order.get(),
+        |index| *index,
+        move |index| {
+            let column = &header_columns[index];
+            let (title, sortable, min_width) =
+                (column.title.clone(), column.sortable, column.min_width);
+            let title = label(move || {
+                let arrow = match sort.get() {
+                    Some((column, SortDirection::Ascending)) if column == index => " ▲",
+                    Some((column, SortDirection::Descending)) if column == index => " ▼",
+                    _ => "",
+                };
+                format!("{title}{arrow}")
+            })
+            .style(move |s| {
+                s.flex_grow(1.0)
+                    .min_width(0.0)
+                    .padding_horiz(tokens().spacing.sm)
+                    .items_center()
+                    .font_bold()
+                    .apply_if(sortable, |s| s.cursor(CursorStyle::Pointer))
+            })
+            .on_click(move |_| {
+                if sortable {
+                    sort.update(|sort| *sort = Some(next_sort(*sort, index)));
+                }
+                sortable
+            })
+            .draggable()
+            .dragging_style(|s| s.background(tokens().colors.surface))
+            .on_event(EventListener::DragStart, move |_| {
+                dragged.set(Some(index));
+                false
+            })
+            .on_event(EventListener::Drop, move |_| {
+                if let Some(from) = dragged.get_untracked() {
+                    dragged.set(None);
+                    order.update(|order| move_column(order, from, index));
+                    true
+                } else {
+                    false
+                }
+            })
+            .on_event(EventListener::DragEnd, move |_| {
+                dragged.set(None);
+                false
+            });
+
+            // where in the handle it was grabbed while it's being dragged, so it stays under
+            // the pointer
+            let grab_x: RwSignal<Option<f64>> = create_rw_signal(None);
+            let handle = empty();
+            let handle_id = handle.id();
+            let handle = handle
+                .style(|s| {
+                    s.width(RESIZE_HANDLE_WIDTH)
+                        .height_full()
+                        .cursor(CursorStyle::ColResize)
+                })
+                .hover_style(|s| s.background(tokens().colors.border))
+                .on_event(EventListener::PointerDown, move |event| {
+                    if let Event::PointerDown(event) = event {
+                        if event.button.is_primary() {
+                            grab_x.set(Some(event.pos.x));
+                            handle_id.request_active();
+                            return true;
+                        }
+                    }
+                    false
+                })
+                .on_event(EventListener::PointerMove, move |event| {
+                    if let (Event::PointerMove(event), Some(grab_x)) =
+                        (event, grab_x.get_untracked())
+                    {
+                        let delta = event.pos.x - grab_x;
+                        widths.update(|widths| {
+                            widths[index] = (widths[index] + delta).max(min_width);
+                        });
+                        true
+                    } else {
+                        false
+                    }
+                })
+                .on_event(EventListener::PointerUp, move |_| {
+                    grab_x.set(None);
+                    false
+                });
+
+            stack((title, handle)).style(move |s| {
+                s.width(widths.with(|widths| widths[index]))
+                    .height_full()
+                    .border_right(1.0)
+                    .border_color(tokens().colors.border)
+            })
+        },
+    )
+    .style(move |s| {
+        let tokens = tokens();
+        s.height(row_height.get())
+            .margin_left(-scroll_x.get())
+            .background(tokens.colors.surface)
+    });
+    let header = clip(header).style(|s| {
+        s.width_full()
+            .flex_shrink(0.0)
+            .border_bottom(1.0)
+            .border_color(tokens().colors.border)
+    });
+
+    let list_key = key_fn.clone();
+    let rows = virtual_list(
+        VirtualListDirection::Vertical,
+        VirtualListItemSize::Fixed(Box::new(move || row_height.get())),
+        each_fn,
+        move |item| list_key(item),
+        move |item| {
+            let key = key_fn(&item);
+            let columns = columns.clone();
+            let cells = list(
+                move || order.get(),
+                |index| *index,
+                move |index| {
+                    container_box((columns[index].cell)(&item)).style(move |s| {
+                        s.width(widths.with(|widths| widths[index]))
+                            .height_full()
+                            .min_width(0.0)
+                            .padding_horiz(tokens().spacing.sm)
+                            .items_center()
+                    })
+                },
+            );
+            let style_key = key.clone();
+            cells
+                .style(move |s| {
+                    let is_selected =
+                        selected.with(|selected| selected.as_ref() == Some(&style_key));
+                    s.height(row_height.get())
+                        .apply_if(is_selected, |s| s.background(tokens().colors.selected))
+                })
+                .hover_style(|s| s.background(tokens().colors.hover))
+                .on_click(move |_| {
+                    selected.set(Some(key.clone()));
+                    true
+                })
+        },
+    )
+    .style(|s| s.flex_col());
+    let rows = scroll(rows)
+        .on_scroll(move |viewport| scroll_x.set(viewport.x0))
+        .style(|s| s.width_full().flex_grow(1.0).min_height(0.0));
+
+    let child = stack((header, rows)).style(|s| {
+        s.flex_col()
+            .size_full()
+            .border(1.0)
+            .border_color(tokens().colors.border)
+    });
+
+    composite(
+        "Table",
+        child,
+        TableState {
+            selected,
+            sort,
+            widths,
+            order,
+            row_height,
+        },
+    )
+}
+
+/// The sort after clicking the header of `column`: ascending first, then descending
+fn next_sort(sort: Option<(usize, SortDirection)>, column: usize) -> (usize, SortDirection) {
+    match sort {
+        Some((sorted, SortDirection::Ascending)) if sorted == column => {
+            (column, SortDirection::Descending)
+        }
+        _ => (column, SortDirection::Ascending),
+    }
+}
+
+/// Moves the column `from` to where the column `to` is shown
+fn move_column(order: &mut Vec<usize>, from: usize, to: usize) {
+    let from = order.iter().position(|column| *column == from);
+    let to = order.iter().position(|column| *column == to);
+    if let (Some(from), Some(to)) = (from, to) {
+        let column = order.remove(from);
+        order.insert(to, column);
+    }
+}
+
+impl<K: 'static> Table<K> {
+    /// The height of the header and of every row
+    pub fn row_height(self, height: f64) -> Self {
+        self.state().row_height.set(height);
+        self
+    }
+
+    /// Called with the index of the column in the columns the table was made with, and the
+    /// direction, when the table gets sorted by a different column or direction
+    pub fn on_sort(self, action: impl Fn(usize, SortDirection) + 'static) -> Self {
+        let sort = self.state().sort;
+        create_effect(move |_| {
+            if let Some((column, direction)) = sort.get() {
+                untrack(|| action(column, direction));
+            }
+        });
+        self
+    }
+
+    /// The key of the selected row, which can be set to change the selection
+    pub fn selected_signal(&self) -> RwSignal<Option<K>> {
+        self.state().selected
+    }
+
+    /// The column the table is sorted by and its direction, which can be set to sort it
+    /// without a click
+    pub fn sort_signal(&self) -> RwSignal<Option<(usize, SortDirection)>> {
+        self.state().sort
+    }
+
+    /// The width of each column, in the order the table was made with, for keeping the
+    /// widths the user resized them to
+    pub fn column_widths_signal(&self) -> RwSignal<Vec<f64>> {
+        self.state().widths
+    }
+
+    /// The order the columns are shown in, as indices into the columns the table was
+    /// made with
+    pub fn column_order_signal(&self) -> RwSignal<Vec<usize>> {
+        self.state().order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{move_column, next_sort, SortDirection};
+
+    #[test]
+    fn clicking_a_header_flips_its_sort() {
+        assert_eq!(next_sort(None, 1), (1, SortDirection::Ascending));
+        let sort = next_sort(Some((1, SortDirection::Ascending)), 1);
+        assert_eq!(sort, (1, SortDirection::Descending));
+        assert_eq!(next_sort(Some(sort), 1), (1, SortDirection::Ascending));
+        // another column starts ascending
+        assert_eq!(
+            next_sort(Some((1, SortDirection::Descending)), 2),
+            (2, SortDirection::Ascending)
+        );
+    }
+
+    #[test]
+    fn dropping_a_column_moves_it_to_the_target() {
+        let mut order = vec![0, 1, 2, 3];
+        move_column(&mut order, 0, 2);
+        assert_eq!(order, [1, 2, 0, 3]);
+        move_column(&mut order, 3, 1);
+        assert_eq!(order, [3, 1, 2, 0]);
+        // dropping on itself or on a column that isn't there changes nothing
+        move_column(&mut order, 2, 2);
+        move_column(&mut order, 2, 7);
+        assert_eq!(order, [3, 1, 2, 0]);
+    }
+}
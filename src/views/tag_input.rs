@@ -0,0 +1,146 @@
+use floem_reactive::{create_effect, create_rw_signal, RwSignal};
+use peniko::Color;
+use taffy::style::FlexWrap;
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    style::CursorStyle,
+    theme::tokens,
+    view::View,
+    views::{composite, label, list, stack, text_input, Composite, Decorators},
+};
+
+/// A text field that turns what's typed into tags. See [`tag_input`]
+pub type TagInput = Composite<TagInputState>;
+
+pub struct TagInputState {
+    delimiters: RwSignal<Vec<char>>,
+}
+
+/// A field showing `tags` as chips in front of the text that's being typed. The text is
+/// added as a tag when Enter or one of the [delimiters](TagInput::delimiters) is typed, and
+/// text that's pasted is split into a tag for each part. Tags that are already there aren't
+/// added again.
+///
+/// Each chip has a button that removes it, and Backspace in the empty field removes the
+/// last one.
+pub fn tag_input(tags: RwSignal<Vec<String>>) -> TagInput {
+    let delimiters = create_rw_signal(vec![',']);
+    let buffer = create_rw_signal(String::new());
+    // whether the field was already empty before the key that's pressed
+    let was_empty = create_rw_signal(true);
+
+    let commit = move |text: &str| {
+        let text = text.trim();
+        if !text.is_empty() && !tags.with_untracked(|tags| tags.iter().any(|tag| tag == text)) {
+            tags.update(|tags| tags.push(text.to_string()));
+        }
+    };
+
+    create_effect(move |_| {
+        let delimiters = delimiters.get();
+        let text = buffer.get();
+        let mut parts: Vec<&str> = text
+            .split(|c: char| delimiters.contains(&c) || c == '\n')
+            .collect();
+        if parts.len() > 1 {
+            // what's after the last delimiter is still being typed
+            let rest = parts.pop().unwrap_or_default().to_string();
+            for part in parts {
+                commit(part);
+            }
+            buffer.set(rest);
+        }
+    });
+
+    let chips = list(
+        move || tags.get().into_iter().enumerate(),
+        |(index, tag)| (*index, tag.clone()),
+        move |(index, tag)| {
+            let remove = label(|| "×".to_string())
+                .style(|s| {
+                    let tokens = tokens();
+                    s.margin_left(tokens.spacing.xs)
+                        .padding_horiz(tokens.spacing.xs)
+                        .border_radius(tokens.radius.sm)
+                        .color(tokens.colors.text_muted)
+                        .cursor(CursorStyle::Pointer)
+                })
+                .hover_style(|s| s.background(tokens().colors.hover))
+                .on_click(move |_| {
+                    tags.update(|tags| {
+                        if index < tags.len() {
+                            tags.remove(index);
+                        }
+                    });
+                    true
+                });
+            stack((label(move || tag.clone()), remove)).style(|s| {
+                let tokens = tokens();
+                s.items_center()
+                    .margin(tokens.spacing.xs)
+                    .padding_left(tokens.spacing.sm)
+                    .padding_vert(tokens.spacing.xs)
+                    .border_radius(tokens.radius.md)
+                    .background(tokens.colors.selected)
+            })
+        },
+    )
+    .style(|s| s.flex_wrap(FlexWrap::Wrap).items_center());
+
+    let input = text_input(buffer)
+        .on_submit(move |text| {
+            commit(text);
+            buffer.set(String::new());
+        })
+        .on_event(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                if key.logical_key == Key::Backspace
+                    && was_empty.get_untracked()
+                    && buffer.with_untracked(|buffer| buffer.is_empty())
+                {
+                    tags.update(|tags| {
+                        tags.pop();
+                    });
+                    return true;
+                }
+            }
+            false
+        })
+        .on_event(EventListener::KeyUp, move |_| {
+            was_empty.set(buffer.with_untracked(|buffer| buffer.is_empty()));
+            false
+        })
+        .style(|s| {
+            s.flex_grow(1.0)
+                .min_width(60.0)
+                .margin(tokens().spacing.xs)
+                .border(0.0)
+                .background(Color::TRANSPARENT)
+        });
+
+    composite(
+        "TagInput",
+        stack((chips, input)).style(|s| s.width_full().flex_wrap(FlexWrap::Wrap).items_center()),
+        TagInputState { delimiters },
+    )
+    .base_style(|s| {
+        let tokens = tokens();
+        s.min_width(200.0)
+            .padding(tokens.spacing.xs)
+            .border(1.0)
+            .border_color(tokens.colors.border)
+            .border_radius(tokens.radius.md)
+    })
+}
+
+impl TagInput {
+    /// The characters that end a tag as they're typed or pasted, a comma by default. A new
+    /// line in pasted text always does.
+    pub fn delimiters(self, delimiters: impl Into<Vec<char>>) -> Self {
+        self.state().delimiters.set(delimiters.into());
+        self
+    }
+}
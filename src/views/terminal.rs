@@ -0,0 +1,490 @@
+use clipboard::{ClipboardContext, ClipboardProvider};
+use floem_reactive::SignalTracker;
+use floem_renderer::{
+    cosmic_text::{Attrs, AttrsList, FamilyOwned, TextLayout, Weight},
+    Renderer,
+};
+use kurbo::{Point, Rect, Size};
+use peniko::Color;
+use taffy::prelude::Node;
+use winit::keyboard::{Key, ModifiersState};
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    keyboard::KeyEvent,
+    style::CursorStyle,
+    theme::tokens,
+    view::{ChangeFlags, View},
+    views::Decorators,
+};
+
+const DEFAULT_FONT_SIZE: f32 = 14.0;
+const MONOSPACE: &[FamilyOwned] = &[FamilyOwned::Monospace];
+/// How many lines a notch of the wheel scrolls through the scrollback
+const WHEEL_LINES: usize = 3;
+
+/// A character on the screen of a terminal, with its colors
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerminalCell {
+    pub c: char,
+    /// The color of the character, or the color of the view for the default one
+    pub fg: Option<Color>,
+    /// The color behind the character, or none for the default background
+    pub bg: Option<Color>,
+    pub bold: bool,
+}
+
+impl Default for TerminalCell {
+    fn default() -> Self {
+        Self {
+            c: ' ',
+            fg: None,
+            bg: None,
+            bold: false,
+        }
+    }
+}
+
+/// The emulator behind a [`terminal`] view, such as one built on `alacritty_terminal`,
+/// which parses the output of a program into a grid of cells and takes its input
+pub trait TerminalBackend {
+    /// Called when the terminal fits a different number of cells
+    fn resize(&mut self, columns: usize, rows: usize);
+
+    /// The rows on screen, from the top, when scrolled `scroll` rows back into the
+    /// scrollback. The view is painted again when a signal read in here changes, so a
+    /// backend should notify a signal or a trigger when new output arrives.
+    fn rows(&self, scroll: usize) -> Vec<Vec<TerminalCell>>;
+
+    /// How many rows there are above the screen to scroll back to
+    fn scrollback_len(&self) -> usize;
+
+    /// The row and the column of the cursor on screen, unless it's hidden
+    fn cursor(&self) -> Option<(usize, usize)>;
+
+    /// Sends the bytes of keys that were typed, or of text that was pasted, to the program
+    fn write(&mut self, bytes: &[u8]);
+
+    /// Whether the program asked for the arrow keys to be sent in application mode
+    fn application_cursor(&self) -> bool {
+        false
+    }
+}
+
+/// A terminal of cells. See [`terminal`]
+pub struct Terminal {
+    id: Id,
+    backend: Box<dyn TerminalBackend>,
+    tracker: SignalTracker,
+    size: Size,
+    font_size: f32,
+    cell_size: Size,
+    grid: (usize, usize),
+    /// How many rows the screen is scrolled back into the scrollback
+    scroll: usize,
+    /// Where the selection started and where it ends, as a row from the top of the
+    /// scrollback and a column
+    selection: Option<((usize, usize), (usize, usize))>,
+    selecting: bool,
+}
+
+/// A view showing the screen of `backend`, a cell for each character in a monospaced font.
+/// The backend is told how many cells fit whenever the view is resized.
+///
+/// While it has focus, the keys that are typed are sent to the backend as the bytes
+/// a VT100 style terminal sends. Text is selected with the pointer, and copied with
+/// Ctrl+Shift+C (Cmd+C on macOS), and Ctrl+Shift+V (Cmd+V) pastes. The wheel scrolls
+/// back through the scrollback, and typing scrolls back down to the screen.
+pub fn terminal(backend: impl TerminalBackend + 'static) -> Terminal {
+    let id = Id::next();
+    Terminal {
+        id,
+        backend: Box::new(backend),
+        tracker: SignalTracker::new(move || id.request_paint()),
+        size: Size::ZERO,
+        font_size: DEFAULT_FONT_SIZE,
+        cell_size: Size::ZERO,
+        grid: (0, 0),
+        scroll: 0,
+        selection: None,
+        selecting: false,
+    }
+    .base_style(|s| {
+        s.size(640.0, 384.0)
+            .background(tokens().colors.surface)
+            .cursor(CursorStyle::Text)
+    })
+    .keyboard_navigatable()
+}
+
+impl Terminal {
+    fn attrs(&self, color: Color, bold: bool) -> Attrs<'static> {
+        let attrs = Attrs::new()
+            .color(color)
+            .family(MONOSPACE)
+            .font_size(self.font_size);
+        if bold {
+            attrs.weight(Weight::BOLD)
+        } else {
+            attrs
+        }
+    }
+
+    fn measure(&mut self) {
+        let mut layout = TextLayout::new();
+        layout.set_text("0", AttrsList::new(self.attrs(Color::BLACK, false)));
+        self.cell_size = Size::new(layout.size().width, (self.font_size as f64 * 1.3).round());
+    }
+
+    /// The cell under `pos`, as a row from the top of the scrollback and a column
+    fn cell_at(&self, pos: Point) -> (usize, usize) {
+        let (columns, rows) = self.grid;
+        let row = ((pos.y / self.cell_size.height).max(0.0) as usize).min(rows.saturating_sub(1));
+        let column = ((pos.x / self.cell_size.width).round().max(0.0) as usize).min(columns);
+        let top = self.backend.scrollback_len().saturating_sub(self.scroll);
+        (top + row, column)
+    }
+
+    fn selected_range(&self) -> Option<((usize, usize), (usize, usize))> {
+        self.selection
+            .filter(|(start, end)| start != end)
+            .map(|(start, end)| {
+                if start <= end {
+                    (start, end)
+                } else {
+                    (end, start)
+                }
+            })
+    }
+
+    fn copy(&self) {
+        let ((start_row, start_column), (end_row, end_column)) = match self.selected_range() {
+            Some(range) => range,
+            None => return,
+        };
+        // the selection can be partly off the screen, so the rows are read from its top
+        let scroll = self.backend.scrollback_len().saturating_sub(start_row);
+        let top = self.backend.scrollback_len() - scroll;
+        let rows = self.backend.rows(scroll);
+        let mut text = String::new();
+        for (index, row) in rows.iter().enumerate() {
+            let row_index = top + index;
+            if row_index < start_row {
+                continue;
+            }
+            if row_index > end_row {
+                break;
+            }
+            let from = if row_index == start_row {
+                start_column
+            } else {
+                0
+            };
+            let to = if row_index == end_row {
+                end_column
+            } else {
+                row.len()
+            };
+            let line: String = row
+                .iter()
+                .skip(from)
+                .take(to.saturating_sub(from))
+                .map(|cell| cell.c)
+                .collect();
+            text.push_str(line.trim_end());
+            if row_index != end_row {
+                text.push('\n');
+            }
+        }
+        let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
+        let _ = ctx.set_contents(text);
+    }
+
+    fn paste(&mut self) {
+        let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
+        if let Ok(text) = ctx.get_contents() {
+            self.backend
+                .write(text.replace("\r\n", "\r").replace('\n', "\r").as_bytes());
+        }
+    }
+
+    fn handle_key_down(&mut self, event: &KeyEvent) -> bool {
+        #[cfg(target_os = "macos")]
+        let command = event.modifiers == ModifiersState::SUPER;
+        #[cfg(not(target_os = "macos"))]
+        let command = event.modifiers == ModifiersState::CONTROL | ModifiersState::SHIFT;
+        if let Key::Character(ch) = &event.key.logical_key {
+            if command && ch.eq_ignore_ascii_case("c") {
+                self.copy();
+                return true;
+            }
+            if command && ch.eq_ignore_ascii_case("v") {
+                self.paste();
+                return true;
+            }
+        }
+        match key_bytes(
+            &event.key.logical_key,
+            event.modifiers,
+            self.backend.application_cursor(),
+        ) {
+            Some(bytes) => {
+                self.scroll = 0;
+                self.selection = None;
+                self.backend.write(&bytes);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// The bytes a VT100 style terminal sends for `key`
+fn key_bytes(key: &Key, modifiers: ModifiersState, application_cursor: bool) -> Option<Vec<u8>> {
+    let control = modifiers.contains(ModifiersState::CONTROL);
+    let alt = modifiers.contains(ModifiersState::ALT);
+    let cursor = |code: char| {
+        let prefix = if application_cursor { "\x1bO" } else { "\x1b[" };
+        format!("{prefix}{code}").into_bytes()
+    };
+    let mut bytes = match key {
+        Key::Character(ch) => {
+            let mut chars = ch.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if control && c.is_ascii_alphabetic() => {
+                    vec![c.to_ascii_lowercase() as u8 & 0x1f]
+                }
+                (Some(c), None) if control && "[\\]^_".contains(c) => vec![c as u8 & 0x1f],
+                _ => ch.as_bytes().to_vec(),
+            }
+        }
+        Key::Space if control => vec![0],
+        Key::Space => vec![b' '],
+        Key::Enter => vec![b'\r'],
+        Key::Backspace => vec![0x7f],
+        Key::Tab if modifiers.contains(ModifiersState::SHIFT) => b"\x1b[Z".to_vec(),
+        Key::Tab => vec![b'\t'],
+        Key::Escape => vec![0x1b],
+        Key::ArrowUp => cursor('A'),
+        Key::ArrowDown => cursor('B'),
+        Key::ArrowRight => cursor('C'),
+        Key::ArrowLeft => cursor('D'),
+        Key::Home => cursor('H'),
+        Key::End => cursor('F'),
+        Key::Insert => b"\x1b[2~".to_vec(),
+        Key::Delete => b"\x1b[3~".to_vec(),
+        Key::PageUp => b"\x1b[5~".to_vec(),
+        Key::PageDown => b"\x1b[6~".to_vec(),
+        Key::F1 => b"\x1bOP".to_vec(),
+        Key::F2 => b"\x1bOQ".to_vec(),
+        Key::F3 => b"\x1bOR".to_vec(),
+        Key::F4 => b"\x1bOS".to_vec(),
+        Key::F5 => b"\x1b[15~".to_vec(),
+        Key::F6 => b"\x1b[17~".to_vec(),
+        Key::F7 => b"\x1b[18~".to_vec(),
+        Key::F8 => b"\x1b[19~".to_vec(),
+        Key::F9 => b"\x1b[20~".to_vec(),
+        Key::F10 => b"\x1b[21~".to_vec(),
+        Key::F11 => b"\x1b[23~".to_vec(),
+        Key::F12 => b"\x1b[24~".to_vec(),
+        _ => return None,
+    };
+    if alt {
+        bytes.insert(0, 0x1b);
+    }
+    Some(bytes)
+}
+
+impl View for Terminal {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Terminal".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |cx| {
+            let font_size = cx.current_font_size().unwrap_or(DEFAULT_FONT_SIZE);
+            if font_size != self.font_size || self.cell_size.width == 0.0 {
+                self.font_size = font_size;
+                self.measure();
+            }
+            Vec::new()
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        if !self.cell_size.width == 0.0 {
+            let grid = (
+                (self.size.width / self.cell_size.width).floor().max(1.0) as usize,
+                (self.size.height / self.cell_size.height).floor().max(1.0) as usize,
+            );
+            if grid != self.grid {
+                self.grid = grid;
+                self.backend.resize(grid.0, grid.1);
+            }
+        }
+        None
+    }
+
+    fn event(&mut self, cx: &mut EventCx, _id_path: Option<&[Id]>, event: Event) -> bool {
+        let handled = match &event {
+            Event::PointerDown(event) => {
+                // the event is handled here, so the view is focused here too
+                cx.app_state.update_focus(self.id, false);
+                let cell = self.cell_at(event.pos);
+                self.selection = Some((cell, cell));
+                self.selecting = true;
+                self.id.request_active();
+                true
+            }
+            Event::PointerMove(event) if self.selecting => {
+                let cell = self.cell_at(event.pos);
+                if let Some((_, end)) = self.selection.as_mut() {
+                    *end = cell;
+                }
+                true
+            }
+            Event::PointerUp(_) => {
+                self.selecting = false;
+                false
+            }
+            Event::PointerWheel(event) => {
+                let lines = (event.delta.y.abs() / self.cell_size.height.max(1.0))
+                    .ceil()
+                    .max(1.0) as usize
+                    * WHEEL_LINES;
+                let scroll = if event.delta.y < 0.0 {
+                    (self.scroll + lines).min(self.backend.scrollback_len())
+                } else {
+                    self.scroll.saturating_sub(lines)
+                };
+                let scrolled = scroll != self.scroll;
+                self.scroll = scroll;
+                scrolled
+            }
+            Event::KeyDown(event) => self.handle_key_down(event),
+            _ => false,
+        };
+        if handled {
+            self.id.request_paint();
+        }
+        handled
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let tokens = tokens();
+        let color = cx.color.unwrap_or(tokens.colors.text);
+        let focused = cx.app_state.is_focused(&self.id);
+        let cell = self.cell_size;
+        let scroll = self.scroll;
+        let backend = &self.backend;
+        let (rows, cursor, scrollback) = self.tracker.track(|| {
+            (
+                backend.rows(scroll),
+                backend.cursor(),
+                backend.scrollback_len(),
+            )
+        });
+        let top = scrollback.saturating_sub(scroll);
+        let selection = self.selected_range();
+
+        cx.save();
+        cx.clip(&self.size.to_rect());
+        for (index, row) in rows.iter().enumerate() {
+            let y = index as f64 * cell.height;
+            for (column, item) in row.iter().enumerate() {
+                if let Some(bg) = item.bg {
+                    let rect = Rect::from_origin_size(
+                        (column as f64 * cell.width, y),
+                        (cell.width, cell.height),
+                    );
+                    cx.fill(&rect, bg, 0.0);
+                }
+            }
+            if let Some(((start_row, start_column), (end_row, end_column))) = selection {
+                let row_index = top + index;
+                if row_index >= start_row && row_index <= end_row {
+                    let from = if row_index == start_row {
+                        start_column
+                    } else {
+                        0
+                    };
+                    let to = if row_index == end_row {
+                        end_column
+                    } else {
+                        self.grid.0
+                    };
+                    let rect = Rect::new(
+                        from as f64 * cell.width,
+                        y,
+                        to as f64 * cell.width,
+                        y + cell.height,
+                    );
+                    cx.fill(&rect, tokens.colors.selected, 0.0);
+                }
+            }
+
+            let text: String = row.iter().map(|item| item.c).collect();
+            let mut attrs_list = AttrsList::new(self.attrs(color, false));
+            let mut offset = 0;
+            for item in row {
+                let len = item.c.len_utf8();
+                if item.fg.is_some() || item.bold {
+                    attrs_list.add_span(
+                        offset..offset + len,
+                        self.attrs(item.fg.unwrap_or(color), item.bold),
+                    );
+                }
+                offset += len;
+            }
+            let mut layout = TextLayout::new();
+            layout.set_text(&text, attrs_list);
+            let height = layout.size().height;
+            cx.draw_text(&layout, Point::new(0.0, y + (cell.height - height) / 2.0));
+        }
+
+        // the cursor is on the screen, which is below the scrollback that's shown
+        if let Some((row, column)) = cursor {
+            let row = row + scroll;
+            if row < self.grid.1 {
+                let rect = Rect::from_origin_size(
+                    (column as f64 * cell.width, row as f64 * cell.height),
+                    (cell.width, cell.height),
+                );
+                if focused {
+                    cx.fill(&rect, color.with_alpha_factor(0.6), 0.0);
+                } else {
+                    cx.stroke(&rect, color, 1.0);
+                }
+            }
+        }
+        cx.restore();
+    }
+}
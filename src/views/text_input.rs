@@ -1,19 +1,22 @@
-use crate::action::exec_after;
-use crate::keyboard::{self, KeyEvent};
+use crate::action::{exec_after, set_ime_allowed, set_ime_purpose};
+use crate::keyboard::{self, InputPurpose, KeyEvent};
 use crate::reactive::{create_effect, RwSignal};
 use crate::unit::PxPct;
-use crate::{context::LayoutCx, style::CursorStyle};
+use crate::{
+    context::{AppState, LayoutCx},
+    style::{CaretShape, CursorStyle},
+};
 use clipboard::{ClipboardContext, ClipboardProvider};
 use taffy::prelude::{Layout, Node};
 
 use floem_renderer::{
-    cosmic_text::{Cursor, Style as FontStyle, Weight},
+    cosmic_text::{Style as FontStyle, Weight},
     Renderer,
 };
 use unicode_segmentation::UnicodeSegmentation;
 use winit::keyboard::{Key, ModifiersState, SmolStr};
 
-use crate::{peniko::Color, style::Style, view::View};
+use crate::{peniko::Color, style::Style, theme::tokens, view::View};
 
 use std::{
     any::Any,
@@ -45,6 +48,10 @@ enum InputKind {
     },
 }
 
+enum TextInputState {
+    ReadOnly(bool),
+}
+
 /// Text Input View
 pub struct TextInput {
     id: Id,
@@ -55,17 +62,12 @@ pub struct TextInput {
     cursor_x: f64,
     text_buf: Option<TextLayout>,
     text_node: Option<Node>,
-    // Shown when the width exceeds node width for single line input
-    clipped_text: Option<String>,
-    // Glyph index from which we started clipping
-    clip_start_idx: usize,
-    // This can be retrieved from the clip start glyph, but we store it for efficiency
-    clip_start_x: f64,
-    clip_txt_buf: Option<TextLayout>,
-    // When the visible range changes, we also may need to have a small offset depending on the direction we moved.
-    // This makes sure character under the cursor is always fully visible and correctly aligned,
-    // and may cause the last character in the opposite direction to be "cut"
-    clip_offset_x: f64,
+    // How far the text is scrolled horizontally, to keep the cursor visible
+    // when the text is wider than the input
+    scroll_x: f64,
+    // The range of the buffer which is currently visible
+    visible_range: Range<usize>,
+    on_visible_range: Option<Box<dyn Fn(Range<usize>)>>,
     color: Option<Color>,
     selection: Option<Range<usize>>,
     font_size: f32,
@@ -75,7 +77,15 @@ pub struct TextInput {
     font_weight: Option<Weight>,
     font_style: Option<FontStyle>,
     input_kind: InputKind,
-    cursor_width: f64, // TODO: make this configurable
+    cursor_width: f64,
+    // `None` keeps the caret solid instead of blinking
+    cursor_blink_interval: Option<Duration>,
+    input_purpose: InputPurpose,
+    read_only: bool,
+    on_submit: Option<Box<dyn Fn(&str)>>,
+    on_escape: Option<Box<dyn Fn(&str) -> bool>>,
+    on_focus: Option<Box<dyn Fn()>>,
+    on_blur: Option<Box<dyn Fn(&str)>>,
     is_focused: bool,
     last_cursor_action_on: Instant,
 }
@@ -110,8 +120,9 @@ pub fn text_input(buffer: RwSignal<String>) -> TextInput {
         buffer,
         text_buf: None,
         text_node: None,
-        clipped_text: None,
-        clip_txt_buf: None,
+        scroll_x: 0.0,
+        visible_range: 0..0,
+        on_visible_range: None,
         color: None,
         font_size: DEFAULT_FONT_SIZE,
         font_family: None,
@@ -120,23 +131,21 @@ pub fn text_input(buffer: RwSignal<String>) -> TextInput {
         cursor_x: 0.0,
         selection: None,
         input_kind: InputKind::SingleLine,
-        clip_start_idx: 0,
-        clip_offset_x: 0.0,
-        clip_start_x: 0.0,
-        cursor_width: 1.0,
+        cursor_width: DEFAULT_CURSOR_WIDTH,
+        cursor_blink_interval: Some(Duration::from_millis(CURSOR_BLINK_INTERVAL_MS)),
+        input_purpose: InputPurpose::Normal,
+        read_only: false,
+        on_submit: None,
+        on_escape: None,
+        on_focus: None,
+        on_blur: None,
         width: 0.0,
         height: 0.0,
         is_focused: false,
         last_cursor_action_on: Instant::now(),
     }
     .keyboard_navigatable()
-}
-
-#[derive(Copy, Clone, Debug)]
-enum ClipDirection {
-    None,
-    Forward,
-    Backward,
+    .disabled_style(|s| s.color(tokens().colors.text_muted))
 }
 
 enum TextCommand {
@@ -177,25 +186,148 @@ impl From<(&KeyEvent, &SmolStr)> for TextCommand {
 
 const DEFAULT_FONT_SIZE: f32 = 14.0;
 const CURSOR_BLINK_INTERVAL_MS: u64 = 500;
+const DEFAULT_CURSOR_WIDTH: f64 = 1.0;
 // see https://developer.mozilla.org/en-US/docs/Web/HTML/Element/input/text#size
 const APPROX_VISIBLE_CHARS: f32 = 10.0;
 
 impl TextInput {
+    /// Set the width of the caret, unless a [`Style::caret_width`] applies to the input.
+    /// The caret color is controlled by [`Style::cursor_color`], and its shape by
+    /// [`Style::caret_shape`].
+    pub fn cursor_width(mut self, width: f64) -> Self {
+        self.cursor_width = width;
+        self
+    }
+
+    /// Set how long the caret stays visible (and hidden) during each blink.
+    /// Passing `None` disables blinking and keeps the caret solid while focused.
+    pub fn cursor_blink_interval(mut self, interval: Option<Duration>) -> Self {
+        self.cursor_blink_interval = interval;
+        self
+    }
+
+    /// Hint the platform input method about the kind of text this input expects. Only
+    /// [InputPurpose::Password] reaches the platform, see [InputPurpose].
+    pub fn input_purpose(mut self, purpose: InputPurpose) -> Self {
+        self.input_purpose = purpose;
+        self
+    }
+
+    /// When `read_only` returns true, the text can still be selected and copied,
+    /// but not edited. Use [`Decorators::disabled`] to also block focus.
+    pub fn read_only(self, read_only: impl Fn() -> bool + 'static) -> Self {
+        let id = self.id;
+        create_effect(move |_| {
+            id.update_state(TextInputState::ReadOnly(read_only()), false);
+        });
+        self
+    }
+
+    /// Called with the byte range of the text that is visible whenever it changes,
+    /// e.g. because the text scrolled to follow the cursor.
+    pub fn on_visible_range(mut self, action: impl Fn(Range<usize>) + 'static) -> Self {
+        self.on_visible_range = Some(Box::new(action));
+        self
+    }
+
+    /// Called with the current text when the user presses Enter.
+    pub fn on_submit(mut self, action: impl Fn(&str) + 'static) -> Self {
+        self.on_submit = Some(Box::new(action));
+        self
+    }
+
+    /// Called with the current text when the user presses Escape. The input keeps its focus
+    /// when this returns true, and gives it up otherwise.
+    pub fn on_escape(mut self, action: impl Fn(&str) -> bool + 'static) -> Self {
+        self.on_escape = Some(Box::new(action));
+        self
+    }
+
+    /// Called with the new text every time it changes, whether it was typed, committed
+    /// by an IME, pasted or set on the buffer signal.
+    pub fn on_change(self, action: impl Fn(&str) + 'static) -> Self {
+        let buffer = self.buffer;
+        create_effect(move |prev_text: Option<String>| {
+            let text = buffer.get();
+            if prev_text.is_some_and(|prev_text| prev_text != text) {
+                action(&text);
+            }
+            text
+        });
+        self
+    }
+
+    /// Called when the input gains focus.
+    pub fn on_focus(mut self, action: impl Fn() + 'static) -> Self {
+        self.on_focus = Some(Box::new(action));
+        self
+    }
+
+    /// Called with the final text when the input loses focus.
+    pub fn on_blur(mut self, action: impl Fn(&str) + 'static) -> Self {
+        self.on_blur = Some(Box::new(action));
+        self
+    }
+
+    /// Whether the caret should be drawn right now.
+    /// The caret stays solid while the user is typing or moving it, and only starts
+    /// blinking after a full blink interval without any cursor action.
+    fn is_cursor_visible(&self) -> bool {
+        if let Some(interval) = self.cursor_blink_interval {
+            let interval = interval.as_millis().max(1);
+            (self.last_cursor_action_on.elapsed().as_millis() / interval) % 2 == 0
+        } else {
+            true
+        }
+    }
+
+    /// Keep track of the focus, from the focus events
+    fn focus_changed(&mut self, app_state: &AppState, is_focused: bool) {
+        if is_focused == self.is_focused {
+            return;
+        }
+        self.is_focused = is_focused;
+
+        // Let the IME (and virtual keyboard) follow the focus of the input.
+        // When focus moves to another view, that view is in charge of the IME,
+        // so only disable it when nothing is focused anymore.
+        if is_focused {
+            set_ime_purpose(self.input_purpose);
+            set_ime_allowed(true);
+            if let Some(on_focus) = self.on_focus.as_ref() {
+                on_focus();
+            }
+        } else {
+            if app_state.focus.is_none() {
+                set_ime_allowed(false);
+            }
+            if let Some(on_blur) = self.on_blur.as_ref() {
+                self.buffer.with_untracked(|buff| on_blur(buff));
+            }
+        }
+    }
+
     fn move_cursor(&mut self, move_kind: Movement, direction: Direction) -> bool {
         if matches!(self.input_kind, InputKind::MultiLine { line_index: _ }) {
             todo!();
         }
         match (move_kind, direction) {
             (Movement::Glyph, Direction::Left) => {
-                if self.cursor_glyph_idx >= 1 {
-                    self.cursor_glyph_idx -= 1;
+                if let Some(idx) = self
+                    .buffer
+                    .with_untracked(|buff| prev_grapheme_idx(buff, self.cursor_glyph_idx))
+                {
+                    self.cursor_glyph_idx = idx;
                     return true;
                 }
                 false
             }
             (Movement::Glyph, Direction::Right) => {
-                if self.cursor_glyph_idx < self.buffer.with_untracked(|buff| buff.len()) {
-                    self.cursor_glyph_idx += 1;
+                if let Some(idx) = self
+                    .buffer
+                    .with_untracked(|buff| next_grapheme_idx(buff, self.cursor_glyph_idx))
+                {
+                    self.cursor_glyph_idx = idx;
                     return true;
                 }
                 false
@@ -252,66 +384,37 @@ impl TextInput {
             || self.font_style != cx.font_style
     }
 
-    fn get_line_idx(&self) -> usize {
-        match self.input_kind {
-            InputKind::SingleLine => 0,
-            InputKind::MultiLine { line_index: _ } => todo!(),
-        }
-    }
-
-    fn clip_text(&mut self, node_layout: &Layout) {
-        let virt_text = self.text_buf.as_ref().unwrap();
-        let node_width = node_layout.size.width as f64;
-        let cursor_text_loc = Cursor::new(self.get_line_idx(), self.cursor_glyph_idx);
-        let layout_cursor = virt_text.layout_cursor(&cursor_text_loc);
-        let cursor_glyph_pos = virt_text.hit_position(layout_cursor.glyph);
-        let cursor_x = cursor_glyph_pos.point.x;
-
-        let mut clip_start_x = self.clip_start_x;
-
-        let visible_range = clip_start_x..=clip_start_x + node_width;
-
-        let mut clip_dir = ClipDirection::None;
-        if !visible_range.contains(&cursor_glyph_pos.point.x) {
-            if cursor_x < *visible_range.start() {
-                clip_start_x = cursor_x;
-                clip_dir = ClipDirection::Backward;
-            } else {
-                clip_dir = ClipDirection::Forward;
-                clip_start_x = cursor_x - node_width;
-            }
+    /// Scroll the text horizontally so that the cursor stays visible, scrolling back
+    /// when the text gets shorter or the cursor moves towards the start.
+    fn scroll_to_cursor(&mut self, node_width: f64, caret_width: f64) {
+        let text_buf = self.text_buf.as_ref().unwrap();
+        let buf_width = text_buf.size().width;
+        let cursor_x = text_buf.hit_position(self.cursor_glyph_idx).point.x;
+
+        let max_scroll_x = (buf_width + caret_width - node_width).max(0.0);
+        let mut scroll_x = self.scroll_x.min(max_scroll_x);
+        if cursor_x < scroll_x {
+            scroll_x = cursor_x;
+        } else if cursor_x + caret_width > scroll_x + node_width {
+            scroll_x = cursor_x + caret_width - node_width;
         }
-        self.cursor_x = cursor_x;
+        self.scroll_x = scroll_x.max(0.0);
+        self.cursor_x = cursor_x - self.scroll_x;
 
-        let clip_start = virt_text.hit_point(Point::new(clip_start_x, 0.0)).index;
-        let clip_end = virt_text
-            .hit_point(Point::new(clip_start_x + node_width, 0.0))
+        let visible_start = text_buf.hit_point(Point::new(self.scroll_x, 0.0)).index;
+        let visible_end = text_buf
+            .hit_point(Point::new(self.scroll_x + node_width, 0.0))
             .index;
-
-        let new_text = self
-            .buffer
-            .get()
-            .chars()
-            .skip(clip_start)
-            .take(clip_end - clip_start)
-            .collect();
-
-        self.cursor_x -= clip_start_x;
-        self.clip_start_idx = clip_start;
-        self.clip_start_x = clip_start_x;
-        self.clipped_text = Some(new_text);
-
-        self.update_text_layout();
-        match clip_dir {
-            ClipDirection::None => {}
-            ClipDirection::Forward => {
-                self.clip_offset_x = self.clip_txt_buf.as_ref().unwrap().size().width - node_width
+        let visible_range = visible_start..visible_end;
+        if self.visible_range != visible_range {
+            self.visible_range = visible_range.clone();
+            if let Some(on_visible_range) = self.on_visible_range.as_ref() {
+                on_visible_range(visible_range);
             }
-            ClipDirection::Backward => self.clip_offset_x = 0.0,
         }
     }
 
-    fn get_cursor_rect(&self, node_layout: &Layout) -> Rect {
+    fn get_cursor_rect(&self, node_layout: &Layout, caret_width: f64, shape: CaretShape) -> Rect {
         let virtual_text = self.text_buf.as_ref().unwrap();
         let text_height = virtual_text.size().height;
 
@@ -322,16 +425,31 @@ impl TextInput {
             node_location.y as f64,
         );
 
-        Rect::from_points(
-            cursor_start,
-            Point::new(
-                cursor_start.x + self.cursor_width,
-                cursor_start.y + text_height,
+        // block and underline carets cover the character after the cursor, or half the font size
+        // at the end of the text
+        let char_width = || {
+            self.buffer
+                .with_untracked(|buff| buff[self.cursor_glyph_idx..].chars().next())
+                .map(|c| {
+                    let next = virtual_text.hit_position(self.cursor_glyph_idx + c.len_utf8());
+                    let current = virtual_text.hit_position(self.cursor_glyph_idx);
+                    next.point.x - current.point.x
+                })
+                .unwrap_or(self.font_size as f64 / 2.0)
+        };
+
+        let (x1, y0) = match shape {
+            CaretShape::Bar => (cursor_start.x + caret_width, cursor_start.y),
+            CaretShape::Block => (cursor_start.x + char_width(), cursor_start.y),
+            CaretShape::Underline => (
+                cursor_start.x + char_width(),
+                cursor_start.y + text_height - caret_width.max(1.0),
             ),
-        )
+        };
+        Rect::new(cursor_start.x, y0, x1, cursor_start.y + text_height)
     }
 
-    fn get_selection_rect(&self, node_layout: &Layout, left_padding: f64) -> Rect {
+    fn get_selection_rect(&self, node_layout: &Layout) -> Rect {
         let selection = if let Some(curr_selection) = &self.selection {
             curr_selection
         } else {
@@ -341,25 +459,17 @@ impl TextInput {
         let virtual_text = self.text_buf.as_ref().unwrap();
         let text_height = virtual_text.size().height;
 
-        let selection_start_x =
-            virtual_text.hit_position(selection.start).point.x - self.clip_start_x;
-        let selection_start_x = selection_start_x.max(node_layout.location.x as f64 - left_padding);
-
-        let selection_end_x =
-            virtual_text.hit_position(selection.end).point.x + left_padding - self.clip_start_x;
-        let selection_end_x =
-            selection_end_x.min(selection_start_x + self.width as f64 + left_padding);
-
         let node_location = node_layout.location;
+        let selection_start_x = virtual_text.hit_position(selection.start).point.x - self.scroll_x
+            + node_location.x as f64;
+        let selection_end_x = virtual_text.hit_position(selection.end).point.x - self.scroll_x
+            + node_location.x as f64;
 
-        let selection_start = Point::new(
-            selection_start_x + node_location.x as f64,
+        Rect::new(
+            selection_start_x,
             node_location.y as f64,
-        );
-
-        Rect::from_points(
-            selection_start,
-            Point::new(selection_end_x, selection_start.y + text_height),
+            selection_end_x,
+            node_location.y as f64 + text_height,
         )
     }
 
@@ -368,24 +478,20 @@ impl TextInput {
         let attrs = self.get_text_attrs();
 
         self.buffer
-            .with_untracked(|buff| text_layout.set_text(buff, attrs.clone()));
+            .with_untracked(|buff| text_layout.set_text(buff, attrs));
 
         self.width = APPROX_VISIBLE_CHARS * self.font_size;
         self.height = self.font_size;
 
-        // main buff should always get updated
-        self.text_buf = Some(text_layout.clone());
-
-        if let Some(cr_text) = self.clipped_text.clone().as_ref() {
-            let mut clp_txt_lay = text_layout;
-            clp_txt_lay.set_text(cr_text, attrs);
-
-            self.clip_txt_buf = Some(clp_txt_lay);
-        }
+        self.text_buf = Some(text_layout);
     }
 
     pub fn get_text_attrs(&self) -> AttrsList {
-        let mut attrs = Attrs::new().color(self.color.unwrap_or(Color::BLACK));
+        self.text_attrs_with_color(self.color.unwrap_or(Color::BLACK))
+    }
+
+    fn text_attrs_with_color(&self, color: Color) -> AttrsList {
+        let mut attrs = Attrs::new().color(color);
 
         attrs = attrs.font_size(self.font_size);
 
@@ -409,29 +515,13 @@ impl TextInput {
         self.cursor_glyph_idx = new_cursor_x;
     }
 
-    fn select_all(&mut self, cx: &mut EventCx) {
-        let text_node = self.text_node.unwrap();
-        let node_layout = *cx.app_state.taffy.layout(text_node).unwrap();
+    fn select_all(&mut self) {
         let len = self.buffer.with(|val| val.len());
         self.cursor_glyph_idx = len;
-
-        let text_buf = self.text_buf.as_ref().unwrap();
-        let buf_width = text_buf.size().width;
-        let node_width = node_layout.size.width as f64;
-
-        if buf_width > node_width {
-            self.clip_text(&node_layout);
-        }
-
         self.selection = Some(0..len);
     }
 
-    fn handle_modifier_cmd(
-        &mut self,
-        event: &KeyEvent,
-        cx: &mut EventCx<'_>,
-        character: &SmolStr,
-    ) -> bool {
+    fn handle_modifier_cmd(&mut self, event: &KeyEvent, character: &SmolStr) -> bool {
         if event.modifiers.is_empty() {
             return false;
         }
@@ -439,8 +529,9 @@ impl TextInput {
         let command = (event, character).into();
 
         match command {
+            TextCommand::Cut | TextCommand::Paste if self.read_only => false,
             TextCommand::SelectAll => {
-                self.select_all(cx);
+                self.select_all();
                 true
             }
             TextCommand::Copy => {
@@ -448,11 +539,7 @@ impl TextInput {
                     let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
                     let selection_txt = self
                         .buffer
-                        .get()
-                        .chars()
-                        .skip(selection.start)
-                        .take(selection.end - selection.start)
-                        .collect();
+                        .with_untracked(|buff| buff[selection.clone()].to_string());
                     ctx.set_contents(selection_txt).unwrap();
                 }
                 true
@@ -462,11 +549,7 @@ impl TextInput {
                     let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
                     let selection_txt = self
                         .buffer
-                        .get()
-                        .chars()
-                        .skip(selection.start)
-                        .take(selection.end - selection.start)
-                        .collect();
+                        .with_untracked(|buff| buff[selection.clone()].to_string());
                     ctx.set_contents(selection_txt).unwrap();
 
                     self.buffer
@@ -510,11 +593,15 @@ impl TextInput {
 
     fn handle_key_down(&mut self, cx: &mut EventCx, event: &KeyEvent) -> bool {
         match event.key.logical_key {
+            Key::Space | Key::Backspace | Key::Delete if self.read_only => false,
             Key::Character(ref ch) => {
-                let handled_modifier_cmd = self.handle_modifier_cmd(event, cx, ch);
+                let handled_modifier_cmd = self.handle_modifier_cmd(event, ch);
                 if handled_modifier_cmd {
                     return true;
                 }
+                if self.read_only {
+                    return false;
+                }
 
                 let selection = self.selection.clone();
                 if let Some(selection) = selection {
@@ -525,20 +612,22 @@ impl TextInput {
                 }
 
                 self.buffer
-                    .update(|buf| buf.insert_str(self.cursor_glyph_idx, &ch.clone()));
-                self.move_cursor(Movement::Glyph, Direction::Right)
+                    .update(|buf| buf.insert_str(self.cursor_glyph_idx, ch));
+                // the inserted text may merge with the grapheme before the cursor
+                // (e.g. a combining mark), so move past exactly what was inserted
+                self.cursor_glyph_idx += ch.len();
+                true
             }
             Key::Space => {
-                if let Some(selection) = &self.selection {
+                if let Some(selection) = self.selection.take() {
                     self.buffer
                         .update(|buf| replace_range(buf, selection.clone(), None));
                     self.cursor_glyph_idx = selection.start;
-                    self.selection = None;
-                } else {
-                    self.buffer
-                        .update(|buf| buf.insert(self.cursor_glyph_idx, ' '));
                 }
-                self.move_cursor(Movement::Glyph, Direction::Right)
+                self.buffer
+                    .update(|buf| buf.insert(self.cursor_glyph_idx, ' '));
+                self.cursor_glyph_idx += 1;
+                true
             }
             Key::Backspace => {
                 let selection = self.selection.clone();
@@ -587,9 +676,23 @@ impl TextInput {
                 true
             }
             Key::Escape => {
-                cx.app_state.clear_focus();
+                // the text is copied out, so the action can set the buffer
+                let keep_focus = self
+                    .on_escape
+                    .as_ref()
+                    .is_some_and(|on_escape| on_escape(&self.buffer.get_untracked()));
+                if !keep_focus {
+                    cx.app_state.clear_focus();
+                }
                 true
             }
+            Key::Enter => {
+                // the text is copied out, so the action can set the buffer
+                if let Some(on_submit) = self.on_submit.as_ref() {
+                    on_submit(&self.buffer.get_untracked());
+                }
+                self.on_submit.is_some()
+            }
             Key::End => self.move_cursor(Movement::Line, Direction::Right),
             Key::Home => self.move_cursor(Movement::Line, Direction::Left),
             Key::ArrowLeft => {
@@ -690,6 +793,22 @@ impl TextInput {
     }
 }
 
+/// The byte index of the grapheme cluster boundary before `idx`, if any
+fn prev_grapheme_idx(buff: &str, idx: usize) -> Option<usize> {
+    buff[..idx]
+        .grapheme_indices(true)
+        .next_back()
+        .map(|(idx, _)| idx)
+}
+
+/// The byte index of the grapheme cluster boundary after `idx`, if any
+fn next_grapheme_idx(buff: &str, idx: usize) -> Option<usize> {
+    buff[idx..]
+        .graphemes(true)
+        .next()
+        .map(|grapheme| idx + grapheme.len())
+}
+
 fn replace_range(buff: &mut String, del_range: Range<usize>, replacement: Option<&str>) {
     assert!(del_range.start <= del_range.end);
     if !buff.is_char_boundary(del_range.end) {
@@ -747,7 +866,32 @@ impl View for TextInput {
     }
 
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) -> ChangeFlags {
-        if state.downcast::<String>().is_ok() {
+        let state = match state.downcast::<TextInputState>() {
+            Ok(state) => {
+                match *state {
+                    TextInputState::ReadOnly(read_only) => {
+                        self.read_only = read_only;
+                    }
+                }
+                return ChangeFlags::empty();
+            }
+            Err(state) => state,
+        };
+
+        if let Ok(text) = state.downcast::<String>() {
+            // the buffer can be changed from outside of the input, which can leave the
+            // cursor or the selection past its end
+            if self.cursor_glyph_idx > text.len() || !text.is_char_boundary(self.cursor_glyph_idx) {
+                self.cursor_glyph_idx = text.len();
+            }
+            if self
+                .selection
+                .as_ref()
+                .map(|selection| selection.end > text.len())
+                .unwrap_or(false)
+            {
+                self.selection = None;
+            }
             cx.request_layout(self.id());
             ChangeFlags::LAYOUT
         } else {
@@ -780,7 +924,7 @@ impl View for TextInput {
                         .as_ref()
                         .unwrap()
                         .hit_point(Point::new(
-                            event.pos.x + self.clip_start_x - padding_left as f64,
+                            event.pos.x + self.scroll_x - padding_left as f64,
                             // TODO: prevent cursor incorrectly going to end of buffer when clicking
                             // slightly below the text
                             event.pos.y - padding_top as f64,
@@ -790,6 +934,14 @@ impl View for TextInput {
                 true
             }
             Event::KeyDown(event) => self.handle_key_down(cx, event),
+            Event::FocusGained => {
+                self.focus_changed(cx.app_state, true);
+                true
+            }
+            Event::FocusLost => {
+                self.focus_changed(cx.app_state, false);
+                true
+            }
             Event::PointerMove(_) => {
                 if !matches!(cx.app_state.cursor, Some(CursorStyle::Text)) {
                     cx.app_state.cursor = Some(CursorStyle::Text);
@@ -810,7 +962,6 @@ impl View for TextInput {
 
     fn layout(&mut self, cx: &mut crate::context::LayoutCx) -> taffy::prelude::Node {
         cx.layout_node(self.id, true, |cx| {
-            self.is_focused = cx.app_state().is_focused(&self.id);
             if self.text_layout_changed(cx) {
                 self.font_size = cx.current_font_size().unwrap_or(DEFAULT_FONT_SIZE);
                 self.font_family = cx.current_font_family().map(|s| s.to_string());
@@ -868,27 +1019,15 @@ impl View for TextInput {
         }
 
         let text_node = self.text_node.unwrap();
-        let text_buf = self.text_buf.as_ref().unwrap();
-        let buf_width = text_buf.size().width;
         let node_layout = *cx.app_state.taffy.layout(text_node).unwrap();
         let node_width = node_layout.size.width as f64;
-        let cursor_color = cx.app_state.get_computed_style(self.id).cursor_color;
+        let cursor_color = cx.cursor_color;
+        let caret_width = cx.caret_width.unwrap_or(self.cursor_width);
+        let caret_shape = cx.caret_shape.unwrap_or_default();
 
         match self.input_kind {
             InputKind::SingleLine => {
-                if buf_width > node_width {
-                    self.clip_text(&node_layout);
-                } else {
-                    self.clip_txt_buf = None;
-                    self.clip_start_idx = 0;
-                    self.clip_start_x = 0.0;
-                    let hit_pos = self
-                        .text_buf
-                        .as_ref()
-                        .unwrap()
-                        .hit_position(self.cursor_glyph_idx);
-                    self.cursor_x = hit_pos.point.x;
-                }
+                self.scroll_to_cursor(node_width, caret_width);
             }
             InputKind::MultiLine { .. } => {
                 todo!();
@@ -896,59 +1035,86 @@ impl View for TextInput {
         }
 
         let location = node_layout.location;
-        let text_start_point = Point::new(location.x as f64, location.y as f64);
+        let text_height = self.text_buf.as_ref().unwrap().size().height;
+        let text_rect = Rect::new(
+            location.x as f64,
+            location.y as f64,
+            location.x as f64 + node_width,
+            location.y as f64 + (node_layout.size.height as f64).max(text_height),
+        );
 
-        if let Some(clip_txt) = self.clip_txt_buf.as_mut() {
-            cx.draw_text(
-                clip_txt,
-                Point::new(text_start_point.x - self.clip_offset_x, text_start_point.y),
-            );
-        } else {
-            cx.draw_text(self.text_buf.as_ref().unwrap(), text_start_point);
+        let is_focused = cx.app_state.is_focused(&self.id);
+        if !is_focused {
+            self.selection = None;
+        }
+        let text_origin = Point::new(text_rect.x0 - self.scroll_x, text_rect.y0);
+
+        cx.save();
+        // leave room for the cursor when it sits right after text that fits in the input
+        cx.clip(&text_rect.inflate(caret_width, 0.0));
+
+        let selection_rect = self.get_selection_rect(&node_layout);
+        if self.selection.is_some() {
+            let background = cx.selection_background.unwrap_or_else(|| {
+                let color = cursor_color.unwrap_or(Color::BLACK);
+                Color::rgba8(color.r, color.g, color.b, 80)
+            });
+            cx.fill(&selection_rect, background, 0.0);
         }
 
-        let is_cursor_visible = cx.app_state.is_focused(&self.id)
-            && (self.last_cursor_action_on.elapsed().as_millis()
-                / CURSOR_BLINK_INTERVAL_MS as u128)
-                % 2
-                == 0;
+        cx.draw_text(self.text_buf.as_ref().unwrap(), text_origin);
+
+        if let (Some(_), Some(selection_color)) = (self.selection.as_ref(), cx.selection_color) {
+            // draw the selected part of the text again on top, in the selection color
+            let mut selected_text = TextLayout::new();
+            let attrs = self.text_attrs_with_color(selection_color);
+            self.buffer
+                .with_untracked(|buff| selected_text.set_text(buff, attrs));
+            cx.save();
+            cx.clip(&selection_rect);
+            cx.draw_text(&selected_text, text_origin);
+            cx.restore();
+        }
 
-        if is_cursor_visible {
-            let cursor_rect = self.get_cursor_rect(&node_layout);
+        if is_focused && self.is_cursor_visible() {
+            let cursor_rect = self.get_cursor_rect(&node_layout, caret_width, caret_shape);
             cx.fill(&cursor_rect, cursor_color.unwrap_or(Color::BLACK), 0.0);
         }
-
-        let style = cx.app_state.get_computed_style(self.id);
-
-        let padding_left = match style.padding_left {
-            PxPct::Px(padding) => padding as f32,
-            PxPct::Pct(pct) => pct as f32 * node_layout.size.width,
-        };
-
-        if cx.app_state.is_focused(&self.id) {
-            let selection_rect = self.get_selection_rect(&node_layout, padding_left as f64);
-            cx.fill(
-                &selection_rect,
-                cursor_color.unwrap_or(Color::rgba8(0, 0, 0, 150)),
-                0.0,
+        cx.restore();
+
+        if let Some(interval) = self.cursor_blink_interval {
+            let id = self.id();
+            exec_after(
+                interval,
+                Box::new(move |_| {
+                    id.request_paint();
+                }),
             );
-        } else {
-            self.selection = None;
         }
-
-        let id = self.id();
-        exec_after(
-            Duration::from_millis(CURSOR_BLINK_INTERVAL_MS),
-            Box::new(move |_| {
-                id.request_paint();
-            }),
-        );
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::replace_range;
+    use super::{next_grapheme_idx, prev_grapheme_idx, replace_range};
+
+    #[test]
+    fn grapheme_movement() {
+        // "e" followed by a combining acute accent, then a family emoji made of a ZWJ sequence
+        let s = "ae\u{301}\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}b";
+        let accent_end = 1 + "e\u{301}".len();
+        let emoji_end = s.len() - 1;
+
+        assert_eq!(next_grapheme_idx(s, 0), Some(1));
+        assert_eq!(next_grapheme_idx(s, 1), Some(accent_end));
+        assert_eq!(next_grapheme_idx(s, accent_end), Some(emoji_end));
+        assert_eq!(next_grapheme_idx(s, s.len()), None);
+
+        assert_eq!(prev_grapheme_idx(s, s.len()), Some(emoji_end));
+        assert_eq!(prev_grapheme_idx(s, emoji_end), Some(accent_end));
+        assert_eq!(prev_grapheme_idx(s, accent_end), Some(1));
+        assert_eq!(prev_grapheme_idx(s, 0), None);
+    }
 
     #[test]
     fn replace_range_start() {
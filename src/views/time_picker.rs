@@ -0,0 +1,290 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use chrono::{NaiveTime, Timelike};
+use floem_reactive::{create_rw_signal, RwSignal};
+use peniko::Color;
+use winit::keyboard::Key;
+
+use crate::{
+    event::{Event, EventListener},
+    keyboard::KeyEvent,
+    theme::tokens,
+    view::View,
+    views::{composite, label, stack, Composite, Decorators, Label},
+};
+
+/// How long after typing a digit the next one is still part of the same number
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+const COLUMN_WIDTH: f32 = 36.0;
+
+/// The part of the time that a column of the picker changes
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Hour,
+    Minute,
+    Period,
+}
+
+/// Spinner columns for picking a time of day. See [`time_picker`]
+pub type TimePicker = Composite<TimePickerState>;
+
+pub struct TimePickerState {
+    hour_12: RwSignal<bool>,
+    minute_step: RwSignal<u32>,
+}
+
+/// Columns showing the hour and minute of `time`, each with arrows above and below it to
+/// step it up or down. With [TimePicker::hour_12] there's a third column for AM and PM.
+///
+/// Clicking a column focuses it, and the left and right arrows move the focus between them.
+/// The up and down arrows step the focused column, and typing digits sets it, so typing
+/// `9` `3` `0` in the hour column enters 9:30. In the AM/PM column, `a` and `p` pick one.
+/// Seconds are always set to zero.
+pub fn time_picker(time: RwSignal<NaiveTime>) -> TimePicker {
+    let hour_12 = create_rw_signal(false);
+    let minute_step = create_rw_signal(1);
+    let typed = Rc::new(RefCell::new((String::new(), Instant::now())));
+
+    let hour = label(move || {
+        let hour = time.get().hour();
+        if hour_12.get() {
+            format!("{:02}", to_12_hour(hour))
+        } else {
+            format!("{hour:02}")
+        }
+    });
+    let minute = label(move || format!("{:02}", time.get().minute()));
+    let period = label(move || {
+        let period = if time.get().hour() < 12 { "AM" } else { "PM" };
+        period.to_string()
+    });
+    let ids = [hour.id(), minute.id(), period.id()];
+
+    let column = move |part: Part, value: Label| {
+        let typed = typed.clone();
+        let index = part as usize;
+        let value = value
+            .keyboard_navigatable()
+            .on_event(EventListener::KeyDown, move |event| {
+                if let Event::KeyDown(KeyEvent { key, .. }) = event {
+                    if !matches!(key.logical_key, Key::Character(_)) {
+                        typed.borrow_mut().0.clear();
+                    }
+                    match &key.logical_key {
+                        Key::ArrowUp => change(time, part, 1, minute_step.get_untracked()),
+                        Key::ArrowDown => change(time, part, -1, minute_step.get_untracked()),
+                        Key::ArrowLeft if index > 0 => ids[index - 1].request_focus(),
+                        Key::ArrowRight if index < 1 || (index < 2 && hour_12.get_untracked()) => {
+                            ids[index + 1].request_focus()
+                        }
+                        Key::Character(c) => {
+                            let mut typed = typed.borrow_mut();
+                            if typed.1.elapsed() > TYPE_AHEAD_TIMEOUT {
+                                typed.0.clear();
+                            }
+                            typed.1 = Instant::now();
+                            let (done, next) = type_char(
+                                time.get_untracked(),
+                                part,
+                                &mut typed.0,
+                                c,
+                                hour_12.get_untracked(),
+                                minute_step.get_untracked(),
+                            );
+                            time.set(next);
+                            if done {
+                                typed.0.clear();
+                                if index < 1 || (index < 2 && hour_12.get_untracked()) {
+                                    ids[index + 1].request_focus();
+                                }
+                            }
+                        }
+                        _ => return false,
+                    }
+                    true
+                } else {
+                    false
+                }
+            });
+        spinner(value, move |delta| {
+            change(time, part, delta, minute_step.get_untracked())
+        })
+        .style(move |s| {
+            s.flex_col()
+                .width(COLUMN_WIDTH)
+                .apply_if(part == Part::Period && !hour_12.get(), |s| s.hide())
+        })
+    };
+
+    let child = stack((
+        column(Part::Hour, hour),
+        label(|| ":".to_string()).style(|s| s.font_bold()),
+        column(Part::Minute, minute),
+        column(Part::Period, period),
+    ))
+    .style(|s| {
+        let tokens = tokens();
+        s.items_center()
+            .padding(tokens.spacing.sm)
+            .border(1.0)
+            .border_color(tokens.colors.border)
+            .border_radius(tokens.radius.md)
+    });
+
+    composite(
+        "TimePicker",
+        child,
+        TimePickerState {
+            hour_12,
+            minute_step,
+        },
+    )
+}
+
+impl TimePicker {
+    /// Shows the hour from 1 to 12 with an AM/PM column, instead of from 0 to 23
+    pub fn hour_12(self, hour_12: bool) -> Self {
+        self.state().hour_12.set(hour_12);
+        self
+    }
+
+    /// Makes the minute column step by `step` minutes, so 15 only offers the quarter hours.
+    /// Typed minutes are rounded down to a multiple of it.
+    pub fn minute_step(self, step: u32) -> Self {
+        self.state().minute_step.set(step.clamp(1, 60));
+        self
+    }
+}
+
+fn spinner(value: Label, change: impl Fn(i64) + 'static) -> impl View {
+    let change: Rc<dyn Fn(i64)> = Rc::new(change);
+    let arrow = |text: &'static str, delta: i64, change: Rc<dyn Fn(i64)>| {
+        label(move || text.to_string())
+            .style(|s| s.justify_center().color(tokens().colors.text_muted))
+            .hover_style(|s| s.background(tokens().colors.hover))
+            .on_click(move |_| {
+                change(delta);
+                true
+            })
+    };
+    stack((
+        arrow("▲", 1, change.clone()),
+        value
+            .style(|s| {
+                let tokens = tokens();
+                s.justify_center()
+                    .padding_vert(tokens.spacing.xs)
+                    .border(1.0)
+                    .border_color(Color::TRANSPARENT)
+                    .border_radius(tokens.radius.sm)
+            })
+            .focus_style(|s| s.border_color(tokens().colors.primary)),
+        arrow("▼", -1, change),
+    ))
+}
+
+fn to_12_hour(hour: u32) -> u32 {
+    match hour % 12 {
+        0 => 12,
+        hour => hour,
+    }
+}
+
+fn with_hour_minute(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or_default()
+}
+
+/// Steps one part of `time` up or down, wrapping around within that part
+fn step(time: NaiveTime, part: Part, delta: i64, minute_step: u32) -> NaiveTime {
+    let (hour, minute) = (time.hour() as i64, time.minute() as i64);
+    match part {
+        Part::Hour => with_hour_minute((hour + delta).rem_euclid(24) as u32, minute as u32),
+        Part::Minute => {
+            let step = minute_step.max(1) as i64;
+            // land on a multiple of the step, even if the minute wasn't on one
+            let minute = if delta > 0 {
+                (minute / step + 1) * step
+            } else if minute % step != 0 {
+                minute - minute % step
+            } else {
+                minute - step
+            };
+            let last = 59 / step * step;
+            let minute = if minute > last {
+                0
+            } else if minute < 0 {
+                last
+            } else {
+                minute
+            };
+            with_hour_minute(hour as u32, minute as u32)
+        }
+        Part::Period => with_hour_minute(((hour + 12) % 24) as u32, minute as u32),
+    }
+}
+
+fn change(time: RwSignal<NaiveTime>, part: Part, delta: i64, minute_step: u32) {
+    time.set(step(time.get_untracked(), part, delta, minute_step));
+}
+
+/// Applies a typed character to `part` of `time`, with the digits typed into the part so far
+/// in `typed`. Returns the new time, and whether the part is complete so focus moves on.
+fn type_char(
+    time: NaiveTime,
+    part: Part,
+    typed: &mut String,
+    c: &str,
+    hour_12: bool,
+    minute_step: u32,
+) -> (bool, NaiveTime) {
+    let (hour, minute) = (time.hour(), time.minute());
+    if part == Part::Period {
+        let hour = match c.to_lowercase().as_str() {
+            "a" => hour % 12,
+            "p" => hour % 12 + 12,
+            _ => return (false, time),
+        };
+        return (true, with_hour_minute(hour, minute));
+    }
+
+    if !c.chars().all(|c| c.is_ascii_digit()) {
+        return (false, time);
+    }
+    typed.push_str(c);
+    let max = match part {
+        Part::Hour if hour_12 => 12,
+        Part::Hour => 23,
+        _ => 59,
+    };
+    let mut value: u32 = typed.parse().unwrap_or(0);
+    if value > max {
+        // start over from the digit that didn't fit
+        *typed = c.to_string();
+        value = typed.parse::<u32>().unwrap_or(0).min(max);
+    }
+    // another digit can't make a valid number
+    let done = typed.len() >= 2 || value * 10 > max;
+
+    let time = match part {
+        Part::Hour if hour_12 => {
+            // 12 AM is midnight, so 12 is hour 0 of the half of the day
+            let pm = hour >= 12;
+            let hour = value % 12 + if pm { 12 } else { 0 };
+            if value == 0 {
+                time
+            } else {
+                with_hour_minute(hour, minute)
+            }
+        }
+        Part::Hour => with_hour_minute(value, minute),
+        _ => {
+            let step = minute_step.max(1);
+            with_hour_minute(hour, value / step * step)
+        }
+    };
+    (done, time)
+}
@@ -0,0 +1,349 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use floem_reactive::{create_effect, create_rw_signal, untrack, RwSignal};
+use floem_renderer::Renderer;
+use kurbo::{Circle, Point, Rect, RoundedRect, Size};
+use peniko::Color;
+use taffy::prelude::Node;
+use winit::keyboard::Key;
+
+use crate::{
+    action::exec_after,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::{ComputedStyle, Style},
+    theme::tokens,
+    unit::PxPct,
+    view::{ChangeFlags, View},
+    views::Decorators,
+};
+
+const ANIMATION_DURATION: Duration = Duration::from_millis(120);
+const ANIMATION_FRAME: Duration = Duration::from_millis(16);
+/// How far the pointer has to move before a press counts as a drag instead of a click
+const DRAG_THRESHOLD: f64 = 3.0;
+
+type StyleFn = Rc<dyn Fn(Style) -> Style>;
+
+enum ToggleSwitchState {
+    On(bool),
+    TrackStyle(ComputedStyle),
+    OnTrackStyle(ComputedStyle),
+    ThumbStyle(ComputedStyle),
+}
+
+struct Slide {
+    from: f64,
+    started: Instant,
+}
+
+/// Where the pointer went down, and where the thumb was at that moment
+struct Drag {
+    start_x: f64,
+    start_position: f64,
+    moved: bool,
+}
+
+pub struct ToggleSwitch {
+    id: Id,
+    state: Rc<dyn Fn() -> bool>,
+    on: bool,
+    /// Thumb position, from 0.0 when off to 1.0 when on
+    position: f64,
+    slide: Option<Slide>,
+    drag: Option<Drag>,
+    on_toggle: Option<Box<dyn Fn(bool)>>,
+    size: Size,
+    track_style: ComputedStyle,
+    on_track_style: ComputedStyle,
+    thumb_style: ComputedStyle,
+    track_style_fn: RwSignal<StyleFn>,
+    on_track_style_fn: RwSignal<StyleFn>,
+    thumb_style_fn: RwSignal<StyleFn>,
+}
+
+fn default_track_style(s: Style) -> Style {
+    s.background(tokens().colors.track)
+}
+
+fn default_on_track_style(s: Style) -> Style {
+    s.background(tokens().colors.primary)
+}
+
+fn default_thumb_style(s: Style) -> Style {
+    s.background(tokens().colors.surface).padding(2.0)
+}
+
+/// The style of one part of the switch, which is computed and sent to it whenever
+/// the style or the tokens its default is made from change
+fn part_style(
+    id: Id,
+    default: fn(Style) -> Style,
+    state: fn(ComputedStyle) -> ToggleSwitchState,
+) -> RwSignal<StyleFn> {
+    let style: RwSignal<StyleFn> = create_rw_signal(Rc::new(|s| s));
+    create_effect(move |_| {
+        let computed = style.get()(default(Style::BASE)).compute(&ComputedStyle::default());
+        id.update_state(state(computed), false);
+    });
+    style
+}
+
+/// A switch showing whether `state` is on, as an alternative to a checkbox for settings.
+/// Like the checkbox it doesn't change `state` itself, update it in [ToggleSwitch::on_toggle],
+/// which is called when the switch is clicked, dragged to the other side,
+/// or activated with Space or Enter while it has focus.
+pub fn toggle_switch(state: impl Fn() -> bool + 'static) -> ToggleSwitch {
+    let id = Id::next();
+    let state: Rc<dyn Fn() -> bool> = Rc::new(state);
+    // the switch starts out in its initial state, with nothing to slide from
+    let on = untrack(|| state());
+    {
+        let state = state.clone();
+        create_effect(move |_| {
+            id.update_state(ToggleSwitchState::On(state()), false);
+        });
+    }
+    ToggleSwitch {
+        id,
+        state,
+        on,
+        position: if on { 1.0 } else { 0.0 },
+        slide: None,
+        drag: None,
+        on_toggle: None,
+        size: Size::ZERO,
+        track_style: ComputedStyle::default(),
+        on_track_style: ComputedStyle::default(),
+        thumb_style: ComputedStyle::default(),
+        track_style_fn: part_style(id, default_track_style, ToggleSwitchState::TrackStyle),
+        on_track_style_fn: part_style(id, default_on_track_style, ToggleSwitchState::OnTrackStyle),
+        thumb_style_fn: part_style(id, default_thumb_style, ToggleSwitchState::ThumbStyle),
+    }
+    .base_style(|s| s.width(36.0).height(20.0))
+    .keyboard_navigatable()
+}
+
+impl ToggleSwitch {
+    pub fn on_toggle(mut self, on_toggle: impl Fn(bool) + 'static) -> Self {
+        self.on_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
+    /// The track is drawn with the `background` of this style while the switch is off
+    pub fn track_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.track_style_fn.set(Rc::new(style));
+        self
+    }
+
+    /// The track is drawn with the `background` of this style while the switch is on,
+    /// blending from the off color as the thumb slides over
+    pub fn on_track_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.on_track_style_fn.set(Rc::new(style));
+        self
+    }
+
+    /// The thumb is drawn with the `background` of this style, inset from the track
+    /// by its left padding
+    pub fn thumb_style(self, style: impl Fn(Style) -> Style + 'static) -> Self {
+        self.thumb_style_fn.set(Rc::new(style));
+        self
+    }
+
+    fn toggle(&mut self, on: bool) {
+        if on != self.on {
+            if let Some(on_toggle) = self.on_toggle.as_ref() {
+                on_toggle(on);
+            }
+        }
+        // slide to the state `on_toggle` left, which is back where it was if the toggle
+        // was rejected
+        self.on = untrack(|| (self.state)());
+        self.slide_to_current();
+    }
+
+    fn slide_to_current(&mut self) {
+        let target = if self.on { 1.0 } else { 0.0 };
+        if self.position != target {
+            self.slide = Some(Slide {
+                from: self.position,
+                started: Instant::now(),
+            });
+            self.id.request_paint();
+        }
+    }
+
+    fn thumb_inset(&self) -> f64 {
+        match self.thumb_style.padding_left {
+            PxPct::Px(px) => px,
+            PxPct::Pct(pct) => self.size.height * pct / 100.0,
+        }
+    }
+
+    fn thumb_radius(&self) -> f64 {
+        (self.size.height / 2.0 - self.thumb_inset()).max(0.0)
+    }
+
+    /// How far the thumb center moves between off and on
+    fn travel(&self) -> f64 {
+        (self.size.width - self.size.height).max(0.0)
+    }
+
+    fn advance_slide(&mut self) {
+        if let Some(slide) = self.slide.as_ref() {
+            let target = if self.on { 1.0 } else { 0.0 };
+            let progress =
+                (slide.started.elapsed().as_secs_f64() / ANIMATION_DURATION.as_secs_f64()).min(1.0);
+            self.position = slide.from + (target - slide.from) * progress;
+            if progress >= 1.0 {
+                self.slide = None;
+            } else {
+                let id = self.id;
+                exec_after(ANIMATION_FRAME, move |_| id.request_paint());
+            }
+        }
+    }
+}
+
+fn lerp_color(from: Color, to: Color, t: f64) -> Color {
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    Color::rgba8(
+        lerp(from.r, to.r),
+        lerp(from.g, to.g),
+        lerp(from.b, to.b),
+        lerp(from.a, to.a),
+    )
+}
+
+impl View for ToggleSwitch {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "ToggleSwitch".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) -> ChangeFlags {
+        if let Ok(state) = state.downcast::<ToggleSwitchState>() {
+            match *state {
+                ToggleSwitchState::On(on) => {
+                    // a toggle from the switch itself has already started sliding
+                    if on != self.on {
+                        self.on = on;
+                        if self.drag.is_none() {
+                            self.slide_to_current();
+                        }
+                    }
+                }
+                ToggleSwitchState::TrackStyle(style) => self.track_style = style,
+                ToggleSwitchState::OnTrackStyle(style) => self.on_track_style = style,
+                ToggleSwitchState::ThumbStyle(style) => self.thumb_style = style,
+            }
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        None
+    }
+
+    fn event(&mut self, cx: &mut EventCx, _id_path: Option<&[Id]>, event: Event) -> bool {
+        match event {
+            Event::PointerDown(event) => {
+                if event.button.is_primary() {
+                    cx.update_active(self.id);
+                    cx.update_focus(self.id, false);
+                    self.slide = None;
+                    self.drag = Some(Drag {
+                        start_x: event.pos.x,
+                        start_position: self.position,
+                        moved: false,
+                    });
+                    return true;
+                }
+                false
+            }
+            Event::PointerMove(event) => {
+                let travel = self.travel();
+                if let Some(drag) = self.drag.as_mut() {
+                    let dx = event.pos.x - drag.start_x;
+                    drag.moved |= dx.abs() > DRAG_THRESHOLD;
+                    if drag.moved && travel > 0.0 {
+                        self.position = (drag.start_position + dx / travel).clamp(0.0, 1.0);
+                        self.id.request_paint();
+                    }
+                    return true;
+                }
+                false
+            }
+            Event::PointerUp(_) => {
+                if let Some(drag) = self.drag.take() {
+                    let on = if drag.moved {
+                        self.position > 0.5
+                    } else {
+                        !self.on
+                    };
+                    self.toggle(on);
+                    return true;
+                }
+                false
+            }
+            Event::KeyDown(event) => match event.key.logical_key {
+                Key::Space | Key::Enter => {
+                    self.toggle(!self.on);
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.advance_slide();
+
+        let track = self.size.to_rect();
+        let radius = self.size.height / 2.0;
+        let off_color = self.track_style.background.unwrap_or(Color::TRANSPARENT);
+        let on_color = self.on_track_style.background.unwrap_or(off_color);
+        cx.fill(
+            &RoundedRect::from_rect(track, radius),
+            lerp_color(off_color, on_color, self.position),
+            0.0,
+        );
+
+        if let Some(background) = self.thumb_style.background {
+            let center = Point::new(radius + self.travel() * self.position, radius);
+            cx.fill(&Circle::new(center, self.thumb_radius()), background, 0.0);
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    rc::Rc,
+};
+
+use floem_reactive::{create_rw_signal, untrack, RwSignal};
+use peniko::Color;
+use winit::keyboard::{Key, ModifiersState};
+
+use crate::{
+    event::{Event, EventListener},
+    id::Id,
+    keyboard::KeyEvent,
+    theme::tokens,
+    view::View,
+    views::{composite_with_id, empty, label, list, stack, Composite, Decorators},
+};
+
+const INDENT: f32 = 16.0;
+
+/// A node of the tree as it's shown, one row of the flattened tree
+struct TreeRow<T, K> {
+    item: T,
+    key: K,
+    depth: usize,
+    expandable: bool,
+    parent: Option<K>,
+}
+
+/// A view of hierarchical data, like the files of a directory. See [`tree`]
+pub type Tree<K> = Composite<TreeState<K>>;
+
+pub struct TreeState<K: 'static> {
+    selected: RwSignal<Vec<K>>,
+    expanded: RwSignal<HashSet<K>>,
+    multi_select: RwSignal<bool>,
+}
+
+/// A tree of the items from `roots`, where each item that `has_children` has an arrow to
+/// expand it and show the items from `children` below it, indented by a level.
+///
+/// `children` is only called when an item is expanded for the first time, so loading
+/// them can be left until they're needed. The children are kept after that, also when the
+/// item is collapsed again. Each item is shown with the view from `view_fn`, and is told
+/// apart from the others by the key from `key_fn`, which has to be unique in the tree.
+///
+/// Clicking a row selects it. While the tree has focus, the up and down arrows move
+/// between the rows, the right arrow expands the current item or moves to its first
+/// child, and the left arrow collapses it or moves to its parent. With
+/// [Tree::multi_select], Ctrl or Cmd clicking adds to the selection and Shift clicking
+/// selects a range of rows.
+pub fn tree<T, K, V>(
+    roots: impl Fn() -> Vec<T> + 'static,
+    children: impl Fn(&T) -> Vec<T> + 'static,
+    has_children: impl Fn(&T) -> bool + 'static,
+    key_fn: impl Fn(&T) -> K + 'static,
+    view_fn: impl Fn(T) -> V + 'static,
+) -> Tree<K>
+where
+    T: Clone + 'static,
+    K: Clone + Eq + Hash + 'static,
+    V: View + 'static,
+{
+    let id = Id::next();
+    let expanded: RwSignal<HashSet<K>> = create_rw_signal(HashSet::new());
+    let selected: RwSignal<Vec<K>> = create_rw_signal(Vec::new());
+    let multi_select = create_rw_signal(false);
+    // the row that the keyboard moves from, which isn't always selected with multi select
+    let current: RwSignal<Option<K>> = create_rw_signal(None);
+    let range_start: Rc<RefCell<Option<K>>> = Rc::new(RefCell::new(None));
+    let loaded: Rc<RefCell<HashMap<K, Vec<T>>>> = Rc::new(RefCell::new(HashMap::new()));
+
+    let rows = {
+        let key_fn = Rc::new(key_fn);
+        Rc::new(move || {
+            let mut rows = Vec::new();
+            let mut stack: Vec<(T, usize, Option<K>)> = roots()
+                .into_iter()
+                .rev()
+                .map(|item| (item, 0, None))
+                .collect();
+            expanded.with(|expanded| {
+                while let Some((item, depth, parent)) = stack.pop() {
+                    let key = key_fn(&item);
+                    let expandable = has_children(&item);
+                    if expandable && expanded.contains(&key) {
+                        let mut loaded = loaded.borrow_mut();
+                        let items = loaded
+                            .entry(key.clone())
+                            .or_insert_with(|| untrack(|| children(&item)));
+                        for child in items.iter().rev() {
+                            stack.push((child.clone(), depth + 1, Some(key.clone())));
+                        }
+                    }
+                    rows.push(TreeRow {
+                        item,
+                        key,
+                        depth,
+                        expandable,
+                        parent,
+                    });
+                }
+            });
+            rows
+        })
+    };
+
+    let toggle = move |key: &K| {
+        expanded.update(|expanded| {
+            if !expanded.remove(key) {
+                expanded.insert(key.clone());
+            }
+        });
+    };
+
+    let select = {
+        let rows = rows.clone();
+        let range_start = range_start.clone();
+        move |key: &K, modifiers: ModifiersState| {
+            current.set(Some(key.clone()));
+            let multi = multi_select.get_untracked();
+            if multi && (modifiers.control_key() || modifiers.super_key()) {
+                selected.update(|selected| {
+                    if let Some(index) = selected.iter().position(|selected| selected == key) {
+                        selected.remove(index);
+                    } else {
+                        selected.push(key.clone());
+                    }
+                });
+                *range_start.borrow_mut() = Some(key.clone());
+            } else if multi && modifiers.shift_key() && range_start.borrow().is_some() {
+                let rows = rows();
+                let position = |key: &K| rows.iter().position(|row| &row.key == key);
+                let start = range_start
+                    .borrow()
+                    .as_ref()
+                    .and_then(|start| position(start));
+                if let (Some(start), Some(end)) = (start, position(key)) {
+                    let (from, to) = (start.min(end), start.max(end));
+                    selected.set(rows[from..=to].iter().map(|row| row.key.clone()).collect());
+                }
+            } else {
+                selected.set(vec![key.clone()]);
+                *range_start.borrow_mut() = Some(key.clone());
+            }
+        }
+    };
+    let select = Rc::new(select);
+
+    let list_rows = rows.clone();
+    let row_select = select.clone();
+    let view_fn = Rc::new(view_fn);
+    let rows_view = list(
+        move || list_rows(),
+        |row: &TreeRow<T, K>| row.key.clone(),
+        move |row| {
+            let select = row_select.clone();
+            let (key, depth, expandable) = (row.key.clone(), row.depth, row.expandable);
+            let guides = list(
+                move || 0..depth,
+                |level| *level,
+                |_| {
+                    // a line down the middle of each level of indentation
+                    empty().style(|s| {
+                        s.width(INDENT / 2.0)
+                            .margin_left(INDENT / 2.0)
+                            .border_left(1.0)
+                            .border_color(tokens().colors.border)
+                    })
+                },
+            );
+            let arrow_key = key.clone();
+            let arrow = label(move || {
+                if !expandable {
+                    String::new()
+                } else if expanded.with(|expanded| expanded.contains(&arrow_key)) {
+                    "▾".to_string()
+                } else {
+                    "▸".to_string()
+                }
+            })
+            .style(|s| s.width(INDENT).justify_center().items_center())
+            .on_click({
+                let key = key.clone();
+                move |_| {
+                    toggle(&key);
+                    true
+                }
+            });
+
+            let (style_key, current_key) = (key.clone(), key.clone());
+            stack((guides, arrow, (view_fn)(row.item)))
+                .style(move |s| {
+                    let tokens = tokens();
+                    let is_selected = selected.with(|selected| selected.contains(&style_key));
+                    s.padding_right(tokens.spacing.sm)
+                        .border(1.0)
+                        .border_color(Color::TRANSPARENT)
+                        .apply_if(is_selected, |s| s.background(tokens.colors.selected))
+                        .apply_if(current.get().as_ref() == Some(&current_key), |s| {
+                            s.border_color(tokens.colors.primary)
+                        })
+                })
+                .hover_style(|s| s.background(tokens().colors.hover))
+                .on_event(EventListener::PointerDown, move |event| {
+                    if let Event::PointerDown(event) = event {
+                        if event.button.is_primary() {
+                            select(&key, event.modifiers);
+                            id.request_focus();
+                        }
+                    }
+                    false
+                })
+        },
+    )
+    .style(|s| s.flex_col());
+
+    let key_rows = rows;
+    composite_with_id(
+        id,
+        "Tree",
+        rows_view,
+        TreeState {
+            selected,
+            expanded,
+            multi_select,
+        },
+    )
+    .keyboard_navigatable()
+    .on_event(EventListener::KeyDown, move |event| {
+        if let Event::KeyDown(KeyEvent { key, modifiers }) = event {
+            let rows = key_rows();
+            let index = current
+                .get_untracked()
+                .and_then(|current| rows.iter().position(|row| row.key == current));
+            let move_to = |index: usize| {
+                if let Some(row) = rows.get(index) {
+                    select(&row.key, *modifiers);
+                }
+            };
+            match (&key.logical_key, index) {
+                (Key::ArrowDown | Key::ArrowUp | Key::Home | Key::End, None) => move_to(0),
+                (Key::ArrowDown, Some(index)) => move_to((index + 1).min(rows.len() - 1)),
+                (Key::ArrowUp, Some(index)) => move_to(index.saturating_sub(1)),
+                (Key::Home, Some(_)) => move_to(0),
+                (Key::End, Some(_)) => move_to(rows.len().saturating_sub(1)),
+                (Key::ArrowRight, Some(index)) => {
+                    let row = &rows[index];
+                    if row.expandable {
+                        if expanded.with_untracked(|expanded| expanded.contains(&row.key)) {
+                            // the first child is the next row, if there are any
+                            if rows.get(index + 1).map(|next| next.depth) == Some(row.depth + 1) {
+                                move_to(index + 1);
+                            }
+                        } else {
+                            toggle(&row.key);
+                        }
+                    }
+                }
+                (Key::ArrowLeft, Some(index)) => {
+                    let row = &rows[index];
+                    if row.expandable
+                        && expanded.with_untracked(|expanded| expanded.contains(&row.key))
+                    {
+                        toggle(&row.key);
+                    } else if let Some(parent) = row.parent.as_ref() {
+                        if let Some(parent) = rows.iter().position(|row| &row.key == parent) {
+                            move_to(parent);
+                        }
+                    }
+                }
+                (Key::Enter | Key::Space, Some(index)) => {
+                    let row = &rows[index];
+                    if row.expandable {
+                        toggle(&row.key);
+                    }
+                }
+                _ => return false,
+            }
+            true
+        } else {
+            false
+        }
+    })
+}
+
+impl<K: 'static> Tree<K> {
+    /// Lets more than one row be selected, by Ctrl or Cmd clicking them or Shift clicking
+    /// the end of a range
+    pub fn multi_select(self, multi_select: bool) -> Self {
+        self.state().multi_select.set(multi_select);
+        self
+    }
+
+    /// The keys of the selected items, in the order they were selected, which can be set
+    /// to change the selection
+    pub fn selected_signal(&self) -> RwSignal<Vec<K>> {
+        self.state().selected
+    }
+
+    /// The keys of the expanded items, which can be set to expand or collapse them
+    pub fn expanded_signal(&self) -> RwSignal<HashSet<K>> {
+        self.state().expanded
+    }
+}
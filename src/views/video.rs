@@ -0,0 +1,407 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use floem_reactive::{
+    create_effect, create_rw_signal, create_trigger, untrack, ReadSignal, RwSignal, Trigger,
+};
+use floem_renderer::Renderer;
+use kurbo::{Rect, Size};
+use taffy::prelude::Node;
+
+use crate::{
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    ext_event::EXT_EVENT_HANDLER,
+    id::Id,
+    view::{ChangeFlags, View},
+    views::Decorators,
+};
+
+type FrameFn = Rc<RefCell<Option<Box<dyn Fn(&VideoFrame)>>>>;
+
+/// A decoded frame of a video
+pub struct VideoFrame {
+    /// The RGBA8 pixels of the frame, a row at a time from the top
+    pub pixels: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    /// When the frame is shown, from the start of the video
+    pub timestamp: Duration,
+}
+
+/// Decodes the frames of a video for a [`video`] view, in order, from a file or a stream,
+/// with a media library such as FFmpeg or GStreamer. It's run on a thread of its own, so
+/// decoding doesn't hold up the UI.
+pub trait VideoDecoder: Send {
+    /// The width and height of the frames, which the view is sized to by default
+    fn dimensions(&self) -> (u32, u32);
+
+    /// How long the video is, if it's known
+    fn duration(&self) -> Option<Duration>;
+
+    /// Moves to `position`, so the next frame is the one shown at that time
+    fn seek(&mut self, position: Duration);
+
+    /// The next frame of the video, or `None` at the end of it
+    fn next_frame(&mut self) -> Option<VideoFrame>;
+}
+
+/// What the decoding thread of a [`video`] is told to do
+enum Command {
+    /// Plays from the frame after the one decoded last, which is shown at `from`
+    Play {
+        generation: u64,
+        from: Duration,
+    },
+    Pause,
+    /// Moves to `position`, and plays from there if `play`, or else decodes the frame
+    /// there so it's shown while paused
+    Seek {
+        generation: u64,
+        position: Duration,
+        play: bool,
+    },
+}
+
+/// What the decoding thread sends back, along with the generation of the command it was
+/// decoded for
+enum Decoded {
+    Frame(u64, VideoFrame),
+    End(u64),
+}
+
+/// Plays, pauses and seeks a [`video`], and tells where it is
+#[derive(Clone, Copy)]
+pub struct VideoControls {
+    playing: RwSignal<bool>,
+    position: RwSignal<Duration>,
+    seek_to: RwSignal<Option<Duration>>,
+    duration: Option<Duration>,
+}
+
+impl VideoControls {
+    pub fn play(&self) {
+        self.playing.set(true);
+    }
+
+    pub fn pause(&self) {
+        self.playing.set(false);
+    }
+
+    /// Plays the video if it's paused, and pauses it if it's playing
+    pub fn toggle(&self) {
+        self.playing.update(|playing| *playing = !*playing);
+    }
+
+    /// Moves to `position`, and keeps playing from there if the video was playing
+    pub fn seek(&self, position: Duration) {
+        let position = match self.duration {
+            Some(duration) => position.min(duration),
+            None => position,
+        };
+        self.seek_to.set(Some(position));
+    }
+
+    /// Whether the video is playing. It stops at the end of the video.
+    pub fn playing(&self) -> ReadSignal<bool> {
+        self.playing.read_only()
+    }
+
+    /// The timestamp of the frame that's shown
+    pub fn position(&self) -> ReadSignal<Duration> {
+        self.position.read_only()
+    }
+
+    /// How long the video is, if the decoder knows it
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+}
+
+/// A video that's played by decoding its frames. See [`video`]
+pub struct Video {
+    id: Id,
+    frame: Option<VideoFrame>,
+    /// The key of the video's image in the renderer, which each frame replaces
+    frame_key: Vec<u8>,
+    /// Counts the frames, so the renderer knows when the pixels are new
+    frame_version: u64,
+    size: Size,
+    controls: VideoControls,
+    on_frame: FrameFn,
+}
+
+/// A view showing the frames of a video from `decoder`, each at its time, scaled to fit the
+/// view while keeping their aspect ratio. The view is the size of the frames unless its
+/// style sizes it.
+///
+/// It starts paused, on the first frame. It's played, paused and moved through with its
+/// [`controls`](Video::controls).
+pub fn video(decoder: impl VideoDecoder + 'static) -> Video {
+    let id = Id::next();
+    let (width, height) = decoder.dimensions();
+    let controls = VideoControls {
+        playing: create_rw_signal(false),
+        position: create_rw_signal(Duration::ZERO),
+        seek_to: create_rw_signal(None),
+        duration: decoder.duration(),
+    };
+    let on_frame: FrameFn = Rc::new(RefCell::new(None));
+    // changed on each play, pause and seek, to drop what was decoded before it
+    let generation = Rc::new(Cell::new(0));
+
+    let (commands, command_rx) = crossbeam_channel::unbounded();
+    let (decoded_tx, decoded) = crossbeam_channel::unbounded();
+    let trigger = create_trigger();
+    let decoder: Box<dyn VideoDecoder> = Box::new(decoder);
+    std::thread::spawn(move || decode(decoder, command_rx, decoded_tx, trigger));
+
+    {
+        let generation = generation.clone();
+        let on_frame = on_frame.clone();
+        create_effect(move |_| {
+            trigger.track();
+            untrack(|| {
+                while let Ok(decoded) = decoded.try_recv() {
+                    match decoded {
+                        Decoded::Frame(frame_generation, frame) => {
+                            if frame_generation == generation.get() {
+                                show_frame(id, frame, controls, &on_frame);
+                            }
+                        }
+                        Decoded::End(end_generation) => {
+                            if end_generation == generation.get() {
+                                controls.playing.set(false);
+                            }
+                        }
+                    }
+                }
+            });
+        });
+    }
+
+    {
+        let generation = generation.clone();
+        let commands = commands.clone();
+        create_effect(move |_| {
+            let playing = controls.playing.get();
+            generation.set(generation.get() + 1);
+            let command = if playing {
+                Command::Play {
+                    generation: generation.get(),
+                    from: controls.position.get_untracked(),
+                }
+            } else {
+                Command::Pause
+            };
+            let _ = commands.send(command);
+        });
+    }
+
+    create_effect(move |_| {
+        let position = match controls.seek_to.get() {
+            Some(position) => position,
+            None => return,
+        };
+        generation.set(generation.get() + 1);
+        controls.position.set(position);
+        let _ = commands.send(Command::Seek {
+            generation: generation.get(),
+            position,
+            play: controls.playing.get_untracked(),
+        });
+    });
+    // the first frame is shown before the video is played
+    controls.seek(Duration::ZERO);
+
+    Video {
+        id,
+        frame: None,
+        frame_key: format!("video-{}", id.to_raw()).into_bytes(),
+        frame_version: 0,
+        size: Size::ZERO,
+        controls,
+        on_frame,
+    }
+    .base_style(move |s| s.width(width as f64).height(height as f64))
+}
+
+/// Runs the `decoder` on the thread of the video, sending each frame when it's time to
+/// show it while playing, until the video is dropped
+fn decode(
+    mut decoder: Box<dyn VideoDecoder>,
+    commands: Receiver<Command>,
+    decoded: Sender<Decoded>,
+    trigger: Trigger,
+) {
+    let send = |value| {
+        if decoded.send(value).is_ok() {
+            EXT_EVENT_HANDLER.add_trigger(trigger);
+        }
+    };
+    // the generation it's playing for, when the playback started, and from which position
+    let mut playing: Option<(u64, Instant, Duration)> = None;
+    // a command that came while waiting for a frame
+    let mut next = None;
+    loop {
+        let command = match next.take() {
+            Some(command) => Some(command),
+            None if playing.is_some() => None,
+            None => match commands.recv() {
+                Ok(command) => Some(command),
+                Err(_) => return,
+            },
+        };
+        match command {
+            Some(Command::Play { generation, from }) => {
+                playing = Some((generation, Instant::now(), from));
+            }
+            Some(Command::Pause) => playing = None,
+            Some(Command::Seek {
+                generation,
+                position,
+                play,
+            }) => {
+                decoder.seek(position);
+                if play {
+                    playing = Some((generation, Instant::now(), position));
+                } else {
+                    playing = None;
+                    if let Some(frame) = decoder.next_frame() {
+                        send(Decoded::Frame(generation, frame));
+                    }
+                }
+            }
+            None => {}
+        }
+
+        let (generation, started, from) = match playing {
+            Some(playing) => playing,
+            None => continue,
+        };
+        let frame = match decoder.next_frame() {
+            Some(frame) => frame,
+            None => {
+                send(Decoded::End(generation));
+                playing = None;
+                continue;
+            }
+        };
+        let delay = frame
+            .timestamp
+            .saturating_sub(from)
+            .saturating_sub(started.elapsed());
+        // the frame is dropped if the video is paused or moved before it's time to show it
+        match commands.recv_timeout(delay) {
+            Ok(command) => next = Some(command),
+            Err(RecvTimeoutError::Timeout) => send(Decoded::Frame(generation, frame)),
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+fn show_frame(id: Id, frame: VideoFrame, controls: VideoControls, on_frame: &FrameFn) {
+    controls.position.set(frame.timestamp);
+    if let Some(on_frame) = on_frame.borrow().as_ref() {
+        on_frame(&frame);
+    }
+    id.update_state(frame, false);
+}
+
+impl Video {
+    /// The controls of the video, which can be kept to play and pause it from elsewhere
+    pub fn controls(&self) -> VideoControls {
+        self.controls
+    }
+
+    /// Called with each frame as it's about to be shown
+    pub fn on_frame(self, on_frame: impl Fn(&VideoFrame) + 'static) -> Self {
+        *self.on_frame.borrow_mut() = Some(Box::new(on_frame));
+        self
+    }
+}
+
+impl View for Video {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Video".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) -> ChangeFlags {
+        if let Ok(frame) = state.downcast::<VideoFrame>() {
+            self.frame_version += 1;
+            self.frame = Some(*frame);
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.get_layout(self.id).unwrap();
+        self.size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        None
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let frame = match self.frame.as_ref() {
+            Some(frame) => frame,
+            None => return,
+        };
+        if frame.width == 0
+            || frame.height == 0
+            || frame.pixels.len() < (frame.width * frame.height * 4) as usize
+        {
+            return;
+        }
+        // letterboxed to keep the aspect ratio of the frame
+        let scale =
+            (self.size.width / frame.width as f64).min(self.size.height / frame.height as f64);
+        let size = Size::new(frame.width as f64 * scale, frame.height as f64 * scale);
+        let origin = (
+            (self.size.width - size.width) / 2.0,
+            (self.size.height - size.height) / 2.0,
+        );
+        cx.draw_pixels(
+            floem_renderer::Img {
+                data: &frame.pixels,
+                hash: &self.frame_key,
+            },
+            self.frame_version,
+            frame.width,
+            frame.height,
+            Rect::from_origin_size(origin, size),
+        );
+    }
+}
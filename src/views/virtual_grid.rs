@@ -0,0 +1,132 @@
+use std::rc::Rc;
+
+use crate::{
+    view::View,
+    views::{
+        container_box, virtual_list, ContainerBox, Decorators, VirtualList, VirtualListDirection,
+        VirtualListItemSize,
+    },
+};
+
+/// A vertical [VirtualList] of rows, each being a horizontal [VirtualList] of cells.
+/// See [`virtual_grid`]
+pub type VirtualGrid = VirtualList<VirtualList<ContainerBox, usize>, usize>;
+
+/// The sizes of the rows or the columns of a grid, shared by all the rows
+#[derive(Clone)]
+enum GridSize {
+    Fixed(Rc<dyn Fn() -> f64>),
+    Fn(Rc<dyn Fn(usize) -> f64>),
+    Measured(Rc<dyn Fn(usize, f64) -> f64>),
+}
+
+impl GridSize {
+    fn new(size: VirtualListItemSize<usize>) -> Self {
+        match size {
+            VirtualListItemSize::Fixed(size) => GridSize::Fixed(Rc::from(size)),
+            VirtualListItemSize::Fn(size) => GridSize::Fn(Rc::new(move |index| size(&index))),
+            VirtualListItemSize::Measured(estimate) => {
+                GridSize::Measured(Rc::new(move |index, cross_size| {
+                    estimate(&index, cross_size)
+                }))
+            }
+        }
+    }
+
+    fn item_size(&self) -> VirtualListItemSize<usize> {
+        match self.clone() {
+            GridSize::Fixed(size) => VirtualListItemSize::Fixed(Box::new(move || size())),
+            GridSize::Fn(size) => VirtualListItemSize::Fn(Box::new(move |index| size(*index))),
+            GridSize::Measured(estimate) => {
+                VirtualListItemSize::Measured(Box::new(move |index, cross_size| {
+                    estimate(*index, cross_size)
+                }))
+            }
+        }
+    }
+
+    /// The size of the row or the column at `index`, unless it's measured
+    fn size(&self, index: usize) -> Option<f64> {
+        match self {
+            GridSize::Fixed(size) => Some(size()),
+            GridSize::Fn(size) => Some(size(index)),
+            GridSize::Measured(_) => None,
+        }
+    }
+
+    /// The size of the first `len` rows or columns together, unless they're measured
+    fn total(&self, len: usize) -> Option<f64> {
+        match self {
+            GridSize::Fixed(size) => Some(size() * len as f64),
+            GridSize::Fn(size) => Some((0..len).map(size.as_ref()).sum()),
+            GridSize::Measured(_) => None,
+        }
+    }
+}
+
+/// A grid of `rows` by `columns` cells, with the view from `cell_fn` for the row and the
+/// column of each cell, where only the cells in the viewport of the enclosing
+/// [scroll](crate::views::scroll()) are built, on both axes. This is for grids too large to
+/// build all at once, like a gallery of thumbnails or the cells of a spreadsheet.
+///
+/// `row_size` and `column_size` are the sizes of the rows and the columns by their index,
+/// the same as the item sizes of a [virtual_list], and each cell is sized to its row and
+/// its column. Rows can be [measured](VirtualListItemSize::Measured), in which case a row
+/// is as tall as its tallest shown cell. Columns have to line up across all the rows, so
+/// they're never measured, and a measured column size is only used for its estimate, with
+/// the height of 0.
+///
+/// The grid has to be the direct child of a scroll for measured rows to keep the row at
+/// the top of the viewport in place while the rows before it are measured.
+pub fn virtual_grid<V: View + 'static>(
+    rows: impl Fn() -> usize + 'static,
+    columns: impl Fn() -> usize + 'static,
+    row_size: VirtualListItemSize<usize>,
+    column_size: VirtualListItemSize<usize>,
+    cell_fn: impl Fn(usize, usize) -> V + 'static,
+) -> VirtualGrid {
+    let row_size = GridSize::new(row_size);
+    let column_size = match GridSize::new(column_size) {
+        GridSize::Measured(estimate) => GridSize::Fn(Rc::new(move |index| estimate(index, 0.0))),
+        column_size => column_size,
+    };
+    let columns = Rc::new(columns);
+    let cell_fn = Rc::new(cell_fn);
+
+    let grid_columns = columns.clone();
+    let grid_column_size = column_size.clone();
+    let grid_row_size = row_size.item_size();
+    virtual_list(
+        VirtualListDirection::Vertical,
+        grid_row_size,
+        move || 0..rows(),
+        |row| *row,
+        move |row| {
+            let columns = columns.clone();
+            let cell_fn = cell_fn.clone();
+            let cell_size = column_size.clone();
+            let row_size = row_size.clone();
+            virtual_list(
+                VirtualListDirection::Horizontal,
+                column_size.item_size(),
+                move || 0..columns(),
+                |column| *column,
+                move |column| {
+                    let cell_size = cell_size.clone();
+                    container_box(cell_fn(row, column)).style(move |s| {
+                        s.flex_shrink(0.0)
+                            .apply_opt(cell_size.size(column), |s, width| s.width(width))
+                    })
+                },
+            )
+            .style(move |s| {
+                s.flex_shrink(0.0)
+                    .apply_opt(row_size.size(row), |s, height| s.height(height))
+            })
+        },
+    )
+    .style(move |s| {
+        let width = grid_column_size.total(grid_columns());
+        s.flex_col().apply_opt(width, |s, width| s.min_width(width))
+    })
+}
@@ -1,7 +1,18 @@
-use std::{hash::Hash, marker::PhantomData, ops::Range};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::Range,
+    rc::Rc,
+};
 
-use floem_reactive::{as_child_of_current_scope, create_effect, create_signal, Scope, WriteSignal};
-use kurbo::{Rect, Size};
+use floem_reactive::{
+    as_child_of_current_scope, create_effect, create_signal, create_trigger, RwSignalVec, Scope,
+    Trigger, WriteSignal,
+};
+use kurbo::{Rect, Size, Vec2};
+use rustc_hash::FxHasher;
 use smallvec::SmallVec;
 use taffy::{prelude::Node, style::Dimension};
 
@@ -11,7 +22,11 @@ use crate::{
     view::{ChangeFlags, View},
 };
 
-use super::{apply_diff, diff, Diff, DiffOpAdd, FxIndexSet, HashRun};
+use super::{apply_diff, diff, scroll::ScrollState, Diff, DiffOpAdd, FxIndexSet, HashRun};
+
+/// Measured sizes that differ from the size that was assumed by less than this are ignored,
+/// so rounding in the layout doesn't keep the list updating
+const MEASURE_TOLERANCE: f64 = 0.5;
 
 #[derive(Clone, Copy)]
 pub enum VirtualListDirection {
@@ -22,6 +37,16 @@ pub enum VirtualListDirection {
 pub enum VirtualListItemSize<T> {
     Fn(Box<dyn Fn(&T) -> f64>),
     Fixed(Box<dyn Fn() -> f64>),
+    /// Sizes measured from the items once they've been laid out, for items whose size
+    /// depends on the width of the list, like wrapped text. The function estimates the
+    /// size of an item that hasn't been measured yet from the item and the list's size on
+    /// the other axis, which is its width for a vertical list.
+    ///
+    /// A size is only kept for the width it was measured at, so when the width changes
+    /// the items are estimated again and the shown ones are measured again. The item at
+    /// the top of the viewport stays where it is while the sizes before it change, as long
+    /// as the list is the direct child of a [scroll](crate::views::scroll).
+    Measured(Box<dyn Fn(&T, f64) -> f64>),
 }
 
 pub trait VirtualListVector<T> {
@@ -55,12 +80,112 @@ where
     after_size: f64,
     before_node: Option<Node>,
     after_node: Option<Node>,
+    cross_size: f64,
+    set_cross_size: WriteSignal<f64>,
+    /// The sizes of the shown items, by the hash of their key, for measured lists
+    assumed_sizes: Vec<(u64, f64)>,
+    measurements: Rc<RefCell<Vec<Measurement>>>,
+    remeasure: Trigger,
 }
 
 struct VirtualListState<T> {
     diff: Diff<T>,
     before_size: f64,
     after_size: f64,
+    assumed_sizes: Vec<(u64, f64)>,
+    /// How far to scroll to keep the item at the top of the viewport in place
+    scroll_delta: f64,
+}
+
+/// The size of an item whose laid out size differs from the one that was assumed
+struct Measurement {
+    key: u64,
+    cross_size: f64,
+    size: f64,
+}
+
+/// What a measured list remembers between its runs
+#[derive(Default)]
+struct MeasuredSizes {
+    /// The size of each item that's been measured, and the cross size it was measured at
+    sizes: HashMap<u64, (f64, f64)>,
+    /// The first shown item, and where it started
+    anchor: Option<(u64, f64)>,
+}
+
+impl MeasuredSizes {
+    /// The measured size of the item, if it was measured at `cross_size`
+    fn size(&self, key: u64, cross_size: f64) -> Option<f64> {
+        match self.sizes.get(&key) {
+            Some((measured_at, size)) if *measured_at == cross_size => Some(*size),
+            _ => None,
+        }
+    }
+}
+
+/// Which of the items of a measured list are shown, and the space taken by the ones
+/// before and after them
+#[derive(Debug, PartialEq)]
+struct MeasuredPlacement {
+    shown: Range<usize>,
+    before_size: f64,
+    after_size: f64,
+    /// How far the viewport moves to keep the anchor where it was
+    scroll_delta: f64,
+    /// The first shown item, and where it starts
+    anchor: Option<(u64, f64)>,
+}
+
+/// Places the items with the keys and sizes in `sizes` for the viewport from `min` to `max`,
+/// moving the viewport to where the `anchor` item is now if the items before it changed size
+fn place_measured(
+    sizes: &[(u64, f64)],
+    anchor: Option<(u64, f64)>,
+    min: f64,
+    max: f64,
+) -> MeasuredPlacement {
+    let mut scroll_delta = 0.0;
+    if let Some((anchor, anchor_start)) = anchor {
+        let mut start = 0.0;
+        for (key, size) in sizes {
+            if *key == anchor {
+                scroll_delta = start - anchor_start;
+                break;
+            }
+            start += size;
+        }
+    }
+    let (min, max) = (min + scroll_delta, max + scroll_delta);
+
+    let mut placement = MeasuredPlacement {
+        shown: 0..0,
+        before_size: 0.0,
+        after_size: 0.0,
+        scroll_delta,
+        anchor: None,
+    };
+    let mut main_axis = 0.0;
+    for (index, (key, size)) in sizes.iter().enumerate() {
+        if main_axis + size < min {
+            placement.before_size += size;
+            placement.shown = index + 1..index + 1;
+        } else if main_axis <= max {
+            if placement.anchor.is_none() {
+                placement.anchor = Some((*key, main_axis));
+            }
+            placement.shown.end = index + 1;
+        } else {
+            placement.after_size += size;
+        }
+        main_axis += size;
+    }
+    placement
+}
+
+fn hash_key<K: Hash>(key: &K) -> u64 {
+    let mut hasher = FxHasher::default();
+    key.hash(&mut hasher);
+    hasher.finish()
 }
 
 pub fn virtual_list<T, IF, I, KF, K, VF, V>(
@@ -82,7 +207,12 @@ where
     let id = Id::next();
 
     let (viewport, set_viewport) = create_signal(Rect::ZERO);
+    let (cross_size, set_cross_size) = create_signal(0.0);
+    let measurements: Rc<RefCell<Vec<Measurement>>> = Rc::new(RefCell::new(Vec::new()));
+    let remeasure = create_trigger();
+    let measured_sizes = RefCell::new(MeasuredSizes::default());
 
+    let effect_measurements = measurements.clone();
     create_effect(move |prev_hash_run| {
         let mut items_vector = each_fn();
         let viewport = viewport.get();
@@ -98,6 +228,8 @@ where
 
         let mut before_size = 0.0;
         let mut after_size = 0.0;
+        let mut assumed_sizes = Vec::new();
+        let mut scroll_delta = 0.0;
         match &item_size {
             VirtualListItemSize::Fixed(item_size) => {
                 let item_size = item_size();
@@ -145,6 +277,46 @@ where
                     }
                 }
             }
+            VirtualListItemSize::Measured(estimate) => {
+                remeasure.track();
+                let cross_size = cross_size.get();
+                let mut measured_sizes = measured_sizes.borrow_mut();
+                for measurement in effect_measurements.borrow_mut().drain(..) {
+                    measured_sizes
+                        .sizes
+                        .insert(measurement.key, (measurement.cross_size, measurement.size));
+                }
+
+                let total_len = items_vector.total_len();
+                let sized_items: Vec<_> = items_vector
+                    .slice(0..total_len)
+                    .map(|item| {
+                        let key = hash_key(&key_fn(&item));
+                        let size = measured_sizes
+                            .size(key, cross_size)
+                            .unwrap_or_else(|| estimate(&item, cross_size));
+                        (item, key, size)
+                    })
+                    .collect();
+                let sizes: Vec<_> = sized_items
+                    .iter()
+                    .map(|(_, key, size)| (*key, *size))
+                    .collect();
+
+                let placement = place_measured(&sizes, measured_sizes.anchor, min, max);
+                measured_sizes.anchor = placement.anchor;
+                before_size = placement.before_size;
+                after_size = placement.after_size;
+                scroll_delta = placement.scroll_delta;
+                for (item, key, size) in sized_items
+                    .into_iter()
+                    .skip(placement.shown.start)
+                    .take(placement.shown.len())
+                {
+                    assumed_sizes.push((key, size));
+                    items.push(item);
+                }
+            }
         };
 
         let hashed_items = items.iter().map(&key_fn).collect::<FxIndexSet<_>>();
@@ -173,6 +345,8 @@ where
                 diff,
                 before_size,
                 after_size,
+                assumed_sizes,
+                scroll_delta,
             },
             false,
         );
@@ -193,6 +367,11 @@ where
         after_size: 0.0,
         before_node: None,
         after_node: None,
+        cross_size: 0.0,
+        set_cross_size,
+        assumed_sizes: Vec::new(),
+        measurements,
+        remeasure,
     }
 }
 
@@ -251,6 +430,17 @@ impl<V: View + 'static, T> View for VirtualList<V, T> {
         state: Box<dyn std::any::Any>,
     ) -> crate::view::ChangeFlags {
         if let Ok(state) = state.downcast::<VirtualListState<T>>() {
+            self.assumed_sizes = state.assumed_sizes;
+            if state.scroll_delta != 0.0 {
+                if let Some(parent) = self.id.parent() {
+                    let delta = match self.direction {
+                        VirtualListDirection::Vertical => Vec2::new(0.0, state.scroll_delta),
+                        VirtualListDirection::Horizontal => Vec2::new(state.scroll_delta, 0.0),
+                    };
+                    // after the layout, so the new size of the list is there to scroll in
+                    parent.update_state(ScrollState::ScrollDelta(delta), true);
+                }
+            }
             if self.before_size == state.before_size
                 && self.after_size == state.after_size
                 && state.diff.is_empty()
@@ -339,11 +529,45 @@ impl<V: View + 'static, T> View for VirtualList<V, T> {
     }
 
     fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let layout = cx.app_state().get_layout(self.id).unwrap();
+        let size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        let (cross_size, main_size): (f64, fn(Size) -> f64) = match self.direction {
+            VirtualListDirection::Vertical => (size.width, |size: Size| size.height),
+            VirtualListDirection::Horizontal => (size.height, |size: Size| size.width),
+        };
+
+        let shown = self.children.iter().filter_map(|child| child.as_ref());
+        if self.assumed_sizes.len() == shown.clone().count() {
+            let mut measurements = Vec::new();
+            for ((key, assumed), (child, _)) in self.assumed_sizes.iter_mut().zip(shown) {
+                if let Some(layout) = cx.app_state().get_layout(child.id()) {
+                    let measured = main_size(Size::new(
+                        layout.size.width as f64,
+                        layout.size.height as f64,
+                    ));
+                    if (measured - *assumed).abs() > MEASURE_TOLERANCE {
+                        *assumed = measured;
+                        measurements.push(Measurement {
+                            key: *key,
+                            cross_size,
+                            size: measured,
+                        });
+                    }
+                }
+            }
+            if !measurements.is_empty() {
+                self.measurements.borrow_mut().extend(measurements);
+                self.remeasure.notify();
+            }
+        }
+
+        if self.cross_size != cross_size {
+            self.cross_size = cross_size;
+            self.set_cross_size.set(cross_size);
+        }
+
         let viewport = cx.viewport.unwrap_or_default();
         if self.viewport != viewport {
-            let layout = cx.app_state().get_layout(self.id).unwrap();
-            let _size = Size::new(layout.size.width as f64, layout.size.height as f64);
-
             self.viewport = viewport;
             self.set_viewport.set(viewport);
         }
@@ -363,23 +587,23 @@ impl<V: View + 'static, T> View for VirtualList<V, T> {
         id_path: Option<&[Id]>,
         event: crate::event::Event,
     ) -> bool {
-        for child in self.children.iter_mut() {
-            if let Some((child, _)) = child.as_mut() {
-                let id = child.id();
-                if cx.should_send(id, &event) && child.event_main(cx, id_path, event.clone()) {
-                    return true;
-                }
-            }
-        }
-        false
+        let children = self
+            .children
+            .iter_mut()
+            .filter_map(|child| child.as_mut())
+            .map(|(child, _)| child as &mut dyn View)
+            .collect();
+        cx.event_children(children, id_path, &event)
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        for child in &mut self.children {
-            if let Some((child, _)) = child.as_mut() {
-                child.paint_main(cx);
-            }
-        }
+        let children = self
+            .children
+            .iter_mut()
+            .filter_map(|child| child.as_mut())
+            .map(|(child, _)| child as &mut dyn View)
+            .collect();
+        cx.paint_children(children);
     }
 }
 
@@ -394,3 +618,65 @@ impl<T: Clone> VirtualListVector<T> for im::Vector<T> {
         self.slice(range).into_iter()
     }
 }
+
+impl<T: Clone + 'static> VirtualListVector<T> for RwSignalVec<T> {
+    type ItemIterator = std::vec::IntoIter<T>;
+
+    fn total_len(&self) -> usize {
+        self.len()
+    }
+
+    fn slice(&mut self, range: Range<usize>) -> Self::ItemIterator {
+        self.with(|items| items[range].to_vec()).into_iter()
+    }
+}
+
+impl VirtualListVector<usize> for Range<usize> {
+    type ItemIterator = Range<usize>;
+
+    fn total_len(&self) -> usize {
+        self.len()
+    }
+
+    fn slice(&mut self, range: Range<usize>) -> Self::ItemIterator {
+        let start = (self.start + range.start).min(self.end);
+        start..(self.start + range.end).clamp(start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{place_measured, MeasuredSizes};
+
+    #[test]
+    fn measured_sizes_only_count_at_their_width() {
+        let mut sizes = MeasuredSizes::default();
+        sizes.sizes.insert(1, (200.0, 40.0));
+        assert_eq!(sizes.size(1, 200.0), Some(40.0));
+        assert_eq!(sizes.size(1, 300.0), None);
+        assert_eq!(sizes.size(2, 200.0), None);
+    }
+
+    #[test]
+    fn place_measured_items() {
+        let sizes = [(1, 20.0), (2, 20.0), (3, 40.0), (4, 20.0), (5, 20.0)];
+        let placement = place_measured(&sizes, None, 30.0, 80.0);
+        assert_eq!(placement.shown, 1..4);
+        assert_eq!(placement.before_size, 20.0);
+        assert_eq!(placement.after_size, 20.0);
+        assert_eq!(placement.scroll_delta, 0.0);
+        assert_eq!(placement.anchor, Some((2, 20.0)));
+    }
+
+    #[test]
+    fn place_measured_keeps_the_anchor_in_place() {
+        // the first item grew by 30 since item 3 was the first one shown, starting at 40
+        let sizes = [(1, 50.0), (2, 20.0), (3, 40.0), (4, 20.0)];
+        let placement = place_measured(&sizes, Some((3, 40.0)), 41.0, 60.0);
+        assert_eq!(placement.scroll_delta, 30.0);
+        assert_eq!(placement.shown, 2..3);
+        assert_eq!(placement.anchor, Some((3, 70.0)));
+        assert_eq!(placement.before_size, 70.0);
+        assert_eq!(placement.after_size, 20.0);
+    }
+}
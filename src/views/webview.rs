@@ -0,0 +1,341 @@
+use std::{cell::RefCell, rc::Rc};
+
+use floem_reactive::{create_effect, create_rw_signal, ReadSignal, RwSignal};
+use kurbo::{Rect, Size, Vec2};
+use taffy::prelude::Node;
+
+use crate::{
+    action::with_window,
+    context::{AppState, EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    view::{ChangeFlags, View},
+    views::Decorators,
+};
+
+type NavigationFn = Rc<RefCell<Option<Box<dyn Fn(&str) -> bool>>>>;
+type MessageFn = Rc<RefCell<Option<Box<dyn Fn(String)>>>>;
+
+/// What went wrong in a [`webview`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebViewError {
+    /// The platform's webview couldn't be created, so the view stays empty
+    Create(String),
+    /// A script from [evaluate_script](WebViewControls::evaluate_script) couldn't be run
+    Script(String),
+}
+
+/// Runs scripts in a [`webview`] and tells which page it's on
+#[derive(Clone, Copy)]
+pub struct WebViewControls {
+    /// The scripts waiting for the webview to be created
+    scripts: RwSignal<Vec<String>>,
+    url: RwSignal<String>,
+    error: RwSignal<Option<WebViewError>>,
+}
+
+impl WebViewControls {
+    /// Runs `script` in the page that's loaded
+    pub fn evaluate_script(&self, script: impl Into<String>) {
+        let script = script.into();
+        self.scripts.update(|scripts| scripts.push(script));
+    }
+
+    /// Sends `message` to the page, which gets it as the `data` of a `message` event on
+    /// `window`
+    pub fn post_message(&self, message: &str) {
+        self.evaluate_script(format!(
+            "window.dispatchEvent(new MessageEvent('message', {{ data: {} }}))",
+            js_string(message)
+        ));
+    }
+
+    /// The URL of the page the webview navigated to last
+    pub fn url(&self) -> ReadSignal<String> {
+        self.url.read_only()
+    }
+
+    /// The last error of the webview, if something went wrong
+    pub fn error(&self) -> ReadSignal<Option<WebViewError>> {
+        self.error.read_only()
+    }
+}
+
+/// The text as a JavaScript string literal
+fn js_string(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\u{2028}' => literal.push_str("\\u2028"),
+            '\u{2029}' => literal.push_str("\\u2029"),
+            c if c.is_control() => literal.push_str(&format!("\\u{:04x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// A web page shown by the platform's webview. See [`webview`]
+pub struct WebView {
+    id: Id,
+    webview: Rc<RefCell<Option<wry::WebView>>>,
+    /// The part of the window the webview was last put in, which is `None` while it's hidden
+    bounds: Option<Rect>,
+    controls: WebViewControls,
+    on_navigation: NavigationFn,
+    on_message: MessageFn,
+}
+
+/// A view showing the page at `url`, which loads the new page whenever the URL changes.
+/// The page is shown by the webview of the platform, with [wry], in a native child window
+/// that's put over the area of the view.
+///
+/// As the child window is drawn above the rest of the window, it's shown over views painted
+/// on top of the webview, and it's hidden while the view is hidden. It can't be clipped
+/// either, so it's shrunk to the part of the view that the scrolls it's in don't hide, and
+/// the page is laid out again in that smaller area: while the view is partly scrolled out,
+/// the page is squeezed into the part that's left instead of being cut off at the edge.
+/// [clip](crate::views::clip) and the other views that only clip what they paint don't
+/// shrink it.
+pub fn webview(url: impl Fn() -> String + 'static) -> WebView {
+    let id = Id::next();
+    let controls = WebViewControls {
+        scripts: create_rw_signal(Vec::new()),
+        url: create_rw_signal(String::new()),
+        error: create_rw_signal(None),
+    };
+    let webview = Rc::new(RefCell::new(None));
+    let on_navigation: NavigationFn = Rc::new(RefCell::new(None));
+    let on_message: MessageFn = Rc::new(RefCell::new(None));
+    let created = create_rw_signal(false);
+    let requested_url = create_rw_signal(String::new());
+    create_effect(move |_| requested_url.set(url()));
+
+    {
+        let webview = webview.clone();
+        let on_navigation = on_navigation.clone();
+        let on_message = on_message.clone();
+        with_window(move |window| {
+            let url = requested_url.get_untracked();
+            let built = wry::WebViewBuilder::new_as_child(window)
+                .with_bounds(wry::Rect {
+                    x: 0,
+                    y: 0,
+                    width: 0,
+                    height: 0,
+                })
+                .with_visible(false)
+                .with_navigation_handler(move |url| {
+                    let allowed = on_navigation
+                        .borrow()
+                        .as_ref()
+                        .map(|on_navigation| on_navigation(&url))
+                        .unwrap_or(true);
+                    if allowed {
+                        controls.url.set(url);
+                    }
+                    allowed
+                })
+                .with_ipc_handler(move |message| {
+                    if let Some(on_message) = on_message.borrow().as_ref() {
+                        on_message(message);
+                    }
+                })
+                .with_url(&url)
+                .and_then(|builder| builder.build());
+            match built {
+                Ok(built) => {
+                    *webview.borrow_mut() = Some(built);
+                    created.set(true);
+                    id.request_layout();
+                }
+                Err(err) => controls
+                    .error
+                    .set(Some(WebViewError::Create(err.to_string()))),
+            }
+        });
+    }
+
+    {
+        let webview = webview.clone();
+        create_effect(move |loaded: Option<String>| {
+            let url = requested_url.get();
+            if !created.get() {
+                return url;
+            }
+            if loaded.as_ref() != Some(&url) {
+                if let Some(webview) = webview.borrow().as_ref() {
+                    webview.load_url(&url);
+                }
+            }
+            url
+        });
+    }
+
+    {
+        let webview = webview.clone();
+        create_effect(move |_| {
+            if !created.get() || controls.scripts.with(|scripts| scripts.is_empty()) {
+                return;
+            }
+            let scripts = controls.scripts.get_untracked();
+            controls.scripts.set(Vec::new());
+            if let Some(webview) = webview.borrow().as_ref() {
+                for script in scripts {
+                    if let Err(err) = webview.evaluate_script(&script) {
+                        controls
+                            .error
+                            .set(Some(WebViewError::Script(err.to_string())));
+                    }
+                }
+            }
+        });
+    }
+
+    let cleanup = webview.clone();
+    WebView {
+        id,
+        webview,
+        bounds: None,
+        controls,
+        on_navigation,
+        on_message,
+    }
+    .base_style(|s| s.size(400.0, 300.0))
+    .on_cleanup(move || {
+        cleanup.borrow_mut().take();
+    })
+}
+
+/// The part of the view `id` that the scrolls it's in don't hide, in the coordinates of
+/// the window
+fn visible_rect(app_state: &AppState, id: Id) -> Rect {
+    let layout = match app_state.get_layout(id) {
+        Some(layout) => layout,
+        None => return Rect::ZERO,
+    };
+    let mut visible = Size::new(layout.size.width as f64, layout.size.height as f64).to_rect();
+    let mut current = Some(id);
+    while let Some(view) = current {
+        // the child of a scroll shows what's in its viewport, moved to the scroll's origin
+        if let Some(viewport) = app_state
+            .view_states
+            .get(&view)
+            .and_then(|state| state.viewport)
+        {
+            visible = visible.intersect(viewport) - viewport.origin().to_vec2();
+        }
+        if let Some(layout) = app_state.get_layout(view) {
+            visible = visible + Vec2::new(layout.location.x as f64, layout.location.y as f64);
+        }
+        current = view.parent();
+    }
+    visible
+}
+
+impl WebView {
+    /// Runs scripts in the page, and tells which page is loaded
+    pub fn controls(&self) -> WebViewControls {
+        self.controls
+    }
+
+    /// Called with the URL of each page the webview is about to go to, which is only
+    /// loaded if it returns `true`
+    pub fn on_navigation(self, on_navigation: impl Fn(&str) -> bool + 'static) -> Self {
+        *self.on_navigation.borrow_mut() = Some(Box::new(on_navigation));
+        self
+    }
+
+    /// Called with each message the page sends with `window.ipc.postMessage`
+    pub fn on_message(self, on_message: impl Fn(String) + 'static) -> Self {
+        *self.on_message.borrow_mut() = Some(Box::new(on_message));
+        self
+    }
+
+    /// Puts the webview in `bounds` of the window, or hides it for `None` or when there's
+    /// nothing left of the bounds
+    fn place(&mut self, bounds: Option<Rect>) {
+        let bounds = bounds.filter(|bounds| bounds.width() > 0.0 && bounds.height() > 0.0);
+        if self.bounds == bounds {
+            return;
+        }
+        if let Some(webview) = self.webview.borrow().as_ref() {
+            self.bounds = bounds;
+            webview.set_visible(bounds.is_some());
+            if let Some(bounds) = bounds {
+                webview.set_bounds(wry::Rect {
+                    x: bounds.x0.round() as i32,
+                    y: bounds.y0.round() as i32,
+                    width: bounds.width().round() as u32,
+                    height: bounds.height().round() as u32,
+                });
+            }
+        }
+    }
+}
+
+impl Drop for WebView {
+    fn drop(&mut self) {
+        // the child window would otherwise stay in the window until the effects holding
+        // the webview are disposed
+        if let Some(webview) = self.webview.borrow_mut().take() {
+            webview.set_visible(false);
+        }
+    }
+}
+
+impl View for WebView {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "WebView".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        // the layout isn't computed for hidden views, so they're hidden here
+        if cx.app_state().is_hidden_recursive(self.id) {
+            self.place(None);
+        }
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let visible = visible_rect(cx.app_state(), self.id);
+        self.place(Some(visible));
+        None
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        false
+    }
+
+    fn paint(&mut self, _cx: &mut PaintCx) {}
+}
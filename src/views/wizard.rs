@@ -0,0 +1,280 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use floem_reactive::{create_rw_signal, ReadSignal, RwSignal};
+
+use crate::{
+    style::CursorStyle,
+    theme::tokens,
+    view::View,
+    views::{composite, empty, label, list, stack, tab, Composite, Decorators, Label},
+};
+
+/// The size of the numbered circle of each step in the header
+const INDICATOR_SIZE: f64 = 24.0;
+
+type ValidateFn = Rc<dyn Fn(usize) -> bool>;
+
+/// One page of a [wizard]. See [`wizard_step`]
+pub struct WizardStep {
+    title: String,
+    view_fn: Box<dyn Fn() -> Box<dyn View>>,
+    validate: Option<Box<dyn Fn() -> bool>>,
+}
+
+/// A step of a [wizard] titled `title` in the header, showing the view from `view_fn`
+pub fn wizard_step<V: View + 'static>(
+    title: impl Into<String>,
+    view_fn: impl Fn() -> V + 'static,
+) -> WizardStep {
+    WizardStep {
+        title: title.into(),
+        view_fn: Box::new(move || Box::new(view_fn())),
+        validate: None,
+    }
+}
+
+impl WizardStep {
+    /// Called when leaving the step with Next or Finish, which only happens if it returns
+    /// `true`. Going back doesn't validate the step.
+    pub fn validate(mut self, validate: impl Fn() -> bool + 'static) -> Self {
+        self.validate = Some(Box::new(validate));
+        self
+    }
+}
+
+/// Moves a [wizard] between its steps, from its buttons or from anywhere else
+#[derive(Clone, Copy)]
+pub struct WizardControls {
+    step: RwSignal<usize>,
+    len: usize,
+    validate: RwSignal<ValidateFn>,
+    finished: RwSignal<bool>,
+    on_finish: RwSignal<Option<Rc<dyn Fn()>>>,
+}
+
+impl WizardControls {
+    /// Goes to the next step if the current one is valid
+    pub fn next(&self) {
+        let step = self.step.get_untracked();
+        if step + 1 < self.len && self.validate.get_untracked()(step) {
+            self.step.set(step + 1);
+        }
+    }
+
+    /// Goes to the step before the current one
+    pub fn back(&self) {
+        let step = self.step.get_untracked();
+        if step > 0 {
+            self.step.set(step - 1);
+        }
+    }
+
+    /// Finishes the wizard if it's on the last step and the step is valid, which calls
+    /// [Wizard::on_finish]
+    pub fn finish(&self) {
+        let step = self.step.get_untracked();
+        if step + 1 == self.len && self.validate.get_untracked()(step) {
+            self.finished.set(true);
+            if let Some(on_finish) = self.on_finish.get_untracked() {
+                on_finish();
+            }
+        }
+    }
+
+    /// The index of the step that's shown
+    pub fn step(&self) -> ReadSignal<usize> {
+        self.step.read_only()
+    }
+
+    /// Whether the wizard was finished
+    pub fn finished(&self) -> ReadSignal<bool> {
+        self.finished.read_only()
+    }
+}
+
+/// Steps shown one at a time below a header showing all of them. See [`wizard`]
+pub type Wizard = Composite<WizardState>;
+
+pub struct WizardState {
+    controls: WizardControls,
+}
+
+/// Shows the `steps` one at a time, with Back and Next buttons at the bottom, and Finish in
+/// place of Next on the last step. The header shows each step's number and title, and the
+/// steps that are done can be clicked to go back to them.
+///
+/// Each step is only left for the next one, or finished, once its
+/// [validate](WizardStep::validate) callback allows it. The same actions can be taken from
+/// outside the wizard with [Wizard::controls].
+pub fn wizard(steps: Vec<WizardStep>) -> Wizard {
+    let len = steps.len();
+    let titles: Vec<String> = steps.iter().map(|step| step.title.clone()).collect();
+    let (views, validators): (Vec<_>, Vec<_>) = steps
+        .into_iter()
+        .map(|step| (step.view_fn, step.validate))
+        .unzip();
+    let validate: ValidateFn = Rc::new(move |step| {
+        validators
+            .get(step)
+            .and_then(|validate| validate.as_ref())
+            .map(|validate| validate())
+            .unwrap_or(true)
+    });
+    let controls = WizardControls {
+        step: create_rw_signal(0),
+        len,
+        validate: create_rw_signal(validate),
+        finished: create_rw_signal(false),
+        on_finish: create_rw_signal(None),
+    };
+    let step = controls.step;
+
+    let header = list(
+        move || titles.clone().into_iter().enumerate(),
+        |(index, _)| *index,
+        move |(index, title)| {
+            let state = move || step.get().cmp(&index);
+            let indicator = label(move || match state() {
+                Ordering::Greater => "✓".to_string(),
+                _ => (index + 1).to_string(),
+            })
+            .style(move |s| {
+                let colors = tokens().colors;
+                let reached = state() != Ordering::Less;
+                s.size(INDICATOR_SIZE, INDICATOR_SIZE)
+                    .justify_center()
+                    .items_center()
+                    .border(1.0)
+                    .border_radius(INDICATOR_SIZE / 2.0)
+                    .border_color(colors.border)
+                    .color(colors.text_muted)
+                    .apply_if(reached, |s| {
+                        s.border_color(colors.primary)
+                            .background(colors.primary)
+                            .color(colors.surface)
+                    })
+            });
+            let title = label(move || title.clone()).style(move |s| {
+                let tokens = tokens();
+                s.margin_left(tokens.spacing.sm)
+                    .color(tokens.colors.text_muted)
+                    .apply_if(state() == Ordering::Equal, |s| s.color(tokens.colors.text))
+            });
+            stack((indicator, title))
+                .style(move |s| {
+                    s.items_center()
+                        .margin_right(tokens().spacing.lg)
+                        .apply_if(state() == Ordering::Greater, |s| {
+                            s.cursor(CursorStyle::Pointer)
+                        })
+                })
+                .on_click(move |_| {
+                    if step.get_untracked() > index {
+                        step.set(index);
+                    }
+                    true
+                })
+        },
+    )
+    .style(|s| {
+        let tokens = tokens();
+        s.items_center()
+            .padding_bottom(tokens.spacing.md)
+            .margin_bottom(tokens.spacing.md)
+            .border_bottom(1.0)
+            .border_color(tokens.colors.border)
+    });
+
+    let content = tab(
+        move || step.get(),
+        move || 0..len,
+        |index| *index,
+        move |index| {
+            views
+                .get(index)
+                .map(|view_fn| view_fn())
+                .unwrap_or_else(|| Box::new(empty()))
+        },
+    )
+    .style(|s| s.flex_grow(1.0).min_height(0.0));
+
+    let first = move || step.get() == 0;
+    let last = move || step.get() + 1 >= len;
+    let back = wizard_button(|| "Back".to_string(), false, move || controls.back())
+        .style(move |s| s.apply_if(first(), |s| s.hide()));
+    let next = wizard_button(
+        move || if last() { "Finish" } else { "Next" }.to_string(),
+        true,
+        move || {
+            if last() {
+                controls.finish();
+            } else {
+                controls.next();
+            }
+        },
+    )
+    .disabled(move || controls.finished.get());
+    let footer = stack((back, next)).style(|s| {
+        let tokens = tokens();
+        s.justify_end()
+            .padding_top(tokens.spacing.md)
+            .margin_top(tokens.spacing.md)
+            .border_top(1.0)
+            .border_color(tokens.colors.border)
+    });
+
+    composite(
+        "Wizard",
+        stack((header, content, footer)).style(|s| s.flex_col().size_full()),
+        WizardState { controls },
+    )
+}
+
+fn wizard_button(
+    text: impl Fn() -> String + 'static,
+    primary: bool,
+    action: impl Fn() + 'static,
+) -> Label {
+    label(text)
+        .base_style(move |s| {
+            let tokens = tokens();
+            s.margin_left(tokens.spacing.sm)
+                .padding_horiz(tokens.spacing.md)
+                .padding_vert(tokens.spacing.xs)
+                .border(1.0)
+                .border_radius(tokens.radius.md)
+                .border_color(tokens.colors.border)
+                .cursor(CursorStyle::Pointer)
+                .apply_if(primary, |s| {
+                    s.border_color(tokens.colors.primary)
+                        .background(tokens.colors.primary)
+                        .color(tokens.colors.surface)
+                })
+        })
+        .hover_style(move |s| s.apply_if(!primary, |s| s.background(tokens().colors.hover)))
+        .disabled_style(|s| {
+            s.cursor(CursorStyle::Default)
+                .color(tokens().colors.text_muted)
+        })
+        .on_click(move |_| {
+            action();
+            true
+        })
+        .keyboard_navigatable()
+}
+
+impl Wizard {
+    /// Moves the wizard between its steps, and tells which step it's on
+    pub fn controls(&self) -> WizardControls {
+        self.state().controls
+    }
+
+    /// Called when the wizard is finished on its last step
+    pub fn on_finish(self, on_finish: impl Fn() + 'static) -> Self {
+        self.state()
+            .controls
+            .on_finish
+            .set(Some(Rc::new(on_finish)));
+        self
+    }
+}
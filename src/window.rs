@@ -1,3 +1,6 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use floem_reactive::{ReadSignal, RwSignal, Scope};
 use kurbo::{Point, Size};
 pub use winit::window::ResizeDirection;
 pub use winit::window::Theme;
@@ -8,11 +11,43 @@ use crate::{
     view::View,
 };
 
+thread_local! {
+    static WINDOW_SIGNALS: RefCell<HashMap<WindowId, WindowSignals>> = RefCell::new(HashMap::new());
+}
+
+/// How a window repaints while it is being interactively resized.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeMode {
+    /// Schedule a redraw like any other update. This is the cheapest option, but some
+    /// platforms (notably Windows) show flicker or black borders while resizing.
+    #[default]
+    Deferred,
+    /// Lay out and repaint the window right away when it is resized, so every size
+    /// the platform shows has a matching frame. This costs a frame per resize event.
+    Synchronous,
+}
+
+/// Whether the arrow keys move the focus between `keyboard_navigatable` views based on
+/// where they are in the window, instead of only by Tab order.
+/// Arrow keys that a focused view handles itself, like in a text input, aren't affected.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialNavigation {
+    #[default]
+    Disabled,
+    /// The focus stays put when there's no view in the direction of the arrow key
+    Enabled,
+    /// Moving past the last view in a direction goes to the furthest view on the opposite side,
+    /// so a row or column of views loops around
+    Wrapping,
+}
+
 #[derive(Default, Debug)]
 pub struct WindowConfig {
     pub(crate) size: Option<Size>,
     pub(crate) position: Option<Point>,
     pub(crate) show_titlebar: Option<bool>,
+    pub(crate) resize_mode: ResizeMode,
+    pub(crate) spatial_navigation: SpatialNavigation,
 }
 
 impl WindowConfig {
@@ -30,6 +65,90 @@ impl WindowConfig {
         self.show_titlebar = Some(show_titlebar);
         self
     }
+
+    pub fn resize_mode(mut self, resize_mode: ResizeMode) -> Self {
+        self.resize_mode = resize_mode;
+        self
+    }
+
+    pub fn spatial_navigation(mut self, spatial_navigation: SpatialNavigation) -> Self {
+        self.spatial_navigation = spatial_navigation;
+        self
+    }
+}
+
+/// Signals following the size, maximized state and theme of a window, for responsive logic
+/// that would otherwise need a `WindowResized` listener on the root view.
+///
+/// While the window is being resized they're only updated once it has kept the same size
+/// for a moment, so effects depending on them don't rerun for every intermediate size.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowSignals {
+    inner_size: RwSignal<Size>,
+    outer_size: RwSignal<Size>,
+    is_maximized: RwSignal<bool>,
+    theme: RwSignal<Option<Theme>>,
+}
+
+impl WindowSignals {
+    pub(crate) fn new(
+        window_id: WindowId,
+        scope: Scope,
+        inner_size: Size,
+        outer_size: Size,
+        is_maximized: bool,
+        theme: RwSignal<Option<Theme>>,
+    ) -> Self {
+        let signals = Self {
+            inner_size: scope.create_rw_signal(inner_size),
+            outer_size: scope.create_rw_signal(outer_size),
+            is_maximized: scope.create_rw_signal(is_maximized),
+            theme,
+        };
+        WINDOW_SIGNALS.with(|windows| windows.borrow_mut().insert(window_id, signals));
+        signals
+    }
+
+    pub(crate) fn remove(window_id: WindowId) {
+        WINDOW_SIGNALS.with(|windows| windows.borrow_mut().remove(&window_id));
+    }
+
+    pub(crate) fn set(&self, inner_size: Size, outer_size: Size, is_maximized: bool) {
+        if self.inner_size.get_untracked() != inner_size {
+            self.inner_size.set(inner_size);
+        }
+        if self.outer_size.get_untracked() != outer_size {
+            self.outer_size.set(outer_size);
+        }
+        if self.is_maximized.get_untracked() != is_maximized {
+            self.is_maximized.set(is_maximized);
+        }
+    }
+
+    /// The size of the window's content area, in logical pixels
+    pub fn inner_size(&self) -> ReadSignal<Size> {
+        self.inner_size.read_only()
+    }
+
+    /// The size of the whole window including its decorations, in logical pixels
+    pub fn outer_size(&self) -> ReadSignal<Size> {
+        self.outer_size.read_only()
+    }
+
+    pub fn is_maximized(&self) -> ReadSignal<bool> {
+        self.is_maximized.read_only()
+    }
+
+    /// The light or dark theme of the system for the window, if the platform tells it
+    pub fn theme(&self) -> ReadSignal<Option<Theme>> {
+        self.theme.read_only()
+    }
+}
+
+/// The [WindowSignals] of an open window, which can be called from within
+/// the window's view function with the `WindowId` it's given
+pub fn window_signals(window_id: WindowId) -> Option<WindowSignals> {
+    WINDOW_SIGNALS.with(|windows| windows.borrow().get(&window_id).copied())
 }
 
 /// create a new window. You'll need to create Application first, otherwise it
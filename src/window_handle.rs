@@ -1,42 +1,64 @@
-use std::time::{Duration, Instant};
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 use floem_reactive::{with_scope, RwSignal, Scope};
 use floem_renderer::Renderer;
 use kurbo::{Affine, Point, Rect, Size, Vec2};
+use taffy::style::LengthPercentage;
 
 #[cfg(target_os = "linux")]
 use winit::window::WindowId;
 use winit::{
     dpi::{LogicalPosition, LogicalSize},
-    event::{ElementState, Ime, MouseButton, MouseScrollDelta},
+    event::{ElementState, Ime, MouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent},
     keyboard::{Key, ModifiersState},
     window::{CursorIcon, Theme},
 };
 
-#[cfg(target_os = "linux")]
 use crate::unit::UnitExt;
-#[cfg(target_os = "linux")]
 use crate::views::{container_box, stack, Decorators};
 use crate::{
-    action::exec_after,
+    action::{exec_after, TimerToken},
     animate::{AnimPropKind, AnimUpdateMsg, AnimValue, AnimatedProp, SizeUnit},
     context::{
         AppState, EventCx, LayoutCx, MoveListener, PaintCx, PaintState, ResizeListener, UpdateCx,
     },
     event::{Event, EventListener},
+    fonts::font_generation,
     id::{Id, IdPath, ID_PATHS},
     keyboard::KeyEvent,
     menu::Menu,
-    pointer::{PointerButton, PointerInputEvent, PointerMoveEvent, PointerWheelEvent},
+    modal::{cancel_top_modal, modal_layer, open_modals, register_modal_layer},
+    overlay::{click_outside_overlays, overlay_layer, register_overlay_layer},
+    pointer::{
+        update_pointer_type, PenState, PointerButton, PointerInputEvent, PointerMoveEvent,
+        PointerType, PointerWheelEvent,
+    },
     style::{CursorStyle, StyleSelector},
+    stylesheet::stylesheet_generation,
+    toast::{register_toast_layer, toast_layer, ToastLayer},
+    unit::PxPct,
     update::{
         UpdateMessage, ANIM_UPDATE_MESSAGES, CENTRAL_DEFERRED_UPDATE_MESSAGES,
         CENTRAL_UPDATE_MESSAGES, CURRENT_RUNNING_VIEW_HANDLE, DEFERRED_UPDATE_MESSAGES,
         UPDATE_MESSAGES,
     },
-    view::{view_children_set_parent_id, ChangeFlags, View},
+    view::{
+        view_children_set_parent_id, view_spatial_navigation, ChangeFlags, FocusDirection, View,
+    },
+    window::{ResizeMode, SpatialNavigation, WindowSignals},
 };
 
+/// How long a window has to keep the same size before its [WindowSignals] are updated
+const WINDOW_SIGNALS_DEBOUNCE: Duration = Duration::from_millis(100);
+/// How long after a frame the views that asked for another one are painted again
+const ANIMATION_FRAME: Duration = Duration::from_millis(16);
+/// How long after a touch the mouse events are taken to be emulated from it
+const EMULATED_MOUSE_DELAY: Duration = Duration::from_millis(500);
+
 /// The top-level window handle that owns the winit Window.
 /// Meant only for use with the root view of the application.
 /// Owns the `AppState` and is responsible for
@@ -47,7 +69,7 @@ pub(crate) struct WindowHandle {
     pub(crate) window: Option<winit::window::Window>,
     /// Reactive Scope for this WindowHandle
     scope: Scope,
-    view: Box<dyn View>,
+    pub(crate) view: Box<dyn View>,
     app_state: AppState,
     paint_state: PaintState,
     size: RwSignal<Size>,
@@ -58,40 +80,126 @@ pub(crate) struct WindowHandle {
     pub(crate) cursor_position: Point,
     pub(crate) window_position: Point,
     pub(crate) last_pointer_down: Option<(u8, Instant)>,
+    /// When the last touch happened, to tell real mouse events from emulated ones
+    last_touch: Option<Instant>,
+    resize_mode: ResizeMode,
+    spatial_navigation: SpatialNavigation,
+    window_signals: WindowSignals,
+    /// The timer of the last resize, the only one that's allowed to update the window signals
+    last_resize_timer: Rc<Cell<TimerToken>>,
+    /// The open modals, with what had keyboard focus before each of them opened
+    modal_focus: Vec<(Id, Option<Id>)>,
+    /// Whether something changed since the last paint, for when there's no window to redraw
+    pub(crate) needs_paint: bool,
+    /// The stylesheet the views were last styled with, see [stylesheet_generation]
+    stylesheet_generation: u64,
+    /// The fonts the text of the views was laid out with, see [font_generation]
+    font_generation: u64,
     #[cfg(target_os = "linux")]
     pub(crate) context_menu: RwSignal<Option<(Menu, Point)>>,
 }
 
+/// What a window handle needs to know about the window or surface it draws to
+pub(crate) struct SurfaceInfo {
+    pub(crate) window_id: winit::window::WindowId,
+    pub(crate) scale: f64,
+    /// The logical size of the area that's drawn to
+    pub(crate) size: Size,
+    pub(crate) outer_size: Size,
+    pub(crate) theme: Option<Theme>,
+    pub(crate) is_maximized: bool,
+}
+
 impl WindowHandle {
     pub(crate) fn new(
         window: winit::window::Window,
         view_fn: impl FnOnce(winit::window::WindowId) -> Box<dyn View> + 'static,
+        resize_mode: ResizeMode,
+        spatial_navigation: SpatialNavigation,
     ) -> Self {
-        let scope = Scope::new();
-        let window_id = window.id();
         let scale = window.scale_factor();
         let size: LogicalSize<f64> = window.inner_size().to_logical(scale);
-        let size = Size::new(size.width, size.height);
-        let size = scope.create_rw_signal(Size::new(size.width, size.height));
-        let theme = scope.create_rw_signal(window.theme());
-        let is_maximized = window.is_maximized();
+        let outer_size: LogicalSize<f64> = window.outer_size().to_logical(scale);
+        let surface = SurfaceInfo {
+            window_id: window.id(),
+            scale,
+            size: Size::new(size.width, size.height),
+            outer_size: Size::new(outer_size.width, outer_size.height),
+            theme: window.theme(),
+            is_maximized: window.is_maximized(),
+        };
+        let paint_state = PaintState::new(&window, scale, surface.size * scale);
+        Self::with_surface(
+            Some(window),
+            surface,
+            paint_state,
+            view_fn,
+            resize_mode,
+            spatial_navigation,
+        )
+    }
+
+    /// A handle that paints with `paint_state`, which only controls a winit window
+    /// (its cursor, title, redraws and so on) if it has one
+    pub(crate) fn with_surface(
+        window: Option<winit::window::Window>,
+        surface: SurfaceInfo,
+        paint_state: PaintState,
+        view_fn: impl FnOnce(winit::window::WindowId) -> Box<dyn View> + 'static,
+        resize_mode: ResizeMode,
+        spatial_navigation: SpatialNavigation,
+    ) -> Self {
+        let scope = Scope::new();
+        let window_id = surface.window_id;
+        let scale = surface.scale;
+        let size = scope.create_rw_signal(surface.size);
+        let theme = scope.create_rw_signal(surface.theme);
+        let is_maximized = surface.is_maximized;
+        let window_signals = WindowSignals::new(
+            window_id,
+            scope,
+            surface.size,
+            surface.outer_size,
+            is_maximized,
+            theme,
+        );
+
+        let overlays = scope.create_rw_signal(Vec::new());
+        let modals = scope.create_rw_signal(Vec::new());
+        let toasts = ToastLayer::new(scope);
 
         #[cfg(target_os = "linux")]
         let context_menu = scope.create_rw_signal(None);
 
         #[cfg(not(target_os = "linux"))]
-        let view = with_scope(scope, move || view_fn(window_id));
+        let view: Box<dyn View> = with_scope(scope, move || {
+            Box::new(
+                stack((
+                    container_box(view_fn(window_id)).style(|s| s.size(100.pct(), 100.pct())),
+                    modal_layer(modals),
+                    toast_layer(toasts),
+                    overlay_layer(overlays),
+                ))
+                .style(|s| s.size(100.pct(), 100.pct())),
+            )
+        });
 
         #[cfg(target_os = "linux")]
-        let view = with_scope(scope, move || {
+        let view: Box<dyn View> = with_scope(scope, move || {
             Box::new(
                 stack((
                     container_box(view_fn(window_id)).style(|s| s.size(100.pct(), 100.pct())),
+                    modal_layer(modals),
+                    toast_layer(toasts),
+                    overlay_layer(overlays),
                     context_menu_view(scope, window_id, context_menu, size),
                 ))
                 .style(|s| s.size(100.pct(), 100.pct())),
             )
         });
+        register_overlay_layer(view.id(), overlays);
+        register_modal_layer(view.id(), modals);
+        register_toast_layer(view.id(), toasts);
 
         ID_PATHS.with(|id_paths| {
             id_paths
@@ -100,9 +208,8 @@ impl WindowHandle {
         });
         view_children_set_parent_id(&*view);
 
-        let paint_state = PaintState::new(&window, scale, size.get_untracked() * scale);
         let mut window_handle = Self {
-            window: Some(window),
+            window,
             scope,
             view,
             app_state: AppState::new(),
@@ -117,6 +224,15 @@ impl WindowHandle {
             #[cfg(target_os = "linux")]
             context_menu,
             last_pointer_down: None,
+            last_touch: None,
+            resize_mode,
+            spatial_navigation,
+            window_signals,
+            last_resize_timer: Rc::new(Cell::new(TimerToken::INVALID)),
+            modal_focus: Vec::new(),
+            needs_paint: true,
+            stylesheet_generation: stylesheet_generation(),
+            font_generation: font_generation(),
         };
         window_handle.app_state.set_root_size(size.get_untracked());
         window_handle
@@ -126,6 +242,10 @@ impl WindowHandle {
         set_current_view(self.view.id());
         let event = event.scale(self.app_state.scale);
 
+        if let Event::PointerDown(pointer_event) = &event {
+            click_outside_overlays(&self.app_state, self.view.id(), pointer_event.pos);
+        }
+
         let mut cx = EventCx {
             app_state: &mut self.app_state,
         };
@@ -171,12 +291,32 @@ impl WindowHandle {
                     }
                 }
 
+                if !processed {
+                    if let Event::KeyDown(KeyEvent { key, .. }) = &event {
+                        if key.logical_key == Key::Escape {
+                            processed = cancel_top_modal(self.view.id());
+                        }
+                    }
+                }
+
                 if !processed {
                     if let Event::KeyDown(KeyEvent { key, modifiers }) = &event {
+                        let direction = match key.logical_key {
+                            Key::ArrowUp => Some(FocusDirection::Up),
+                            Key::ArrowDown => Some(FocusDirection::Down),
+                            Key::ArrowLeft => Some(FocusDirection::Left),
+                            Key::ArrowRight => Some(FocusDirection::Right),
+                            _ => None,
+                        };
                         if key.logical_key == Key::Tab {
                             let _backwards = modifiers.contains(ModifiersState::SHIFT);
                             // view_tab_navigation(&self.view, cx.app_state, backwards);
                             // view_debug_tree(&self.view);
+                        } else if let Some(direction) = direction
+                            .filter(|_| self.spatial_navigation != SpatialNavigation::Disabled)
+                        {
+                            let wrap = self.spatial_navigation == SpatialNavigation::Wrapping;
+                            view_spatial_navigation(cx.app_state, direction, wrap);
                         } else if let Key::Character(character) = &key.logical_key {
                             // 'I' displays some debug information
                             if character.eq_ignore_ascii_case("i") {
@@ -211,6 +351,11 @@ impl WindowHandle {
                 self.view
                     .event_main(&mut cx, Some(&id_path.0), event.clone());
             }
+            if is_pointer_move && cx.app_state.cursor.is_none() {
+                // the pressed view keeps its cursor while the pointer is dragged off of it,
+                // like a splitter being resized
+                cx.app_state.cursor = cx.app_state.get_computed_style(id).cursor;
+            }
             if let Event::PointerUp(_) = &event {
                 // To remove the styles applied by the Active selector
                 if cx.app_state.has_style_for_sel(id, StyleSelector::Active) {
@@ -226,6 +371,14 @@ impl WindowHandle {
         if let Event::PointerUp(_) = &event {
             cx.app_state.drag_start = None;
         }
+        if let Event::PointerWheel(_) = &event {
+            // tooltips would be left behind by whatever is scrolled
+            for id in cx.app_state.hovered.clone() {
+                if let Some(tooltip) = cx.app_state.view_state(id).tooltip.as_ref() {
+                    tooltip.hide();
+                }
+            }
+        }
         if is_pointer_move {
             let hovered = &cx.app_state.hovered.clone();
             for id in was_hovered.unwrap().symmetric_difference(hovered) {
@@ -237,13 +390,19 @@ impl WindowHandle {
                     cx.app_state.request_layout(*id);
                 }
                 if hovered.contains(id) {
+                    if let Some(tooltip) = cx.app_state.view_state(*id).tooltip.as_ref() {
+                        tooltip.schedule(*id);
+                    }
                     if let Some(action) = cx.get_event_listener(*id, &EventListener::PointerEnter) {
                         (*action)(&event);
                     }
-                } else if let Some(action) =
-                    cx.get_event_listener(*id, &EventListener::PointerLeave)
-                {
-                    (*action)(&event);
+                } else {
+                    if let Some(tooltip) = cx.app_state.view_state(*id).tooltip.as_ref() {
+                        tooltip.hide();
+                    }
+                    if let Some(action) = cx.get_event_listener(*id, &EventListener::PointerLeave) {
+                        (*action)(&event);
+                    }
                 }
             }
             let dragging_over = &cx.app_state.dragging_over.clone();
@@ -274,6 +433,14 @@ impl WindowHandle {
         self.request_paint();
     }
 
+    /// Paints to another surface from now on, with the renderer set up for it in `paint_state`
+    pub(crate) fn set_paint_state(&mut self, paint_state: PaintState) {
+        self.paint_state = paint_state;
+        self.paint_state
+            .set_scale(self.scale * self.app_state.scale);
+        self.request_paint();
+    }
+
     pub(crate) fn theme_changed(&mut self, theme: Theme) {
         self.theme.set(Some(theme));
     }
@@ -293,10 +460,36 @@ impl WindowHandle {
                 self.event(Event::WindowMaximizeChanged(is_maximized));
             }
         }
+        self.debounce_window_signals(size);
 
         self.layout();
         self.process_update();
-        self.request_paint();
+        match self.resize_mode {
+            ResizeMode::Deferred => self.request_paint(),
+            // Paint before returning to the platform, so the resized surface is never
+            // presented without content
+            ResizeMode::Synchronous => self.paint(),
+        }
+    }
+
+    fn debounce_window_signals(&mut self, inner_size: Size) {
+        let outer_size = match self.window.as_ref() {
+            Some(window) => {
+                let outer_size: LogicalSize<f64> = window.outer_size().to_logical(self.scale);
+                Size::new(outer_size.width, outer_size.height)
+            }
+            // the surface of an embedded view has no frame around it
+            None => inner_size,
+        };
+        let is_maximized = self.is_maximized;
+        let window_signals = self.window_signals;
+        let last_resize_timer = self.last_resize_timer.clone();
+        let token = exec_after(WINDOW_SIGNALS_DEBOUNCE, move |token| {
+            if last_resize_timer.get() == token {
+                window_signals.set(inner_size, outer_size, is_maximized);
+            }
+        });
+        self.last_resize_timer.set(token);
     }
 
     pub(crate) fn position(&mut self, point: Point) {
@@ -323,6 +516,8 @@ impl WindowHandle {
             let event = PointerMoveEvent {
                 pos,
                 modifiers: self.modifiers,
+                pointer_type: PointerType::Mouse,
+                pen: None,
             };
             self.event(Event::PointerMove(event));
         }
@@ -346,7 +541,72 @@ impl WindowHandle {
 
     pub(crate) fn mouse_input(&mut self, button: MouseButton, state: ElementState) {
         let button: PointerButton = button.into();
-        let count = if state.is_pressed() && button.is_primary() {
+        let count = self.pointer_down_count(button, state);
+        let event = PointerInputEvent {
+            pos: self.cursor_position,
+            button,
+            modifiers: self.modifiers,
+            count,
+            pointer_type: PointerType::Mouse,
+            pen: None,
+        };
+        match state {
+            ElementState::Pressed => {
+                self.event(Event::PointerDown(event));
+            }
+            ElementState::Released => {
+                self.event(Event::PointerUp(event));
+            }
+        }
+    }
+
+    /// Pen contact goes through the same pointer events as the mouse, with the tip acting as
+    /// the primary button
+    pub(crate) fn pen_input(&mut self, touch: Touch, pen: PenState) {
+        let position: LogicalPosition<f64> = touch.location.to_logical(self.scale);
+        let pos = Point::new(position.x, position.y);
+        if self.cursor_position != pos {
+            self.last_pointer_down = None;
+            self.cursor_position = pos;
+        }
+        let button = PointerButton::Primary;
+        match touch.phase {
+            TouchPhase::Started => {
+                let count = self.pointer_down_count(button, ElementState::Pressed);
+                self.event(Event::PointerDown(PointerInputEvent {
+                    pos,
+                    button,
+                    modifiers: self.modifiers,
+                    count,
+                    pointer_type: PointerType::Pen,
+                    pen: Some(pen),
+                }));
+            }
+            // Pressure and tilt change without the pen moving, so this is sent even
+            // when the position is unchanged
+            TouchPhase::Moved => {
+                self.event(Event::PointerMove(PointerMoveEvent {
+                    pos,
+                    modifiers: self.modifiers,
+                    pointer_type: PointerType::Pen,
+                    pen: Some(pen),
+                }));
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.event(Event::PointerUp(PointerInputEvent {
+                    pos,
+                    button,
+                    modifiers: self.modifiers,
+                    count: 0,
+                    pointer_type: PointerType::Pen,
+                    pen: Some(pen),
+                }));
+            }
+        }
+    }
+
+    fn pointer_down_count(&mut self, button: PointerButton, state: ElementState) -> u8 {
+        if state.is_pressed() && button.is_primary() {
             if let Some((count, instant)) = self.last_pointer_down.as_mut() {
                 if *count == 4 {
                     *count = 1;
@@ -363,23 +623,21 @@ impl WindowHandle {
             }
         } else {
             0
-        };
-        let event = PointerInputEvent {
-            pos: self.cursor_position,
-            button,
-            modifiers: self.modifiers,
-            count,
-        };
-        match state {
-            ElementState::Pressed => {
-                self.event(Event::PointerDown(event));
-            }
-            ElementState::Released => {
-                self.event(Event::PointerUp(event));
-            }
         }
     }
 
+    #[cfg(feature = "gamepad")]
+    pub(crate) fn spatial_navigation(&mut self, direction: FocusDirection) {
+        set_current_view(self.view.id());
+        let old = self.app_state.focus;
+        let wrap = self.spatial_navigation == SpatialNavigation::Wrapping;
+        view_spatial_navigation(&mut self.app_state, direction, wrap);
+        if old != self.app_state.focus {
+            self.app_state.focus_changed(old, self.app_state.focus);
+        }
+        self.process_update();
+    }
+
     pub(crate) fn focused(&mut self, focused: bool) {
         if focused {
             self.event(Event::WindowGotFocus);
@@ -388,6 +646,92 @@ impl WindowHandle {
         }
     }
 
+    /// Handles an event of the window, other than it closing
+    pub(crate) fn window_event(&mut self, event: WindowEvent) {
+        match event {
+            WindowEvent::ActivationTokenDone { .. } => {}
+            WindowEvent::Resized(size) => {
+                let size: LogicalSize<f64> = size.to_logical(self.scale);
+                let size = Size::new(size.width, size.height);
+                self.size(size);
+            }
+            WindowEvent::Moved(position) => {
+                let position: LogicalPosition<f64> = position.to_logical(self.scale);
+                let point = Point::new(position.x, position.y);
+                self.position(point);
+            }
+            // closing is up to whatever owns the window
+            WindowEvent::CloseRequested | WindowEvent::Destroyed => {}
+            WindowEvent::DroppedFile(_) => {}
+            WindowEvent::HoveredFile(_) => {}
+            WindowEvent::HoveredFileCancelled => {}
+            WindowEvent::Focused(focused) => {
+                self.focused(focused);
+            }
+            WindowEvent::KeyboardInput { event, .. } => {
+                self.key_event(event);
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
+            }
+            WindowEvent::Ime(ime) => {
+                self.ime(ime);
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.mouse_used();
+                let position: LogicalPosition<f64> = position.to_logical(self.scale);
+                let point = Point::new(position.x, position.y);
+                self.pointer_move(point);
+            }
+            WindowEvent::CursorEntered { .. } => {}
+            WindowEvent::CursorLeft { .. } => {}
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.mouse_used();
+                self.mouse_wheel(delta);
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.mouse_used();
+                self.mouse_input(button, state);
+            }
+            WindowEvent::TouchpadMagnify { .. } => {}
+            WindowEvent::SmartMagnify { .. } => {}
+            WindowEvent::TouchpadRotate { .. } => {}
+            WindowEvent::TouchpadPressure { .. } => {}
+            WindowEvent::AxisMotion { .. } => {}
+            WindowEvent::Touch(touch) => {
+                self.last_touch = Some(Instant::now());
+                if let Some(pen) = touch.force.and_then(PenState::from_force) {
+                    update_pointer_type(PointerType::Pen);
+                    self.pen_input(touch, pen);
+                } else {
+                    update_pointer_type(PointerType::Touch);
+                }
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.scale(scale_factor);
+            }
+            WindowEvent::ThemeChanged(theme) => {
+                self.theme_changed(theme);
+            }
+            WindowEvent::Occluded(_) => {}
+            WindowEvent::MenuAction(id) => {
+                self.menu_action(id);
+            }
+        }
+    }
+
+    /// Switches the pointer type back to the mouse, unless the mouse event is likely one the
+    /// platform emulates from a touch
+    fn mouse_used(&mut self) {
+        let emulated = self
+            .last_touch
+            .map(|touch| touch.elapsed() < EMULATED_MOUSE_DELAY)
+            .unwrap_or(false);
+        if !emulated {
+            update_pointer_type(PointerType::Mouse);
+        }
+    }
+
     fn layout(&mut self) {
         let mut cx = LayoutCx::new(&mut self.app_state);
 
@@ -397,39 +741,59 @@ impl WindowHandle {
         cx.clear();
         self.view.compute_layout_main(&mut cx);
 
-        // Currently we only need one ID with animation in progress to request layout, which will
-        // advance the all the animations in progress.
-        // This will be reworked once we change from request_layout to request_paint
-        let id = self.app_state.ids_with_anim_in_progress().get(0).cloned();
-
-        if let Some(id) = id {
+        // Every view with an animation or a style transition in progress requests a layout for
+        // the next frame, which only marks it and its ancestors dirty, so the rest of the tree
+        // keeps its cached layout while each animated view gets its interpolated style applied.
+        let mut ids = self.app_state.ids_with_anim_in_progress();
+        ids.extend(self.app_state.ids_with_transition_in_progress());
+        if !ids.is_empty() {
             exec_after(Duration::from_millis(1), move |_| {
-                id.request_layout();
+                for id in ids {
+                    id.request_layout();
+                }
             });
         }
     }
 
     pub fn paint(&mut self) {
+        self.needs_paint = false;
+        self.app_state.frame_time = Instant::now();
         let mut cx = PaintCx {
             app_state: &mut self.app_state,
             paint_state: &mut self.paint_state,
             transform: Affine::IDENTITY,
             clip: None,
+            mask: None,
             color: None,
             font_size: None,
             font_family: None,
             font_weight: None,
             font_style: None,
             line_height: None,
+            letter_spacing: None,
+            text_decoration: None,
+            cursor_color: None,
+            caret_width: None,
+            caret_shape: None,
+            selection_background: None,
+            selection_color: None,
             z_index: None,
             saved_transforms: Vec::new(),
             saved_clips: Vec::new(),
+            saved_masks: Vec::new(),
             saved_colors: Vec::new(),
             saved_font_sizes: Vec::new(),
             saved_font_families: Vec::new(),
             saved_font_weights: Vec::new(),
             saved_font_styles: Vec::new(),
             saved_line_heights: Vec::new(),
+            saved_letter_spacings: Vec::new(),
+            saved_text_decorations: Vec::new(),
+            saved_cursor_colors: Vec::new(),
+            saved_caret_widths: Vec::new(),
+            saved_caret_shapes: Vec::new(),
+            saved_selection_backgrounds: Vec::new(),
+            saved_selection_colors: Vec::new(),
             saved_z_indexes: Vec::new(),
             scroll_bar_color: None,
             scroll_bar_rounded: None,
@@ -446,13 +810,37 @@ impl WindowHandle {
             window.pre_present_notify();
         }
         cx.paint_state.renderer.finish();
+
+        let frame_requests = std::mem::take(&mut self.app_state.frame_requests);
+        if !frame_requests.is_empty() {
+            exec_after(ANIMATION_FRAME, move |_| {
+                for id in frame_requests {
+                    id.request_paint();
+                }
+            });
+        }
         self.process_update();
     }
 
     pub(crate) fn process_update(&mut self) {
         let mut flags = ChangeFlags::empty();
+        let generation = stylesheet_generation();
+        if generation != self.stylesheet_generation {
+            self.stylesheet_generation = generation;
+            self.app_state.request_layout_all();
+        }
+        let generation = font_generation();
+        if generation != self.font_generation {
+            self.font_generation = generation;
+            self.app_state.request_layout_all();
+        }
         loop {
             flags |= self.process_update_messages();
+            self.update_modal_focus();
+            if self.send_focus_events() {
+                // the views may have sent update messages about it
+                continue;
+            }
             if !self.needs_layout()
                 && !self.has_deferred_update_messages()
                 && !self.has_anim_update_messages()
@@ -473,6 +861,72 @@ impl WindowHandle {
         }
     }
 
+    /// Sends the queued focus events to the views that lost or gained focus. Returns false if
+    /// there were none.
+    fn send_focus_events(&mut self) -> bool {
+        let events = std::mem::take(&mut self.app_state.focus_events);
+        if events.is_empty() {
+            return false;
+        }
+        let mut cx = EventCx {
+            app_state: &mut self.app_state,
+        };
+        for (id, event) in events {
+            let id_path = ID_PATHS.with(|paths| paths.borrow().get(&id).cloned());
+            if let Some(id_path) = id_path {
+                self.view.event_main(&mut cx, Some(&id_path.0), event);
+            }
+        }
+        true
+    }
+
+    /// Keeps keyboard focus inside the topmost modal, moving it in when a modal opens
+    /// and back to where it was when the modal closes
+    fn update_modal_focus(&mut self) {
+        let modals = open_modals(self.view.id());
+        let mut restore = None;
+        while let Some((modal, focus)) = self.modal_focus.last().copied() {
+            if modals.contains(&modal) {
+                break;
+            }
+            self.modal_focus.pop();
+            restore = Some(focus);
+        }
+        for modal in &modals {
+            if !self.modal_focus.iter().any(|(open, _)| open == modal) {
+                self.modal_focus.push((*modal, self.app_state.focus));
+            }
+        }
+
+        let scope = modals.last().copied();
+        if self.app_state.focus_scope == scope && restore.is_none() {
+            return;
+        }
+        self.app_state.focus_scope = scope;
+
+        let old = self.app_state.focus;
+        let new = restore
+            .unwrap_or(old)
+            .filter(|id| id.has_id_path() && self.app_state.in_focus_scope(*id));
+        let new = new.or_else(|| {
+            // views are made in the order they're laid out in, so this is the first one
+            scope.and_then(|_| {
+                self.app_state
+                    .keyboard_navigable
+                    .iter()
+                    .copied()
+                    .filter(|id| {
+                        !self.app_state.is_disabled(id) && self.app_state.in_focus_scope(*id)
+                    })
+                    .min_by_key(|id| id.to_raw())
+            })
+        });
+        if new != old {
+            self.app_state.focus = new;
+            self.app_state.focus_changed(old, new);
+        }
+    }
+
     fn process_central_messages(&self) {
         CENTRAL_UPDATE_MESSAGES.with(|central_msgs| {
             if !central_msgs.borrow().is_empty() {
@@ -529,7 +983,7 @@ impl WindowHandle {
                         cx.app_state.request_layout(id);
                     }
                     UpdateMessage::Focus(id) => {
-                        if cx.app_state.focus != Some(id) {
+                        if cx.app_state.focus != Some(id) && cx.app_state.in_focus_scope(id) {
                             let old = cx.app_state.focus;
                             cx.app_state.focus = Some(id);
                             cx.app_state.focus_changed(old, cx.app_state.focus);
@@ -554,13 +1008,29 @@ impl WindowHandle {
                         }
                     }
                     UpdateMessage::Disabled { id, is_disabled } => {
+                        // the views inside a disabled view are disabled along with it
+                        let in_subtree = |other: &Id| {
+                            other
+                                .id_path()
+                                .map(|path| path.0.contains(&id))
+                                .unwrap_or(false)
+                        };
                         if is_disabled {
                             cx.app_state.disabled.insert(id);
-                            cx.app_state.hovered.remove(&id);
+                            cx.app_state.hovered.retain(|hovered| !in_subtree(hovered));
+                            // a disabled view can't keep the focus
+                            if cx
+                                .app_state
+                                .focus
+                                .map(|focus| in_subtree(&focus))
+                                .unwrap_or(false)
+                            {
+                                cx.app_state.clear_focus();
+                            }
                         } else {
                             cx.app_state.disabled.remove(&id);
                         }
-                        cx.app_state.request_layout(id);
+                        cx.app_state.request_layout_subtree(id);
                     }
                     UpdateMessage::State { id, state } => {
                         let id_path = ID_PATHS.with(|paths| paths.borrow().get(&id).cloned());
@@ -578,11 +1048,28 @@ impl WindowHandle {
                         state.style = style;
                         cx.request_layout(id);
                     }
+                    UpdateMessage::DynStyle { id, style } => {
+                        let state = cx.app_state.view_state(id);
+                        state.dyn_style = Some(style);
+                        if cx.app_state.restyle(id) {
+                            flags |= ChangeFlags::PAINT;
+                        } else {
+                            cx.request_layout(id);
+                        }
+                    }
                     UpdateMessage::ResponsiveStyle { id, style, size } => {
                         let state = cx.app_state.view_state(id);
 
                         state.add_responsive_style(size, style);
                     }
+                    UpdateMessage::WidthStyle {
+                        id,
+                        key,
+                        query,
+                        style,
+                    } => {
+                        cx.app_state.set_width_style(id, key, query, style);
+                    }
                     UpdateMessage::StyleSelector {
                         id,
                         style,
@@ -681,6 +1168,11 @@ impl WindowHandle {
                         let scale = self.scale * cx.app_state.scale;
                         self.paint_state.set_scale(scale);
                     }
+                    UpdateMessage::KeyframeAnimation { id, animation } => {
+                        let state = cx.app_state.view_state(id);
+                        state.keyframe_animation = Some(animation);
+                        cx.request_layout(id);
+                    }
                     UpdateMessage::ContextMenu { id, menu } => {
                         let state = cx.app_state.view_state(id);
                         state.context_menu = Some(menu);
@@ -689,6 +1181,26 @@ impl WindowHandle {
                         let state = cx.app_state.view_state(id);
                         state.popout_menu = Some(menu);
                     }
+                    UpdateMessage::Tooltip { id, tooltip } => {
+                        let state = cx.app_state.view_state(id);
+                        state.tooltip = Some(tooltip);
+                    }
+                    UpdateMessage::Mask { id, mask } => {
+                        let state = cx.app_state.view_state(id);
+                        state.mask = Some(mask);
+                    }
+                    UpdateMessage::AddClass { id, class } => {
+                        let state = cx.app_state.view_state(id);
+                        if !state.classes.contains(&class) {
+                            state.classes.push(class);
+                        }
+                        cx.request_layout(id);
+                    }
+                    UpdateMessage::StyleId { id, style_id } => {
+                        let state = cx.app_state.view_state(id);
+                        state.style_id = Some(style_id);
+                        cx.request_layout(id);
+                    }
                     UpdateMessage::ShowContextMenu { menu, pos } => {
                         let mut menu = menu.popup();
                         let platform_menu = menu.platform_menu();
@@ -730,6 +1242,17 @@ impl WindowHandle {
                             );
                         }
                     }
+                    UpdateMessage::SetImePurpose { purpose } => {
+                        if let Some(window) = self.window.as_ref() {
+                            window.set_ime_purpose(purpose.into());
+                        }
+                    }
+                    #[cfg(feature = "webview")]
+                    UpdateMessage::WithWindow { action } => {
+                        if let Some(window) = self.window.as_ref() {
+                            action(window);
+                        }
+                    }
                 }
             }
         }
@@ -792,24 +1315,61 @@ impl WindowHandle {
         val: AnimValue,
     ) -> ChangeFlags {
         let layout = self.app_state.get_layout(view_id).unwrap();
+        let parent_size = self.parent_content_size(view_id);
         let view_state = self.app_state.view_state(view_id);
         let anim = view_state.animation.as_mut().unwrap();
         let prop = match kind {
             AnimPropKind::Scale => todo!(),
             AnimPropKind::Width => {
-                let width = layout.size.width;
-                AnimatedProp::Width {
-                    from: width as f64,
-                    to: val.get_f64(),
-                    unit: SizeUnit::Px,
+                let width = layout.size.width as f64;
+                match val {
+                    AnimValue::Pct(to) => AnimatedProp::Width {
+                        from: as_pct(width, parent_size.width),
+                        to,
+                        unit: SizeUnit::Pct,
+                    },
+                    val => AnimatedProp::Width {
+                        from: width,
+                        to: val.get_f64(),
+                        unit: SizeUnit::Px,
+                    },
                 }
             }
             AnimPropKind::Height => {
-                let height = layout.size.height;
-                AnimatedProp::Width {
-                    from: height as f64,
+                let height = layout.size.height as f64;
+                match val {
+                    AnimValue::Pct(to) => AnimatedProp::Height {
+                        from: as_pct(height, parent_size.height),
+                        to,
+                        unit: SizeUnit::Pct,
+                    },
+                    val => AnimatedProp::Height {
+                        from: height,
+                        to: val.get_f64(),
+                        unit: SizeUnit::Px,
+                    },
+                }
+            }
+            AnimPropKind::Padding => {
+                let padding = match view_state.computed_style.padding_left {
+                    PxPct::Px(padding) => padding,
+                    PxPct::Pct(pct) => parent_size.width * pct / 100.0,
+                };
+                AnimatedProp::Padding {
+                    from: padding,
+                    to: val.get_f64(),
+                }
+            }
+            AnimPropKind::Gap => {
+                let gap = match view_state.computed_style.gap.width {
+                    LengthPercentage::Points(gap) => gap as f64,
+                    LengthPercentage::Percent(fraction) => {
+                        layout.size.width as f64 * fraction as f64
+                    }
+                };
+                AnimatedProp::Gap {
+                    from: gap,
                     to: val.get_f64(),
-                    unit: SizeUnit::Px,
                 }
             }
             AnimPropKind::BorderRadius => {
@@ -859,6 +1419,35 @@ impl WindowHandle {
         ChangeFlags::LAYOUT
     }
 
+    /// The size a child's percentage width and height are resolved against,
+    /// which is the parent's size without its padding and borders
+    fn parent_content_size(&mut self, id: Id) -> Size {
+        let (parent, layout) = match id
+            .parent()
+            .and_then(|parent| Some((parent, self.app_state.get_layout(parent)?)))
+        {
+            Some(parent) => parent,
+            None => return Size::ZERO,
+        };
+        let style = &self.app_state.view_state(parent).computed_style;
+        let px = |value: PxPct| match value {
+            PxPct::Px(px) => px,
+            PxPct::Pct(_) => 0.0,
+        };
+        let horizontal = px(style.padding_left)
+            + px(style.padding_right)
+            + style.border_left.0
+            + style.border_right.0;
+        let vertical = px(style.padding_top)
+            + px(style.padding_bottom)
+            + style.border_top.0
+            + style.border_bottom.0;
+        Size::new(
+            (layout.size.width as f64 - horizontal).max(0.0),
+            (layout.size.height as f64 - vertical).max(0.0),
+        )
+    }
+
     fn needs_layout(&mut self) -> bool {
         self.app_state.view_state(self.view.id()).request_layout
     }
@@ -912,6 +1501,13 @@ impl WindowHandle {
             Some(CursorStyle::NResize) => CursorIcon::NResize,
             Some(CursorStyle::NeswResize) => CursorIcon::NeswResize,
             Some(CursorStyle::NwseResize) => CursorIcon::NwseResize,
+            Some(CursorStyle::Move) => CursorIcon::Move,
+            Some(CursorStyle::Grab) => CursorIcon::Grab,
+            Some(CursorStyle::Grabbing) => CursorIcon::Grabbing,
+            Some(CursorStyle::NotAllowed) => CursorIcon::NotAllowed,
+            Some(CursorStyle::Wait) => CursorIcon::Wait,
+            Some(CursorStyle::Crosshair) => CursorIcon::Crosshair,
+            Some(CursorStyle::Help) => CursorIcon::Help,
             None => CursorIcon::Default,
         };
         if cursor != self.app_state.last_cursor {
@@ -922,7 +1518,8 @@ impl WindowHandle {
         }
     }
 
-    fn request_paint(&self) {
+    fn request_paint(&mut self) {
+        self.needs_paint = true;
         if let Some(window) = self.window.as_ref() {
             window.request_redraw();
         }
@@ -1028,9 +1625,34 @@ fn context_menu_view(
 
     use crate::{
         app::{add_app_update_event, AppUpdateEvent},
-        views::{empty, list, svg, text},
+        views::{auto_scroll_speed, empty, list, svg, text, AUTO_SCROLL_INTERVAL_MS},
     };
 
+    /// How close to the top or bottom of the window the pointer has to be to scroll a menu
+    /// that's taller than the window
+    const MENU_AUTO_SCROLL_ZONE: f64 = 24.0;
+
+    /// Moves the menu by `speed` every tick until `speed` is set back to zero, keeping the
+    /// offset between zero and `overflow`, how much taller the menu is than the window
+    fn schedule_menu_scroll(
+        offset: RwSignal<f64>,
+        speed: RwSignal<f64>,
+        overflow: impl Fn() -> f64 + 'static,
+        scheduled: Rc<Cell<bool>>,
+    ) {
+        scheduled.set(true);
+        exec_after(Duration::from_millis(AUTO_SCROLL_INTERVAL_MS), move |_| {
+            let delta = speed.get_untracked();
+            if delta == 0.0 {
+                scheduled.set(false);
+                return;
+            }
+            let overflow_now = overflow().max(0.0);
+            offset.update(|offset| *offset = (*offset + delta).clamp(0.0, overflow_now));
+            schedule_menu_scroll(offset, speed, overflow, scheduled);
+        });
+    }
+
     #[derive(Clone, PartialEq, Eq, Hash)]
     struct MenuDisplay {
         id: Option<u64>,
@@ -1227,6 +1849,35 @@ fn context_menu_view(
         }
     }
 
+    // A scroll view would clip the submenus cascading out of the menu, so a menu taller than
+    // the window is moved up and down instead, while the pointer is near the top or the bottom
+    // of the window, the way a scroll's auto_scroll_on_hover would scroll it.
+    let scroll_offset = cx.create_rw_signal(0.0);
+    let scroll_speed = cx.create_rw_signal(0.0);
+    let scroll_scheduled = Rc::new(Cell::new(false));
+    let overflow =
+        move || context_menu_size.get_untracked().height - window_size.get_untracked().height;
+    create_effect(move |_| {
+        context_menu.with(|_| ());
+        scroll_offset.set(0.0);
+        scroll_speed.set(0.0);
+    });
+    // where the menu is in the window, opening towards the other side of the position when
+    // there isn't room for the menu, and kept inside the window if there isn't room on either side
+    let menu_origin = cx.create_memo(move |_| {
+        let window_size = window_size.get();
+        let menu_size = context_menu_size.get();
+        let mut pos = context_menu.with(|m| m.as_ref().map(|(_, pos)| *pos).unwrap_or_default());
+        if pos.x + menu_size.width > window_size.width {
+            pos.x = (pos.x - menu_size.width).max(0.0);
+        }
+        if pos.y + menu_size.height > window_size.height {
+            pos.y = (pos.y - menu_size.height).max(0.0);
+        }
+        pos.y -= scroll_offset.get();
+        pos
+    });
+
     let on_child_submenu = create_rw_signal(false);
     let view = list(
         move || context_menu_items.get().unwrap_or_default(),
@@ -1236,6 +1887,31 @@ fn context_menu_view(
     .on_resize(move |rect| {
         context_menu_size.set(rect.size());
     })
+    .on_event(EventListener::PointerMove, move |event| {
+        if let Event::PointerMove(event) = event {
+            let speed = if overflow() > 0.0 {
+                let y = menu_origin.get_untracked().y + event.pos.y;
+                let height = window_size.get_untracked().height;
+                auto_scroll_speed(y, 0.0, height, MENU_AUTO_SCROLL_ZONE)
+            } else {
+                0.0
+            };
+            scroll_speed.set(speed);
+            if speed != 0.0 && !scroll_scheduled.get() {
+                schedule_menu_scroll(
+                    scroll_offset,
+                    scroll_speed,
+                    overflow,
+                    scroll_scheduled.clone(),
+                );
+            }
+        }
+        false
+    })
+    .on_event(EventListener::PointerLeave, move |_| {
+        scroll_speed.set(0.0);
+        false
+    })
     .on_event(EventListener::PointerDown, move |_| true)
     .keyboard_navigatable()
     .on_event(EventListener::KeyDown, move |event| {
@@ -1265,16 +1941,8 @@ fn context_menu_view(
         true
     })
     .style(move |s| {
-        let window_size = window_size.get();
-        let menu_size = context_menu_size.get();
         let is_acitve = context_menu.with(|m| m.is_some());
-        let mut pos = context_menu.with(|m| m.as_ref().map(|(_, pos)| *pos).unwrap_or_default());
-        if pos.x + menu_size.width > window_size.width {
-            pos.x = window_size.width - menu_size.width;
-        }
-        if pos.y + menu_size.height > window_size.height {
-            pos.y = window_size.height - menu_size.height;
-        }
+        let pos = menu_origin.get();
         s.absolute()
             .min_width(200.0)
             .flex_col()
@@ -1302,3 +1970,12 @@ fn context_menu_view(
 
     view
 }
+
+/// `value` as a percentage of `total`
+fn as_pct(value: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        value / total * 100.0
+    } else {
+        0.0
+    }
+}
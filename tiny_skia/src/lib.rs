@@ -1,8 +1,9 @@
 use anyhow::{anyhow, Result};
 use floem_renderer::cosmic_text::{CacheKey, SubpixelBin, SwashCache, SwashContent, TextLayout};
 use floem_renderer::tiny_skia::{
-    self, FillRule, FilterQuality, GradientStop, LinearGradient, Mask, MaskType, Paint, Path,
-    PathBuilder, Pattern, Pixmap, RadialGradient, Shader, SpreadMode, Stroke, Transform,
+    self, BlendMode, FillRule, FilterQuality, GradientStop, LinearGradient, Mask, MaskType, Paint,
+    Path, PathBuilder, Pattern, Pixmap, PixmapPaint, RadialGradient, Shader, SpreadMode, Stroke,
+    Transform,
 };
 use floem_renderer::Img;
 use floem_renderer::Renderer;
@@ -35,6 +36,15 @@ struct Glyph {
 #[derive(PartialEq, Clone, Copy)]
 struct CacheColor(bool);
 
+/// A layer being drawn, in place of the pixmap it's composited onto
+struct Layer {
+    parent: Pixmap,
+    alpha: f32,
+    blur: f64,
+    /// The area of the layer that can be drawn in, in pixels
+    bounds: Rect,
+}
+
 pub struct TinySkiaRenderer {
     #[allow(unused)]
     context: Context,
@@ -44,11 +54,14 @@ pub struct TinySkiaRenderer {
     scale: f64,
     transform: Affine,
     clip: Option<Rect>,
+    layers: Vec<Layer>,
 
     /// The cache color value set for cache entries accessed this frame.
     cache_color: CacheColor,
 
     image_cache: HashMap<Vec<u8>, (CacheColor, Rc<Pixmap>)>,
+    /// The pixels drawn with [Renderer::draw_pixels], along with their version
+    pixels_cache: HashMap<Vec<u8>, (CacheColor, u64, Rc<Pixmap>)>,
     #[allow(clippy::type_complexity)]
     glyph_cache: HashMap<(CacheKey, Color), (CacheColor, Option<Rc<Glyph>>)>,
 }
@@ -83,8 +96,10 @@ impl TinySkiaRenderer {
             scale,
             transform: Affine::IDENTITY,
             clip: None,
+            layers: Vec::new(),
             cache_color: CacheColor(false),
             image_cache: Default::default(),
+            pixels_cache: Default::default(),
             glyph_cache: Default::default(),
         })
     }
@@ -116,6 +131,68 @@ fn to_point(point: Point) -> tiny_skia::Point {
     tiny_skia::Point::from_xy(point.x as f32, point.y as f32)
 }
 
+/// A blurred copy of the area of `pixmap` in `rect`, with enough around it for the blur to
+/// spread, along with where the copy is in `pixmap`
+fn blurred(pixmap: &Pixmap, rect: Rect, radius: f64) -> Option<(Pixmap, i32, i32)> {
+    let rect = rect
+        .inflate(radius * 3.0, radius * 3.0)
+        .intersect(Rect::new(
+            0.0,
+            0.0,
+            pixmap.width() as f64,
+            pixmap.height() as f64,
+        ))
+        .expand();
+    let (x, y) = (rect.x0 as i32, rect.y0 as i32);
+    let mut area = Pixmap::new(rect.width() as u32, rect.height() as u32)?;
+    area.draw_pixmap(
+        -x,
+        -y,
+        pixmap.as_ref(),
+        &PixmapPaint::default(),
+        Transform::identity(),
+        None,
+    );
+    // three box blurs are close to a gaussian blur with `radius` as its standard deviation
+    let box_radius = ((radius * 0.94).round() as usize).max(1);
+    let (width, height) = (area.width() as usize, area.height() as usize);
+    for _ in 0..3 {
+        box_blur(area.data_mut(), width, height, box_radius, true);
+        box_blur(area.data_mut(), width, height, box_radius, false);
+    }
+    Some((area, x, y))
+}
+
+/// Averages each channel of the RGBA `data` over the `radius` pixels on both sides, along
+/// the rows or the columns
+fn box_blur(data: &mut [u8], width: usize, height: usize, radius: usize, horizontal: bool) {
+    let (len, lines, step, line_step) = if horizontal {
+        (width, height, 4, width * 4)
+    } else {
+        (height, width, width * 4, 4)
+    };
+    let window = (radius * 2 + 1) as u32;
+    let mut line = vec![0u8; len * 4];
+    for l in 0..lines {
+        let start = l * line_step;
+        for i in 0..len {
+            let at = start + i * step;
+            line[i * 4..i * 4 + 4].copy_from_slice(&data[at..at + 4]);
+        }
+        for c in 0..4 {
+            // the pixels past the edges take the value of the ones on the edges
+            let value = |i: isize| line[i.clamp(0, len as isize - 1) as usize * 4 + c] as u32;
+            let radius = radius as isize;
+            let mut sum: u32 = (-radius..=radius).map(value).sum();
+            for i in 0..len as isize {
+                data[start + i as usize * step + c] = (sum / window) as u8;
+                sum += value(i + radius + 1);
+                sum -= value(i - radius);
+            }
+        }
+    }
+}
+
 impl TinySkiaRenderer {
     fn shape_to_path(&self, shape: &impl Shape) -> Option<Path> {
         let mut builder = PathBuilder::new();
@@ -195,6 +272,61 @@ impl TinySkiaRenderer {
         )
     }
 
+    /// The decoded pixels of `img`, from the cache if it was drawn before
+    fn img_pixmap(&mut self, img: &Img<'_>) -> Option<Rc<Pixmap>> {
+        if let Some((color, pixmap)) = self.image_cache.get_mut(img.hash) {
+            *color = self.cache_color;
+            return Some(pixmap.clone());
+        }
+
+        let rgba_image = image::load_from_memory(img.data).ok()?.into_rgba8();
+        let mut pixmap = Pixmap::new(rgba_image.width(), rgba_image.height())?;
+        for (a, &b) in pixmap.pixels_mut().iter_mut().zip(rgba_image.pixels()) {
+            *a = tiny_skia::Color::from_rgba8(b.0[0], b.0[1], b.0[2], b.0[3])
+                .premultiply()
+                .to_color_u8();
+        }
+
+        let pixmap = Rc::new(pixmap);
+        self.image_cache
+            .insert(img.hash.to_owned(), (self.cache_color, pixmap.clone()));
+        Some(pixmap)
+    }
+
+    /// The pixmap of the pixels of `img`, from the cache unless it's another `version` of
+    /// them, which is written over the cached pixmap when it's the same size
+    fn pixels_pixmap(
+        &mut self,
+        img: &Img<'_>,
+        version: u64,
+        width: u32,
+        height: u32,
+    ) -> Option<Rc<Pixmap>> {
+        if let Some((color, cached_version, pixmap)) = self.pixels_cache.get_mut(img.hash) {
+            *color = self.cache_color;
+            if *cached_version == version {
+                return Some(pixmap.clone());
+            }
+            if pixmap.width() == width && pixmap.height() == height {
+                if let Some(pixels) = Rc::get_mut(pixmap) {
+                    write_pixels(pixels, img.data);
+                    *cached_version = version;
+                    return Some(pixmap.clone());
+                }
+            }
+        }
+
+        let mut pixmap = Pixmap::new(width, height)?;
+        write_pixels(&mut pixmap, img.data);
+
+        let pixmap = Rc::new(pixmap);
+        self.pixels_cache.insert(
+            img.hash.to_owned(),
+            (self.cache_color, version, pixmap.clone()),
+        );
+        Some(pixmap)
+    }
+
     fn clip_rect(&self, rect: tiny_skia::Rect) -> Option<tiny_skia::Rect> {
         let clip = if let Some(clip) = self.clip {
             clip
@@ -225,8 +357,12 @@ impl TinySkiaRenderer {
         };
 
         if let Some(rect) = self.clip_rect(rect) {
-            self.pixmap
-                .fill_rect(rect, &paint, Transform::identity(), None);
+            self.pixmap.fill_rect(
+                rect,
+                &paint,
+                Transform::identity(),
+                self.clip.is_some().then_some(&self.mask),
+            );
         }
     }
 
@@ -337,9 +473,21 @@ impl TinySkiaRenderer {
     }
 }
 
+/// Writes the RGBA8 `data` over the pixels of `pixmap`
+fn write_pixels(pixmap: &mut Pixmap, data: &[u8]) {
+    for (a, b) in pixmap.pixels_mut().iter_mut().zip(data.chunks_exact(4)) {
+        *a = tiny_skia::Color::from_rgba8(b[0], b[1], b[2], b[3])
+            .premultiply()
+            .to_color_u8();
+    }
+}
+
 impl Renderer for TinySkiaRenderer {
     fn begin(&mut self) {
         self.transform = Affine::IDENTITY;
+        if let Some(layer) = self.layers.drain(..).next() {
+            self.pixmap = layer.parent;
+        }
         self.pixmap.fill(tiny_skia::Color::WHITE);
         self.clip = None;
     }
@@ -379,7 +527,12 @@ impl Renderer for TinySkiaRenderer {
         }
     }
 
-    fn draw_text(&mut self, layout: &TextLayout, pos: impl Into<Point>) {
+    fn draw_text_spaced(
+        &mut self,
+        layout: &TextLayout,
+        pos: impl Into<Point>,
+        letter_spacing: f64,
+    ) {
         let offset = self.transform.translation();
         let pos: Point = pos.into();
         let clip = self.clip;
@@ -394,8 +547,9 @@ impl Renderer for TinySkiaRenderer {
                 }
             }
 
-            'line_loop: for glyph_run in line.glyphs {
-                let x = glyph_run.x + pos.x as f32 + offset.x as f32;
+            'line_loop: for (i, glyph_run) in line.glyphs.iter().enumerate() {
+                let spacing = (letter_spacing * i as f64) as f32;
+                let x = glyph_run.x + spacing + pos.x as f32 + offset.x as f32;
                 let y = line.line_y + pos.y as f32 + offset.y as f32;
 
                 if let Some(rect) = clip {
@@ -436,25 +590,14 @@ impl Renderer for TinySkiaRenderer {
 
     fn draw_img(&mut self, img: Img<'_>, _img_width: u32, _img_height: u32, rect: Rect) {
         let rect = try_ret!(self.rect(rect));
-        if let Some((color, pixmap)) = self.image_cache.get_mut(img.hash) {
-            *color = self.cache_color;
-            let pixmap = pixmap.clone();
-            self.render_pixmap_rect(&pixmap, rect);
-            return;
-        }
-
-        let rgba_image = try_ret!(image::load_from_memory(img.data).ok()).into_rgba8();
-        let mut pixmap = try_ret!(Pixmap::new(rgba_image.width(), rgba_image.height()));
-        for (a, &b) in pixmap.pixels_mut().iter_mut().zip(rgba_image.pixels()) {
-            *a = tiny_skia::Color::from_rgba8(b.0[0], b.0[1], b.0[2], b.0[3])
-                .premultiply()
-                .to_color_u8();
-        }
-
+        let pixmap = try_ret!(self.img_pixmap(&img));
         self.render_pixmap_rect(&pixmap, rect);
+    }
 
-        self.image_cache
-            .insert(img.hash.to_owned(), (self.cache_color, Rc::new(pixmap)));
+    fn draw_pixels(&mut self, img: Img<'_>, version: u64, width: u32, height: u32, rect: Rect) {
+        let rect = try_ret!(self.rect(rect));
+        let pixmap = try_ret!(self.pixels_pixmap(&img, version, width, height));
+        self.render_pixmap_rect(&pixmap, rect);
     }
 
     fn draw_svg<'b>(
@@ -521,9 +664,140 @@ impl Renderer for TinySkiaRenderer {
         self.clip = None;
     }
 
+    fn intersect_clip(&mut self, shape: &impl Shape) {
+        let offset = self.transform.translation();
+        let rect = shape.bounding_box() + offset;
+        let path = try_ret!(self.shape_to_path(shape));
+        // anti-aliased, unlike `clip`, since masks are usually curved
+        if let Some(clip) = self.clip {
+            self.clip = Some(clip.intersect(rect));
+            self.mask
+                .intersect_path(&path, FillRule::Winding, true, self.current_transform());
+        } else {
+            self.clip = Some(rect);
+            self.mask.clear();
+            self.mask
+                .fill_path(&path, FillRule::Winding, true, self.current_transform());
+        }
+    }
+
+    fn intersect_clip_img(&mut self, img: Img<'_>, rect: Rect) {
+        let pixmap = try_ret!(self.img_pixmap(&img));
+        let mut layer = try_ret!(Pixmap::new(self.mask.width(), self.mask.height()));
+        let paint = Paint {
+            shader: Pattern::new(
+                (*pixmap).as_ref(),
+                SpreadMode::Pad,
+                FilterQuality::Bilinear,
+                1.0,
+                Transform::from_scale(
+                    rect.width() as f32 / pixmap.width() as f32,
+                    rect.height() as f32 / pixmap.height() as f32,
+                )
+                .post_translate(rect.x0 as f32, rect.y0 as f32),
+            ),
+            ..Default::default()
+        };
+        layer.fill_rect(
+            try_ret!(self.rect(rect)),
+            &paint,
+            self.current_transform(),
+            None,
+        );
+        let img_mask = Mask::from_pixmap(layer.as_ref(), MaskType::Alpha);
+
+        let offset = self.transform.translation();
+        let rect = rect + offset;
+        if let Some(clip) = self.clip {
+            self.clip = Some(clip.intersect(rect));
+            for (coverage, img_coverage) in self.mask.data_mut().iter_mut().zip(img_mask.data()) {
+                *coverage = (*coverage as u16 * *img_coverage as u16 / 255) as u8;
+            }
+        } else {
+            self.clip = Some(rect);
+            self.mask = img_mask;
+        }
+    }
+
+    fn push_layer(&mut self, alpha: f32, blur: f64) {
+        let pixmap = try_ret!(Pixmap::new(self.pixmap.width(), self.pixmap.height()));
+        let bounds = match self.clip {
+            Some(clip) => clip.scale_from_origin(self.scale),
+            None => Rect::new(0.0, 0.0, pixmap.width() as f64, pixmap.height() as f64),
+        };
+        let parent = std::mem::replace(&mut self.pixmap, pixmap);
+        self.layers.push(Layer {
+            parent,
+            alpha,
+            blur: blur * self.scale,
+            bounds,
+        });
+    }
+
+    fn pop_layer(&mut self) {
+        let layer = try_ret!(self.layers.pop());
+        let mut pixmap = std::mem::replace(&mut self.pixmap, layer.parent);
+        if layer.blur > 0.0 {
+            if let Some((blurred, x, y)) = blurred(&pixmap, layer.bounds, layer.blur) {
+                pixmap.draw_pixmap(
+                    x,
+                    y,
+                    blurred.as_ref(),
+                    &PixmapPaint {
+                        blend_mode: BlendMode::Source,
+                        ..Default::default()
+                    },
+                    Transform::identity(),
+                    None,
+                );
+            }
+        }
+        // the layer was already clipped while it was drawn
+        self.pixmap.draw_pixmap(
+            0,
+            0,
+            pixmap.as_ref(),
+            &PixmapPaint {
+                opacity: layer.alpha,
+                ..Default::default()
+            },
+            Transform::identity(),
+            None,
+        );
+    }
+
+    fn blur_backdrop(&mut self, shape: &impl Shape, radius: f64) {
+        let path = try_ret!(self.shape_to_path(shape));
+        let bounds = self
+            .transform
+            .transform_rect_bbox(shape.bounding_box())
+            .scale_from_origin(self.scale);
+        let (blurred, x, y) = try_ret!(blurred(&self.pixmap, bounds, radius * self.scale));
+        let transform = try_ret!(self.current_transform().invert());
+        let paint = Paint {
+            shader: Pattern::new(
+                blurred.as_ref(),
+                SpreadMode::Pad,
+                FilterQuality::Nearest,
+                1.0,
+                transform.pre_translate(x as f32, y as f32),
+            ),
+            ..Default::default()
+        };
+        self.pixmap.fill_path(
+            &path,
+            &paint,
+            FillRule::Winding,
+            self.current_transform(),
+            self.clip.is_some().then_some(&self.mask),
+        );
+    }
+
     fn finish(&mut self) {
         // Remove cache entries which were not accessed.
         self.image_cache.retain(|_, (c, _)| *c == self.cache_color);
+        self.pixels_cache
+            .retain(|_, (c, _, _)| *c == self.cache_color);
         self.glyph_cache.retain(|_, (c, _)| *c == self.cache_color);
 
         // Swap the cache color.
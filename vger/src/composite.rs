@@ -0,0 +1,487 @@
+use floem_renderer::tiny_skia::Mask;
+use peniko::kurbo::{Affine, Rect, Size};
+use wgpu::util::DeviceExt;
+use wgpu::{Device, Queue, TextureFormat};
+
+/// A texture the size of the surface that's drawn into, by vger and by the [Compositor]
+pub struct Target {
+    pub texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    pub size: Size,
+}
+
+impl Target {
+    pub fn new(device: &Device, format: TextureFormat, width: u32, height: u32) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("floem target"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            texture,
+            view,
+            size: Size::new(width as f64, height as f64),
+        }
+    }
+
+    pub fn texture(&self) -> TextureRef<'_> {
+        TextureRef {
+            view: &self.view,
+            size: self.size,
+        }
+    }
+
+    pub fn clear(&self, device: &Device, queue: &Queue, color: wgpu::Color) {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("floem clear"),
+        });
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &self.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(color),
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        });
+        queue.submit(Some(encoder.finish()));
+    }
+
+    /// An alpha mask of the pixels of `mask`, to draw a target in with
+    pub fn mask_texture(device: &Device, queue: &Queue, mask: &Mask) -> wgpu::TextureView {
+        let size = wgpu::Extent3d {
+            width: mask.width(),
+            height: mask.height(),
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("floem mask"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            texture.as_image_copy(),
+            mask.data(),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(mask.width()),
+                rows_per_image: Some(mask.height()),
+            },
+            size,
+        );
+        texture.create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    /// Copies what's drawn in `source`, which is the same size
+    pub fn copy_from(&self, device: &Device, queue: &Queue, source: &Target) {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("floem copy"),
+        });
+        encoder.copy_texture_to_texture(
+            source.texture.as_image_copy(),
+            self.texture.as_image_copy(),
+            wgpu::Extent3d {
+                width: self.size.width as u32,
+                height: self.size.height as u32,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(Some(encoder.finish()));
+    }
+}
+
+/// The pixels drawn with [draw_pixels](floem_renderer::Renderer::draw_pixels), as a texture
+/// that's written over by the next version of them
+pub struct Pixels {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    size: Size,
+    pub version: u64,
+    /// Whether they were drawn this frame
+    pub used: bool,
+}
+
+impl Pixels {
+    pub fn new(device: &Device, width: u32, height: u32, version: u64) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("floem pixels"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            texture,
+            view,
+            size: Size::new(width as f64, height as f64),
+            version,
+            used: true,
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Writes the RGBA8 `data` over the pixels
+    pub fn write(&self, queue: &Queue, data: &[u8]) {
+        let (width, height) = (self.size.width as u32, self.size.height as u32);
+        queue.write_texture(
+            self.texture.as_image_copy(),
+            data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    pub fn texture(&self) -> TextureRef<'_> {
+        TextureRef {
+            view: &self.view,
+            size: self.size,
+        }
+    }
+}
+
+/// A texture the [Compositor] draws from or into, along with its size in pixels
+#[derive(Clone, Copy)]
+pub struct TextureRef<'a> {
+    pub view: &'a wgpu::TextureView,
+    pub size: Size,
+}
+
+/// How a texture is drawn onto a target
+#[derive(Clone, Copy)]
+pub struct Composite<'a> {
+    /// Maps the pixels of the texture to the ones of the target
+    pub transform: Affine,
+    /// The part of the texture that's drawn, in its pixels
+    pub source: Rect,
+    /// The part of the target that can be drawn in, in its pixels
+    pub scissor: Option<Rect>,
+    pub alpha: f32,
+    /// The standard deviation of the blur, in pixels, which only
+    /// [draw_blurred](Compositor::draw_blurred) blurs by
+    pub blur: f64,
+    /// The alpha the target is drawn in with, which is the size of the target
+    pub mask: Option<&'a wgpu::TextureView>,
+    /// Whether the alpha of the texture isn't premultiplied, like the pixels of an image
+    pub straight_alpha: bool,
+}
+
+impl Composite<'_> {
+    /// Draws all of a texture of `size` where it is, as it is
+    pub fn copy(size: Size) -> Self {
+        Self {
+            transform: Affine::IDENTITY,
+            source: size.to_rect(),
+            scissor: None,
+            alpha: 1.0,
+            blur: 0.0,
+            mask: None,
+            straight_alpha: false,
+        }
+    }
+}
+
+/// Draws textures onto targets, with the premultiplied alpha that vger draws with, for what
+/// vger can't draw itself
+pub struct Compositor {
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+}
+
+impl Compositor {
+    pub fn new(device: &Device, format: TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("floem composite"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("composite.wgsl").into()),
+        });
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("floem composite"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("floem composite"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("floem composite"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("floem composite"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        Self {
+            pipeline,
+            layout,
+            sampler,
+        }
+    }
+
+    /// Draws `source` onto `target`, without blurring it
+    pub fn draw(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        source: TextureRef<'_>,
+        target: TextureRef<'_>,
+        composite: &Composite,
+    ) {
+        self.pass(device, queue, source, target, composite, (0.0, 0.0));
+    }
+
+    /// Draws `source` onto `target` blurred by the blur of `composite`, first across into
+    /// `scratch`, which is the same size as `source`, then down into `target`
+    pub fn draw_blurred(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        source: TextureRef<'_>,
+        scratch: &Target,
+        target: TextureRef<'_>,
+        composite: &Composite,
+    ) {
+        if composite.blur <= 0.0 {
+            return self.draw(device, queue, source, target, composite);
+        }
+        // what's around the part that's drawn is blurred into it
+        let source_rect = composite
+            .source
+            .inflate(composite.blur * 3.0, composite.blur * 3.0)
+            .intersect(source.size.to_rect())
+            .expand();
+        scratch.clear(device, queue, wgpu::Color::TRANSPARENT);
+        let across = Composite {
+            transform: Affine::IDENTITY,
+            source: source_rect,
+            scissor: None,
+            alpha: 1.0,
+            blur: composite.blur,
+            mask: None,
+            straight_alpha: composite.straight_alpha,
+        };
+        self.pass(
+            device,
+            queue,
+            source,
+            scratch.texture(),
+            &across,
+            (1.0, 0.0),
+        );
+        let down = Composite {
+            source: source_rect,
+            ..*composite
+        };
+        self.pass(device, queue, scratch.texture(), target, &down, (0.0, 1.0));
+    }
+
+    fn pass(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        source: TextureRef<'_>,
+        target: TextureRef<'_>,
+        composite: &Composite,
+        direction: (f64, f64),
+    ) {
+        let target_size = target.size;
+        let scissor = composite
+            .scissor
+            .unwrap_or(target_size.to_rect())
+            .intersect(target_size.to_rect())
+            .expand();
+        if scissor.width() <= 0.0 || scissor.height() <= 0.0 {
+            return;
+        }
+
+        let [a, b, c, d, e, f] = composite.transform.as_coeffs();
+        let source_rect = composite.source;
+        let params = [
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            target_size.width,
+            target_size.height,
+            source_rect.x0,
+            source_rect.y0,
+            source_rect.x1,
+            source_rect.y1,
+            source.size.width,
+            source.size.height,
+            if composite.mask.is_some() { 1.0 } else { 0.0 },
+            if composite.straight_alpha { 1.0 } else { 0.0 },
+            direction.0,
+            direction.1,
+            if direction == (0.0, 0.0) {
+                0.0
+            } else {
+                composite.blur
+            },
+            composite.alpha as f64,
+        ];
+        let params: Vec<u8> = params
+            .iter()
+            .flat_map(|value| (*value as f32).to_ne_bytes())
+            .collect();
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("floem composite"),
+            contents: &params,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("floem composite"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(source.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                // the source stands in for the mask when there's none, since it isn't sampled
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(
+                        composite.mask.unwrap_or(source.view),
+                    ),
+                },
+            ],
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("floem composite"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.set_scissor_rect(
+                scissor.x0 as u32,
+                scissor.y0 as u32,
+                scissor.width() as u32,
+                scissor.height() as u32,
+            );
+            pass.draw(0..6, 0..1);
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+}
@@ -0,0 +1,93 @@
+// Draws a rectangle of a texture onto a target, through an affine transform
+
+struct Params {
+    // x' = a x + c y + e and y' = b x + d y + f, as a b c d
+    matrix: vec4<f32>,
+    // e f, then the size of the target
+    offset: vec4<f32>,
+    // the rectangle of the texture that's drawn as x0 y0 x1 y1, in its pixels
+    source: vec4<f32>,
+    // the size of the texture, then 1 when it's drawn through the mask and 1 when its alpha
+    // isn't premultiplied
+    sizes: vec4<f32>,
+    // the direction it's blurred in and the standard deviation of the blur in pixels, then
+    // the alpha it's drawn with
+    effect: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> params: Params;
+@group(0) @binding(1)
+var source_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var source_sampler: sampler;
+// the alpha the target is drawn in with, which covers all of the target
+@group(0) @binding(3)
+var mask_texture: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let point = mix(params.source.xy, params.source.zw, corners[index]);
+    let target_point = vec2<f32>(
+        params.matrix.x * point.x + params.matrix.z * point.y + params.offset.x,
+        params.matrix.y * point.x + params.matrix.w * point.y + params.offset.y,
+    );
+    var output: VertexOutput;
+    output.position = vec4<f32>(
+        target_point.x / params.offset.z * 2.0 - 1.0,
+        1.0 - target_point.y / params.offset.w * 2.0,
+        0.0,
+        1.0,
+    );
+    output.uv = point / params.sizes.xy;
+    return output;
+}
+
+// a gaussian blur along the direction, with at most 64 samples on each side
+fn blurred(uv: vec2<f32>) -> vec4<f32> {
+    let sigma = params.effect.z;
+    let reach = ceil(sigma * 3.0);
+    let taps = min(reach, 64.0);
+    let stride = reach / taps;
+    let step = params.effect.xy / params.sizes.xy * stride;
+    var color = vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    var total = 0.0;
+    for (var i: f32 = -taps; i <= taps; i = i + 1.0) {
+        let x = i * stride;
+        let weight = exp(-x * x / (2.0 * sigma * sigma));
+        color = color + textureSampleLevel(source_texture, source_sampler, uv + step * i, 0.0) * weight;
+        total = total + weight;
+    }
+    return color / total;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    var color: vec4<f32>;
+    if params.effect.z > 0.0 {
+        color = blurred(input.uv);
+    } else {
+        color = textureSampleLevel(source_texture, source_sampler, input.uv, 0.0);
+    }
+    if params.sizes.w > 0.0 {
+        color = vec4<f32>(color.rgb * color.a, color.a);
+    }
+    if params.sizes.z > 0.0 {
+        let mask_uv = input.position.xy / params.offset.zw;
+        color = color * textureSampleLevel(mask_texture, source_sampler, mask_uv, 0.0).r;
+    }
+    return color * params.effect.w;
+}
@@ -1,11 +1,19 @@
+mod composite;
+
+use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::Arc;
 
 use anyhow::Result;
+use composite::{Composite, Compositor, Pixels, Target, TextureRef};
 use floem_renderer::cosmic_text::{SubpixelBin, SwashCache, TextLayout};
-use floem_renderer::{tiny_skia, Img, Renderer};
+use floem_renderer::tiny_skia::{
+    self, FillRule, FilterQuality, Mask, MaskType, Paint, PathBuilder, Pattern, Pixmap, SpreadMode,
+};
+use floem_renderer::{Img, Renderer};
 use image::EncodableLayout;
 use peniko::{
-    kurbo::{Affine, Point, Rect, Shape, Vec2},
+    kurbo::{Affine, PathEl, Point, Rect, RoundedRect, Shape, Size, Vec2},
     BrushRef, Color, GradientKind,
 };
 use vger::{Image, PaintIndex, PixelFormat, Vger};
@@ -13,14 +21,67 @@ use wgpu::{Device, DeviceType, Queue, Surface, SurfaceConfiguration, TextureForm
 
 pub struct VgerRenderer {
     device: Arc<Device>,
-    #[allow(unused)]
     queue: Arc<Queue>,
     surface: Surface,
     vger: Vger,
     config: SurfaceConfiguration,
     scale: f64,
     transform: Affine,
-    clip: Option<Rect>,
+    /// `transform` into the pixels of the target being drawn into, which only ever moves and
+    /// evenly scales, since that's all vger can draw with
+    px_transform: Affine,
+    /// The clip in the pixels of the target being drawn into, with its corner radius
+    clip: Option<(Rect, f64)>,
+    /// The alpha mask of the clip, when it isn't a rounded rectangle, in the pixels of the
+    /// target being drawn into
+    mask: Option<Rc<Mask>>,
+    /// The images masked with, and whether they were masked with this frame
+    mask_images: HashMap<Vec<u8>, (bool, Rc<Pixmap>)>,
+    /// The pixels drawn with [Renderer::draw_pixels], by their hash
+    pixels: HashMap<Vec<u8>, Pixels>,
+    z_index: i32,
+    /// Whether vger drew anything since it began
+    drawn: bool,
+    compositor: Compositor,
+    /// What a frame is drawn into, before it's copied onto the surface
+    frame: Target,
+    /// The groups being drawn, each into a target of its own that's composited onto the one
+    /// under it when it ends. vger is drawn into a target before each group starts and ends,
+    /// so the z_index only orders what's drawn in between.
+    groups: Vec<Group>,
+    /// The targets of the groups that ended, to draw the next ones into
+    spare_targets: Vec<Target>,
+}
+
+struct Group {
+    target: Target,
+    kind: GroupKind,
+}
+
+enum GroupKind {
+    /// Drawn with a transform that rotates, skews or scales unevenly, which vger can't draw
+    /// with, so the group is drawn upright and transformed when it's composited
+    Transform {
+        /// Maps the pixels of the group to the ones of the target under it
+        to_parent: Affine,
+        /// The clip of the target under the group, which is restored when it ends
+        parent_clip: Option<(Rect, f64)>,
+        parent_mask: Option<Rc<Mask>>,
+    },
+    /// Drawn with a clip that isn't a rounded rectangle, which vger can't scissor to, so the
+    /// group is composited through the mask of the clip
+    Mask {
+        mask: Rc<Mask>,
+        texture: wgpu::TextureView,
+    },
+    /// A layer that's blurred by `blur` and composited with `alpha` as a whole
+    Layer {
+        alpha: f32,
+        /// The standard deviation of the blur, in pixels
+        blur: f64,
+        /// The part of the target that can be drawn in
+        bounds: Rect,
+    },
 }
 
 impl VgerRenderer {
@@ -78,6 +139,8 @@ impl VgerRenderer {
         surface.configure(&device, &config);
 
         let vger = vger::Vger::new(device.clone(), queue.clone(), texture_format);
+        let compositor = Compositor::new(&device, texture_format);
+        let frame = Target::new(&device, texture_format, width, height);
 
         Ok(Self {
             device,
@@ -87,7 +150,17 @@ impl VgerRenderer {
             scale,
             config,
             transform: Affine::IDENTITY,
+            px_transform: Affine::scale(scale),
             clip: None,
+            mask: None,
+            mask_images: HashMap::new(),
+            pixels: HashMap::new(),
+            z_index: 0,
+            drawn: false,
+            compositor,
+            frame,
+            groups: Vec::new(),
+            spare_targets: Vec::new(),
         })
     }
 
@@ -130,12 +203,13 @@ impl VgerRenderer {
     }
 
     fn vger_point(&self, point: Point) -> vger::defs::LocalPoint {
-        let coeffs = self.transform.as_coeffs();
-        let point = point + Vec2::new(coeffs[4], coeffs[5]);
-        vger::defs::LocalPoint::new(
-            (point.x * self.scale).round() as f32,
-            (point.y * self.scale).round() as f32,
-        )
+        let point = self.px_transform * point;
+        vger::defs::LocalPoint::new(point.x.round() as f32, point.y.round() as f32)
+    }
+
+    /// How much `px_transform` scales by
+    fn px_scale(&self) -> f64 {
+        self.px_transform.as_coeffs()[0]
     }
 
     fn vger_rect(&self, rect: Rect) -> vger::defs::LocalRect {
@@ -148,24 +222,321 @@ impl VgerRenderer {
         let size = (end - origin).to_size();
         vger::defs::LocalRect::new(origin, size)
     }
-}
 
-impl Renderer for VgerRenderer {
-    fn begin(&mut self) {
-        self.transform = Affine::IDENTITY;
+    fn target(&self) -> &Target {
+        self.groups
+            .last()
+            .map(|group| &group.target)
+            .unwrap_or(&self.frame)
+    }
+
+    /// Begins drawing with vger again, with the clip and z_index it had
+    fn begin_vger(&mut self) {
         self.vger.begin(
             self.config.width as f32,
             self.config.height as f32,
             self.scale as f32,
         );
+        self.vger.set_z_index(self.z_index);
+        self.scissor();
+        self.drawn = false;
+    }
+
+    /// Scissors vger to the clip
+    fn scissor(&mut self) {
+        if let Some((rect, radius)) = self.clip {
+            let origin = vger::defs::LocalPoint::new(rect.x0 as f32, rect.y0 as f32);
+            let end = vger::defs::LocalPoint::new(rect.x1 as f32, rect.y1 as f32);
+            self.vger.scissor(
+                vger::defs::LocalRect::new(origin, (end - origin).to_size()),
+                radius as f32,
+            );
+        } else {
+            self.vger.reset_scissor();
+        }
+    }
+
+    /// Draws what vger has so far into the target it's drawing into
+    fn flush(&mut self) {
+        if !self.drawn {
+            return;
+        }
+        let target = self
+            .groups
+            .last()
+            .map(|group| &group.target)
+            .unwrap_or(&self.frame);
+        let desc = wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        };
+        self.vger.encode(&desc);
+        self.begin_vger();
+    }
+
+    fn take_target(&mut self) -> Target {
+        self.spare_targets.pop().unwrap_or_else(|| {
+            Target::new(
+                &self.device,
+                self.config.format,
+                self.config.width,
+                self.config.height,
+            )
+        })
+    }
+
+    fn start_group(&mut self, kind: GroupKind) {
+        self.flush();
+        let target = self.take_target();
+        target.clear(&self.device, &self.queue, wgpu::Color::TRANSPARENT);
+        self.groups.push(Group { target, kind });
+        self.begin_vger();
+    }
+
+    /// Composites the last group onto the target under it
+    fn end_group(&mut self) {
+        self.flush();
+        let group = match self.groups.pop() {
+            Some(group) => group,
+            None => return,
+        };
+        let composite = match group.kind {
+            GroupKind::Transform {
+                to_parent,
+                parent_clip,
+                parent_mask,
+            } => {
+                self.clip = parent_clip;
+                self.mask = parent_mask;
+                Composite {
+                    transform: to_parent,
+                    source: group.target.size.to_rect(),
+                    scissor: parent_clip.map(|(rect, _)| rect),
+                    ..Composite::copy(group.target.size)
+                }
+            }
+            // the layer was already clipped while it was drawn
+            GroupKind::Layer {
+                alpha,
+                blur,
+                bounds,
+            } => Composite {
+                source: bounds,
+                alpha,
+                blur,
+                ..Composite::copy(group.target.size)
+            },
+            GroupKind::Mask { ref texture, .. } => Composite {
+                mask: Some(texture),
+                ..Composite::copy(group.target.size)
+            },
+        };
+        self.draw_group(&group.target, &composite);
+        self.spare_targets.push(group.target);
+        self.begin_vger();
+    }
+
+    /// Draws `source` onto the target being drawn into, blurred if `composite` blurs
+    fn draw_group(&mut self, source: &Target, composite: &Composite) {
+        if composite.blur > 0.0 {
+            let scratch = self.take_target();
+            self.compositor.draw_blurred(
+                &self.device,
+                &self.queue,
+                source.texture(),
+                &scratch,
+                self.target().texture(),
+                composite,
+            );
+            self.spare_targets.push(scratch);
+        } else {
+            self.compositor.draw(
+                &self.device,
+                &self.queue,
+                source.texture(),
+                self.target().texture(),
+                composite,
+            );
+        }
+    }
+
+    /// Starts the mask group of the mask of the clip, after ending the one of the previous
+    /// mask, so what's drawn next is masked by it
+    fn sync_mask(&mut self) {
+        loop {
+            // the mask that's drawn through already, from the groups in the same pixels
+            let masked = self
+                .groups
+                .iter()
+                .rev()
+                .take_while(|group| !matches!(group.kind, GroupKind::Transform { .. }))
+                .find_map(|group| match &group.kind {
+                    GroupKind::Mask { mask, .. } => Some(mask),
+                    _ => None,
+                });
+            let same = match (masked, &self.mask) {
+                (Some(masked), Some(mask)) => {
+                    Rc::ptr_eq(masked, mask) || masked.data() == mask.data()
+                }
+                (None, None) => true,
+                _ => false,
+            };
+            if same {
+                return;
+            }
+            if matches!(
+                self.groups.last(),
+                Some(Group {
+                    kind: GroupKind::Mask { .. },
+                    ..
+                })
+            ) {
+                self.end_group();
+                continue;
+            }
+            // a mask that's drawn through under a layer can't be left out until it ends
+            if let Some(mask) = self.mask.clone() {
+                let texture = Target::mask_texture(&self.device, &self.queue, &mask);
+                self.start_group(GroupKind::Mask { mask, texture });
+            }
+            return;
+        }
+    }
+
+    /// The mask of the clip, with its rounded corners when it has them, or none when it
+    /// doesn't clip
+    fn clip_mask(&self) -> Option<Mask> {
+        if let Some(mask) = self.mask.as_ref() {
+            return Some((**mask).clone());
+        }
+        let (rect, radius) = self.clip?;
+        let size = self.target().size;
+        let mut mask = Mask::new(size.width as u32, size.height as u32)?;
+        let path = shape_to_path(&RoundedRect::from_rect(rect, radius))?;
+        mask.fill_path(
+            &path,
+            FillRule::Winding,
+            true,
+            tiny_skia::Transform::identity(),
+        );
+        Some(mask)
+    }
+
+    /// Clips to `rect`, in pixels, and the alpha of `mask`
+    fn set_clip_mask(&mut self, rect: Rect, mask: Mask) {
+        let rect = match self.clip {
+            Some((clip, _)) => rect.intersect(clip),
+            None => rect,
+        };
+        self.clip = Some((rect, 0.0));
+        self.mask = Some(Rc::new(mask));
+        self.scissor();
+    }
+
+    fn skia_transform(&self) -> tiny_skia::Transform {
+        let [a, b, c, d, e, f] = self.px_transform.as_coeffs();
+        tiny_skia::Transform::from_row(a as f32, b as f32, c as f32, d as f32, e as f32, f as f32)
+    }
+
+    /// The decoded pixels of the image `img`
+    fn mask_image(&mut self, img: &Img<'_>) -> Option<Rc<Pixmap>> {
+        if let Some((used, pixmap)) = self.mask_images.get_mut(img.hash) {
+            *used = true;
+            return Some(pixmap.clone());
+        }
+        let rgba_image = image::load_from_memory(img.data).ok()?.into_rgba8();
+        let mut pixmap = Pixmap::new(rgba_image.width(), rgba_image.height())?;
+        for (a, &b) in pixmap.pixels_mut().iter_mut().zip(rgba_image.pixels()) {
+            *a = tiny_skia::Color::from_rgba8(b.0[0], b.0[1], b.0[2], b.0[3])
+                .premultiply()
+                .to_color_u8();
+        }
+        let pixmap = Rc::new(pixmap);
+        self.mask_images
+            .insert(img.hash.to_owned(), (true, pixmap.clone()));
+        Some(pixmap)
+    }
+
+    /// Maps the pixels of the target being drawn into to the ones of the frame
+    fn to_frame(&self) -> Affine {
+        self.groups
+            .iter()
+            .fold(Affine::IDENTITY, |to_frame, group| match group.kind {
+                GroupKind::Transform { to_parent, .. } => to_frame * to_parent,
+                GroupKind::Layer { .. } | GroupKind::Mask { .. } => to_frame,
+            })
+    }
+}
+
+fn shape_to_path(shape: &impl Shape) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+    for element in shape.path_elements(0.1) {
+        match element {
+            PathEl::ClosePath => builder.close(),
+            PathEl::MoveTo(p) => builder.move_to(p.x as f32, p.y as f32),
+            PathEl::LineTo(p) => builder.line_to(p.x as f32, p.y as f32),
+            PathEl::QuadTo(p1, p2) => {
+                builder.quad_to(p1.x as f32, p1.y as f32, p2.x as f32, p2.y as f32)
+            }
+            PathEl::CurveTo(p1, p2, p3) => builder.cubic_to(
+                p1.x as f32,
+                p1.y as f32,
+                p2.x as f32,
+                p2.y as f32,
+                p3.x as f32,
+                p3.y as f32,
+            ),
+        }
+    }
+    builder.finish()
+}
+
+/// Whether `transform` only moves and evenly scales
+fn is_upright(transform: Affine) -> bool {
+    let [a, b, c, d, _, _] = transform.as_coeffs();
+    let epsilon = a.abs() * 1e-6;
+    a > 0.0 && b.abs() <= epsilon && c.abs() <= epsilon && (a - d).abs() <= epsilon
+}
+
+impl Renderer for VgerRenderer {
+    fn begin(&mut self) {
+        self.transform = Affine::IDENTITY;
+        self.px_transform = Affine::scale(self.scale);
+        self.clip = None;
+        self.mask = None;
+        self.spare_targets
+            .extend(self.groups.drain(..).map(|group| group.target));
+        let size = Size::new(self.config.width as f64, self.config.height as f64);
+        if self.frame.size != size {
+            self.frame = Target::new(
+                &self.device,
+                self.config.format,
+                self.config.width,
+                self.config.height,
+            );
+            self.spare_targets.clear();
+        }
+        self.frame
+            .clear(&self.device, &self.queue, wgpu::Color::WHITE);
+        self.begin_vger();
     }
 
     fn stroke<'b>(&mut self, shape: &impl Shape, brush: impl Into<BrushRef<'b>>, width: f64) {
+        self.sync_mask();
         let paint = match self.brush_to_paint(brush) {
             Some(paint) => paint,
             None => return,
         };
-        let width = (width * self.scale).round() as f32;
+        self.drawn = true;
+        let width = (width * self.px_scale()).round() as f32;
         if let Some(rect) = shape.as_rect() {
             let min = rect.origin();
             let max = min + rect.size().to_vec2();
@@ -179,7 +550,7 @@ impl Renderer for VgerRenderer {
         } else if let Some(rect) = shape.as_rounded_rect() {
             let min = rect.origin();
             let max = min + rect.rect().size().to_vec2();
-            let radius = (rect.radii().top_left * self.scale) as f32;
+            let radius = (rect.radii().top_left * self.px_scale()) as f32;
             self.vger.stroke_rect(
                 self.vger_point(min),
                 self.vger_point(max),
@@ -214,28 +585,31 @@ impl Renderer for VgerRenderer {
     }
 
     fn fill<'b>(&mut self, path: &impl Shape, brush: impl Into<BrushRef<'b>>, blur_radius: f64) {
+        self.sync_mask();
         let paint = match self.brush_to_paint(brush) {
             Some(paint) => paint,
             None => return,
         };
+        self.drawn = true;
+        let scale = self.px_scale();
         if let Some(rect) = path.as_rect() {
             self.vger.fill_rect(
                 self.vger_rect(rect),
                 0.0,
                 paint,
-                (blur_radius * self.scale) as f32,
+                (blur_radius * scale) as f32,
             );
         } else if let Some(rect) = path.as_rounded_rect() {
             self.vger.fill_rect(
                 self.vger_rect(rect.rect()),
-                (rect.radii().top_left * self.scale) as f32,
+                (rect.radii().top_left * scale) as f32,
                 paint,
-                (blur_radius * self.scale) as f32,
+                (blur_radius * scale) as f32,
             );
         } else if let Some(circle) = path.as_circle() {
             self.vger.fill_circle(
                 self.vger_point(circle.center),
-                (circle.radius * self.scale) as f32,
+                (circle.radius * scale) as f32,
                 paint,
             )
         } else {
@@ -265,44 +639,50 @@ impl Renderer for VgerRenderer {
         }
     }
 
-    fn draw_text(&mut self, layout: &TextLayout, pos: impl Into<Point>) {
+    fn draw_text_spaced(
+        &mut self,
+        layout: &TextLayout,
+        pos: impl Into<Point>,
+        letter_spacing: f64,
+    ) {
+        self.sync_mask();
         let mut swash_cache = SwashCache::new();
-        let transform = self.transform.as_coeffs();
-        let offset = Vec2::new(transform[4], transform[5]);
+        let transform = self.px_transform;
+        let scale = self.px_scale();
         let pos: Point = pos.into();
-        let clip = self.clip;
+        let clip = self.clip.map(|(rect, _)| rect);
+        self.drawn = true;
         for line in layout.layout_runs() {
             if let Some(rect) = clip {
-                let y = pos.y + offset.y + line.line_y as f64;
-                if y + (line.line_height as f64) < rect.y0 {
+                let y = (transform * Point::new(pos.x, pos.y + line.line_y as f64)).y;
+                let line_height = line.line_height as f64 * scale;
+                if y + line_height < rect.y0 {
                     continue;
                 }
-                if y - (line.line_height as f64) > rect.y1 {
+                if y - line_height > rect.y1 {
                     break;
                 }
             }
-            'line_loop: for glyph_run in line.glyphs {
-                let x = glyph_run.x + pos.x as f32 + offset.x as f32;
-                let y = line.line_y + pos.y as f32 + offset.y as f32;
+            'line_loop: for (i, glyph_run) in line.glyphs.iter().enumerate() {
+                let x = glyph_run.x as f64 + letter_spacing * i as f64 + pos.x;
+                let point = transform * Point::new(x, line.line_y as f64 + pos.y);
 
                 if let Some(rect) = clip {
-                    if ((x + glyph_run.w) as f64) < rect.x0 {
+                    if point.x + glyph_run.w as f64 * scale < rect.x0 {
                         continue;
-                    } else if x as f64 > rect.x1 {
+                    } else if point.x > rect.x1 {
                         break 'line_loop;
                     }
                 }
 
                 if let Some(paint) = self.brush_to_paint(glyph_run.color) {
-                    let glyph_x = x * self.scale as f32;
-                    let (new_x, subpx_x) = SubpixelBin::new(glyph_x);
+                    let (new_x, subpx_x) = SubpixelBin::new(point.x as f32);
                     let glyph_x = new_x as f32;
 
-                    let glyph_y = (y * self.scale as f32).round();
-                    let (new_y, subpx_y) = SubpixelBin::new(glyph_y);
+                    let (new_y, subpx_y) = SubpixelBin::new(point.y.round() as f32);
                     let glyph_y = new_y as f32;
 
-                    let font_size = (glyph_run.font_size * self.scale as f32).round() as u32;
+                    let font_size = (glyph_run.font_size as f64 * scale).round() as u32;
                     self.vger.render_glyph(
                         glyph_x,
                         glyph_y,
@@ -326,19 +706,22 @@ impl Renderer for VgerRenderer {
     }
 
     fn draw_img(&mut self, img: Img<'_>, img_width: u32, img_height: u32, rect: Rect) {
-        let transform = self.transform.as_coeffs();
-        let target_width = (rect.width() * self.scale).round() as u32;
-        let target_height = (rect.height() * self.scale).round() as u32;
+        self.sync_mask();
+        let scale = self.px_scale();
+        let target_width = (rect.width() * scale).round() as u32;
+        let target_height = (rect.height() * scale).round() as u32;
         let width = target_width.max(1);
         let height = target_height.max(1);
         // for now we center the contents in the container
         // TODO: take into account ObjectPosition here
-        let offset_x = transform[4] + ((rect.width() - img_width as f64) * 0.5);
-        let offset_y = transform[5] + ((rect.height() - img_height as f64) * 0.5);
-
-        let origin = rect.origin();
-        let x = (origin.x + offset_x).round() as f32;
-        let y = (origin.y + offset_y).round() as f32;
+        let offset = Vec2::new(
+            (rect.width() - img_width as f64) * 0.5,
+            (rect.height() - img_height as f64) * 0.5,
+        );
+        let point = self.px_transform * (rect.origin() + offset);
+        let x = point.x.round() as f32;
+        let y = point.y.round() as f32;
+        self.drawn = true;
 
         self.vger.render_image(x, y, img.hash, width, height, || {
             let new_img = image::load_from_memory(img.data).unwrap();
@@ -366,20 +749,68 @@ impl Renderer for VgerRenderer {
         });
     }
 
+    fn draw_pixels(&mut self, img: Img<'_>, version: u64, width: u32, height: u32, rect: Rect) {
+        if width == 0 || height == 0 || img.data.len() < (width * height * 4) as usize {
+            return;
+        }
+        self.sync_mask();
+        self.flush();
+
+        // vger can't replace an image it keeps, so the pixels are kept in a texture of their
+        // own, which is written over when they change
+        let size = Size::new(width as f64, height as f64);
+        match self.pixels.get_mut(img.hash) {
+            Some(pixels) if pixels.size() == size => {
+                if pixels.version != version {
+                    pixels.write(&self.queue, img.data);
+                    pixels.version = version;
+                }
+                pixels.used = true;
+            }
+            _ => {
+                let pixels = Pixels::new(&self.device, width, height, version);
+                pixels.write(&self.queue, img.data);
+                self.pixels.insert(img.hash.to_owned(), pixels);
+            }
+        }
+        let Some(pixels) = self.pixels.get(img.hash) else {
+            return;
+        };
+
+        let transform = self.px_transform
+            * Affine::translate(rect.origin().to_vec2())
+            * Affine::scale_non_uniform(rect.width() / size.width, rect.height() / size.height);
+        let composite = Composite {
+            transform,
+            scissor: self.clip.map(|(rect, _)| rect),
+            straight_alpha: true,
+            ..Composite::copy(size)
+        };
+        self.compositor.draw(
+            &self.device,
+            &self.queue,
+            pixels.texture(),
+            self.target().texture(),
+            &composite,
+        );
+    }
+
     fn draw_svg<'b>(
         &mut self,
         svg: floem_renderer::Svg<'b>,
         rect: Rect,
         brush: Option<impl Into<BrushRef<'b>>>,
     ) {
-        let transform = self.transform.as_coeffs();
-        let width = (rect.width() * self.scale).round() as u32;
-        let height = (rect.height() * self.scale).round() as u32;
+        self.sync_mask();
+        let scale = self.px_scale();
+        let width = (rect.width() * scale).round() as u32;
+        let height = (rect.height() * scale).round() as u32;
         let width = width.max(1);
         let height = height.max(1);
-        let origin = rect.origin();
-        let x = ((origin.x + transform[4]) * self.scale).round() as f32;
-        let y = ((origin.y + transform[5]) * self.scale).round() as f32;
+        let point = self.px_transform * rect.origin();
+        let x = point.x.round() as f32;
+        let y = point.y.round() as f32;
+        self.drawn = true;
 
         let paint = brush.and_then(|brush| self.brush_to_paint(brush));
         self.vger.render_svg(
@@ -403,53 +834,253 @@ impl Renderer for VgerRenderer {
 
     fn transform(&mut self, transform: Affine) {
         self.transform = transform;
+        let px_transform = Affine::scale(self.scale) * transform;
+        let in_target = |to_frame: Affine| to_frame.inverse() * px_transform;
+
+        // the groups up to the innermost transform group the transform is upright in, or
+        // none of them when it's upright in the frame
+        let mut to_frame = Affine::IDENTITY;
+        let mut upright_in = is_upright(in_target(to_frame)).then_some(0);
+        for (i, group) in self.groups.iter().enumerate() {
+            match group.kind {
+                GroupKind::Transform { to_parent, .. } => {
+                    to_frame = to_frame * to_parent;
+                    if is_upright(in_target(to_frame)) {
+                        upright_in = Some(i + 1);
+                    }
+                }
+                GroupKind::Layer { .. } | GroupKind::Mask { .. } => {}
+            }
+        }
+
+        match upright_in {
+            Some(len) => {
+                while self.groups[len..]
+                    .iter()
+                    .any(|group| matches!(group.kind, GroupKind::Transform { .. }))
+                {
+                    self.end_group();
+                }
+                self.px_transform = in_target(self.to_frame());
+            }
+            None => {
+                let in_target = in_target(self.to_frame());
+                // the group is drawn at the scale it's composited with, so only rotating and
+                // skewing it resamples it
+                let scale = in_target.determinant().abs().sqrt().max(1e-6);
+                // the group is drawn in the mask of its parent, since it's drawn without it
+                self.sync_mask();
+                let parent_clip = self.clip.take();
+                let parent_mask = self.mask.take();
+                self.start_group(GroupKind::Transform {
+                    to_parent: in_target * Affine::scale(scale.recip()),
+                    parent_clip,
+                    parent_mask,
+                });
+                self.px_transform = Affine::scale(scale);
+            }
+        }
     }
 
     fn set_z_index(&mut self, z_index: i32) {
+        self.z_index = z_index;
         self.vger.set_z_index(z_index);
     }
 
     fn clip(&mut self, shape: &impl Shape) {
+        self.mask = None;
         let (rect, radius) = if let Some(rect) = shape.as_rect() {
             (rect, 0.0)
         } else if let Some(rect) = shape.as_rounded_rect() {
             (rect.rect(), rect.radii().top_left)
         } else {
-            (shape.bounding_box(), 0.0)
+            // vger can only scissor to rounded rects, so other shapes are masked
+            self.clip = None;
+            return self.intersect_clip(shape);
         };
 
-        self.vger
-            .scissor(self.vger_rect(rect), (radius * self.scale) as f32);
-
-        let transform = self.transform.as_coeffs();
-        let offset = Vec2::new(transform[4], transform[5]);
-        self.clip = Some(rect + offset);
+        let rect = self.px_transform.transform_rect_bbox(rect);
+        self.clip = Some((rect, radius * self.px_scale()));
+        self.scissor();
     }
 
     fn clear_clip(&mut self) {
-        self.vger.reset_scissor();
         self.clip = None;
+        self.mask = None;
+        self.scissor();
+    }
+
+    fn intersect_clip(&mut self, shape: &impl Shape) {
+        let rect = self.px_transform.transform_rect_bbox(shape.bounding_box());
+        let rounded = matches!(self.clip, Some((_, radius)) if radius > 0.0);
+        if shape.as_rect().is_some() && !rounded {
+            self.clip = Some(match self.clip {
+                Some((clip, _)) => (rect.intersect(clip), 0.0),
+                None => (rect, 0.0),
+            });
+            self.scissor();
+            return;
+        }
+
+        let Some(path) = shape_to_path(shape) else {
+            return;
+        };
+        let transform = self.skia_transform();
+        let mask = match self.clip_mask() {
+            Some(mut mask) => {
+                mask.intersect_path(&path, FillRule::Winding, true, transform);
+                mask
+            }
+            None => {
+                let size = self.target().size;
+                let Some(mut mask) = Mask::new(size.width as u32, size.height as u32) else {
+                    return;
+                };
+                mask.fill_path(&path, FillRule::Winding, true, transform);
+                mask
+            }
+        };
+        self.set_clip_mask(rect, mask);
+    }
+
+    fn intersect_clip_img(&mut self, img: Img<'_>, rect: Rect) {
+        let Some(pixmap) = self.mask_image(&img) else {
+            return;
+        };
+        let size = self.target().size;
+        let Some(mut layer) = Pixmap::new(size.width as u32, size.height as u32) else {
+            return;
+        };
+        let Some(skia_rect) = tiny_skia::Rect::from_ltrb(
+            rect.x0 as f32,
+            rect.y0 as f32,
+            rect.x1 as f32,
+            rect.y1 as f32,
+        ) else {
+            return;
+        };
+        let paint = Paint {
+            shader: Pattern::new(
+                (*pixmap).as_ref(),
+                SpreadMode::Pad,
+                FilterQuality::Bilinear,
+                1.0,
+                tiny_skia::Transform::from_scale(
+                    rect.width() as f32 / pixmap.width() as f32,
+                    rect.height() as f32 / pixmap.height() as f32,
+                )
+                .post_translate(rect.x0 as f32, rect.y0 as f32),
+            ),
+            ..Default::default()
+        };
+        layer.fill_rect(skia_rect, &paint, self.skia_transform(), None);
+        let img_mask = Mask::from_pixmap(layer.as_ref(), MaskType::Alpha);
+
+        let mask = match self.clip_mask() {
+            Some(mut mask) => {
+                for (coverage, img_coverage) in mask.data_mut().iter_mut().zip(img_mask.data()) {
+                    *coverage = (*coverage as u16 * *img_coverage as u16 / 255) as u8;
+                }
+                mask
+            }
+            None => img_mask,
+        };
+        let rect = self.px_transform.transform_rect_bbox(rect);
+        self.set_clip_mask(rect, mask);
+    }
+
+    fn push_layer(&mut self, alpha: f32, blur: f64) {
+        self.sync_mask();
+        let size = self.target().size;
+        let blur = blur * self.px_scale();
+        let bounds = match self.clip {
+            Some((clip, _)) => clip
+                .inflate(blur * 3.0, blur * 3.0)
+                .intersect(size.to_rect()),
+            None => size.to_rect(),
+        };
+        self.start_group(GroupKind::Layer {
+            alpha,
+            blur,
+            bounds,
+        });
+    }
+
+    fn pop_layer(&mut self) {
+        let layer = self
+            .groups
+            .iter()
+            .rposition(|group| matches!(group.kind, GroupKind::Layer { .. }));
+        if let Some(layer) = layer {
+            while self.groups.len() > layer {
+                self.end_group();
+            }
+        }
+    }
+
+    fn blur_backdrop(&mut self, shape: &impl Shape, radius: f64) {
+        self.sync_mask();
+        self.flush();
+        let bounds = self.px_transform.transform_rect_bbox(shape.bounding_box());
+        let scissor = match self.clip {
+            Some((clip, _)) => bounds.intersect(clip),
+            None => bounds,
+        };
+        // the blur is only drawn inside of the shape
+        let size = self.target().size;
+        let mask = if shape.as_rect().is_some() {
+            None
+        } else {
+            let Some(path) = shape_to_path(shape) else {
+                return;
+            };
+            let Some(mut mask) = Mask::new(size.width as u32, size.height as u32) else {
+                return;
+            };
+            mask.fill_path(&path, FillRule::Winding, true, self.skia_transform());
+            Some(Target::mask_texture(&self.device, &self.queue, &mask))
+        };
+        let backdrop = self.take_target();
+        backdrop.copy_from(&self.device, &self.queue, self.target());
+        let composite = Composite {
+            source: bounds,
+            scissor: Some(scissor),
+            blur: radius * self.px_scale(),
+            mask: mask.as_ref(),
+            ..Composite::copy(backdrop.size)
+        };
+        self.draw_group(&backdrop, &composite);
+        self.spare_targets.push(backdrop);
     }
 
     fn finish(&mut self) {
+        while !self.groups.is_empty() {
+            self.end_group();
+        }
+        self.mask_images.retain(|_, (used, _)| *used);
+        for (used, _) in self.mask_images.values_mut() {
+            *used = false;
+        }
+        self.pixels.retain(|_, pixels| pixels.used);
+        for pixels in self.pixels.values_mut() {
+            pixels.used = false;
+        }
+        self.flush();
         if let Ok(frame) = self.surface.get_current_texture() {
-            let texture_view = frame
+            let view = frame
                 .texture
                 .create_view(&wgpu::TextureViewDescriptor::default());
-            let desc = wgpu::RenderPassDescriptor {
-                label: None,
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &texture_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
-                        store: true,
-                    },
-                })],
-                depth_stencil_attachment: None,
+            let surface = TextureRef {
+                view: &view,
+                size: Size::new(frame.texture.width() as f64, frame.texture.height() as f64),
             };
-
-            self.vger.encode(&desc);
+            self.compositor.draw(
+                &self.device,
+                &self.queue,
+                self.frame.texture(),
+                surface,
+                &Composite::copy(self.frame.size),
+            );
             frame.present();
         }
     }